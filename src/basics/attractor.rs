@@ -0,0 +1,103 @@
+//! Attractors
+//!
+//! This module provides gravity wells that pull nearby objects toward a point,
+//! or push them away when their strength is negative, for orbit puzzles and
+//! black-hole effects.
+//!
+//! # Features
+//! - Limited range, so only nearby objects are affected
+//! - Constant, linear or inverse-square falloff with distance
+//! - Scaled by each object's `gravity_scale`
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::attractor::{Attractor, Falloff};
+//!
+//! world.add_attractor(
+//!     Attractor::new(vec2(400.0, 300.0), 120.0, 250.0).with_falloff(Falloff::InverseSquare),
+//! );
+//! ```
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How an attractor's pull weakens with distance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Falloff {
+    /// Full strength everywhere in range
+    Constant,
+    /// Fades from full strength at the center to nothing at the edge of the range
+    #[default]
+    Linear,
+    /// Full strength inside the core, then weakening with the square of the distance
+    InverseSquare,
+}
+
+/// A point that pulls or pushes objects within its range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Attractor {
+    /// Center of the pull
+    pub position: Vec2,
+    /// Acceleration at full strength, in pixels per second squared; negative values push away
+    pub strength: f32,
+    /// Distance beyond which objects are unaffected
+    pub radius: f32,
+    pub falloff: Falloff,
+    /// Distance inside which inverse-square falloff stays at full strength
+    pub core_radius: f32,
+}
+
+impl Attractor {
+    /// Create an attractor with linear falloff
+    ///
+    /// # Arguments
+    /// * `position` - Center of the pull
+    /// * `strength` - Acceleration at full strength, in pixels per second squared; negative pushes away
+    /// * `radius` - Range of the pull
+    pub fn new(position: Vec2, strength: f32, radius: f32) -> Self {
+        Self {
+            position,
+            strength,
+            radius: radius.max(0.0),
+            falloff: Falloff::Linear,
+            core_radius: 16.0,
+        }
+    }
+
+    /// Set how the pull weakens with distance
+    pub fn with_falloff(mut self, falloff: Falloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Set the distance inside which inverse-square falloff stays at full strength
+    pub fn with_core_radius(mut self, core_radius: f32) -> Self {
+        self.core_radius = core_radius.max(f32::EPSILON);
+        self
+    }
+
+    /// Acceleration for an object at `point`, in pixels per second squared
+    pub fn acceleration_at(&self, point: Vec2) -> Vec2 {
+        let offset = self.position - point;
+        let distance = offset.length();
+        if distance > self.radius || distance == 0.0 {
+            return Vec2::ZERO;
+        }
+        let scale = match self.falloff {
+            Falloff::Constant => 1.0,
+            Falloff::Linear => 1.0 - distance / self.radius,
+            Falloff::InverseSquare => (self.core_radius / distance.max(self.core_radius)).powi(2),
+        };
+        offset / distance * self.strength * scale
+    }
+
+    /// Square enclosing the attractor's range
+    pub fn bounds(&self) -> Rect {
+        Rect::new(
+            self.position.x - self.radius,
+            self.position.y - self.radius,
+            self.radius * 2.0,
+            self.radius * 2.0,
+        )
+    }
+}
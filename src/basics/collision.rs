@@ -6,9 +6,14 @@
 //! 
 //! # Features
 //! - Point-to-point collision detection and response
-//! - Quad-to-quad collision detection and response
+//! - Quad-to-quad AABB and rotated (OBB) collision detection and response
+//! - Circle-to-circle and circle-to-quad collision detection and response
+//! - Convex polygon collision against polygons, quads and circles (SAT)
+//! - Quads against thick segments, for walking on slopes made of point chains
 //! - Slope physics for realistic movement
 //! - Configurable bounce and friction effects
+//! - Swept AABB and circle casts returning time of impact and contact normal
+//! - Spatial hash broad-phase for finding candidate pairs
 //! 
 //! # Examples
 //! ```rust
@@ -18,10 +23,13 @@
 //! let collision = Collision::new(0.5, 0.8);
 //! ```
 
-use crate::basics::Component;
+use crate::basics::{BodyType, Component};
+use crate::objects::circle::CircleBody;
 use crate::objects::point::Point;
+use crate::objects::polygon::PolygonCollider;
 use crate::objects::quad::Quad;
 use macroquad::prelude::*;
+use std::collections::HashMap;
 
 /// Component that handles collision detection and response
 /// 
@@ -38,6 +46,8 @@ pub struct Collision {
     bounce: f32,
     /// How much friction to apply on slopes (0.0 to 1.0)
     slope_friction: f32,
+    /// Whether collisions are handled at all
+    pub enabled: bool,
 }
 
 impl Collision {
@@ -53,9 +63,20 @@ impl Collision {
         Self {
             bounce: bounce.clamp(0.0, 1.0),
             slope_friction: slope_friction.clamp(0.0, 1.0),
+            enabled: true,
         }
     }
 
+    /// The bounce coefficient (0.0 to 1.0)
+    pub fn bounce(&self) -> f32 {
+        self.bounce
+    }
+
+    /// The slope friction coefficient (0.0 to 1.0)
+    pub fn slope_friction(&self) -> f32 {
+        self.slope_friction
+    }
+
     /// Calculate the normal vector of a slope between two points
     /// 
     /// # Arguments
@@ -90,7 +111,7 @@ impl Collision {
                 (point.position.1 - slope_p1.position.1) * dy) / (length * length);
         
         // Check if projection is within slope segment
-        if t < 0.0 || t > 1.0 {
+        if !(0.0..=1.0).contains(&t) {
             return false;
         }
 
@@ -111,7 +132,7 @@ impl Collision {
     /// * `slope_p1` - First point of the slope
     /// * `slope_p2` - Second point of the slope
     fn apply_slope_physics(&self, point: &mut Point, slope_p1: &Point, slope_p2: &Point) {
-        let normal = self.calculate_slope_normal(slope_p1, slope_p2);
+        let _normal = self.calculate_slope_normal(slope_p1, slope_p2);
         
         // Project velocity onto slope direction
         let slope_dir = Vec2::new(slope_p2.position.0 - slope_p1.position.0,
@@ -129,6 +150,14 @@ impl Collision {
 }
 
 impl Component<Point> for Collision {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
     /// Called when a collision occurs between this Point (`me`) and another Point (`other`)
     /// 
     /// # Arguments
@@ -160,22 +189,22 @@ impl Component<Point> for Collision {
                 let impulse_y = impulse * ny;
 
                 // Apply impulse
-                if !me.fixed {
+                if !me.is_fixed() {
                     me.velocity.0 -= impulse_x;
                     me.velocity.1 -= impulse_y;
                 }
-                if !other.fixed {
+                if !other.is_fixed() {
                     other.velocity.0 += impulse_x;
                     other.velocity.1 += impulse_y;
                 }
 
                 // Separate points
                 let overlap = min_distance - distance;
-                if !me.fixed {
+                if !me.is_fixed() {
                     me.position.0 -= nx * overlap * 0.5;
                     me.position.1 -= ny * overlap * 0.5;
                 }
-                if !other.fixed {
+                if !other.is_fixed() {
                     other.position.0 += nx * overlap * 0.5;
                     other.position.1 += ny * overlap * 0.5;
                 }
@@ -192,60 +221,921 @@ impl Component<Point> for Collision {
     }
 
     /// Update is unused for this component but required by the trait
-    fn update(&mut self, _point: &mut Point) {
+    fn update(&mut self, _point: &mut Point, _dt: f32) {
         // No update needed for collision component
     }
 }
 
 impl Component<Quad> for Collision {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
     /// Called when a collision occurs between this Quad (`me`) and another Quad (`other`)
     /// 
+    /// The quads are pushed apart along the axis of least overlap (the minimum
+    /// translation vector) and only their velocities along that axis bounce.
+    /// 
     /// # Arguments
     /// * `me` - The Quad that owns this Collision component
     /// * `other` - The Quad with which `me` has collided
     fn on_collide(&mut self, me: &mut Quad, other: &mut Quad) {
-        // Calculate distance between quads
-        let dx = other.position.0 - me.position.0;
-        let dy = other.position.1 - me.position.1;
-        let distance = (dx * dx + dy * dy).sqrt();
-        let min_distance = (me.size.0 + other.size.0) * 0.5;
+        let overlap = if me.rotation != 0.0 || other.rotation != 0.0 {
+            obb_overlap(me, other)
+        } else {
+            aabb_overlap(me, other)
+        };
+        if let Some(mtv) = overlap {
+            resolve_overlap(self.bounce, mtv, me, other);
+        }
+    }
 
-        // Check for collision
-        if distance < min_distance {
-            // Calculate collision normal
-            let nx = dx / distance;
-            let ny = dy / distance;
+    /// Called when this Quad (`me`) touches a segment of terrain
+    ///
+    /// The Quad is pushed out along the segment's normal and loses the
+    /// velocity heading into it, so it slides down slopes and walks up them.
+    ///
+    /// # Arguments
+    /// * `me` - The Quad that owns this Collision component
+    /// * `segment` - The segment `me` touched
+    fn on_collide_segment(&mut self, me: &mut Quad, segment: &Segment) {
+        if me.is_trigger || !me.body_type.is_dynamic() {
+            return;
+        }
+        if let Some(mtv) = segment_quad_overlap(segment, me) {
+            me.position.0 += mtv.x;
+            me.position.1 += mtv.y;
+            let normal = mtv.normalize_or_zero();
+            let velocity = vec2(me.velocity_x, me.velocity_y);
+            let into = velocity.dot(normal);
+            if into < 0.0 {
+                let bounce = self.bounce.max(me.restitution).clamp(0.0, 1.0);
+                let velocity = velocity - normal * into * (1.0 + bounce);
+                me.velocity_x = velocity.x;
+                me.velocity_y = velocity.y;
+            }
+        }
+    }
 
-            // Calculate relative velocity
-            let relative_vel_x = other.velocity_x - me.velocity_x;
-            let relative_vel_y = other.velocity_y - me.velocity_y;
-            let relative_vel_dot_normal = relative_vel_x * nx + relative_vel_y * ny;
+    /// Update is unused for this component but required by the trait
+    fn update(&mut self, _quad: &mut Quad, _dt: f32) {
+        // No update needed for collision component
+    }
+}
 
-            // Only resolve if objects are moving toward each other
-            if relative_vel_dot_normal < 0.0 {
-                // Calculate impulse
-                let impulse = -(1.0 + self.bounce) * relative_vel_dot_normal;
-                let impulse_x = impulse * nx;
-                let impulse_y = impulse * ny;
+impl Component<CircleBody> for Collision {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
 
-                // Apply impulse
-                me.velocity_x -= impulse_x;
-                me.velocity_y -= impulse_y;
-                other.velocity_x += impulse_x;
-                other.velocity_y += impulse_y;
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
 
-                // Separate quads
-                let overlap = min_distance - distance;
-                me.position.0 -= nx * overlap * 0.5;
-                me.position.1 -= ny * overlap * 0.5;
-                other.position.0 += nx * overlap * 0.5;
-                other.position.1 += ny * overlap * 0.5;
-            }
+    /// Called when a collision occurs between this circle (`me`) and another circle (`other`)
+    /// 
+    /// # Arguments
+    /// * `me` - The circle that owns this Collision component
+    /// * `other` - The circle with which `me` has collided
+    fn on_collide(&mut self, me: &mut CircleBody, other: &mut CircleBody) {
+        if let Some(mtv) = circle_overlap(me, other) {
+            resolve_overlap(self.bounce, mtv, me, other);
+        }
+    }
+
+    /// Called when this circle (`me`) overlaps a Quad, rotated or not
+    /// 
+    /// # Arguments
+    /// * `me` - The circle that owns this Collision component
+    /// * `quad` - The Quad with which `me` has collided
+    fn on_collide_quad(&mut self, me: &mut CircleBody, quad: &mut Quad) {
+        if let Some(mtv) = circle_quad_overlap(me, quad) {
+            resolve_overlap(self.bounce, mtv, me, quad);
         }
     }
 
     /// Update is unused for this component but required by the trait
-    fn update(&mut self, _quad: &mut Quad) {
+    fn update(&mut self, _circle: &mut CircleBody, _dt: f32) {
         // No update needed for collision component
     }
 }
+
+impl Component<PolygonCollider> for Collision {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Called when a collision occurs between this polygon (`me`) and another polygon (`other`)
+    /// 
+    /// # Arguments
+    /// * `me` - The polygon that owns this Collision component
+    /// * `other` - The polygon with which `me` has collided
+    fn on_collide(&mut self, me: &mut PolygonCollider, other: &mut PolygonCollider) {
+        if let Some(mtv) = polygon_overlap(me, other) {
+            resolve_overlap(self.bounce, mtv, me, other);
+        }
+    }
+
+    /// Called when this polygon (`me`) overlaps a Quad
+    fn on_collide_quad(&mut self, me: &mut PolygonCollider, quad: &mut Quad) {
+        if let Some(mtv) = polygon_quad_overlap(me, quad) {
+            resolve_overlap(self.bounce, mtv, me, quad);
+        }
+    }
+
+    /// Called when this polygon (`me`) overlaps a circle
+    fn on_collide_circle(&mut self, me: &mut PolygonCollider, circle: &mut CircleBody) {
+        if let Some(mtv) = polygon_circle_overlap(me, circle) {
+            resolve_overlap(self.bounce, mtv, me, circle);
+        }
+    }
+
+    /// Update is unused for this component but required by the trait
+    fn update(&mut self, _polygon: &mut PolygonCollider, _dt: f32) {
+        // No update needed for collision component
+    }
+}
+
+/// Position and velocity access for bodies that collide as solid shapes
+trait RigidBody {
+    fn body_type(&self) -> BodyType;
+    fn mass(&self) -> f32;
+    fn restitution(&self) -> f32;
+    fn is_trigger(&self) -> bool;
+    fn is_sleeping(&self) -> bool;
+    fn position_mut(&mut self) -> &mut (f32, f32);
+    fn velocity(&self) -> Vec2;
+    fn set_velocity(&mut self, velocity: Vec2);
+}
+
+impl RigidBody for Quad {
+    fn body_type(&self) -> BodyType {
+        self.body_type
+    }
+
+    fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    fn restitution(&self) -> f32 {
+        self.restitution
+    }
+
+    fn is_trigger(&self) -> bool {
+        self.is_trigger
+    }
+
+    fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    fn position_mut(&mut self) -> &mut (f32, f32) {
+        &mut self.position
+    }
+
+    fn velocity(&self) -> Vec2 {
+        vec2(self.velocity_x, self.velocity_y)
+    }
+
+    fn set_velocity(&mut self, velocity: Vec2) {
+        self.velocity_x = velocity.x;
+        self.velocity_y = velocity.y;
+    }
+}
+
+impl RigidBody for CircleBody {
+    fn body_type(&self) -> BodyType {
+        self.body_type
+    }
+
+    fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    fn restitution(&self) -> f32 {
+        self.restitution
+    }
+
+    fn is_trigger(&self) -> bool {
+        self.is_trigger
+    }
+
+    fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    fn position_mut(&mut self) -> &mut (f32, f32) {
+        &mut self.position
+    }
+
+    fn velocity(&self) -> Vec2 {
+        vec2(self.velocity_x, self.velocity_y)
+    }
+
+    fn set_velocity(&mut self, velocity: Vec2) {
+        self.velocity_x = velocity.x;
+        self.velocity_y = velocity.y;
+    }
+}
+
+impl RigidBody for PolygonCollider {
+    fn body_type(&self) -> BodyType {
+        self.body_type
+    }
+
+    fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    fn restitution(&self) -> f32 {
+        self.restitution
+    }
+
+    fn is_trigger(&self) -> bool {
+        self.is_trigger
+    }
+
+    fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    fn position_mut(&mut self) -> &mut (f32, f32) {
+        &mut self.position
+    }
+
+    fn velocity(&self) -> Vec2 {
+        vec2(self.velocity_x, self.velocity_y)
+    }
+
+    fn set_velocity(&mut self, velocity: Vec2) {
+        self.velocity_x = velocity.x;
+        self.velocity_y = velocity.y;
+    }
+}
+
+/// Push two bodies apart by a minimum translation vector and bounce them
+/// 
+/// Only velocity along the collision axis changes, so sliding along the
+/// other body is left untouched. Each body takes a share of the response
+/// in proportion to its inverse mass, so light bodies give way to heavy
+/// ones. Triggers are never pushed or bounced, and static, kinematic and
+/// sleeping bodies push dynamic ones without being pushed back.
+/// 
+/// # Arguments
+/// * `bounce` - The bounce coefficient (0.0 to 1.0), raised to the bouncier body's restitution
+/// * `mtv` - How far `other` must move away from `me`
+fn resolve_overlap(bounce: f32, mtv: Vec2, me: &mut impl RigidBody, other: &mut impl RigidBody) {
+    if me.is_trigger() || other.is_trigger() {
+        return;
+    }
+    // Inverse mass, with immovable and sleeping bodies weighing nothing
+    let weight = |body: &dyn RigidBody| {
+        let movable = body.body_type().is_dynamic() && !body.is_sleeping() && body.mass() > 0.0;
+        if movable { 1.0 / body.mass() } else { 0.0 }
+    };
+    let (my_weight, other_weight) = (weight(me), weight(other));
+    let total = my_weight + other_weight;
+    if total == 0.0 {
+        return;
+    }
+    let (my_share, other_share) = (my_weight / total, other_weight / total);
+
+    let position = me.position_mut();
+    position.0 -= mtv.x * my_share;
+    position.1 -= mtv.y * my_share;
+    let position = other.position_mut();
+    position.0 += mtv.x * other_share;
+    position.1 += mtv.y * other_share;
+
+    let normal = mtv.normalize_or_zero();
+    let relative_vel_dot_normal = (other.velocity() - me.velocity()).dot(normal);
+
+    // Only resolve if objects are moving toward each other
+    if relative_vel_dot_normal < 0.0 {
+        let bounce = bounce.max(me.restitution()).max(other.restitution()).clamp(0.0, 1.0);
+        let impulse = normal * (-(1.0 + bounce) * relative_vel_dot_normal);
+        me.set_velocity(me.velocity() - impulse * my_share);
+        other.set_velocity(other.velocity() + impulse * other_share);
+    }
+}
+
+/// Minimum translation vector separating two overlapping circles
+///
+/// # Returns
+/// How far `other` must move away from `me` for the circles to stop
+/// overlapping, or `None` if they don't overlap
+pub fn circle_overlap(me: &CircleBody, other: &CircleBody) -> Option<Vec2> {
+    let delta = vec2(other.position.0 - me.position.0, other.position.1 - me.position.1);
+    let distance = delta.length();
+    let overlap = me.radius + other.radius - distance;
+    if overlap <= 0.0 {
+        return None;
+    }
+    // Circles with the same center are pushed apart vertically
+    let normal = if distance > 0.0 { delta / distance } else { Vec2::Y };
+    Some(normal * overlap)
+}
+
+/// Minimum translation vector separating a circle from a Quad
+///
+/// Rotated quads are handled by testing in the quad's own frame.
+///
+/// # Returns
+/// How far `quad` must move away from `circle` for them to stop overlapping,
+/// or `None` if they don't overlap
+pub fn circle_quad_overlap(circle: &CircleBody, quad: &Quad) -> Option<Vec2> {
+    let pivot = quad.pivot_point();
+    let rotation = Vec2::from_angle(quad.rotation);
+    let center = vec2(circle.position.0, circle.position.1);
+    // Circle center in the quad's unrotated frame
+    let local = pivot + Vec2::from_angle(-quad.rotation).rotate(center - pivot);
+
+    let min = vec2(quad.position.0, quad.position.1);
+    let max = min + vec2(quad.size.0, quad.size.1);
+    let closest = local.clamp(min, max);
+    let offset = local - closest;
+    let distance = offset.length();
+
+    let local_mtv = if distance > 0.0 {
+        if distance >= circle.radius {
+            return None;
+        }
+        // Quad moves away from the circle, opposite to the offset
+        -offset / distance * (circle.radius - distance)
+    } else {
+        // Center inside the quad: leave through the nearest edge
+        let edges = [
+            (local.x - min.x, vec2(-1.0, 0.0)),
+            (max.x - local.x, vec2(1.0, 0.0)),
+            (local.y - min.y, vec2(0.0, -1.0)),
+            (max.y - local.y, vec2(0.0, 1.0)),
+        ];
+        let (depth, outward) = edges
+            .into_iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap_or((0.0, Vec2::Y));
+        -outward * (depth + circle.radius)
+    };
+    Some(rotation.rotate(local_mtv))
+}
+
+/// Minimum translation vector separating two overlapping quads
+///
+/// # Returns
+/// How far `other` must move away from `me` (along a single axis) for the quads
+/// to stop overlapping, or `None` if they don't overlap
+pub fn aabb_overlap(me: &Quad, other: &Quad) -> Option<Vec2> {
+    let half_me = vec2(me.size.0, me.size.1) * 0.5;
+    let half_other = vec2(other.size.0, other.size.1) * 0.5;
+    let delta = (vec2(other.position.0, other.position.1) + half_other)
+        - (vec2(me.position.0, me.position.1) + half_me);
+    let overlap = half_me + half_other - delta.abs();
+    if overlap.x <= 0.0 || overlap.y <= 0.0 {
+        return None;
+    }
+    // Quads with the same center are pushed apart vertically
+    let sign = |d: f32| if d < 0.0 { -1.0 } else { 1.0 };
+    if overlap.x < overlap.y {
+        Some(vec2(overlap.x * sign(delta.x), 0.0))
+    } else {
+        Some(vec2(0.0, overlap.y * sign(delta.y)))
+    }
+}
+
+/// Minimum translation vector separating two rotated quads
+///
+/// # Returns
+/// How far `other` must move away from `me` for the quads to stop overlapping,
+/// or `None` if they don't overlap
+pub fn obb_overlap(me: &Quad, other: &Quad) -> Option<Vec2> {
+    let mtv = sat_overlap(&me.corners(), &other.corners())?;
+    Some(face_away(mtv, me.center(), other.center()))
+}
+
+/// Minimum translation vector separating two convex polygons
+///
+/// # Returns
+/// How far `other` must move away from `me` for the polygons to stop
+/// overlapping, or `None` if they don't overlap
+pub fn polygon_overlap(me: &PolygonCollider, other: &PolygonCollider) -> Option<Vec2> {
+    let mtv = sat_overlap(&me.world_vertices(), &other.world_vertices())?;
+    Some(face_away(mtv, me.center(), other.center()))
+}
+
+/// Minimum translation vector separating a convex polygon from a Quad
+///
+/// # Returns
+/// How far `quad` must move away from `polygon` for them to stop overlapping,
+/// or `None` if they don't overlap
+pub fn polygon_quad_overlap(polygon: &PolygonCollider, quad: &Quad) -> Option<Vec2> {
+    let mtv = sat_overlap(&polygon.world_vertices(), &quad.corners())?;
+    Some(face_away(mtv, polygon.center(), quad.center()))
+}
+
+/// Minimum translation vector separating a convex polygon from a circle
+///
+/// Besides the polygon's edge normals, the axis from the closest vertex to the
+/// circle's center is tested, which catches circles just off a corner.
+///
+/// # Returns
+/// How far `circle` must move away from `polygon` for them to stop
+/// overlapping, or `None` if they don't overlap
+pub fn polygon_circle_overlap(polygon: &PolygonCollider, circle: &CircleBody) -> Option<Vec2> {
+    let center = vec2(circle.position.0, circle.position.1);
+    convex_circle_overlap(&polygon.world_vertices(), polygon.center(), center, circle.radius)
+}
+
+/// Minimum translation vector separating a convex outline centered on `middle` from a circle
+fn convex_circle_overlap(vertices: &[Vec2], middle: Vec2, center: Vec2, radius: f32) -> Option<Vec2> {
+    let closest = vertices
+        .iter()
+        .copied()
+        .min_by(|a, b| a.distance_squared(center).total_cmp(&b.distance_squared(center)))?;
+
+    let mut axes: Vec<Vec2> = (0..vertices.len())
+        .map(|i| {
+            let edge = vertices[(i + 1) % vertices.len()] - vertices[i];
+            vec2(-edge.y, edge.x).normalize_or_zero()
+        })
+        .collect();
+    axes.push((center - closest).normalize_or_zero());
+
+    let mut best: Option<Vec2> = None;
+    for axis in axes.into_iter().filter(|axis| *axis != Vec2::ZERO) {
+        let (min_a, max_a) = project(vertices, axis);
+        let d = center.dot(axis);
+        let overlap = max_a.min(d + radius) - min_a.max(d - radius);
+        if overlap <= 0.0 {
+            return None;
+        }
+        if best.is_none_or(|best| overlap < best.length()) {
+            best = Some(axis * overlap);
+        }
+    }
+    Some(face_away(best?, middle, center))
+}
+
+/// Plain copy of a body's collision shape
+///
+/// Holds no components, so overlap tests can run on other threads while the
+/// bodies themselves stay put.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BodyShape {
+    /// Unrotated Quad
+    Rect(Rect),
+    /// Rotated Quad or convex polygon, with its corners and center
+    Convex(Vec<Vec2>, Vec2),
+    /// Circle, with its center and radius
+    Circle(Vec2, f32),
+}
+
+impl BodyShape {
+    /// Copy the shape of a Quad
+    pub fn quad(quad: &Quad) -> Self {
+        if quad.rotation != 0.0 {
+            return Self::Convex(quad.corners().to_vec(), quad.center());
+        }
+        Self::Rect(Rect::new(quad.position.0, quad.position.1, quad.size.0, quad.size.1))
+    }
+
+    /// Copy the shape of a circle
+    pub fn circle(circle: &CircleBody) -> Self {
+        Self::Circle(vec2(circle.position.0, circle.position.1), circle.radius)
+    }
+
+    /// Copy the shape of a polygon
+    pub fn polygon(polygon: &PolygonCollider) -> Self {
+        Self::Convex(polygon.world_vertices(), polygon.center())
+    }
+
+    /// Outline and center of a shape with corners
+    fn corners(&self) -> Option<(Vec<Vec2>, Vec2)> {
+        match self {
+            Self::Rect(rect) => Some((
+                vec![rect.point(), vec2(rect.right(), rect.y), vec2(rect.right(), rect.bottom()), vec2(rect.x, rect.bottom())],
+                rect.center(),
+            )),
+            Self::Convex(vertices, center) => Some((vertices.clone(), *center)),
+            Self::Circle(..) => None,
+        }
+    }
+
+    /// Check if two shapes overlap, the same way the bodies they were copied from would
+    pub fn overlaps(&self, other: &BodyShape) -> bool {
+        match (self, other) {
+            (Self::Rect(a), Self::Rect(b)) => {
+                a.x < b.right() && a.right() > b.x && a.y < b.bottom() && a.bottom() > b.y
+            }
+            (Self::Circle(a, radius_a), Self::Circle(b, radius_b)) => a.distance(*b) < radius_a + radius_b,
+            (Self::Circle(center, radius), shape) | (shape, Self::Circle(center, radius)) => shape
+                .corners()
+                .is_some_and(|(vertices, middle)| convex_circle_overlap(&vertices, middle, *center, *radius).is_some()),
+            (a, b) => match (a.corners(), b.corners()) {
+                (Some((a, _)), Some((b, _))) => sat_overlap(&a, &b).is_some(),
+                _ => false,
+            },
+        }
+    }
+}
+
+/// A line segment with thickness, such as the link between two terrain points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start: Vec2,
+    pub end: Vec2,
+    /// Half the thickness of the segment
+    pub radius: f32,
+}
+
+impl Segment {
+    /// Create a segment from `start` to `end`, `radius` thick on each side
+    pub fn new(start: Vec2, end: Vec2, radius: f32) -> Self {
+        Self { start, end, radius: radius.max(0.0) }
+    }
+
+    /// Closest point on the segment's center line to `point`
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        let along = self.end - self.start;
+        let length_squared = along.length_squared();
+        if length_squared == 0.0 {
+            return self.start;
+        }
+        let t = ((point - self.start).dot(along) / length_squared).clamp(0.0, 1.0);
+        self.start + along * t
+    }
+
+    /// Unit normal of the segment on the side facing `point`
+    pub fn normal_towards(&self, point: Vec2) -> Vec2 {
+        let normal = (self.end - self.start).perp().normalize_or_zero();
+        if normal.dot(point - self.start) < 0.0 { -normal } else { normal }
+    }
+
+    /// Rectangle enclosing the segment and its thickness
+    pub fn bounds(&self) -> Rect {
+        let min = self.start.min(self.end) - Vec2::splat(self.radius);
+        let max = self.start.max(self.end) + Vec2::splat(self.radius);
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    /// Corners of the segment's thickness, ignoring the rounded ends
+    fn corners(&self) -> [Vec2; 4] {
+        let offset = (self.end - self.start).perp().normalize_or_zero() * self.radius;
+        [self.start + offset, self.end + offset, self.end - offset, self.start - offset]
+    }
+}
+
+/// Minimum translation vector separating a Quad from a thick segment
+///
+/// While the Quad's center is alongside the segment it is always pushed out
+/// along the segment's normal, so it glides over the joints of a chain of
+/// segments instead of catching on them.
+///
+/// # Returns
+/// How far `quad` must move away from `segment` for them to stop overlapping,
+/// or `None` if they don't overlap
+pub fn segment_quad_overlap(segment: &Segment, quad: &Quad) -> Option<Vec2> {
+    let corners = quad.corners();
+    let center = quad.center();
+    let along = segment.end - segment.start;
+    if along == Vec2::ZERO {
+        return None;
+    }
+    let t = (center - segment.start).dot(along) / along.length_squared();
+    if (0.0..=1.0).contains(&t) {
+        let normal = segment.normal_towards(center);
+        let (nearest, _) = project(&corners, normal);
+        let depth = segment.start.dot(normal) + segment.radius - nearest;
+        return (depth > 0.0).then(|| normal * depth);
+    }
+    let mtv = sat_overlap(&segment.corners(), &corners)?;
+    Some(face_away(mtv, segment.closest_point(center), center))
+}
+
+/// Flip a separating vector so it points from `from` towards `to`
+fn face_away(mtv: Vec2, from: Vec2, to: Vec2) -> Vec2 {
+    if mtv.dot(to - from) < 0.0 { -mtv } else { mtv }
+}
+
+/// Separating Axis Theorem test between two convex polygons
+///
+/// # Returns
+/// The smallest overlap along any edge normal of either polygon, with an
+/// arbitrary sign, or `None` if some axis separates them
+fn sat_overlap(a: &[Vec2], b: &[Vec2]) -> Option<Vec2> {
+    let mut best: Option<Vec2> = None;
+    for polygon in [a, b] {
+        for (i, &start) in polygon.iter().enumerate() {
+            let edge = polygon[(i + 1) % polygon.len()] - start;
+            let axis = vec2(-edge.y, edge.x).normalize_or_zero();
+            if axis == Vec2::ZERO {
+                continue;
+            }
+            let (min_a, max_a) = project(a, axis);
+            let (min_b, max_b) = project(b, axis);
+            let overlap = max_a.min(max_b) - min_a.max(min_b);
+            if overlap <= 0.0 {
+                return None;
+            }
+            if best.is_none_or(|best| overlap < best.length()) {
+                best = Some(axis * overlap);
+            }
+        }
+    }
+    best
+}
+
+/// Range a polygon covers when projected onto an axis
+fn project(polygon: &[Vec2], axis: Vec2) -> (f32, f32) {
+    polygon.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), vertex| {
+        let d = vertex.dot(axis);
+        (min.min(d), max.max(d))
+    })
+}
+
+/// Where a moving shape first touches a surface
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CastHit {
+    /// Fraction of the motion travelled before contact (0.0 to 1.0)
+    pub time: f32,
+    /// Surface normal at the contact, pointing back towards the moving shape
+    pub normal: Vec2,
+}
+
+/// Sweep a moving rectangle against a still one
+///
+/// Moving `moving` by `motion * hit.time` leaves it exactly touching `target`.
+/// Rectangles that already overlap hit at time 0.0.
+///
+/// # Arguments
+/// * `moving` - Bounds of the moving shape at the start of the motion
+/// * `motion` - Full displacement over the step
+/// * `target` - Bounds of the obstacle
+pub fn sweep_aabb(moving: Rect, motion: Vec2, target: Rect) -> Option<CastHit> {
+    // Shrink the mover to a point by growing the target by its size
+    let expanded = Rect::new(
+        target.x - moving.w,
+        target.y - moving.h,
+        target.w + moving.w,
+        target.h + moving.h,
+    );
+    ray_rect(moving.point(), motion, expanded)
+}
+
+/// Sweep a moving circle against a still circle
+///
+/// # Arguments
+/// * `center` - Center of the moving circle at the start of the motion
+/// * `radius` - Radius of the moving circle
+/// * `motion` - Full displacement over the step
+/// * `target_center`, `target_radius` - The obstacle
+pub fn circle_cast_circle(
+    center: Vec2,
+    radius: f32,
+    motion: Vec2,
+    target_center: Vec2,
+    target_radius: f32,
+) -> Option<CastHit> {
+    ray_circle(center, motion, target_center, radius + target_radius)
+}
+
+/// Sweep a moving circle against a still rectangle
+///
+/// # Arguments
+/// * `center` - Center of the moving circle at the start of the motion
+/// * `radius` - Radius of the moving circle
+/// * `motion` - Full displacement over the step
+/// * `target` - Bounds of the obstacle
+pub fn circle_cast_aabb(center: Vec2, radius: f32, motion: Vec2, target: Rect) -> Option<CastHit> {
+    // The circle's center stops on the target grown by the radius, with rounded corners
+    let expanded = Rect::new(
+        target.x - radius,
+        target.y - radius,
+        target.w + radius * 2.0,
+        target.h + radius * 2.0,
+    );
+    let hit = ray_rect(center, motion, expanded)?;
+    let contact = center + motion * hit.time;
+    let corner = contact.clamp(target.point(), target.point() + target.size());
+    let in_corner = (contact.x < target.x || contact.x > target.right())
+        && (contact.y < target.y || contact.y > target.bottom());
+    if in_corner {
+        ray_circle(center, motion, corner, radius)
+    } else {
+        Some(hit)
+    }
+}
+
+/// Sweep a moving circle against a Quad, rotated or not
+///
+/// # Arguments
+/// * `center` - Center of the moving circle at the start of the motion
+/// * `radius` - Radius of the moving circle
+/// * `motion` - Full displacement over the step
+/// * `quad` - The obstacle
+pub fn circle_cast_quad(center: Vec2, radius: f32, motion: Vec2, quad: &Quad) -> Option<CastHit> {
+    let rect = Rect::new(quad.position.0, quad.position.1, quad.size.0, quad.size.1);
+    if quad.rotation == 0.0 {
+        return circle_cast_aabb(center, radius, motion, rect);
+    }
+    // Cast in the quad's unrotated frame and turn the normal back
+    let pivot = quad.pivot_point();
+    let unrotate = Vec2::from_angle(-quad.rotation);
+    let local_center = pivot + unrotate.rotate(center - pivot);
+    let hit = circle_cast_aabb(local_center, radius, unrotate.rotate(motion), rect)?;
+    Some(CastHit {
+        normal: Vec2::from_angle(quad.rotation).rotate(hit.normal),
+        ..hit
+    })
+}
+
+/// First time a ray from `origin` along `motion` enters `rect`
+fn ray_rect(origin: Vec2, motion: Vec2, rect: Rect) -> Option<CastHit> {
+    let min = rect.point();
+    let max = min + rect.size();
+    if origin.x > min.x && origin.x < max.x && origin.y > min.y && origin.y < max.y {
+        // Already inside: report the nearest face
+        let faces = [
+            (origin.x - min.x, vec2(-1.0, 0.0)),
+            (max.x - origin.x, vec2(1.0, 0.0)),
+            (origin.y - min.y, vec2(0.0, -1.0)),
+            (max.y - origin.y, vec2(0.0, 1.0)),
+        ];
+        let (_, normal) = faces.into_iter().min_by(|a, b| a.0.total_cmp(&b.0))?;
+        return Some(CastHit { time: 0.0, normal });
+    }
+
+    let mut enter = f32::NEG_INFINITY;
+    let mut exit = f32::INFINITY;
+    let mut normal = Vec2::ZERO;
+    for (o, d, lo, hi, axis) in [
+        (origin.x, motion.x, min.x, max.x, Vec2::X),
+        (origin.y, motion.y, min.y, max.y, Vec2::Y),
+    ] {
+        if d == 0.0 {
+            if o < lo || o > hi {
+                return None;
+            }
+            continue;
+        }
+        let (t1, t2) = ((lo - o) / d, (hi - o) / d);
+        let (near, far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
+        if near > enter {
+            enter = near;
+            normal = -axis * d.signum();
+        }
+        exit = exit.min(far);
+    }
+    if enter > exit || !(0.0..=1.0).contains(&enter) {
+        return None;
+    }
+    Some(CastHit { time: enter, normal })
+}
+
+/// First time a ray from `origin` along `motion` enters a circle
+fn ray_circle(origin: Vec2, motion: Vec2, center: Vec2, radius: f32) -> Option<CastHit> {
+    let offset = origin - center;
+    let c = offset.length_squared() - radius * radius;
+    if c < 0.0 {
+        // Already inside
+        let normal = offset.try_normalize().unwrap_or(-Vec2::Y);
+        return Some(CastHit { time: 0.0, normal });
+    }
+    let a = motion.length_squared();
+    if a == 0.0 {
+        return None;
+    }
+    let b = offset.dot(motion);
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let time = (-b - discriminant.sqrt()) / a;
+    if !(0.0..=1.0).contains(&time) {
+        return None;
+    }
+    let normal = (origin + motion * time - center).normalize_or_zero();
+    Some(CastHit { time, normal })
+}
+
+/// Broad-phase grid that buckets objects by the cells their bounds touch
+///
+/// Only objects sharing a cell are reported as candidate pairs, so collision
+/// checks scale with how crowded the scene is rather than with its size.
+///
+/// # Parameters
+/// * `cell_size` - Width and height of a grid cell, roughly the size of a typical object
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    /// Width and height of a grid cell
+    pub cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    /// Cells holding at least one object since the last clear
+    occupied: Vec<(i32, i32)>,
+}
+
+impl SpatialHash {
+    /// Creates an empty spatial hash
+    ///
+    /// # Arguments
+    /// * `cell_size` - Width and height of a grid cell
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+            occupied: Vec::new(),
+        }
+    }
+
+    /// Remove every object, keeping the cells used since the last clear for reuse
+    ///
+    /// Cells nothing was inserted into since the last clear are dropped, so
+    /// the map only holds cells objects are near rather than every cell they
+    /// ever passed through.
+    pub fn clear(&mut self) {
+        self.cells.retain(|_, bucket| !bucket.is_empty());
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+        self.occupied.clear();
+    }
+
+    /// Cell containing a position
+    fn cell(&self, position: Vec2) -> (i32, i32) {
+        (
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Insert an object by the corners of its bounding box
+    ///
+    /// # Arguments
+    /// * `index` - Index identifying the object
+    /// * `min` - Top-left corner of the bounds
+    /// * `max` - Bottom-right corner of the bounds
+    pub fn insert(&mut self, index: usize, min: Vec2, max: Vec2) {
+        let (x0, y0) = self.cell(min);
+        let (x1, y1) = self.cell(max);
+        for x in x0..=x1 {
+            for y in y0..=y1 {
+                let bucket = self.cells.entry((x, y)).or_default();
+                if bucket.is_empty() {
+                    self.occupied.push((x, y));
+                }
+                bucket.push(index);
+            }
+        }
+    }
+
+    /// Pairs of objects sharing at least one cell
+    ///
+    /// # Returns
+    /// Each pair once, as `(lower index, higher index)`, sorted
+    pub fn candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for bucket in self.occupied.iter().filter_map(|cell| self.cells.get(cell)) {
+            for (n, &a) in bucket.iter().enumerate() {
+                for &b in &bucket[n + 1..] {
+                    if a != b {
+                        pairs.push((a.min(b), a.max(b)));
+                    }
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// Objects sharing at least one cell with a box
+    ///
+    /// # Arguments
+    /// * `min` - Top-left corner of the box
+    /// * `max` - Bottom-right corner of the box
+    ///
+    /// # Returns
+    /// Each object once, sorted by index
+    pub fn query(&self, min: Vec2, max: Vec2) -> Vec<usize> {
+        let (x0, y0) = self.cell(min);
+        let (x1, y1) = self.cell(max);
+        let mut found = Vec::new();
+        for x in x0..=x1 {
+            for y in y0..=y1 {
+                if let Some(bucket) = self.cells.get(&(x, y)) {
+                    found.extend_from_slice(bucket);
+                }
+            }
+        }
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+}
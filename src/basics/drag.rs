@@ -0,0 +1,162 @@
+use macroquad::math::{Vec2, vec2};
+
+use crate::basics::Component;
+use crate::objects::circle::CircleBody;
+use crate::objects::game_object::GameObject;
+use crate::objects::point::Point;
+use crate::objects::polygon::PolygonCollider;
+use crate::objects::quad::Quad;
+
+/// Component that slows an object down like air or water would
+///
+/// Unlike `Friction`, which removes the same fraction of velocity every
+/// moment, the quadratic term makes fast objects lose speed much quicker
+/// than slow ones, giving them a natural terminal velocity.
+pub struct Drag {
+    /// Fraction of the speed lost per second
+    pub linear: f32,
+    /// Speed lost per second for each unit of speed squared
+    pub quadratic: f32,
+    /// Whether drag slows the object at all
+    pub enabled: bool,
+}
+
+impl Drag {
+    /// Creates a new Drag component.
+    ///
+    /// # Parameters
+    /// - `linear`: Fraction of the speed lost per second.
+    /// - `quadratic`: Speed lost per second for each unit of speed squared.
+    ///
+    /// # Returns
+    /// A new `Drag` instance.
+    pub fn new(linear: f32, quadratic: f32) -> Self {
+        Self {
+            linear: linear.max(0.0),
+            quadratic: quadratic.max(0.0),
+            enabled: true,
+        }
+    }
+
+    /// Returns `velocity` after `dt` seconds of drag.
+    ///
+    /// Drag only ever slows an object down, it never reverses it.
+    pub fn apply(&self, velocity: Vec2, dt: f32) -> Vec2 {
+        let speed = velocity.length();
+        if speed == 0.0 {
+            return velocity;
+        }
+        let loss = ((self.linear * speed + self.quadratic * speed * speed) * dt).min(speed);
+        velocity * (1.0 - loss / speed)
+    }
+}
+
+impl Component<Point> for Drag {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Slows the Point's velocity.
+    fn update(&mut self, point: &mut Point, dt: f32) {
+        if !point.is_fixed() {
+            let velocity = self.apply(vec2(point.velocity.0, point.velocity.1), dt);
+            point.velocity = (velocity.x, velocity.y);
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut Point, _other: &mut Point) {
+        // No collision handling needed for drag
+    }
+}
+
+impl Component<Quad> for Drag {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Slows the Quad's horizontal and vertical velocities.
+    fn update(&mut self, quad: &mut Quad, dt: f32) {
+        if quad.body_type.is_dynamic() {
+            let velocity = self.apply(vec2(quad.velocity_x, quad.velocity_y), dt);
+            quad.velocity_x = velocity.x;
+            quad.velocity_y = velocity.y;
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut Quad, _other: &mut Quad) {
+        // No collision handling needed for drag
+    }
+}
+
+impl Component<CircleBody> for Drag {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Slows the circle's velocities.
+    fn update(&mut self, circle: &mut CircleBody, dt: f32) {
+        if circle.body_type.is_dynamic() {
+            let velocity = self.apply(vec2(circle.velocity_x, circle.velocity_y), dt);
+            circle.velocity_x = velocity.x;
+            circle.velocity_y = velocity.y;
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut CircleBody, _other: &mut CircleBody) {
+        // No collision handling needed for drag
+    }
+}
+
+impl Component<PolygonCollider> for Drag {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Slows the polygon's velocities.
+    fn update(&mut self, polygon: &mut PolygonCollider, dt: f32) {
+        if polygon.body_type.is_dynamic() {
+            let velocity = self.apply(vec2(polygon.velocity_x, polygon.velocity_y), dt);
+            polygon.velocity_x = velocity.x;
+            polygon.velocity_y = velocity.y;
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut PolygonCollider, _other: &mut PolygonCollider) {
+        // No collision handling needed for drag
+    }
+}
+
+impl Component<GameObject> for Drag {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Slows the object's velocity.
+    fn update(&mut self, object: &mut GameObject, dt: f32) {
+        object.velocity = self.apply(object.velocity, dt);
+    }
+
+    fn on_collide(&mut self, _me: &mut GameObject, _other: &mut GameObject) {
+        // No collision handling needed for drag
+    }
+}
@@ -1,11 +1,14 @@
 use crate::basics::Component;
+use crate::objects::circle::CircleBody;
+use crate::objects::game_object::GameObject;
 use crate::objects::point::Point;
+use crate::objects::polygon::PolygonCollider;
 use crate::objects::quad::Quad;
 use macroquad::prelude::*;
 
 /// A component representing a force applied to a Quad, affecting its velocity.
 ///
-/// This component adds a force vector `(x, y)` to the Quad's velocity each update.
+/// This component accelerates the Quad by the force vector `(x, y)`, in pixels per second squared.
 /// It supports both *decaying* (one-time) forces and *permanent* forces that persist.
 ///
 /// # Fields
@@ -29,6 +32,8 @@ use macroquad::prelude::*;
 pub struct Force {
     /// The force vector to apply
     pub force: Vec2,
+    /// Whether the force is applied at all
+    pub enabled: bool,
 }
 
 impl Force {
@@ -43,7 +48,7 @@ impl Force {
     /// # Returns
     /// A `Force` instance with `decay` set to `true`.
     pub fn new(force: Vec2) -> Self {
-        Self { force }
+        Self { force, enabled: true }
     }
 
     /// Creates a new permanent force with the given x and y components.
@@ -57,22 +62,30 @@ impl Force {
     /// # Returns
     /// A `Force` instance with `decay` set to `false`.
     pub fn permanent(x: f32, y: f32) -> Self {
-        Self { force: Vec2::new(x, y) }
+        Self::new(Vec2::new(x, y))
     }
 }
 
 impl Component<Point> for Force {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
     /// Applies the force to the Point's velocity each update.
     ///
-    /// Adds `x` and `y` components to `velocity.0` and `velocity.1` respectively.
+    /// Adds `x` and `y` components times `dt` to `velocity.0` and `velocity.1` respectively.
     /// If the force is set to decay, it resets the force to zero after applying.
     ///
     /// # Parameters
     /// - `point`: The Point instance to update.
-    fn update(&mut self, point: &mut Point) {
-        if !point.fixed {
-            point.velocity.0 += self.force.x;
-            point.velocity.1 += self.force.y;
+    fn update(&mut self, point: &mut Point, dt: f32) {
+        if !point.is_fixed() {
+            point.velocity.0 += self.force.x * dt;
+            point.velocity.1 += self.force.y * dt;
         }
     }
 
@@ -82,19 +95,92 @@ impl Component<Point> for Force {
 }
 
 impl Component<Quad> for Force {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
     /// Applies the force to the Quad's velocity each update.
     ///
-    /// Adds `x` and `y` components to `velocity_x` and `velocity_y` respectively.
+    /// Adds `x` and `y` components times `dt` to `velocity_x` and `velocity_y` respectively.
     /// If the force is set to decay, it resets the force to zero after applying.
     ///
     /// # Parameters
     /// - `quad`: The Quad instance to update.
-    fn update(&mut self, quad: &mut Quad) {
-        quad.velocity_x += self.force.x;
-        quad.velocity_y += self.force.y;
+    fn update(&mut self, quad: &mut Quad, dt: f32) {
+        if quad.body_type.is_dynamic() {
+            quad.velocity_x += self.force.x * dt;
+            quad.velocity_y += self.force.y * dt;
+        }
     }
 
     fn on_collide(&mut self, _me: &mut Quad, _other: &mut Quad) {
         // No collision handling needed for force
     }
 }
+
+impl Component<CircleBody> for Force {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Applies the force to the circle's velocity each update.
+    fn update(&mut self, circle: &mut CircleBody, dt: f32) {
+        if circle.body_type.is_dynamic() {
+            circle.velocity_x += self.force.x * dt;
+            circle.velocity_y += self.force.y * dt;
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut CircleBody, _other: &mut CircleBody) {
+        // No collision handling needed for force
+    }
+}
+
+impl Component<PolygonCollider> for Force {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Applies the force to the polygon's velocity each update.
+    fn update(&mut self, polygon: &mut PolygonCollider, dt: f32) {
+        if polygon.body_type.is_dynamic() {
+            polygon.velocity_x += self.force.x * dt;
+            polygon.velocity_y += self.force.y * dt;
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut PolygonCollider, _other: &mut PolygonCollider) {
+        // No collision handling needed for force
+    }
+}
+
+impl Component<GameObject> for Force {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Applies the force to the object's velocity each update.
+    fn update(&mut self, object: &mut GameObject, dt: f32) {
+        object.velocity += self.force * dt;
+    }
+
+    fn on_collide(&mut self, _me: &mut GameObject, _other: &mut GameObject) {
+        // No collision handling needed for force
+    }
+}
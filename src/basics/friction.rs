@@ -1,11 +1,31 @@
 use crate::basics::Component;
+use crate::basics::collision::Segment;
+use crate::objects::circle::CircleBody;
 use crate::objects::point::Point;
+use crate::objects::polygon::PolygonCollider;
 use crate::objects::quad::Quad;
 
 /// Component that applies friction to an object's movement
+///
+/// The coefficient is the fraction of speed kept every sixtieth of a second,
+/// so values tuned for 60 updates per second slow objects just as much at
+/// any other frame rate.
+///
+/// By default friction slows the object all the time. Ground friction only
+/// slows it on the update after it touched something, so it keeps its speed
+/// in the air; use `Drag` for air resistance. Once the object is slower than
+/// `stop_speed` it is stopped outright instead of sliding ever more slowly.
 pub struct Friction {
-    /// The friction coefficient (0.0 to 1.0)
+    /// The friction coefficient (0.0 to 1.0), the fraction of speed kept every sixtieth of a second
     pub coefficient: f32,
+    /// Speed below which friction stops the object completely
+    pub stop_speed: f32,
+    /// Only slow the object after it touched something
+    pub ground_only: bool,
+    /// Whether friction slows the object at all
+    pub enabled: bool,
+    /// Whether the object collided since the last update
+    touching: bool,
 }
 
 impl Friction {
@@ -17,38 +37,168 @@ impl Friction {
     /// # Returns
     /// A new `Friction` instance.
     pub fn new(coefficient: f32) -> Self {
-        Self { coefficient }
+        Self {
+            coefficient,
+            stop_speed: 0.0,
+            ground_only: false,
+            enabled: true,
+            touching: false,
+        }
+    }
+
+    /// Creates friction that only slows the object while it touches the ground or another object.
+    ///
+    /// Objects slower than one pixel per second are stopped, so they come to a real rest.
+    ///
+    /// # Parameters
+    /// - `coefficient`: A value (usually < 1) that slows the object while it touches something.
+    ///
+    /// # Returns
+    /// A new `Friction` instance.
+    pub fn ground(coefficient: f32) -> Self {
+        Self {
+            stop_speed: 1.0,
+            ground_only: true,
+            ..Self::new(coefficient)
+        }
+    }
+
+    /// Set the speed below which friction stops the object completely
+    pub fn with_stop_speed(mut self, stop_speed: f32) -> Self {
+        self.stop_speed = stop_speed.max(0.0);
+        self
+    }
+
+    /// Returns `velocity` after `dt` seconds of friction.
+    fn slow(&mut self, velocity: (f32, f32), dt: f32) -> (f32, f32) {
+        let touching = std::mem::take(&mut self.touching);
+        if self.ground_only && !touching {
+            return velocity;
+        }
+        let kept = self.coefficient.powf(dt * 60.0);
+        let slowed = (velocity.0 * kept, velocity.1 * kept);
+        if slowed.0.hypot(slowed.1) < self.stop_speed {
+            (0.0, 0.0)
+        } else {
+            slowed
+        }
     }
 }
 
 impl Component<Point> for Friction {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
     /// Updates the Point's velocity by applying friction.
     ///
-    /// Multiplies `velocity.0` and `velocity.1` by the friction coefficient each frame,
-    /// gradually reducing speed until it stops when below a small threshold.
-    fn update(&mut self, point: &mut Point) {
-        if !point.fixed {
-            point.velocity.0 *= self.coefficient;
-            point.velocity.1 *= self.coefficient;
+    /// Multiplies `velocity.0` and `velocity.1` by the friction coefficient every sixtieth of a second,
+    /// gradually reducing speed until it stops when below `stop_speed`.
+    fn update(&mut self, point: &mut Point, dt: f32) {
+        if !point.is_fixed() {
+            point.velocity = self.slow(point.velocity, dt);
         }
     }
 
     fn on_collide(&mut self, _me: &mut Point, _other: &mut Point) {
-        // No collision handling needed for friction
+        self.touching = true;
+    }
+
+    fn on_collide_quad(&mut self, _me: &mut Point, _quad: &mut Quad) {
+        self.touching = true;
+    }
+
+    fn on_collide_circle(&mut self, _me: &mut Point, _circle: &mut CircleBody) {
+        self.touching = true;
     }
 }
 
 impl Component<Quad> for Friction {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
     /// Updates the Quad's horizontal and vertical velocities by applying friction.
     ///
-    /// Multiplies `velocity_x` and `velocity_y` by the friction coefficient each frame,
-    /// gradually reducing speed until it stops when below a small threshold.
-    fn update(&mut self, quad: &mut Quad) {
-        quad.velocity_x *= self.coefficient;
-        quad.velocity_y *= self.coefficient;
+    /// Multiplies `velocity_x` and `velocity_y` by the friction coefficient every sixtieth of a second,
+    /// gradually reducing speed until it stops when below `stop_speed`.
+    fn update(&mut self, quad: &mut Quad, dt: f32) {
+        if quad.body_type.is_dynamic() {
+            (quad.velocity_x, quad.velocity_y) = self.slow((quad.velocity_x, quad.velocity_y), dt);
+        }
     }
 
     fn on_collide(&mut self, _me: &mut Quad, _other: &mut Quad) {
-        // No collision handling needed for friction
+        self.touching = true;
+    }
+
+    fn on_collide_circle(&mut self, _me: &mut Quad, _circle: &mut CircleBody) {
+        self.touching = true;
+    }
+
+    fn on_collide_segment(&mut self, _me: &mut Quad, _segment: &Segment) {
+        self.touching = true;
+    }
+}
+
+impl Component<CircleBody> for Friction {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Updates the circle's velocities by applying friction.
+    fn update(&mut self, circle: &mut CircleBody, dt: f32) {
+        if circle.body_type.is_dynamic() {
+            (circle.velocity_x, circle.velocity_y) = self.slow((circle.velocity_x, circle.velocity_y), dt);
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut CircleBody, _other: &mut CircleBody) {
+        self.touching = true;
+    }
+
+    fn on_collide_quad(&mut self, _me: &mut CircleBody, _quad: &mut Quad) {
+        self.touching = true;
+    }
+}
+
+impl Component<PolygonCollider> for Friction {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Updates the polygon's velocities by applying friction.
+    fn update(&mut self, polygon: &mut PolygonCollider, dt: f32) {
+        if polygon.body_type.is_dynamic() {
+            (polygon.velocity_x, polygon.velocity_y) = self.slow((polygon.velocity_x, polygon.velocity_y), dt);
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut PolygonCollider, _other: &mut PolygonCollider) {
+        self.touching = true;
+    }
+
+    fn on_collide_quad(&mut self, _me: &mut PolygonCollider, _quad: &mut Quad) {
+        self.touching = true;
+    }
+
+    fn on_collide_circle(&mut self, _me: &mut PolygonCollider, _circle: &mut CircleBody) {
+        self.touching = true;
     }
 }
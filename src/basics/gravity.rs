@@ -1,33 +1,72 @@
+use macroquad::math::{Vec2, vec2};
+
 use crate::basics::Component;
+use crate::objects::circle::CircleBody;
+use crate::objects::game_object::GameObject;
 use crate::objects::point::Point;
+use crate::objects::polygon::PolygonCollider;
 use crate::objects::quad::Quad;
 
 /// Component that applies gravity to an object
+///
+/// The pull is scaled by each object's `gravity_scale`, so floaty or
+/// heavier-feeling objects can share one setup.
 pub struct Gravity {
-    /// The strength of gravity (positive values pull downward)
+    /// The strength of gravity in pixels per second squared (positive values pull along `direction`)
     pub strength: f32,
+    /// Unit vector gravity pulls along, straight down by default
+    pub direction: Vec2,
+    /// Whether gravity pulls at all
+    pub enabled: bool,
 }
 
 impl Gravity {
     /// Creates a new Gravity component with the specified strength.
     ///
     /// # Parameters
-    /// - `strength`: The gravity acceleration to apply per update frame.
+    /// - `strength`: The gravity acceleration, in pixels per second squared.
     ///
     /// # Returns
     /// A new `Gravity` instance.
     pub fn new(strength: f32) -> Self {
-        Self { strength }
+        Self {
+            strength,
+            direction: vec2(0.0, 1.0),
+            enabled: true,
+        }
+    }
+
+    /// Sets the direction gravity pulls in, for wall-walking or flipped gravity.
+    ///
+    /// The direction is normalized; a zero vector turns gravity off.
+    pub fn with_direction(mut self, direction: Vec2) -> Self {
+        self.direction = direction.normalize_or_zero();
+        self
+    }
+
+    /// Returns the velocity change per second before an object's `gravity_scale`.
+    pub fn acceleration(&self) -> Vec2 {
+        self.direction * self.strength
     }
 }
 
 impl Component<Point> for Gravity {
-    /// Updates the Point's velocity by adding the gravity force to its vertical velocity.
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Updates the Point's velocity by adding the gravity force along `direction`.
     ///
-    /// This simulates gravity pulling the Point downward every frame.
-    fn update(&mut self, point: &mut Point) {
-        if !point.fixed {
-            point.velocity.1 += self.strength;
+    /// This simulates gravity pulling the Point, by the same amount per second at any frame rate.
+    fn update(&mut self, point: &mut Point, dt: f32) {
+        if !point.is_fixed() {
+            let pull = self.acceleration() * point.gravity_scale * dt;
+            point.velocity.0 += pull.x;
+            point.velocity.1 += pull.y;
         }
     }
 
@@ -38,11 +77,23 @@ impl Component<Point> for Gravity {
 }
 
 impl Component<Quad> for Gravity {
-    /// Updates the Quad's velocity by adding the gravity force to its vertical velocity.
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Updates the Quad's velocity by adding the gravity force along `direction`.
     ///
-    /// This simulates gravity pulling the Quad downward every frame.
-    fn update(&mut self, quad: &mut Quad) {
-        quad.velocity_y += self.strength;
+    /// This simulates gravity pulling the Quad, by the same amount per second at any frame rate.
+    fn update(&mut self, quad: &mut Quad, dt: f32) {
+        if quad.body_type.is_dynamic() {
+            let pull = self.acceleration() * quad.gravity_scale * dt;
+            quad.velocity_x += pull.x;
+            quad.velocity_y += pull.y;
+        }
     }
 
     /// No collision handling needed for gravity
@@ -50,3 +101,71 @@ impl Component<Quad> for Gravity {
         // No collision handling needed for gravity
     }
 }
+
+impl Component<CircleBody> for Gravity {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Updates the circle's velocity by adding the gravity force along `direction`.
+    fn update(&mut self, circle: &mut CircleBody, dt: f32) {
+        if circle.body_type.is_dynamic() {
+            let pull = self.acceleration() * circle.gravity_scale * dt;
+            circle.velocity_x += pull.x;
+            circle.velocity_y += pull.y;
+        }
+    }
+
+    /// No collision handling needed for gravity
+    fn on_collide(&mut self, _me: &mut CircleBody, _other: &mut CircleBody) {
+        // No collision handling needed for gravity
+    }
+}
+
+impl Component<PolygonCollider> for Gravity {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Updates the polygon's velocity by adding the gravity force along `direction`.
+    fn update(&mut self, polygon: &mut PolygonCollider, dt: f32) {
+        if polygon.body_type.is_dynamic() {
+            let pull = self.acceleration() * polygon.gravity_scale * dt;
+            polygon.velocity_x += pull.x;
+            polygon.velocity_y += pull.y;
+        }
+    }
+
+    /// No collision handling needed for gravity
+    fn on_collide(&mut self, _me: &mut PolygonCollider, _other: &mut PolygonCollider) {
+        // No collision handling needed for gravity
+    }
+}
+
+impl Component<GameObject> for Gravity {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Updates the object's velocity by adding the gravity force along `direction`.
+    fn update(&mut self, object: &mut GameObject, dt: f32) {
+        object.velocity += self.acceleration() * object.gravity_scale * dt;
+    }
+
+    /// No collision handling needed for gravity
+    fn on_collide(&mut self, _me: &mut GameObject, _other: &mut GameObject) {
+        // No collision handling needed for gravity
+    }
+}
@@ -1,23 +1,249 @@
-use crate::objects::point::Point;
+use std::any::Any;
+
+use serde::{Deserialize, Serialize};
+
+use crate::basics::collision::Segment;
+use crate::basics::physics_world::BodyHandle;
+use crate::basics::transform::Transform;
+use crate::objects::circle::CircleBody;
 use crate::objects::quad::Quad;
 
 /// Trait for components that can be attached to objects
-pub trait Component<T> {
-    /// Update the component's state
-    fn update(&mut self, object: &mut T);
+///
+/// Components are `Any`, so objects can hand back the ones attached to them
+/// by type with `get_component`. A disabled component stays attached, but
+/// its owner skips it until it is enabled again.
+pub trait Component<T>: Any {
+    /// Update the component's state, `dt` seconds after the last update
+    fn update(&mut self, object: &mut T, dt: f32);
+
+    /// Called when the component is attached to `owner` with `add_component`
+    fn on_attach(&mut self, _owner: &mut T) {}
+
+    /// Called when the component is taken off `owner` with `remove_component`
+    fn on_detach(&mut self, _owner: &mut T) {}
+
+    /// Called when `owner` is recycled to be reused, to put the component back as it was when attached
+    fn on_reset(&mut self, _owner: &mut T) {}
+
+    /// Whether the owner should call the component's hooks, other than `on_attach` and `on_detach`
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    /// Turn the component off or back on without removing it
+    ///
+    /// Components that can't be turned off ignore this.
+    fn set_enabled(&mut self, _enabled: bool) {}
     
     /// Handle collision with another object
     fn on_collide(&mut self, me: &mut T, other: &mut T);
+
+    /// Handle collision with a Quad, for objects that aren't quads themselves
+    fn on_collide_quad(&mut self, _me: &mut T, _quad: &mut Quad) {}
+
+    /// Handle collision with a circle, for objects that aren't circles themselves
+    fn on_collide_circle(&mut self, _me: &mut T, _circle: &mut CircleBody) {}
+
+    /// Handle touching a segment of terrain, such as the link between two fixed points
+    fn on_collide_segment(&mut self, _me: &mut T, _segment: &Segment) {}
+
+    /// Called by a `PhysicsWorld` on the first step `me` touches `other`
+    fn on_collision_enter(&mut self, _me: &mut T, _other: BodyHandle) {}
+
+    /// Called by a `PhysicsWorld` on every later step `me` keeps touching `other`
+    fn on_collision_stay(&mut self, _me: &mut T, _other: BodyHandle) {}
+
+    /// Called by a `PhysicsWorld` on the first step `me` no longer touches `other`
+    fn on_collision_exit(&mut self, _me: &mut T, _other: BodyHandle) {}
+
+    /// Called by a `PhysicsWorld` when `me` starts overlapping `other` and either is a trigger
+    fn on_trigger_enter(&mut self, _me: &mut T, _other: BodyHandle) {}
+
+    /// Called by a `PhysicsWorld` when `me` stops overlapping `other` and either is a trigger
+    fn on_trigger_exit(&mut self, _me: &mut T, _other: BodyHandle) {}
+}
+
+/// Objects that components can be attached to
+///
+/// Owners only hand out their component list; attaching, finding, removing
+/// and running components works the same for every object. Components are
+/// taken out of their owner while their hooks run, so a component can't find
+/// itself or its siblings on `me` with `get_component`.
+///
+/// # Example
+///
+/// ```rust
+/// let mut quad = Quad::new(100.0, 200.0, 50.0, 50.0, WHITE);
+/// quad.add_component(Box::new(Gravity::new(600.0)));
+/// if let Some(gravity) = quad.get_component_mut::<Gravity>() {
+///     gravity.strength = 200.0;
+/// }
+/// quad.remove_component::<Gravity>();
+/// ```
+pub trait ComponentOwner: Sized + 'static {
+    /// The attached components
+    fn components(&self) -> &[Box<dyn Component<Self>>];
+
+    /// The attached components, mutably
+    fn components_mut(&mut self) -> &mut Vec<Box<dyn Component<Self>>>;
+
+    /// Attach a component, running its `on_attach` hook first
+    fn add_component(&mut self, mut comp: Box<dyn Component<Self>>) {
+        comp.on_attach(self);
+        self.components_mut().push(comp);
+    }
+
+    /// Returns the first attached component of type `C`, if any
+    fn get_component<C: Component<Self>>(&self) -> Option<&C> {
+        self.components().iter().find_map(|comp| (comp.as_ref() as &dyn Any).downcast_ref())
+    }
+
+    /// Returns the first attached component of type `C` mutably, if any
+    fn get_component_mut<C: Component<Self>>(&mut self) -> Option<&mut C> {
+        self.components_mut().iter_mut().find_map(|comp| (comp.as_mut() as &mut dyn Any).downcast_mut())
+    }
+
+    /// Checks whether a component of type `C` is attached
+    fn has_component<C: Component<Self>>(&self) -> bool {
+        self.get_component::<C>().is_some()
+    }
+
+    /// Removes every attached component of type `C`, running their `on_detach` hooks once they are off
+    fn remove_component<C: Component<Self>>(&mut self) {
+        let (removed, kept) = std::mem::take(self.components_mut())
+            .into_iter()
+            .partition::<Vec<_>, _>(|comp| (comp.as_ref() as &dyn Any).is::<C>());
+        *self.components_mut() = kept;
+        for mut comp in removed {
+            comp.on_detach(self);
+        }
+    }
+
+    /// Call `hook` with every enabled component and the owner, taking the components out while it runs
+    fn for_each_component(&mut self, mut hook: impl FnMut(&mut dyn Component<Self>, &mut Self)) {
+        let mut comps = std::mem::take(self.components_mut());
+        for comp in comps.iter_mut().filter(|comp| comp.is_enabled()) {
+            hook(comp.as_mut(), self);
+        }
+        *self.components_mut() = comps;
+    }
+
+    /// Runs the `on_reset` hook of every component, enabled or not, so the owner can be reused
+    fn reset_components(&mut self) {
+        let mut comps = std::mem::take(self.components_mut());
+        for comp in comps.iter_mut() {
+            comp.on_reset(self);
+        }
+        *self.components_mut() = comps;
+    }
+
+    /// Updates every enabled component, `dt` seconds after the last update
+    ///
+    /// Usually called once per game loop iteration with `get_frame_time()`.
+    fn update_components(&mut self, dt: f32) {
+        self.for_each_component(|comp, me| comp.update(me, dt));
+    }
+}
+
+/// Objects that can be tagged with names gameplay code looks them up by
+///
+/// Tags are plain strings, so an enum works as a tag once it converts to
+/// one with `Into<String>` and `AsRef<str>`.
+///
+/// # Example
+///
+/// ```rust
+/// let goblin = Quad::new(100.0, 200.0, 32.0, 32.0, GREEN).with_tag("enemy");
+/// if goblin.has_tag("enemy") {
+///     score += 10;
+/// }
+/// ```
+pub trait Tagged {
+    /// The object's tags
+    fn tags(&self) -> &[String];
+
+    /// The object's tags, mutably
+    fn tags_mut(&mut self) -> &mut Vec<String>;
+
+    /// Add a tag, unless the object has it already
+    fn add_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.has_tag(&tag) {
+            self.tags_mut().push(tag);
+        }
+    }
+
+    /// Add a tag and return the object, for building it in one expression
+    fn with_tag(mut self, tag: impl Into<String>) -> Self
+    where
+        Self: Sized,
+    {
+        self.add_tag(tag);
+        self
+    }
+
+    /// Remove a tag, returning whether the object had it
+    fn remove_tag(&mut self, tag: impl AsRef<str>) -> bool {
+        let tags = self.tags_mut();
+        let before = tags.len();
+        tags.retain(|t| t != tag.as_ref());
+        tags.len() != before
+    }
+
+    /// Check if the object has a tag
+    fn has_tag(&self, tag: impl AsRef<str>) -> bool {
+        self.tags().iter().any(|t| t == tag.as_ref())
+    }
 }
 
 /// Trait for components that can be drawn
 pub trait Drawable {
-    /// Draw the component
-    fn draw(&self);
+    /// Draw the component placed, turned and scaled by `transform`
+    fn draw(&self, transform: &Transform);
+}
+
+/// How an object takes part in the simulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BodyType {
+    /// Never moves, like floors and walls
+    Static,
+    /// Moves only by the velocity set from code and pushes dynamic bodies without being pushed back
+    Kinematic,
+    /// Moved by forces, collisions and constraints
+    #[default]
+    Dynamic,
+}
+
+impl BodyType {
+    /// Check if forces, collisions and constraints move the object
+    pub fn is_dynamic(self) -> bool {
+        self == BodyType::Dynamic
+    }
+
+    /// Check if the object moves by its velocity
+    pub fn moves(self) -> bool {
+        self != BodyType::Static
+    }
 }
 
+pub mod attractor;
 pub mod collision;
+pub mod drag;
 pub mod force;
 pub mod friction;
 pub mod gravity;
+#[cfg(feature = "parallel")]
+pub mod parallel;
 pub mod physics_config;
+pub mod physics_world;
+pub mod platformer;
+pub mod pool;
+pub mod point_body;
+pub mod render;
+pub mod scene;
+pub mod scene_manager;
+pub mod snapshot;
+pub mod spatial;
+pub mod sprite_animation;
+pub mod transform;
@@ -0,0 +1,139 @@
+//! Parallel Solving
+//!
+//! This module spreads the heaviest parts of a `PhysicsWorld` step over
+//! rayon's thread pool. It is only built with the `parallel` feature; without
+//! it the world solves and collides everything on one thread.
+//!
+//! # Features
+//! - Constraints grouped into batches that share no points, so each batch is solved at once
+//! - Batches kept between steps and only regrouped when the constraints change
+//! - Candidate collision pairs tested for overlap in parallel
+//!
+//! The thread pool only ever sees plain copies of positions, masses and
+//! shapes, never the objects themselves, so components attached to them
+//! don't need to be `Send` or `Sync`.
+//!
+//! # Examples
+//! ```toml
+//! [dependencies]
+//! ruty = { version = "0.1", features = ["parallel"] }
+//! ```
+
+use rayon::prelude::*;
+
+use crate::objects::constraint::{Constraint, ConstraintEnd};
+use crate::objects::point::Point;
+
+/// Group constraints into batches where no two constraints share a point
+///
+/// Constraints in one batch can be solved at the same time without fighting
+/// over a point. Constraints referring to missing points are left out.
+///
+/// # Returns
+/// Indices into `constraints`, batch by batch
+pub fn constraint_batches(constraints: &[Constraint], point_count: usize) -> Vec<Vec<usize>> {
+    let mut batches: Vec<Vec<usize>> = Vec::new();
+    // Which points each batch already moves
+    let mut used: Vec<Vec<bool>> = Vec::new();
+    for (index, constraint) in constraints.iter().enumerate() {
+        let (a, b) = (constraint.point1, constraint.point2);
+        if a >= point_count || b >= point_count {
+            continue;
+        }
+        let batch = match (0..batches.len()).find(|&batch| !used[batch][a] && !used[batch][b]) {
+            Some(batch) => batch,
+            None => {
+                batches.push(Vec::new());
+                used.push(vec![false; point_count]);
+                batches.len() - 1
+            }
+        };
+        used[batch][a] = true;
+        used[batch][b] = true;
+        batches[batch].push(index);
+    }
+    batches
+}
+
+/// Constraint batches kept between steps
+///
+/// Grouping is only redone when a constraint joins different points or the
+/// number of points changes, so a world with a fixed set of constraints
+/// groups them once.
+#[derive(Debug, Clone, Default)]
+pub struct ConstraintBatches {
+    /// Points each constraint joined when the batches were built
+    links: Vec<(usize, usize)>,
+    point_count: usize,
+    batches: Vec<Vec<usize>>,
+}
+
+impl ConstraintBatches {
+    /// Regroup `constraints` if they changed since the last call
+    pub fn update(&mut self, constraints: &[Constraint], point_count: usize) {
+        let unchanged = self.point_count == point_count
+            && self.links.len() == constraints.len()
+            && self
+                .links
+                .iter()
+                .zip(constraints)
+                .all(|(&link, constraint)| link == (constraint.point1, constraint.point2));
+        if unchanged {
+            return;
+        }
+        self.links = constraints.iter().map(|constraint| (constraint.point1, constraint.point2)).collect();
+        self.point_count = point_count;
+        self.batches = constraint_batches(constraints, point_count);
+    }
+
+    /// Indices into the constraints, batch by batch
+    pub fn batches(&self) -> &[Vec<usize>] {
+        &self.batches
+    }
+}
+
+/// Solve constraints once, batch by batch, each batch on the thread pool
+///
+/// # Arguments
+/// * `constraints` - All constraints
+/// * `batches` - Batches from `constraint_batches`
+/// * `points` - The points the constraints join
+/// * `solves` - Whether the constraint at an index is solved this time
+pub fn solve_constraints(
+    constraints: &[Constraint],
+    batches: &[Vec<usize>],
+    points: &mut [Point],
+    solves: impl Fn(usize) -> bool + Sync,
+) {
+    let mut ends: Vec<ConstraintEnd> = points.iter().map(ConstraintEnd::from).collect();
+    for batch in batches {
+        let shared = &ends;
+        let moves: Vec<_> = batch
+            .par_iter()
+            .filter(|&&index| solves(index))
+            .filter_map(|&index| {
+                let constraint = &constraints[index];
+                let (move1, move2) =
+                    constraint.correction_between(shared[constraint.point1], shared[constraint.point2])?;
+                Some((constraint.point1, move1, constraint.point2, move2))
+            })
+            .collect();
+        for (point1, move1, point2, move2) in moves {
+            ends[point1].position += move1;
+            ends[point2].position += move2;
+        }
+    }
+    for (point, end) in points.iter_mut().zip(&ends) {
+        point.position = end.position.into();
+    }
+}
+
+/// Keep the candidate pairs for which `overlaps` holds, testing them on the thread pool
+///
+/// The pairs stay in their original order.
+pub fn overlapping_pairs(
+    pairs: Vec<(usize, usize)>,
+    overlaps: impl Fn(usize, usize) -> bool + Sync,
+) -> Vec<(usize, usize)> {
+    pairs.into_par_iter().filter(|&(i, j)| overlaps(i, j)).collect()
+}
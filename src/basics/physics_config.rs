@@ -8,41 +8,76 @@
 //! - Adjustable friction
 //! - Configurable collision response
 //! - Physics presets
+//! - Solver iteration and substep counts
+//! - Velocity damping and a speed limit that keep tangled shapes from exploding
+//! - Per-layer overrides for solver iterations, self-collision and air resistance
+//! - Loading and saving as TOML or JSON, with hot reloading while playtesting
 //! 
 //! # Examples
 //! ```rust
 //! use ruty::basics::physics_config::PhysicsConfig;
 //! 
 //! let config = PhysicsConfig::new()
-//!     .gravity(9.81)
+//!     .gravity(588.6)
 //!     .friction(0.8)
 //!     .bounce(0.5)
-//!     .air_resistance(0.1);
+//!     .air_resistance(0.1)
+//!     .solver_iterations(12)
+//!     .substeps(2)
+//!     .max_linear_velocity(1500.0)
+//!     .damping(0.2)
+//!     .add_layer("debris", LayerConfig::new().with_solver_iterations(2).with_self_collision(false))
+//!     .add_layer("water", LayerConfig::new().with_air_resistance(3.0));
+//!
+//! let mut watcher = ConfigWatcher::new("assets/physics.toml");
+//! if let Ok(Some(config)) = watcher.poll() {
+//!     world.config = config;
+//! }
 //! ```
 
 use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::data_file;
 
 /// Physics configuration
-#[derive(Debug, Clone)]
+///
+/// Fields missing from a loaded file keep their `new` values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PhysicsConfig {
-    /// Global gravity strength
+    /// Global gravity strength, in pixels per second squared
     pub gravity: f32,
     /// Global friction coefficient
     pub friction: f32,
     /// Global bounce coefficient
     pub bounce: f32,
-    /// Air resistance coefficient
+    /// Air resistance coefficient, the fraction of speed a `PhysicsWorld` removes per second
     pub air_resistance: f32,
+    /// How many times constraints are solved per substep
+    pub solver_iterations: usize,
+    /// How many smaller steps each physics step is split into
+    pub substeps: usize,
+    /// Fastest a dynamic object may move in pixels per second, or `None` for no limit
+    pub max_linear_velocity: Option<f32>,
+    /// Fraction of linear and angular velocity removed per second, applied every substep
+    pub damping: f32,
     /// Physics presets
     pub presets: HashMap<String, PhysicsPreset>,
     /// Custom physics properties
     pub custom_properties: HashMap<String, f32>,
+    /// Overrides for objects whose `layer` has the same name
+    pub layers: HashMap<String, LayerConfig>,
 }
 
 /// Physics preset
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhysicsPreset {
-    /// Gravity strength
+    /// Gravity strength, in pixels per second squared
     pub gravity: f32,
     /// Friction coefficient
     pub friction: f32,
@@ -52,16 +87,35 @@ pub struct PhysicsPreset {
     pub air_resistance: f32,
 }
 
+/// Settings that replace the global ones for objects on one layer
+///
+/// Unset values fall back to the `PhysicsConfig` the layer belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayerConfig {
+    /// Solver iterations for constraints between points on this layer
+    pub solver_iterations: Option<usize>,
+    /// Whether objects on this layer collide with each other
+    pub self_collision: bool,
+    /// Air resistance for objects on this layer, the fraction of speed removed per second
+    pub air_resistance: Option<f32>,
+}
+
 impl PhysicsConfig {
     /// Create a new physics configuration
     pub fn new() -> Self {
         Self {
-            gravity: 9.81,
+            gravity: 588.6,
             friction: 0.8,
             bounce: 0.5,
             air_resistance: 0.1,
+            solver_iterations: 8,
+            substeps: 1,
+            max_linear_velocity: None,
+            damping: 0.0,
             presets: HashMap::new(),
             custom_properties: HashMap::new(),
+            layers: HashMap::new(),
         }
     }
 
@@ -89,6 +143,30 @@ impl PhysicsConfig {
         self
     }
 
+    /// Set how many times constraints are solved per substep
+    pub fn solver_iterations(mut self, iterations: usize) -> Self {
+        self.solver_iterations = iterations;
+        self
+    }
+
+    /// Set how many smaller steps each physics step is split into
+    pub fn substeps(mut self, substeps: usize) -> Self {
+        self.substeps = substeps.max(1);
+        self
+    }
+
+    /// Set the fastest a dynamic object may move in pixels per second
+    pub fn max_linear_velocity(mut self, speed: f32) -> Self {
+        self.max_linear_velocity = Some(speed.max(0.0));
+        self
+    }
+
+    /// Set the fraction of linear and angular velocity removed per second
+    pub fn damping(mut self, damping: f32) -> Self {
+        self.damping = damping.max(0.0);
+        self
+    }
+
     /// Add a physics preset
     pub fn add_preset(mut self, name: &str, preset: PhysicsPreset) -> Self {
         self.presets.insert(name.to_string(), preset);
@@ -101,6 +179,30 @@ impl PhysicsConfig {
         self
     }
 
+    /// Add overrides for objects on the layer called `name`
+    pub fn add_layer(mut self, name: &str, layer: LayerConfig) -> Self {
+        self.layers.insert(name.to_string(), layer);
+        self
+    }
+
+    /// Copy a preset's gravity, friction, bounce and air resistance into this configuration
+    pub fn apply_preset(&mut self, preset: &PhysicsPreset) {
+        self.gravity = preset.gravity;
+        self.friction = preset.friction;
+        self.bounce = preset.bounce;
+        self.air_resistance = preset.air_resistance;
+    }
+
+    /// Load a configuration from a `.toml` or `.json` file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<PhysicsConfig, Box<dyn Error>> {
+        data_file::load(path)
+    }
+
+    /// Save the configuration, presets and custom properties included, to a `.toml` or `.json` file
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        data_file::save(self, path)
+    }
+
     /// Get a physics preset
     pub fn get_preset(&self, name: &str) -> Option<&PhysicsPreset> {
         self.presets.get(name)
@@ -111,10 +213,40 @@ impl PhysicsConfig {
         self.custom_properties.get(name).copied()
     }
 
+    /// Get the overrides for a layer
+    pub fn get_layer(&self, name: &str) -> Option<&LayerConfig> {
+        self.layers.get(name)
+    }
+
+    /// Solver iterations for constraints between points on `layer`
+    pub fn layer_solver_iterations(&self, layer: Option<&str>) -> usize {
+        self.layer_setting(layer, |layer| layer.solver_iterations).unwrap_or(self.solver_iterations)
+    }
+
+    /// Air resistance for objects on `layer`
+    pub fn layer_air_resistance(&self, layer: Option<&str>) -> f32 {
+        self.layer_setting(layer, |layer| layer.air_resistance).unwrap_or(self.air_resistance)
+    }
+
+    /// Check if objects on layers `a` and `b` may collide with each other
+    ///
+    /// Only objects sharing a layer with `self_collision` turned off are kept apart.
+    pub fn layers_collide(&self, a: Option<&str>, b: Option<&str>) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) if a == b => self.get_layer(a).is_none_or(|layer| layer.self_collision),
+            _ => true,
+        }
+    }
+
+    /// Look up a value a layer overrides, if the object has a layer and it sets one
+    fn layer_setting<T>(&self, layer: Option<&str>, setting: impl Fn(&LayerConfig) -> Option<T>) -> Option<T> {
+        self.get_layer(layer?).and_then(setting)
+    }
+
     /// Create a low gravity preset
     pub fn low_gravity() -> PhysicsPreset {
         PhysicsPreset {
-            gravity: 2.0,
+            gravity: 120.0,
             friction: 0.8,
             bounce: 0.7,
             air_resistance: 0.05,
@@ -124,7 +256,7 @@ impl PhysicsConfig {
     /// Create a high friction preset
     pub fn high_friction() -> PhysicsPreset {
         PhysicsPreset {
-            gravity: 9.81,
+            gravity: 588.6,
             friction: 0.95,
             bounce: 0.2,
             air_resistance: 0.2,
@@ -134,7 +266,7 @@ impl PhysicsConfig {
     /// Create a bouncy preset
     pub fn bouncy() -> PhysicsPreset {
         PhysicsPreset {
-            gravity: 9.81,
+            gravity: 588.6,
             friction: 0.5,
             bounce: 0.9,
             air_resistance: 0.05,
@@ -144,7 +276,7 @@ impl PhysicsConfig {
     /// Create a space-like preset
     pub fn space_like() -> PhysicsPreset {
         PhysicsPreset {
-            gravity: 0.1,
+            gravity: 6.0,
             friction: 0.1,
             bounce: 0.8,
             air_resistance: 0.0,
@@ -152,6 +284,47 @@ impl PhysicsConfig {
     }
 }
 
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayerConfig {
+    /// Create a layer that overrides nothing
+    pub fn new() -> Self {
+        Self {
+            solver_iterations: None,
+            self_collision: true,
+            air_resistance: None,
+        }
+    }
+
+    /// Set the solver iterations for constraints between points on this layer
+    pub fn with_solver_iterations(mut self, iterations: usize) -> Self {
+        self.solver_iterations = Some(iterations);
+        self
+    }
+
+    /// Set whether objects on this layer collide with each other
+    pub fn with_self_collision(mut self, self_collision: bool) -> Self {
+        self.self_collision = self_collision;
+        self
+    }
+
+    /// Set the air resistance for objects on this layer
+    pub fn with_air_resistance(mut self, air_resistance: f32) -> Self {
+        self.air_resistance = Some(air_resistance);
+        self
+    }
+}
+
+impl Default for LayerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PhysicsPreset {
     /// Create a new physics preset
     pub fn new(gravity: f32, friction: f32, bounce: f32, air_resistance: f32) -> Self {
@@ -162,4 +335,39 @@ impl PhysicsPreset {
             air_resistance,
         }
     }
-} 
\ No newline at end of file
+} 
+
+/// Reloads a `PhysicsConfig` file whenever it changes on disk
+///
+/// Poll it once a frame while playtesting to pick up tuning as soon as the file is saved.
+pub struct ConfigWatcher {
+    pub path: PathBuf,
+    /// Modification time of the file when it was last loaded
+    loaded: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Watch the `.toml` or `.json` file at `path`; the first `poll` always loads it
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            loaded: None,
+        }
+    }
+
+    /// Load the file if it changed since the last successful load
+    ///
+    /// # Returns
+    /// The new configuration, `None` if the file is unchanged, or the error
+    /// from reading or parsing it. A file that fails to parse is tried again
+    /// on the next poll, so fixing a typo and saving is enough.
+    pub fn poll(&mut self) -> Result<Option<PhysicsConfig>, Box<dyn Error>> {
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+        if self.loaded == Some(modified) {
+            return Ok(None);
+        }
+        let config = PhysicsConfig::from_file(&self.path)?;
+        self.loaded = Some(modified);
+        Ok(Some(config))
+    }
+}
@@ -0,0 +1,1801 @@
+//! Physics World
+//!
+//! This module provides a container that owns the physics objects of a scene
+//! and steps them together, so game code no longer has to update, solve and
+//! collide loose vectors of points, constraints and quads by hand.
+//!
+//! # Features
+//! - Owns points, constraints, quads, circles and convex polygons
+//! - Static, kinematic and dynamic bodies, with static pairs skipped by the broad-phase
+//! - Integrates components and velocities each step
+//! - Air resistance from the `PhysicsConfig` on every dynamic object
+//! - Velocity damping and a speed limit from the `PhysicsConfig`, enforced every substep
+//! - Collision groups keeping the points of one shape from colliding with each other
+//! - Per-layer solver iterations, self-collision and air resistance from the `PhysicsConfig`
+//! - Attractors pulling nearby objects, found through the broad-phase
+//! - Hinge joints between quads, with angle limits and motors
+//! - Motors that spin rings of points like powered wheels
+//! - Ropes tied to fixed anchors, points or quads
+//! - Solves distance constraints, with solver iterations and substeps from a `PhysicsConfig`
+//! - Rigid point bodies that move as one piece instead of solving their constraints
+//! - Shape matching that pulls soft bodies back toward their rest shape
+//! - Quads that follow soft bodies, to carry their sprites
+//! - Solid or gradient surfaces drawn inside soft bodies
+//! - Quads parented to other quads, keeping their spot and angle on them
+//! - With the `parallel` feature, constraints and collision tests run on a thread pool
+//! - Quads walking on terrain made of constraints between fixed points, with the ground normal reported
+//! - Terrain colliders built from polylines or heightmaps
+//! - Particle fluids flowing around quads, circles and terrain
+//! - Finds collisions through a spatial hash broad-phase and calls `on_collide` on the colliding objects' components
+//! - Fixed-timestep stepping independent of the frame rate
+//! - Sleeping for bodies that come to rest, waking on contact, new velocity or an applied force
+//! - Collision enter, stay and exit events tracked across steps
+//! - Trigger bodies that report enter and exit events instead of colliding
+//! - Swept rectangle and circle casts against everything in the world
+//! - Point, rectangle and circle overlap queries backed by the broad-phase
+//! - Lookup of objects by tag, to tell what a collision event hit
+//! - Bodies drawn by draw layer, and optionally by their bottom edge for top-down games
+//! - Snapshots of the moving state that can be restored for rewinds and rollback
+//! - Recycling quads and circles for reuse, with their components reset instead of rebuilt
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::physics_world::PhysicsWorld;
+//! use ruty::objects::shapes::{create_square, ShapeConfig};
+//!
+//! let mut world = PhysicsWorld::new();
+//! let (points, constraints) = create_square(vec2(400.0, 200.0), vec2(430.0, 200.0), ShapeConfig::new().color(RED).point_radius(8.0));
+//! world.add_body(points, constraints);
+//!
+//! loop {
+//!     world.advance(get_frame_time());
+//!     world.draw();
+//!     next_frame().await;
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::ops::Range;
+
+use macroquad::prelude::*;
+
+use crate::basics::{BodyType, Component, ComponentOwner, Tagged};
+use crate::basics::attractor::Attractor;
+use crate::basics::drag::Drag;
+use crate::basics::force::Force;
+#[cfg(feature = "parallel")]
+use crate::basics::collision::BodyShape;
+#[cfg(feature = "parallel")]
+use crate::basics::parallel;
+use crate::basics::physics_config::PhysicsConfig;
+use crate::basics::point_body::{RigidPointBody, ShapeMatch, SoftBodyAnchor};
+use crate::basics::render::Renderer;
+use crate::basics::snapshot::{BodyState, PhysicsSnapshot, PointState};
+use crate::basics::transform::Transform;
+use crate::basics::collision::{
+    CastHit, Segment, SpatialHash, circle_cast_aabb, circle_cast_circle, circle_cast_quad, circle_overlap,
+    circle_quad_overlap, polygon_circle_overlap, polygon_quad_overlap, segment_quad_overlap, sweep_aabb,
+};
+use crate::objects::circle::CircleBody;
+use crate::objects::constraint::{Constraint, Motor};
+use crate::objects::fluid::Fluid;
+use crate::objects::joint::HingeJoint;
+use crate::objects::point::Point;
+use crate::objects::polygon::PolygonCollider;
+use crate::objects::quad::Quad;
+use crate::objects::shapes::ShapeFill;
+use crate::objects::terrain::TerrainCollider;
+
+/// Reference to an object in a `PhysicsWorld` by kind and index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BodyHandle {
+    Point(usize),
+    Quad(usize),
+    Circle(usize),
+    Polygon(usize),
+}
+
+/// What one end of a rope is tied to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RopeEnd {
+    /// Hangs loose
+    Free,
+    /// Pinned where it starts
+    Fixed,
+    /// Tied to a point already in the world, by index
+    Point(usize),
+    /// Tied to a quad in the world, by index, at an offset from its unrotated top-left corner
+    Quad(usize, Vec2),
+}
+
+/// Keeps a point stuck to a spot on a quad
+///
+/// The quad drags the point along but isn't pulled back by it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadAnchor {
+    pub point: usize,
+    pub quad: usize,
+    /// Offset from the quad's unrotated top-left corner
+    pub offset: Vec2,
+}
+
+/// Keeps a quad at a spot and angle on another quad, like a sword in a hand or a turret on a tank
+///
+/// The parent carries the child along but isn't pulled back by it, and the
+/// two never collide. Change `local` to move or turn the child on its
+/// parent, such as aiming the turret.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadChild {
+    pub child: usize,
+    pub parent: usize,
+    /// Child's pivot point and rotation relative to the parent's
+    pub local: Transform,
+}
+
+/// Whether two objects started, kept or stopped touching
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactPhase {
+    Enter,
+    Stay,
+    Exit,
+}
+
+/// Two objects started, kept or stopped touching during a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionEvent {
+    /// The object first in handle order
+    pub a: BodyHandle,
+    pub b: BodyHandle,
+    pub phase: ContactPhase,
+}
+
+/// Whether a trigger overlap began or ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerPhase {
+    Enter,
+    Exit,
+}
+
+/// A body started or stopped overlapping a trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerEvent {
+    /// The trigger body; when both bodies are triggers, the one first in broad-phase order
+    pub trigger: BodyHandle,
+    /// The body that entered or left it
+    pub other: BodyHandle,
+    pub phase: TriggerPhase,
+}
+
+/// Container that owns and simulates physics objects
+pub struct PhysicsWorld {
+    pub points: Vec<Point>,
+    /// Distance constraints, indexing into `points`
+    pub constraints: Vec<Constraint>,
+    pub quads: Vec<Quad>,
+    pub circles: Vec<CircleBody>,
+    pub polygons: Vec<PolygonCollider>,
+    /// Points moved along with quads
+    pub anchors: Vec<QuadAnchor>,
+    /// Quads carried by other quads, indexing into `quads`
+    pub quad_children: Vec<QuadChild>,
+    /// Motors driving rings of points, indexing into `points`
+    pub motors: Vec<Motor>,
+    /// Hinges joining quads, indexing into `quads`
+    pub hinges: Vec<HingeJoint>,
+    /// Point bodies moved as one piece, indexing into `points`
+    pub rigid_bodies: Vec<RigidPointBody>,
+    /// Soft bodies pulled back toward their rest shape, indexing into `points`
+    pub shape_matches: Vec<ShapeMatch>,
+    /// Quads that follow point bodies, indexing into `quads` and `points`
+    pub soft_anchors: Vec<SoftBodyAnchor>,
+    /// Surfaces drawn inside soft bodies, indexing into `points`
+    pub fills: Vec<ShapeFill>,
+    /// Gravity wells pulling objects in range
+    pub attractors: Vec<Attractor>,
+    /// Static ground that points, quads and circles rest on
+    pub terrains: Vec<TerrainCollider>,
+    /// Liquids flowing around the quads, circles and terrain
+    pub fluids: Vec<Fluid>,
+    /// Solver iterations, substeps and air resistance used by `step`
+    pub config: PhysicsConfig,
+    /// Length of one fixed step in seconds, used by `advance`
+    pub timestep: f32,
+    /// Most fixed steps one `advance` call may run, so a long frame can't snowball
+    pub max_steps: usize,
+    /// Speed in pixels per second below which a body counts as resting
+    pub sleep_velocity: f32,
+    /// Steps a body must rest before it falls asleep, or 0 to never sleep
+    pub sleep_steps: u32,
+    /// Draw bodies on the same draw layer by their bottom edge, so lower ones overlap higher ones
+    pub sort_by_y: bool,
+    /// Real time not yet simulated
+    accumulator: f32,
+    broad_phase: SpatialHash,
+    /// Pairs of solid objects touching as of the last step
+    contacts: HashSet<(BodyHandle, BodyHandle)>,
+    collision_events: Vec<CollisionEvent>,
+    /// Pairs overlapping with a trigger as of the last step
+    trigger_contacts: HashSet<(BodyHandle, BodyHandle)>,
+    trigger_events: Vec<TriggerEvent>,
+    /// Most upward normal of the terrain each quad stood on during the last step
+    ground_normals: HashMap<usize, Vec2>,
+    /// Broad-phase of every object as of the last step, used by the overlap queries
+    query_index: SpatialHash,
+    /// Object behind each index in `query_index`
+    query_handles: Vec<BodyHandle>,
+    /// Quads put away by `recycle`, waiting for `respawn_quad`
+    recycled_quads: Vec<usize>,
+    /// Circles put away by `recycle`, waiting for `respawn_circle`
+    recycled_circles: Vec<usize>,
+    /// Forces from `apply_force` waiting for the next step
+    pending_forces: Vec<(BodyHandle, Vec2)>,
+    /// Constraints grouped for solving on the thread pool, kept until the constraints change
+    #[cfg(feature = "parallel")]
+    constraint_batches: parallel::ConstraintBatches,
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhysicsWorld {
+    /// Create an empty world
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            constraints: Vec::new(),
+            quads: Vec::new(),
+            circles: Vec::new(),
+            polygons: Vec::new(),
+            anchors: Vec::new(),
+            quad_children: Vec::new(),
+            motors: Vec::new(),
+            hinges: Vec::new(),
+            rigid_bodies: Vec::new(),
+            shape_matches: Vec::new(),
+            soft_anchors: Vec::new(),
+            fills: Vec::new(),
+            attractors: Vec::new(),
+            terrains: Vec::new(),
+            fluids: Vec::new(),
+            config: PhysicsConfig::new(),
+            timestep: 1.0 / 60.0,
+            max_steps: 8,
+            sleep_velocity: 2.0,
+            sleep_steps: 60,
+            sort_by_y: false,
+            accumulator: 0.0,
+            broad_phase: SpatialHash::new(64.0),
+            contacts: HashSet::new(),
+            collision_events: Vec::new(),
+            trigger_contacts: HashSet::new(),
+            trigger_events: Vec::new(),
+            ground_normals: HashMap::new(),
+            query_index: SpatialHash::new(64.0),
+            query_handles: Vec::new(),
+            recycled_quads: Vec::new(),
+            recycled_circles: Vec::new(),
+            pending_forces: Vec::new(),
+            #[cfg(feature = "parallel")]
+            constraint_batches: parallel::ConstraintBatches::default(),
+        }
+    }
+
+    /// Use `config` for solver iterations, substeps and air resistance
+    pub fn with_config(mut self, config: PhysicsConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set how many fixed steps `advance` runs per second
+    pub fn set_tick_rate(&mut self, hz: f32) {
+        self.timestep = 1.0 / hz.max(1.0);
+    }
+
+    /// Get how many fixed steps `advance` runs per second
+    pub fn tick_rate(&self) -> f32 {
+        1.0 / self.timestep
+    }
+
+    /// Simulate `real_dt` seconds of frame time in fixed steps
+    ///
+    /// Leftover time carries over to the next call. Returns how many steps ran,
+    /// which is zero when the frame was shorter than one step.
+    ///
+    /// The collision and trigger events of every step run are queued together,
+    /// and those not drained since the previous call are dropped.
+    pub fn advance(&mut self, real_dt: f32) -> usize {
+        self.collision_events.clear();
+        self.trigger_events.clear();
+        self.accumulator += real_dt.max(0.0);
+        let mut steps = 0;
+        while self.accumulator >= self.timestep && steps < self.max_steps {
+            self.simulate(self.timestep);
+            self.accumulator -= self.timestep;
+            steps += 1;
+        }
+        if steps == self.max_steps {
+            // Drop the backlog instead of trying to catch up next frame
+            self.accumulator = self.accumulator.min(self.timestep);
+        }
+        steps
+    }
+
+    /// How far the simulation is between the last step and the next, from 0.0 to 1.0
+    ///
+    /// Use it to interpolate drawn positions for smooth motion at any frame rate.
+    pub fn interpolation_alpha(&self) -> f32 {
+        (self.accumulator / self.timestep).clamp(0.0, 1.0)
+    }
+
+    /// Set the cell size of the collision broad-phase
+    ///
+    /// Around the size of a typical object works best: much smaller cells put
+    /// each object in many cells, much larger ones report too many pairs.
+    pub fn set_cell_size(&mut self, cell_size: f32) {
+        self.broad_phase = SpatialHash::new(cell_size);
+    }
+
+    /// Add a point, returning its index
+    pub fn add_point(&mut self, point: Point) -> usize {
+        self.points.push(point);
+        self.points.len() - 1
+    }
+
+    /// Add a constraint between two points already in the world
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+    }
+
+    /// Add a motor driving points already in the world
+    pub fn add_motor(&mut self, motor: Motor) {
+        self.motors.push(motor);
+    }
+
+    /// Add a hinge between two quads already in the world
+    ///
+    /// Returns false, leaving the hinge out, if it joins a quad to itself or
+    /// names a quad that isn't in the world.
+    pub fn add_hinge(&mut self, hinge: HingeJoint) -> bool {
+        if !hinge.is_valid(self.quads.len()) {
+            return false;
+        }
+        self.hinges.push(hinge);
+        true
+    }
+
+    /// Create a hinge joining quads `a` and `b` at a world position
+    ///
+    /// The quads' current relative rotation becomes the hinge's zero angle.
+    /// Returns the hinge for further setup; pass it to `add_hinge`.
+    pub fn hinge_at(&self, a: usize, b: usize, anchor: Vec2) -> HingeJoint {
+        let mut hinge = HingeJoint::new(a, self.quads[a].to_local(anchor), b, self.quads[b].to_local(anchor));
+        hinge.reference_angle = self.quads[b].rotation - self.quads[a].rotation;
+        hinge
+    }
+
+    /// Add an attractor, returning its index
+    pub fn add_attractor(&mut self, attractor: Attractor) -> usize {
+        self.attractors.push(attractor);
+        self.attractors.len() - 1
+    }
+
+    /// Add a terrain, returning its index
+    pub fn add_terrain(&mut self, terrain: TerrainCollider) -> usize {
+        self.terrains.push(terrain);
+        self.terrains.len() - 1
+    }
+
+    /// Add a fluid, returning its index
+    pub fn add_fluid(&mut self, fluid: Fluid) -> usize {
+        self.fluids.push(fluid);
+        self.fluids.len() - 1
+    }
+
+    /// Add a body built from points and the constraints between them
+    ///
+    /// Constraint indices are relative to `points`, as returned by the shape
+    /// factories, and are shifted to match where the points end up in the world.
+    /// Returns the range of indices the points were given.
+    pub fn add_body(&mut self, points: Vec<Point>, constraints: Vec<Constraint>) -> Range<usize> {
+        let offset = self.points.len();
+        self.points.extend(points);
+        self.constraints.extend(constraints.into_iter().map(|mut constraint| {
+            constraint.point1 += offset;
+            constraint.point2 += offset;
+            constraint
+        }));
+        offset..self.points.len()
+    }
+
+    /// Make the points at `range` move as one rigid piece in their current shape
+    ///
+    /// Constraints between two points of the body are no longer solved, as the
+    /// body keeps its shape by itself. Returns the index of the rigid body.
+    pub fn make_rigid(&mut self, range: Range<usize>) -> usize {
+        self.rigid_bodies.push(RigidPointBody::new(&self.points, range));
+        self.rigid_bodies.len() - 1
+    }
+
+    /// Pull the points at `range` back toward their current shape whenever they are pushed out of it
+    ///
+    /// Each substep, after the constraints are solved, every point moves
+    /// `stiffness` of the way back to its spot in the best-fitting rest shape.
+    /// Returns the index of the shape match.
+    pub fn add_shape_match(&mut self, range: Range<usize>, stiffness: f32) -> usize {
+        self.shape_matches.push(ShapeMatch::new(&self.points, range, stiffness));
+        self.shape_matches.len() - 1
+    }
+
+    /// Make the quad at `quad` follow the center and rotation of the points at `range`
+    ///
+    /// The quad becomes kinematic and keeps the offset and rotation it has
+    /// from the points now. Returns the index of the anchor.
+    pub fn attach_quad(&mut self, quad: usize, range: Range<usize>) -> usize {
+        let anchor = SoftBodyAnchor::new(&self.points, range, quad, &self.quads[quad]);
+        self.quads[quad].body_type = BodyType::Kinematic;
+        self.soft_anchors.push(anchor);
+        self.soft_anchors.len() - 1
+    }
+
+    /// Draw a surface inside the points of a soft body, returning the index of the fill
+    ///
+    /// Fills are drawn under the constraints and points, following them as they move.
+    pub fn add_fill(&mut self, fill: ShapeFill) -> usize {
+        self.fills.push(fill);
+        self.fills.len() - 1
+    }
+
+    /// Make the quad at `child` ride on the quad at `parent`, keeping the spot and angle it has on it now
+    ///
+    /// The child becomes kinematic. Parent a quad before giving it children
+    /// of its own, so each step moves parents before their children.
+    /// Returns the index of the link in `quad_children`.
+    pub fn attach_child(&mut self, child: usize, parent: usize) -> usize {
+        let local = self.quads[child].transform().relative_to(&self.quads[parent].transform());
+        self.quads[child].body_type = BodyType::Kinematic;
+        self.quad_children.push(QuadChild { child, parent, local });
+        self.quad_children.len() - 1
+    }
+
+    /// Add a rope from `create_rope`, tying its ends down
+    ///
+    /// Ends tied to a point get a rope constraint to it; ends tied to a quad
+    /// become kinematic and follow the quad. Returns the range of indices the
+    /// rope's points were given.
+    pub fn add_rope(
+        &mut self,
+        points: Vec<Point>,
+        constraints: Vec<Constraint>,
+        start: RopeEnd,
+        end: RopeEnd,
+    ) -> Range<usize> {
+        let (stiffness, color) = constraints.first().map_or((1.0, WHITE), |link| (link.stiffness, link.color));
+        let range = self.add_body(points, constraints);
+        if range.is_empty() {
+            return range;
+        }
+        for (index, tie) in [(range.start, start), (range.end - 1, end)] {
+            match tie {
+                RopeEnd::Free => {}
+                RopeEnd::Fixed => self.points[index].set_fixed(true),
+                RopeEnd::Point(other) => self.add_constraint(Constraint::rope(other, index, 0.0, stiffness, color)),
+                RopeEnd::Quad(quad, offset) => {
+                    self.points[index].body_type = BodyType::Kinematic;
+                    self.anchors.push(QuadAnchor { point: index, quad, offset });
+                }
+            }
+        }
+        range
+    }
+
+    /// Add a quad, returning its index
+    pub fn add_quad(&mut self, quad: Quad) -> usize {
+        self.quads.push(quad);
+        self.quads.len() - 1
+    }
+
+    /// Add a circle, returning its index
+    pub fn add_circle(&mut self, circle: CircleBody) -> usize {
+        self.circles.push(circle);
+        self.circles.len() - 1
+    }
+
+    /// Add a convex polygon, returning its index
+    pub fn add_polygon(&mut self, polygon: PolygonCollider) -> usize {
+        self.polygons.push(polygon);
+        self.polygons.len() - 1
+    }
+
+    /// Put a quad or circle away to be reused, returning false for other objects and ones already put away
+    ///
+    /// The body stops, drops out of the simulation, queries and drawing, and
+    /// has its components reset with their `on_reset` hooks. Its index stays
+    /// taken until `respawn_quad` or `respawn_circle` hands it out again.
+    pub fn recycle(&mut self, handle: BodyHandle) -> bool {
+        match handle {
+            BodyHandle::Quad(i) => match self.quads.get_mut(i) {
+                Some(quad) if quad.active => {
+                    quad.active = false;
+                    quad.velocity_x = 0.0;
+                    quad.velocity_y = 0.0;
+                    quad.angular_velocity = 0.0;
+                    quad.sleeping = true;
+                    quad.reset_components();
+                    self.ground_normals.remove(&i);
+                    self.recycled_quads.push(i);
+                    true
+                }
+                _ => false,
+            },
+            BodyHandle::Circle(i) => match self.circles.get_mut(i) {
+                Some(circle) if circle.active => {
+                    circle.active = false;
+                    circle.velocity_x = 0.0;
+                    circle.velocity_y = 0.0;
+                    circle.sleeping = true;
+                    circle.reset_components();
+                    self.recycled_circles.push(i);
+                    true
+                }
+                _ => false,
+            },
+            BodyHandle::Point(_) | BodyHandle::Polygon(_) => false,
+        }
+    }
+
+    /// Bring back a recycled quad, set up by `setup`, returning its index
+    ///
+    /// Returns `None` if no quad is waiting; add a new one with `add_quad` then.
+    pub fn respawn_quad(&mut self, setup: impl FnOnce(&mut Quad)) -> Option<usize> {
+        let index = self.recycled_quads.pop()?;
+        let quad = &mut self.quads[index];
+        quad.active = true;
+        quad.wake();
+        setup(quad);
+        Some(index)
+    }
+
+    /// Bring back a recycled circle, set up by `setup`, returning its index
+    ///
+    /// Returns `None` if no circle is waiting; add a new one with `add_circle` then.
+    pub fn respawn_circle(&mut self, setup: impl FnOnce(&mut CircleBody)) -> Option<usize> {
+        let index = self.recycled_circles.pop()?;
+        let circle = &mut self.circles[index];
+        circle.active = true;
+        circle.wake();
+        setup(circle);
+        Some(index)
+    }
+
+    /// Remove all objects
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.constraints.clear();
+        self.quads.clear();
+        self.circles.clear();
+        self.polygons.clear();
+        self.anchors.clear();
+        self.quad_children.clear();
+        self.motors.clear();
+        self.hinges.clear();
+        self.rigid_bodies.clear();
+        self.shape_matches.clear();
+        self.soft_anchors.clear();
+        self.fills.clear();
+        self.attractors.clear();
+        self.terrains.clear();
+        self.fluids.clear();
+        self.contacts.clear();
+        self.collision_events.clear();
+        self.trigger_contacts.clear();
+        self.trigger_events.clear();
+        self.ground_normals.clear();
+        self.query_index.clear();
+        self.query_handles.clear();
+        self.recycled_quads.clear();
+        self.recycled_circles.clear();
+        self.pending_forces.clear();
+    }
+
+    /// Copy the moving state of every object and the links between them
+    ///
+    /// Components aren't copied, see `PhysicsSnapshot`.
+    pub fn snapshot(&self) -> PhysicsSnapshot {
+        PhysicsSnapshot {
+            points: self.points.iter().map(PointState::capture).collect(),
+            quads: self.quads.iter().map(BodyState::of_quad).collect(),
+            circles: self.circles.iter().map(BodyState::of_circle).collect(),
+            polygons: self.polygons.iter().map(BodyState::of_polygon).collect(),
+            constraints: self.constraints.clone(),
+            anchors: self.anchors.clone(),
+            motors: self.motors.clone(),
+            hinges: self.hinges.clone(),
+            accumulator: self.accumulator,
+            contacts: self.contacts.clone(),
+            trigger_contacts: self.trigger_contacts.clone(),
+            ground_normals: self.ground_normals.clone(),
+        }
+    }
+
+    /// Put the world back into the state `snapshot` was taken in
+    ///
+    /// Pending collision and trigger events are dropped. Fails without
+    /// changing anything when objects were added or removed since the
+    /// snapshot was taken.
+    pub fn restore(&mut self, snapshot: &PhysicsSnapshot) -> Result<(), Box<dyn Error>> {
+        let counts = [
+            ("points", snapshot.points.len(), self.points.len()),
+            ("quads", snapshot.quads.len(), self.quads.len()),
+            ("circles", snapshot.circles.len(), self.circles.len()),
+            ("polygons", snapshot.polygons.len(), self.polygons.len()),
+        ];
+        for (name, saved, current) in counts {
+            if saved != current {
+                return Err(format!("snapshot has {saved} {name} but the world has {current}").into());
+            }
+        }
+
+        for (state, point) in snapshot.points.iter().zip(&mut self.points) {
+            state.apply(point);
+        }
+        for (state, quad) in snapshot.quads.iter().zip(&mut self.quads) {
+            state.apply_to_quad(quad);
+        }
+        for (state, circle) in snapshot.circles.iter().zip(&mut self.circles) {
+            state.apply_to_circle(circle);
+        }
+        for (state, polygon) in snapshot.polygons.iter().zip(&mut self.polygons) {
+            state.apply_to_polygon(polygon);
+        }
+        self.constraints.clone_from(&snapshot.constraints);
+        self.anchors.clone_from(&snapshot.anchors);
+        self.motors.clone_from(&snapshot.motors);
+        self.hinges.clone_from(&snapshot.hinges);
+        self.accumulator = snapshot.accumulator;
+        self.contacts.clone_from(&snapshot.contacts);
+        self.trigger_contacts.clone_from(&snapshot.trigger_contacts);
+        self.ground_normals.clone_from(&snapshot.ground_normals);
+        self.collision_events.clear();
+        self.trigger_events.clear();
+        self.index_for_queries();
+        Ok(())
+    }
+
+    /// Upward-facing normal of the terrain the quad at `index` stood on during the last step
+    ///
+    /// Terrain is every `TerrainCollider` and every constraint whose two
+    /// points are both fixed, like the slopes made from point chains. Returns
+    /// `None` while the quad is in the air, or when it only touches the
+    /// underside of the terrain.
+    pub fn ground_normal(&self, index: usize) -> Option<Vec2> {
+        self.ground_normals.get(&index).copied()
+    }
+
+    /// Collision events from the steps run since they were last drained
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
+    /// Take the pending collision events
+    pub fn drain_collision_events(&mut self) -> Vec<CollisionEvent> {
+        std::mem::take(&mut self.collision_events)
+    }
+
+    /// Trigger events from the steps run since they were last drained
+    pub fn trigger_events(&self) -> &[TriggerEvent] {
+        &self.trigger_events
+    }
+
+    /// Take the pending trigger events
+    pub fn drain_trigger_events(&mut self) -> Vec<TriggerEvent> {
+        std::mem::take(&mut self.trigger_events)
+    }
+
+    /// Advance the simulation by `dt` seconds
+    ///
+    /// Runs components, applied forces, attractors and air resistance once, then integrates every object,
+    /// solves constraints and resolves collisions once per substep of `config`. Bodies that rested for
+    /// `sleep_steps` steps fall asleep and are skipped until something touches them, their velocity is set
+    /// from code, or a force, impulse, `Force` component or attractor pushes them. Every touching pair then gets its contact
+    /// hooks called and a `CollisionEvent` queued: `Enter` on the first step,
+    /// `Stay` while it lasts and `Exit` once it ends. Trigger overlaps that
+    /// began or ended call the trigger hooks and queue a `TriggerEvent` instead.
+    /// Finally every object is indexed for the overlap queries.
+    ///
+    /// Collision and trigger events not drained since the previous step are dropped.
+    pub fn step(&mut self, dt: f32) {
+        self.collision_events.clear();
+        self.trigger_events.clear();
+        self.simulate(dt);
+    }
+
+    /// Run one step, adding its events to those already queued
+    fn simulate(&mut self, dt: f32) {
+        let starts = (self.sleep_steps > 0 && dt > 0.0).then(|| self.body_positions());
+        for point in &mut self.points {
+            point.update_components(dt);
+        }
+        // Sleeping bodies skip their components and integration until code gives them a velocity or a push
+        for quad in self.quads.iter_mut().filter(|quad| quad.active) {
+            let moving = (quad.velocity_x, quad.velocity_y, quad.angular_velocity) != (0.0, 0.0, 0.0);
+            if quad.sleeping && (moving || is_pushed(quad)) {
+                quad.wake();
+            }
+            if !quad.sleeping {
+                quad.update_components(dt);
+            }
+        }
+        for circle in self.circles.iter_mut().filter(|circle| circle.active) {
+            let moving = (circle.velocity_x, circle.velocity_y) != (0.0, 0.0);
+            if circle.sleeping && (moving || is_pushed(circle)) {
+                circle.wake();
+            }
+            if !circle.sleeping {
+                circle.update_components(dt);
+            }
+        }
+        for polygon in &mut self.polygons {
+            let moving = (polygon.velocity_x, polygon.velocity_y, polygon.angular_velocity) != (0.0, 0.0, 0.0);
+            if polygon.sleeping && (moving || is_pushed(polygon)) {
+                polygon.wake();
+            }
+            if !polygon.sleeping {
+                polygon.update_components(dt);
+            }
+        }
+        self.apply_forces(dt);
+        self.apply_attractors(dt);
+        self.apply_air_resistance(dt);
+
+        let substeps = self.config.substeps.max(1);
+        let substep_dt = dt / substeps as f32;
+        // `Point::update` clears forces, so each substep gets them back to apply its share
+        let forces: Vec<(f32, f32)> = self.points.iter().map(|point| point.force).collect();
+        let mut contacts = HashSet::new();
+        let mut trigger_contacts = HashSet::new();
+        let iterations = self.constraint_iterations();
+        let max_iterations = iterations.iter().copied().max().unwrap_or(0);
+        #[cfg(feature = "parallel")]
+        let batches = {
+            self.constraint_batches.update(&self.constraints, self.points.len());
+            std::mem::take(&mut self.constraint_batches)
+        };
+        // Sleeping quads aren't collided, so they keep the ground they fell asleep on
+        let quads = &self.quads;
+        self.ground_normals.retain(|&index, _| quads.get(index).is_some_and(|quad| quad.sleeping));
+        for _ in 0..substeps {
+            for motor in &self.motors {
+                motor.solve(&mut self.points, substep_dt);
+            }
+            self.wake_hinged();
+            for hinge in &self.hinges {
+                hinge.solve_motor(&mut self.quads, substep_dt);
+            }
+            for _ in 0..self.config.solver_iterations {
+                for hinge in &self.hinges {
+                    hinge.solve(&mut self.quads, substep_dt);
+                }
+            }
+            self.limit_velocities(substep_dt);
+            for (point, &force) in self.points.iter_mut().zip(&forces) {
+                point.force = force;
+                point.update(substep_dt);
+            }
+            self.quads.iter_mut().filter(|quad| quad.active && !quad.sleeping).for_each(|quad| quad.update(substep_dt));
+            self.circles
+                .iter_mut()
+                .filter(|circle| circle.active && !circle.sleeping)
+                .for_each(|circle| circle.update(substep_dt));
+            self.polygons
+                .iter_mut()
+                .filter(|polygon| !polygon.sleeping)
+                .for_each(|polygon| polygon.update(substep_dt));
+            self.follow_parents();
+            self.follow_anchors();
+
+            for iteration in 0..max_iterations {
+                #[cfg(feature = "parallel")]
+                parallel::solve_constraints(&self.constraints, batches.batches(), &mut self.points, |index| {
+                    iteration < iterations[index]
+                });
+                #[cfg(not(feature = "parallel"))]
+                for (constraint, _) in self.constraints.iter().zip(&iterations).filter(|(_, limit)| iteration < **limit) {
+                    constraint.solve(&mut self.points);
+                }
+            }
+            for shape in &mut self.shape_matches {
+                shape.solve(&mut self.points);
+            }
+
+            self.collide_points(&mut contacts);
+            self.collide_bodies(&mut contacts, &mut trigger_contacts);
+            self.collide_terrain();
+            for body in &mut self.rigid_bodies {
+                body.solve(&mut self.points);
+            }
+            self.follow_soft_bodies();
+        }
+        #[cfg(feature = "parallel")]
+        {
+            self.constraint_batches = batches;
+        }
+        self.update_contacts(contacts);
+        self.update_triggers(trigger_contacts);
+        for fluid in &mut self.fluids {
+            fluid.step(dt, &self.quads, &self.circles, &self.terrains);
+        }
+
+        if let Some(starts) = starts {
+            self.update_sleep(&starts, dt);
+        }
+        self.index_for_queries();
+    }
+
+    /// How many solver iterations each constraint gets, the most either of its points' layers asks for
+    ///
+    /// Constraints inside a rigid body get none.
+    fn constraint_iterations(&self) -> Vec<usize> {
+        let iterations = |index: usize| {
+            let layer = self.points.get(index).and_then(|point| point.layer.as_deref());
+            self.config.layer_solver_iterations(layer)
+        };
+        let rigid = |constraint: &Constraint| {
+            self.rigid_bodies
+                .iter()
+                .any(|body| body.points.contains(&constraint.point1) && body.points.contains(&constraint.point2))
+        };
+        self.constraints
+            .iter()
+            .map(|constraint| {
+                if rigid(constraint) { 0 } else { iterations(constraint.point1).max(iterations(constraint.point2)) }
+            })
+            .collect()
+    }
+
+    /// Rebuild the broad-phase used by `query_point`, `query_aabb` and `query_circle`
+    fn index_for_queries(&mut self) {
+        let objects = self.object_bounds();
+        self.query_index.clear();
+        for (index, (_, bounds)) in objects.iter().enumerate() {
+            self.query_index.insert(index, bounds.point(), bounds.point() + bounds.size());
+        }
+        self.query_handles = objects.into_iter().map(|(handle, _)| handle).collect();
+    }
+
+    /// Wake sleeping quads hinged to a quad that moved last step
+    fn wake_hinged(&mut self) {
+        for index in 0..self.hinges.len() {
+            let hinge = self.hinges[index];
+            for (sleeper, other) in [(hinge.quad_a, hinge.quad_b), (hinge.quad_b, hinge.quad_a)] {
+                let (sleeper, other) = (BodyHandle::Quad(sleeper), BodyHandle::Quad(other));
+                if self.is_sleeping(sleeper) && !self.is_resting(other) && !self.is_idle(other) {
+                    self.wake(sleeper);
+                }
+            }
+        }
+    }
+
+    /// Move parented quads to their spots on their parents, matching their motion
+    fn follow_parents(&mut self) {
+        for link in &self.quad_children {
+            let Some(parent) = self.quads.get(link.parent) else {
+                continue;
+            };
+            let transform = parent.transform();
+            let (velocity, spin) = (vec2(parent.velocity_x, parent.velocity_y), parent.angular_velocity);
+            let Some(child) = self.quads.get_mut(link.child) else {
+                continue;
+            };
+            let world = link.local.in_parent(&transform);
+            let velocity = velocity + (world.position - transform.position).perp() * spin;
+            child.set_transform(&world);
+            (child.velocity_x, child.velocity_y) = (velocity.x, velocity.y);
+            child.angular_velocity = spin;
+        }
+    }
+
+    /// Move anchored points to their spots on their quads
+    fn follow_anchors(&mut self) {
+        for anchor in &self.anchors {
+            let (Some(quad), Some(point)) = (self.quads.get(anchor.quad), self.points.get_mut(anchor.point)) else {
+                continue;
+            };
+            let position = quad.to_world(anchor.offset);
+            point.position = (position.x, position.y);
+            point.velocity = (quad.velocity_x, quad.velocity_y);
+        }
+    }
+
+    /// Move quads attached to point bodies to their spots on them
+    fn follow_soft_bodies(&mut self) {
+        for anchor in &mut self.soft_anchors {
+            if let Some(quad) = self.quads.get_mut(anchor.quad) {
+                anchor.follow(&self.points, quad);
+            }
+        }
+    }
+
+    /// Positions of every quad, circle and polygon, in that order
+    fn body_positions(&self) -> Vec<(f32, f32)> {
+        let quads = self.quads.iter().map(|quad| quad.position);
+        let circles = self.circles.iter().map(|circle| circle.position);
+        quads.chain(circles).chain(self.polygons.iter().map(|polygon| polygon.position)).collect()
+    }
+
+    /// Put bodies that barely moved since `starts` closer to sleep
+    fn update_sleep(&mut self, starts: &[(f32, f32)], dt: f32) {
+        let speeds: Vec<f32> = self
+            .body_positions()
+            .iter()
+            .zip(starts)
+            .map(|(end, start)| (end.0 - start.0).hypot(end.1 - start.1) / dt)
+            .collect();
+        let (threshold, steps) = (self.sleep_velocity, self.sleep_steps);
+        let (quad_speeds, rest) = speeds.split_at(self.quads.len());
+        let (circle_speeds, polygon_speeds) = rest.split_at(self.circles.len());
+        for (quad, &speed) in self.quads.iter_mut().zip(quad_speeds) {
+            quad.update_sleep(speed, threshold, steps);
+        }
+        for (circle, &speed) in self.circles.iter_mut().zip(circle_speeds) {
+            circle.update_sleep(speed, threshold, steps);
+        }
+        for (polygon, &speed) in self.polygons.iter_mut().zip(polygon_speeds) {
+            polygon.update_sleep(speed, threshold, steps);
+        }
+    }
+
+    /// Wake every sleeping body, leaving recycled ones asleep
+    pub fn wake_all(&mut self) {
+        self.quads.iter_mut().filter(|quad| quad.active).for_each(Quad::wake);
+        self.circles.iter_mut().filter(|circle| circle.active).for_each(CircleBody::wake);
+        self.polygons.iter_mut().for_each(PolygonCollider::wake);
+    }
+
+    /// Push a body with `force` during the next step, waking it if it sleeps
+    ///
+    /// The force is divided by the body's mass, so heavier bodies speed up less.
+    /// Static, kinematic and recycled bodies aren't moved.
+    pub fn apply_force(&mut self, handle: BodyHandle, force: Vec2) {
+        if self.is_pushable(handle) {
+            self.wake(handle);
+            self.pending_forces.push((handle, force));
+        }
+    }
+
+    /// Change a body's velocity at once by `impulse` divided by its mass, waking it if it sleeps
+    ///
+    /// Static, kinematic and recycled bodies aren't moved.
+    pub fn apply_impulse(&mut self, handle: BodyHandle, impulse: Vec2) {
+        if self.is_pushable(handle) {
+            self.wake(handle);
+            self.add_velocity(handle, impulse / self.mass(handle).max(f32::EPSILON));
+        }
+    }
+
+    /// Check if a body exists, is dynamic and isn't recycled, so forces can move it
+    fn is_pushable(&self, handle: BodyHandle) -> bool {
+        let exists = match handle {
+            BodyHandle::Point(i) => i < self.points.len(),
+            BodyHandle::Quad(i) => self.quads.get(i).is_some_and(|quad| quad.active),
+            BodyHandle::Circle(i) => self.circles.get(i).is_some_and(|circle| circle.active),
+            BodyHandle::Polygon(i) => i < self.polygons.len(),
+        };
+        exists && self.body_type(handle).is_dynamic()
+    }
+
+    /// Speed up bodies by the forces queued with `apply_force`, over `dt` seconds
+    fn apply_forces(&mut self, dt: f32) {
+        for (handle, force) in std::mem::take(&mut self.pending_forces) {
+            self.add_velocity(handle, force / self.mass(handle).max(f32::EPSILON) * dt);
+        }
+    }
+
+    /// Let overlapping points react to each other, recording them in `contacts`
+    fn collide_points(&mut self, contacts: &mut HashSet<(BodyHandle, BodyHandle)>) {
+        self.broad_phase.clear();
+        for (index, point) in self.points.iter().enumerate() {
+            let center = vec2(point.position.0, point.position.1);
+            let extent = Vec2::splat(point.radius);
+            self.broad_phase.insert(index, center - extent, center + extent);
+        }
+        let pairs = self.broad_phase.candidate_pairs();
+        #[cfg(feature = "parallel")]
+        let pairs = {
+            let points: Vec<_> = self
+                .points
+                .iter()
+                .map(|point| (vec2(point.position.0, point.position.1), point.radius, point.collision_group))
+                .collect();
+            parallel::overlapping_pairs(pairs, |i, j| {
+                let ((a, radius_a, group_a), (b, radius_b, group_b)) = (points[i], points[j]);
+                (group_a.is_none() || group_a != group_b) && a.distance(b) < radius_a + radius_b
+            })
+        };
+        for (i, j) in pairs {
+            let (point, other) = pair_mut(&mut self.points, i, j);
+            // Static points never move, so there is nothing to resolve
+            if !point.body_type.moves() && !other.body_type.moves() {
+                continue;
+            }
+            if !point.can_collide_with(other) || !self.config.layers_collide(point.layer.as_deref(), other.layer.as_deref()) {
+                continue;
+            }
+            if point.is_colliding_with(other) {
+                point.dispatch_collision(other);
+                other.dispatch_collision(point);
+                contacts.insert((BodyHandle::Point(i), BodyHandle::Point(j)));
+            }
+        }
+    }
+
+    /// Segments between pairs of fixed points, as thick as the points' average radius
+    fn terrain_segments(&self) -> Vec<Segment> {
+        self.constraints
+            .iter()
+            .filter_map(|constraint| {
+                let (a, b) = (self.points.get(constraint.point1)?, self.points.get(constraint.point2)?);
+                (a.is_fixed() && b.is_fixed()).then(|| {
+                    Segment::new(
+                        vec2(a.position.0, a.position.1),
+                        vec2(b.position.0, b.position.1),
+                        (a.radius + b.radius) * 0.5,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Let awake dynamic quads touching terrain segments react to them, recording the ground they stand on
+    ///
+    /// Terrain colliders push points, quads and circles out themselves, then
+    /// let the quads' components know which segment they touched.
+    fn collide_terrain(&mut self) {
+        for terrain in &self.terrains {
+            let area = terrain.bounds();
+            for point in self.points.iter_mut().filter(|point| area.overlaps(&point_bounds(point))) {
+                terrain.collide_point(point);
+            }
+            for (index, quad) in self.quads.iter_mut().enumerate() {
+                if quad.sleeping || !area.overlaps(&quad.bounds()) {
+                    continue;
+                }
+                if let Some((segment, normal)) = terrain.collide_quad(quad) {
+                    quad.dispatch_segment_collision(&terrain.segment(segment));
+                    record_ground(&mut self.ground_normals, index, normal);
+                }
+            }
+            for circle in self.circles.iter_mut().filter(|circle| !circle.sleeping && area.overlaps(&circle.bounds())) {
+                terrain.collide_circle(circle);
+            }
+        }
+
+        let segments = self.terrain_segments();
+        if segments.is_empty() {
+            return;
+        }
+        self.broad_phase.clear();
+        for (index, segment) in segments.iter().enumerate() {
+            let bounds = segment.bounds();
+            self.broad_phase.insert(index, bounds.point(), bounds.point() + bounds.size());
+        }
+        for (index, quad) in self.quads.iter_mut().enumerate() {
+            if quad.sleeping || quad.is_trigger || !quad.body_type.is_dynamic() {
+                continue;
+            }
+            let bounds = quad.bounds();
+            for segment_index in self.broad_phase.query(bounds.point(), bounds.point() + bounds.size()) {
+                let segment = &segments[segment_index];
+                let Some(mtv) = segment_quad_overlap(segment, quad) else {
+                    continue;
+                };
+                quad.dispatch_segment_collision(segment);
+                record_ground(&mut self.ground_normals, index, mtv.normalize_or_zero());
+            }
+        }
+    }
+
+    /// Pull every object in range of an attractor toward it for `dt` seconds
+    fn apply_attractors(&mut self, dt: f32) {
+        if self.attractors.is_empty() {
+            return;
+        }
+        let objects = self.object_bounds();
+        self.broad_phase.clear();
+        for (index, (_, bounds)) in objects.iter().enumerate() {
+            self.broad_phase.insert(index, bounds.point(), bounds.point() + bounds.size());
+        }
+        for attractor_index in 0..self.attractors.len() {
+            let attractor = self.attractors[attractor_index];
+            let range = attractor.bounds();
+            for index in self.broad_phase.query(range.point(), range.point() + range.size()) {
+                let (handle, bounds) = objects[index];
+                self.accelerate(handle, attractor.acceleration_at(bounds.center()) * dt);
+            }
+        }
+    }
+
+    /// Slow every dynamic, awake object by the configured air resistance
+    fn apply_air_resistance(&mut self, dt: f32) {
+        let config = &self.config;
+        if config.air_resistance <= 0.0 && config.layers.values().all(|layer| layer.air_resistance.is_none()) {
+            return;
+        }
+        let drag = |layer: &Option<String>| Drag::new(config.layer_air_resistance(layer.as_deref()), 0.0);
+        for point in self.points.iter_mut().filter(|point| !point.is_fixed()) {
+            let velocity = drag(&point.layer).apply(vec2(point.velocity.0, point.velocity.1), dt);
+            point.velocity = (velocity.x, velocity.y);
+        }
+        for quad in self.quads.iter_mut().filter(|quad| quad.body_type.is_dynamic() && !quad.sleeping) {
+            let velocity = drag(&quad.layer).apply(vec2(quad.velocity_x, quad.velocity_y), dt);
+            (quad.velocity_x, quad.velocity_y) = (velocity.x, velocity.y);
+        }
+        for circle in self.circles.iter_mut().filter(|circle| circle.body_type.is_dynamic() && !circle.sleeping) {
+            let velocity = drag(&circle.layer).apply(vec2(circle.velocity_x, circle.velocity_y), dt);
+            (circle.velocity_x, circle.velocity_y) = (velocity.x, velocity.y);
+        }
+        for polygon in self.polygons.iter_mut().filter(|polygon| polygon.body_type.is_dynamic() && !polygon.sleeping) {
+            let velocity = drag(&polygon.layer).apply(vec2(polygon.velocity_x, polygon.velocity_y), dt);
+            (polygon.velocity_x, polygon.velocity_y) = (velocity.x, velocity.y);
+        }
+    }
+
+    /// Damp the velocities of awake dynamic objects and clamp them to `max_linear_velocity`
+    fn limit_velocities(&mut self, dt: f32) {
+        let max_speed = self.config.max_linear_velocity.unwrap_or(f32::INFINITY);
+        let damping = self.config.damping;
+        if damping <= 0.0 && max_speed == f32::INFINITY {
+            return;
+        }
+        let factor = 1.0 / (1.0 + damping * dt);
+        let limit = |x: f32, y: f32| (vec2(x, y) * factor).clamp_length_max(max_speed);
+        for point in self.points.iter_mut().filter(|point| !point.is_fixed()) {
+            let velocity = limit(point.velocity.0, point.velocity.1);
+            point.velocity = (velocity.x, velocity.y);
+        }
+        for quad in self.quads.iter_mut().filter(|quad| quad.body_type.is_dynamic() && !quad.sleeping) {
+            let velocity = limit(quad.velocity_x, quad.velocity_y);
+            (quad.velocity_x, quad.velocity_y) = (velocity.x, velocity.y);
+            quad.angular_velocity *= factor;
+        }
+        for circle in self.circles.iter_mut().filter(|circle| circle.body_type.is_dynamic() && !circle.sleeping) {
+            let velocity = limit(circle.velocity_x, circle.velocity_y);
+            (circle.velocity_x, circle.velocity_y) = (velocity.x, velocity.y);
+        }
+        for polygon in self.polygons.iter_mut().filter(|polygon| polygon.body_type.is_dynamic() && !polygon.sleeping) {
+            let velocity = limit(polygon.velocity_x, polygon.velocity_y);
+            (polygon.velocity_x, polygon.velocity_y) = (velocity.x, velocity.y);
+            polygon.angular_velocity *= factor;
+        }
+    }
+
+    /// Change the velocity of a dynamic object, scaled by its `gravity_scale`, waking it if it sleeps
+    fn accelerate(&mut self, handle: BodyHandle, change: Vec2) {
+        if change == Vec2::ZERO || !self.body_type(handle).is_dynamic() {
+            return;
+        }
+        self.wake(handle);
+        let gravity_scale = match handle {
+            BodyHandle::Point(i) => self.points[i].gravity_scale,
+            BodyHandle::Quad(i) => self.quads[i].gravity_scale,
+            BodyHandle::Circle(i) => self.circles[i].gravity_scale,
+            BodyHandle::Polygon(i) => self.polygons[i].gravity_scale,
+        };
+        self.add_velocity(handle, change * gravity_scale);
+    }
+
+    /// Add `change` to an object's velocity
+    fn add_velocity(&mut self, handle: BodyHandle, change: Vec2) {
+        match handle {
+            BodyHandle::Point(i) => {
+                let point = &mut self.points[i];
+                point.velocity.0 += change.x;
+                point.velocity.1 += change.y;
+            }
+            BodyHandle::Quad(i) => {
+                let quad = &mut self.quads[i];
+                quad.velocity_x += change.x;
+                quad.velocity_y += change.y;
+            }
+            BodyHandle::Circle(i) => {
+                let circle = &mut self.circles[i];
+                circle.velocity_x += change.x;
+                circle.velocity_y += change.y;
+            }
+            BodyHandle::Polygon(i) => {
+                let polygon = &mut self.polygons[i];
+                polygon.velocity_x += change.x;
+                polygon.velocity_y += change.y;
+            }
+        }
+    }
+
+    /// Get an object's mass
+    fn mass(&self, handle: BodyHandle) -> f32 {
+        match handle {
+            BodyHandle::Point(i) => self.points[i].mass,
+            BodyHandle::Quad(i) => self.quads[i].mass,
+            BodyHandle::Circle(i) => self.circles[i].mass,
+            BodyHandle::Polygon(i) => self.polygons[i].mass,
+        }
+    }
+
+    /// Handles and bounds of every point and body, in broad-phase index order
+    fn object_bounds(&self) -> Vec<(BodyHandle, Rect)> {
+        let points = self.points.iter().enumerate().map(|(i, point)| (BodyHandle::Point(i), point_bounds(point)));
+        points.chain(self.body_bounds()).collect()
+    }
+
+    /// Handles and bounds of every solid body, in broad-phase index order
+    fn body_bounds(&self) -> Vec<(BodyHandle, Rect)> {
+        let quads = active(&self.quads, |quad| quad.active).map(|(i, quad)| (BodyHandle::Quad(i), quad.bounds()));
+        let circles = active(&self.circles, |circle| circle.active).map(|(i, circle)| (BodyHandle::Circle(i), circle.bounds()));
+        let polygons = self
+            .polygons
+            .iter()
+            .enumerate()
+            .map(|(i, polygon)| (BodyHandle::Polygon(i), polygon.bounds()));
+        quads.chain(circles).chain(polygons).collect()
+    }
+
+    /// Let overlapping quads, circles and polygons react to each other, recording them in `contacts`
+    ///
+    /// Trigger overlaps are recorded in `trigger_contacts` instead.
+    fn collide_bodies(
+        &mut self,
+        contacts: &mut HashSet<(BodyHandle, BodyHandle)>,
+        trigger_contacts: &mut HashSet<(BodyHandle, BodyHandle)>,
+    ) {
+        self.broad_phase.clear();
+        let bodies = self.body_bounds();
+        for (index, (_, bounds)) in bodies.iter().enumerate() {
+            self.broad_phase.insert(index, bounds.point(), bounds.point() + bounds.size());
+        }
+        let pairs = self.broad_phase.candidate_pairs();
+        #[cfg(feature = "parallel")]
+        let pairs = {
+            let shapes: Vec<_> = bodies
+                .iter()
+                .map(|&(handle, _)| (self.is_resting(handle), self.body_shape(handle)))
+                .collect();
+            parallel::overlapping_pairs(pairs, |i, j| {
+                let ((resting_a, a), (resting_b, b)) = (&shapes[i], &shapes[j]);
+                // Resting pairs keep last step's contact without a test
+                (*resting_a && *resting_b) || a.as_ref().zip(b.as_ref()).is_some_and(|(a, b)| a.overlaps(b))
+            })
+        };
+        for (i, j) in pairs {
+            let (a, b) = (bodies[i].0, bodies[j].0);
+            if !self.config.layers_collide(self.layer(a), self.layer(b)) {
+                continue;
+            }
+            let trigger_pair = if self.is_trigger(b) && !self.is_trigger(a) { (b, a) } else { (a, b) };
+            let pair = if self.is_trigger(a) || self.is_trigger(b) { None } else { Some((a.min(b), a.max(b))) };
+            // Static and sleeping bodies don't move, so two of them touch exactly as they did last step
+            if self.is_resting(a) && self.is_resting(b) {
+                match pair {
+                    Some(pair) if self.contacts.contains(&pair) => {
+                        contacts.insert(pair);
+                    }
+                    None if self.trigger_contacts.contains(&trigger_pair) => {
+                        trigger_contacts.insert(trigger_pair);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            if self.is_hinged(a, b) || self.is_parented(a, b) || !self.bodies_overlap(a, b) {
+                continue;
+            }
+            match pair {
+                // Keep the trigger first so events report it as `trigger`
+                None => {
+                    trigger_contacts.insert(trigger_pair);
+                }
+                Some(pair) => {
+                    // A body that moved last step bumping a sleeping one wakes it
+                    for (sleeper, other) in [(a, b), (b, a)] {
+                        if self.is_sleeping(sleeper) && !self.is_resting(other) && !self.is_idle(other) {
+                            self.wake(sleeper);
+                        }
+                    }
+                    self.collide_pair(a, b);
+                    contacts.insert(pair);
+                }
+            }
+        }
+    }
+
+    /// Get how an object takes part in the simulation
+    fn body_type(&self, handle: BodyHandle) -> BodyType {
+        match handle {
+            BodyHandle::Point(i) => self.points[i].body_type,
+            BodyHandle::Quad(i) => self.quads[i].body_type,
+            BodyHandle::Circle(i) => self.circles[i].body_type,
+            BodyHandle::Polygon(i) => self.polygons[i].body_type,
+        }
+    }
+
+    /// Get the name of an object's `PhysicsConfig` layer
+    fn layer(&self, handle: BodyHandle) -> Option<&str> {
+        match handle {
+            BodyHandle::Point(i) => self.points[i].layer.as_deref(),
+            BodyHandle::Quad(i) => self.quads[i].layer.as_deref(),
+            BodyHandle::Circle(i) => self.circles[i].layer.as_deref(),
+            BodyHandle::Polygon(i) => self.polygons[i].layer.as_deref(),
+        }
+    }
+
+    /// Check if two quads are joined by a hinge, which keeps them from colliding
+    fn is_hinged(&self, a: BodyHandle, b: BodyHandle) -> bool {
+        let (BodyHandle::Quad(a), BodyHandle::Quad(b)) = (a, b) else {
+            return false;
+        };
+        self.hinges
+            .iter()
+            .any(|hinge| (hinge.quad_a, hinge.quad_b) == (a, b) || (hinge.quad_a, hinge.quad_b) == (b, a))
+    }
+
+    /// Check if one of two bodies is a quad parented to the other
+    fn is_parented(&self, a: BodyHandle, b: BodyHandle) -> bool {
+        let (BodyHandle::Quad(a), BodyHandle::Quad(b)) = (a, b) else {
+            return false;
+        };
+        self.quad_children
+            .iter()
+            .any(|link| (link.child, link.parent) == (a, b) || (link.child, link.parent) == (b, a))
+    }
+
+    /// Check if a body is asleep
+    fn is_sleeping(&self, handle: BodyHandle) -> bool {
+        match handle {
+            BodyHandle::Point(_) => false,
+            BodyHandle::Quad(i) => self.quads[i].sleeping,
+            BodyHandle::Circle(i) => self.circles[i].sleeping,
+            BodyHandle::Polygon(i) => self.polygons[i].sleeping,
+        }
+    }
+
+    /// Check if a body barely moved last step
+    fn is_idle(&self, handle: BodyHandle) -> bool {
+        match handle {
+            BodyHandle::Point(_) => false,
+            BodyHandle::Quad(i) => self.quads[i].idle_steps > 0,
+            BodyHandle::Circle(i) => self.circles[i].idle_steps > 0,
+            BodyHandle::Polygon(i) => self.polygons[i].idle_steps > 0,
+        }
+    }
+
+    /// Check if a body is static or asleep, so it won't move this step
+    fn is_resting(&self, handle: BodyHandle) -> bool {
+        !self.body_type(handle).moves() || self.is_sleeping(handle)
+    }
+
+    /// Wake a sleeping body
+    fn wake(&mut self, handle: BodyHandle) {
+        match handle {
+            BodyHandle::Point(_) => {}
+            BodyHandle::Quad(i) => self.quads[i].wake(),
+            BodyHandle::Circle(i) => self.circles[i].wake(),
+            BodyHandle::Polygon(i) => self.polygons[i].wake(),
+        }
+    }
+
+    /// Check if a body is a trigger
+    fn is_trigger(&self, handle: BodyHandle) -> bool {
+        match handle {
+            BodyHandle::Point(_) => false,
+            BodyHandle::Quad(i) => self.quads[i].is_trigger,
+            BodyHandle::Circle(i) => self.circles[i].is_trigger,
+            BodyHandle::Polygon(i) => self.polygons[i].is_trigger,
+        }
+    }
+
+    /// Copy of a body's collision shape, or `None` for points
+    #[cfg(feature = "parallel")]
+    fn body_shape(&self, handle: BodyHandle) -> Option<BodyShape> {
+        match handle {
+            BodyHandle::Point(_) => None,
+            BodyHandle::Quad(i) => Some(BodyShape::quad(&self.quads[i])),
+            BodyHandle::Circle(i) => Some(BodyShape::circle(&self.circles[i])),
+            BodyHandle::Polygon(i) => Some(BodyShape::polygon(&self.polygons[i])),
+        }
+    }
+
+    /// Narrow-phase test between two bodies
+    fn bodies_overlap(&self, a: BodyHandle, b: BodyHandle) -> bool {
+        match (a, b) {
+            (BodyHandle::Quad(i), BodyHandle::Quad(j)) => self.quads[i].is_colliding_with(&self.quads[j]),
+            (BodyHandle::Circle(i), BodyHandle::Circle(j)) => self.circles[i].is_colliding_with(&self.circles[j]),
+            (BodyHandle::Polygon(i), BodyHandle::Polygon(j)) => {
+                self.polygons[i].is_colliding_with(&self.polygons[j])
+            }
+            (BodyHandle::Quad(i), BodyHandle::Circle(j)) => self.circles[j].is_colliding_with_quad(&self.quads[i]),
+            (BodyHandle::Quad(i), BodyHandle::Polygon(j)) => self.polygons[j].is_colliding_with_quad(&self.quads[i]),
+            (BodyHandle::Circle(i), BodyHandle::Polygon(j)) => {
+                self.polygons[j].is_colliding_with_circle(&self.circles[i])
+            }
+            // Points collide among themselves in `collide_points`
+            (BodyHandle::Point(_), _) | (_, BodyHandle::Point(_)) => false,
+            (a, b) => self.bodies_overlap(b, a),
+        }
+    }
+
+    /// Call the collision hooks of two overlapping bodies
+    ///
+    /// Handles come in broad-phase order, so quads come before circles and
+    /// circles before polygons.
+    fn collide_pair(&mut self, a: BodyHandle, b: BodyHandle) {
+        match (a, b) {
+            (BodyHandle::Quad(i), BodyHandle::Quad(j)) => {
+                let (quad, other) = pair_mut(&mut self.quads, i, j);
+                quad.dispatch_collision(other);
+                other.dispatch_collision(quad);
+            }
+            (BodyHandle::Circle(i), BodyHandle::Circle(j)) => {
+                let (circle, other) = pair_mut(&mut self.circles, i, j);
+                circle.dispatch_collision(other);
+                other.dispatch_collision(circle);
+            }
+            (BodyHandle::Polygon(i), BodyHandle::Polygon(j)) => {
+                let (polygon, other) = pair_mut(&mut self.polygons, i, j);
+                polygon.dispatch_collision(other);
+                other.dispatch_collision(polygon);
+            }
+            (BodyHandle::Quad(i), BodyHandle::Circle(j)) => {
+                self.circles[j].dispatch_quad_collision(&mut self.quads[i]);
+            }
+            (BodyHandle::Quad(i), BodyHandle::Polygon(j)) => {
+                self.polygons[j].dispatch_quad_collision(&mut self.quads[i]);
+            }
+            (BodyHandle::Circle(i), BodyHandle::Polygon(j)) => {
+                self.polygons[j].dispatch_circle_collision(&mut self.circles[i]);
+            }
+            (BodyHandle::Point(_), _) | (_, BodyHandle::Point(_)) => {}
+            (a, b) => self.collide_pair(b, a),
+        }
+    }
+
+    /// Compare this step's contacts with the last and report enter, stay and exit
+    fn update_contacts(&mut self, contacts: HashSet<(BodyHandle, BodyHandle)>) {
+        let previous = std::mem::replace(&mut self.contacts, contacts);
+        let mut changes: Vec<_> = previous
+            .difference(&self.contacts)
+            .map(|&pair| (pair, ContactPhase::Exit))
+            .chain(self.contacts.iter().map(|&pair| {
+                let phase = if previous.contains(&pair) { ContactPhase::Stay } else { ContactPhase::Enter };
+                (pair, phase)
+            }))
+            .collect();
+        // Sets have no order, so sort for the same events on every run
+        changes.sort_unstable_by_key(|&(pair, phase)| (phase != ContactPhase::Exit, pair));
+        for ((a, b), phase) in changes {
+            self.dispatch_contact(a, b, phase);
+            self.dispatch_contact(b, a, phase);
+            self.collision_events.push(CollisionEvent { a, b, phase });
+        }
+    }
+
+    /// Call the contact hooks of one object
+    ///
+    /// Exits can refer to objects removed since the last step, which are skipped.
+    fn dispatch_contact(&mut self, handle: BodyHandle, other: BodyHandle, phase: ContactPhase) {
+        match handle {
+            BodyHandle::Point(i) => self.points.get_mut(i).map_or((), |point| point.dispatch_contact(other, phase)),
+            BodyHandle::Quad(i) => self.quads.get_mut(i).map_or((), |quad| quad.dispatch_contact(other, phase)),
+            BodyHandle::Circle(i) => {
+                self.circles.get_mut(i).map_or((), |circle| circle.dispatch_contact(other, phase))
+            }
+            BodyHandle::Polygon(i) => {
+                self.polygons.get_mut(i).map_or((), |polygon| polygon.dispatch_contact(other, phase))
+            }
+        }
+    }
+
+    /// Compare this step's trigger overlaps with the last and report the changes
+    fn update_triggers(&mut self, contacts: HashSet<(BodyHandle, BodyHandle)>) {
+        let previous = std::mem::replace(&mut self.trigger_contacts, contacts);
+        let mut entered: Vec<_> = self.trigger_contacts.difference(&previous).copied().collect();
+        let mut exited: Vec<_> = previous.difference(&self.trigger_contacts).copied().collect();
+        // Sets have no order, so sort for the same events on every run
+        entered.sort_unstable();
+        exited.sort_unstable();
+        let changes = exited
+            .into_iter()
+            .map(|pair| (pair, TriggerPhase::Exit))
+            .chain(entered.into_iter().map(|pair| (pair, TriggerPhase::Enter)));
+        for ((trigger, other), phase) in changes {
+            self.dispatch_trigger(trigger, other, phase);
+            self.dispatch_trigger(other, trigger, phase);
+            self.trigger_events.push(TriggerEvent { trigger, other, phase });
+        }
+    }
+
+    /// Call the trigger hooks of one body
+    ///
+    /// Exits can refer to bodies removed since the last step, which are skipped.
+    fn dispatch_trigger(&mut self, handle: BodyHandle, other: BodyHandle, phase: TriggerPhase) {
+        match handle {
+            BodyHandle::Point(_) => {}
+            BodyHandle::Quad(i) => self.quads.get_mut(i).map_or((), |quad| quad.dispatch_trigger(other, phase)),
+            BodyHandle::Circle(i) => {
+                self.circles.get_mut(i).map_or((), |circle| circle.dispatch_trigger(other, phase))
+            }
+            BodyHandle::Polygon(i) => {
+                self.polygons.get_mut(i).map_or((), |polygon| polygon.dispatch_trigger(other, phase))
+            }
+        }
+    }
+
+    /// Sweep a rectangle along `motion` and find the first object it would hit
+    ///
+    /// Moving the rectangle by `motion * hit.time` leaves it touching the
+    /// surface, which stops fast movers from tunnelling through thin geometry.
+    /// Points and circles are exact; rotated quads and polygons are swept
+    /// against their bounding boxes.
+    ///
+    /// # Arguments
+    /// * `rect` - Bounds of the moving shape at the start of the motion
+    /// * `motion` - Full displacement over the step
+    /// * `ignore` - Object to skip, usually the one doing the moving
+    pub fn sweep_aabb(&self, rect: Rect, motion: Vec2, ignore: Option<BodyHandle>) -> Option<(BodyHandle, CastHit)> {
+        let swept = rect.combine_with(Rect::new(rect.x + motion.x, rect.y + motion.y, rect.w, rect.h));
+        // A rectangle hitting a round object is the object hitting the rectangle in reverse
+        let against_circle = |center: Vec2, radius: f32| {
+            circle_cast_aabb(center, radius, -motion, rect).map(|hit| CastHit { normal: -hit.normal, ..hit })
+        };
+        let hit = |handle| match handle {
+            BodyHandle::Point(i) => {
+                let point = &self.points[i];
+                against_circle(vec2(point.position.0, point.position.1), point.radius)
+            }
+            BodyHandle::Quad(i) => sweep_aabb(rect, motion, self.quads[i].bounds()),
+            BodyHandle::Circle(i) => {
+                let circle = &self.circles[i];
+                against_circle(vec2(circle.position.0, circle.position.1), circle.radius)
+            }
+            BodyHandle::Polygon(i) => sweep_aabb(rect, motion, self.polygons[i].bounds()),
+        };
+        earliest_hit(self.object_bounds(), swept, ignore, hit)
+    }
+
+    /// Sweep a circle along `motion` and find the first object it would hit
+    ///
+    /// Points, circles and quads (rotated or not) are exact; polygons are
+    /// swept against their bounding boxes.
+    ///
+    /// # Arguments
+    /// * `center` - Center of the moving circle at the start of the motion
+    /// * `radius` - Radius of the moving circle
+    /// * `motion` - Full displacement over the step
+    /// * `ignore` - Object to skip, usually the one doing the moving
+    pub fn circle_cast(
+        &self,
+        center: Vec2,
+        radius: f32,
+        motion: Vec2,
+        ignore: Option<BodyHandle>,
+    ) -> Option<(BodyHandle, CastHit)> {
+        let start = Rect::new(center.x - radius, center.y - radius, radius * 2.0, radius * 2.0);
+        let swept = start.combine_with(Rect::new(start.x + motion.x, start.y + motion.y, start.w, start.h));
+        let hit = |handle| match handle {
+            BodyHandle::Point(i) => {
+                let point = &self.points[i];
+                circle_cast_circle(center, radius, motion, vec2(point.position.0, point.position.1), point.radius)
+            }
+            BodyHandle::Quad(i) => circle_cast_quad(center, radius, motion, &self.quads[i]),
+            BodyHandle::Circle(i) => {
+                let circle = &self.circles[i];
+                circle_cast_circle(center, radius, motion, vec2(circle.position.0, circle.position.1), circle.radius)
+            }
+            BodyHandle::Polygon(i) => circle_cast_aabb(center, radius, motion, self.polygons[i].bounds()),
+        };
+        earliest_hit(self.object_bounds(), swept, ignore, hit)
+    }
+
+    /// Sweep one of the world's quads along `motion`, ignoring the quad itself
+    pub fn sweep_quad(&self, index: usize, motion: Vec2) -> Option<(BodyHandle, CastHit)> {
+        let quad = self.quads.get(index)?;
+        self.sweep_aabb(quad.bounds(), motion, Some(BodyHandle::Quad(index)))
+    }
+
+    /// Find every object containing `point`
+    ///
+    /// Objects are looked up in the broad-phase built by the last `step`, so
+    /// ones added or moved since are only found once the world steps again.
+    pub fn query_point(&self, point: Vec2) -> Vec<BodyHandle> {
+        self.query(point, point, |handle| match handle {
+            BodyHandle::Point(i) => self.points.get(i).is_some_and(|p| {
+                point.distance(vec2(p.position.0, p.position.1)) <= p.radius
+            }),
+            BodyHandle::Quad(i) => self.quads.get(i).is_some_and(|quad| {
+                let local = quad.to_local(point);
+                (0.0..=quad.size.0).contains(&local.x) && (0.0..=quad.size.1).contains(&local.y)
+            }),
+            BodyHandle::Circle(i) => self.circles.get(i).is_some_and(|circle| {
+                point.distance(vec2(circle.position.0, circle.position.1)) <= circle.radius
+            }),
+            BodyHandle::Polygon(i) => self.polygons.get(i).is_some_and(|polygon| {
+                polygon_contains(&polygon.world_vertices(), point)
+            }),
+        })
+    }
+
+    /// Find every object overlapping `rect`
+    ///
+    /// Uses the broad-phase built by the last `step`, like `query_point`.
+    pub fn query_aabb(&self, rect: Rect) -> Vec<BodyHandle> {
+        let area = Quad::new(rect.x, rect.y, rect.w, rect.h, BLANK);
+        self.query(rect.point(), rect.point() + rect.size(), |handle| match handle {
+            BodyHandle::Point(i) => self.points.get(i).is_some_and(|p| {
+                let center = vec2(p.position.0, p.position.1);
+                center.distance(center.clamp(rect.point(), rect.point() + rect.size())) < p.radius
+            }),
+            BodyHandle::Quad(i) => self.quads.get(i).is_some_and(|quad| area.is_colliding_with(quad)),
+            BodyHandle::Circle(i) => self
+                .circles
+                .get(i)
+                .is_some_and(|circle| circle_quad_overlap(circle, &area).is_some()),
+            BodyHandle::Polygon(i) => self
+                .polygons
+                .get(i)
+                .is_some_and(|polygon| polygon_quad_overlap(polygon, &area).is_some()),
+        })
+    }
+
+    /// Find every object overlapping the circle at `center`
+    ///
+    /// Uses the broad-phase built by the last `step`, like `query_point`.
+    pub fn query_circle(&self, center: Vec2, radius: f32) -> Vec<BodyHandle> {
+        let area = CircleBody::new(center.x, center.y, radius, BLANK);
+        let extent = Vec2::splat(radius);
+        self.query(center - extent, center + extent, |handle| match handle {
+            BodyHandle::Point(i) => self.points.get(i).is_some_and(|p| {
+                center.distance(vec2(p.position.0, p.position.1)) < radius + p.radius
+            }),
+            BodyHandle::Quad(i) => self.quads.get(i).is_some_and(|quad| circle_quad_overlap(&area, quad).is_some()),
+            BodyHandle::Circle(i) => self.circles.get(i).is_some_and(|circle| circle_overlap(&area, circle).is_some()),
+            BodyHandle::Polygon(i) => self
+                .polygons
+                .get(i)
+                .is_some_and(|polygon| polygon_circle_overlap(polygon, &area).is_some()),
+        })
+    }
+
+    /// Find every object with a tag, in handle order
+    ///
+    /// Unlike the overlap queries this looks at every object, so it also
+    /// finds ones added since the last `step`.
+    pub fn find_by_tag(&self, tag: impl AsRef<str>) -> Vec<BodyHandle> {
+        let tag = tag.as_ref();
+        let points = self.points.iter().enumerate().filter(|(_, p)| p.has_tag(tag)).map(|(i, _)| BodyHandle::Point(i));
+        let quads = active(&self.quads, |q| q.active).filter(|(_, q)| q.has_tag(tag)).map(|(i, _)| BodyHandle::Quad(i));
+        let circles = active(&self.circles, |c| c.active).filter(|(_, c)| c.has_tag(tag)).map(|(i, _)| BodyHandle::Circle(i));
+        let polygons = self.polygons.iter().enumerate().filter(|(_, p)| p.has_tag(tag)).map(|(i, _)| BodyHandle::Polygon(i));
+        points.chain(quads).chain(circles).chain(polygons).collect()
+    }
+
+    /// Get the tags of an object, or none if the handle is out of range
+    pub fn tags(&self, handle: BodyHandle) -> &[String] {
+        match handle {
+            BodyHandle::Point(i) => self.points.get(i).map(|p| p.tags()),
+            BodyHandle::Quad(i) => self.quads.get(i).map(|q| q.tags()),
+            BodyHandle::Circle(i) => self.circles.get(i).map(|c| c.tags()),
+            BodyHandle::Polygon(i) => self.polygons.get(i).map(|p| p.tags()),
+        }
+        .unwrap_or(&[])
+    }
+
+    /// Check if an object has a tag, such as the other body in a collision event
+    pub fn has_tag(&self, handle: BodyHandle, tag: impl AsRef<str>) -> bool {
+        self.tags(handle).iter().any(|t| t == tag.as_ref())
+    }
+
+    /// Objects in the query broad-phase near `min`..`max` that pass the exact `hits` test, in handle order
+    fn query(&self, min: Vec2, max: Vec2, hits: impl Fn(BodyHandle) -> bool) -> Vec<BodyHandle> {
+        self.query_index
+            .query(min, max)
+            .into_iter()
+            .map(|index| self.query_handles[index])
+            .filter(|&handle| hits(handle))
+            .collect()
+    }
+
+    /// Draw every constraint, point and body
+    ///
+    /// Points and bodies are drawn by their draw layer, and by their bottom
+    /// edge within a layer if `sort_by_y` is set, over the terrain, fills and constraints.
+    pub fn draw(&self) {
+        for terrain in &self.terrains {
+            terrain.draw();
+        }
+        for fill in &self.fills {
+            fill.draw(&self.points);
+        }
+        for constraint in &self.constraints {
+            constraint.draw(&self.points);
+        }
+        let mut renderer = Renderer::new().with_y_sort(self.sort_by_y);
+        renderer.extend(&self.points);
+        for (_, quad) in active(&self.quads, |quad| quad.active) {
+            renderer.push(quad);
+        }
+        for (_, circle) in active(&self.circles, |circle| circle.active) {
+            renderer.push(circle);
+        }
+        renderer.extend(&self.polygons);
+        renderer.draw();
+        for hinge in &self.hinges {
+            hinge.draw(&self.quads);
+        }
+        for fluid in &self.fluids {
+            fluid.draw();
+        }
+    }
+}
+
+/// Check if a `Force` component on `owner` is pushing it
+fn is_pushed<T: ComponentOwner>(owner: &T) -> bool
+where
+    Force: Component<T>,
+{
+    owner.get_component::<Force>().is_some_and(|force| force.enabled && force.force != Vec2::ZERO)
+}
+
+/// Bodies in `bodies` that haven't been recycled, with their indices
+fn active<T>(bodies: &[T], is_active: impl Fn(&T) -> bool) -> impl Iterator<Item = (usize, &T)> {
+    bodies.iter().enumerate().filter(move |(_, body)| is_active(body))
+}
+
+/// Borrow two distinct elements mutably, `i` must be less than `j`
+fn pair_mut<T>(items: &mut [T], i: usize, j: usize) -> (&mut T, &mut T) {
+    let (left, right) = items.split_at_mut(j);
+    (&mut left[i], &mut right[0])
+}
+
+/// Check if a convex polygon, with vertices in order around its outline, contains `point`
+fn polygon_contains(vertices: &[Vec2], point: Vec2) -> bool {
+    let sides: Vec<f32> = (0..vertices.len())
+        .map(|i| (vertices[(i + 1) % vertices.len()] - vertices[i]).perp_dot(point - vertices[i]))
+        .collect();
+    !vertices.is_empty() && (sides.iter().all(|&side| side >= 0.0) || sides.iter().all(|&side| side <= 0.0))
+}
+
+/// Remember `normal` as the quad's ground if it faces further up than what was recorded
+fn record_ground(ground_normals: &mut HashMap<usize, Vec2>, index: usize, normal: Vec2) {
+    if normal.y < 0.0 {
+        let ground = ground_normals.entry(index).or_insert(normal);
+        if normal.y < ground.y {
+            *ground = normal;
+        }
+    }
+}
+
+/// Bounding box of a point's collision circle
+fn point_bounds(point: &Point) -> Rect {
+    Rect::new(
+        point.position.0 - point.radius,
+        point.position.1 - point.radius,
+        point.radius * 2.0,
+        point.radius * 2.0,
+    )
+}
+
+/// Earliest cast hit among candidates whose bounds touch the swept region
+///
+/// `hit` only runs for candidates inside `swept`, so the others cost only a bounds check.
+fn earliest_hit(
+    candidates: Vec<(BodyHandle, Rect)>,
+    swept: Rect,
+    ignore: Option<BodyHandle>,
+    hit: impl Fn(BodyHandle) -> Option<CastHit>,
+) -> Option<(BodyHandle, CastHit)> {
+    candidates
+        .into_iter()
+        .filter(|(handle, bounds)| Some(*handle) != ignore && bounds.overlaps(&swept))
+        .filter_map(|(handle, _)| hit(handle).map(|hit| (handle, hit)))
+        .min_by(|a, b| a.1.time.total_cmp(&b.1.time))
+}
@@ -0,0 +1,220 @@
+//! Platformer movement
+//!
+//! This module provides a component that turns a Quad into a platformer
+//! character, with the small timing helpers that make jumping feel fair.
+//!
+//! # Features
+//! - Coyote time, so a jump still works just after running off a ledge
+//! - Jump buffering, so a jump pressed just before landing isn't lost
+//! - Variable jump height, releasing the jump early cuts the rise short
+//! - A maximum fall speed
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::platformer::PlatformerMovement;
+//!
+//! player.add_component(Box::new(
+//!     PlatformerMovement::new(220.0, 480.0).with_coyote_time(0.12),
+//! ));
+//! ```
+
+use macroquad::input::{KeyCode, is_key_down};
+
+use crate::basics::Component;
+use crate::basics::collision::Segment;
+use crate::objects::quad::Quad;
+
+/// How far above another Quad's top edge the character still counts as standing on it
+const GROUND_TOLERANCE: f32 = 1.0;
+
+/// Smallest upward part of a terrain normal that still counts as ground, so slopes up to 60 degrees
+const MIN_GROUND_NORMAL: f32 = 0.5;
+
+/// Component that moves a Quad left and right and makes it jump from the keyboard
+///
+/// The character is standing whenever it collides with the top of another
+/// Quad or with a walkable terrain segment, so it works with a `PhysicsWorld`
+/// or with `dispatch_collision`.
+/// Gravity is expected to pull down the screen. Timings are in seconds.
+///
+/// # Fields
+///
+/// - `left`, `right`, `jump`: Keys read each update.
+/// - `speed`: Horizontal velocity while a direction key is held.
+/// - `jump_speed`: Upward velocity given by a jump.
+/// - `coyote_time`: Seconds after leaving the ground that a jump is still allowed.
+/// - `jump_buffer_time`: Seconds a jump press is remembered before landing.
+/// - `jump_cut`: Fraction of the upward velocity kept when the jump is released early.
+/// - `max_fall_speed`: Fastest the character can fall.
+/// - `enabled`: Whether the keys move the character, so control can be taken away during cutscenes.
+pub struct PlatformerMovement {
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub jump: KeyCode,
+    pub speed: f32,
+    pub jump_speed: f32,
+    pub coyote_time: f32,
+    pub jump_buffer_time: f32,
+    pub jump_cut: f32,
+    pub max_fall_speed: f32,
+    pub enabled: bool,
+    touching_ground: bool,
+    air_time: f32,
+    since_jump_press: f32,
+    jump_was_held: bool,
+    rising: bool,
+}
+
+impl PlatformerMovement {
+    /// Creates movement with arrow keys and space, and a tenth of a second of coyote time and jump buffering.
+    ///
+    /// # Parameters
+    /// - `speed`: Horizontal velocity while a direction key is held.
+    /// - `jump_speed`: Upward velocity given by a jump.
+    ///
+    /// # Returns
+    /// A new `PlatformerMovement` instance.
+    pub fn new(speed: f32, jump_speed: f32) -> Self {
+        Self {
+            left: KeyCode::Left,
+            right: KeyCode::Right,
+            jump: KeyCode::Space,
+            speed,
+            jump_speed,
+            coyote_time: 0.1,
+            jump_buffer_time: 0.1,
+            jump_cut: 0.5,
+            max_fall_speed: f32::INFINITY,
+            enabled: true,
+            touching_ground: false,
+            air_time: f32::INFINITY,
+            since_jump_press: f32::INFINITY,
+            jump_was_held: false,
+            rising: false,
+        }
+    }
+
+    /// Set the keys for moving left, moving right and jumping
+    pub fn with_keys(mut self, left: KeyCode, right: KeyCode, jump: KeyCode) -> Self {
+        self.left = left;
+        self.right = right;
+        self.jump = jump;
+        self
+    }
+
+    /// Set how many seconds after leaving the ground a jump is still allowed
+    pub fn with_coyote_time(mut self, seconds: f32) -> Self {
+        self.coyote_time = seconds.max(0.0);
+        self
+    }
+
+    /// Set how many seconds a jump press is remembered before landing
+    pub fn with_jump_buffer_time(mut self, seconds: f32) -> Self {
+        self.jump_buffer_time = seconds.max(0.0);
+        self
+    }
+
+    /// Set the fraction of upward velocity kept when the jump is released early, 1.0 for fixed-height jumps
+    pub fn with_jump_cut(mut self, jump_cut: f32) -> Self {
+        self.jump_cut = jump_cut.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the fastest the character can fall
+    pub fn with_max_fall_speed(mut self, max_fall_speed: f32) -> Self {
+        self.max_fall_speed = max_fall_speed.max(0.0);
+        self
+    }
+
+    /// Check if the character stood on something during the last update
+    pub fn is_grounded(&self) -> bool {
+        self.air_time == 0.0
+    }
+
+    /// Moves and jumps from input given by the caller instead of the keyboard
+    ///
+    /// `update` calls this with the configured keys; call it directly for
+    /// gamepads, replays or computer-controlled characters.
+    ///
+    /// # Parameters
+    /// - `quad`: The character.
+    /// - `horizontal`: Movement direction from -1.0 (left) to 1.0 (right).
+    /// - `jump_held`: Whether the jump input is held down.
+    /// - `dt`: Seconds since the last update.
+    pub fn drive(&mut self, quad: &mut Quad, horizontal: f32, jump_held: bool, dt: f32) {
+        // A sleeping character would stop reading input
+        quad.can_sleep = false;
+        if !quad.body_type.is_dynamic() {
+            return;
+        }
+
+        self.air_time = if self.touching_ground { 0.0 } else { self.air_time + dt };
+        self.touching_ground = false;
+
+        self.since_jump_press = if jump_held && !self.jump_was_held { 0.0 } else { self.since_jump_press + dt };
+        self.jump_was_held = jump_held;
+
+        if self.since_jump_press <= self.jump_buffer_time && self.air_time <= self.coyote_time {
+            quad.velocity_y = -self.jump_speed;
+            self.rising = true;
+            self.since_jump_press = f32::INFINITY;
+            self.air_time = f32::INFINITY;
+        }
+
+        if self.rising && (quad.velocity_y >= 0.0 || !jump_held) {
+            if quad.velocity_y < 0.0 {
+                quad.velocity_y *= self.jump_cut;
+            }
+            self.rising = false;
+        }
+        quad.velocity_y = quad.velocity_y.min(self.max_fall_speed);
+        quad.velocity_x = horizontal.clamp(-1.0, 1.0) * self.speed;
+    }
+}
+
+impl Component<Quad> for PlatformerMovement {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Forgets the ground, jump buffer and jump in progress.
+    fn on_reset(&mut self, _owner: &mut Quad) {
+        self.touching_ground = false;
+        self.air_time = f32::INFINITY;
+        self.since_jump_press = f32::INFINITY;
+        self.jump_was_held = false;
+        self.rising = false;
+    }
+
+    /// Reads the keyboard and moves the character.
+    fn update(&mut self, quad: &mut Quad, dt: f32) {
+        let horizontal = is_key_down(self.right) as i32 - is_key_down(self.left) as i32;
+        let jump_held = is_key_down(self.jump);
+        self.drive(quad, horizontal as f32, jump_held, dt);
+    }
+
+    /// Counts the character as standing when it touches the top of `other`.
+    fn on_collide(&mut self, me: &mut Quad, other: &mut Quad) {
+        if other.is_trigger {
+            return;
+        }
+        let bottom = me.position.1 + me.size.1;
+        let above = me.position.1 + me.size.1 * 0.5 < other.position.1;
+        let overlaps_x = me.position.0 < other.position.0 + other.size.0
+            && me.position.0 + me.size.0 > other.position.0;
+        if above && overlaps_x && bottom >= other.position.1 - GROUND_TOLERANCE {
+            self.touching_ground = true;
+        }
+    }
+
+    /// Counts the character as standing on terrain that isn't too steep.
+    fn on_collide_segment(&mut self, me: &mut Quad, segment: &Segment) {
+        if -segment.normal_towards(me.center()).y >= MIN_GROUND_NORMAL {
+            self.touching_ground = true;
+        }
+    }
+}
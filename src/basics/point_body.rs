@@ -0,0 +1,332 @@
+//! Point Bodies
+//!
+//! This module provides helpers for bodies made of points held together by
+//! constraints, like the ones built by the shape factories, and a rigid mode
+//! that moves such a body as one solid piece.
+//!
+//! # Features
+//! - Center of mass, velocity, angular velocity and moment of inertia of a set of points
+//! - Torque spread over the points so the body spins without drifting
+//! - Best-fit position and rotation of the points compared to their rest shape
+//! - Rigid bodies that keep their points in the rest shape, without solving their constraints
+//! - Shape matching that pulls soft bodies back toward their rest shape after impacts
+//! - Quads that follow the center and rotation of a body, to carry its sprite
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::point_body::{angular_velocity, apply_torque, center_of_mass};
+//!
+//! let wheel = world.add_body(points, constraints);
+//! apply_torque(&mut world.points[wheel.clone()], 5000.0);
+//! let center = center_of_mass(&world.points[wheel.clone()]);
+//! let spin = angular_velocity(&world.points[wheel.clone()]);
+//!
+//! // Or keep the crate from ever bending
+//! let crate_body = world.add_body(crate_points, crate_constraints);
+//! world.make_rigid(crate_body);
+//!
+//! // Or let it dent on impact, then spring back
+//! world.add_shape_match(crate_body, 0.3);
+//!
+//! // Draw a sprite on a wobbly blob
+//! let blob = world.add_body(blob_points, blob_constraints);
+//! let sprite = world.add_quad(Quad::new(280.0, 280.0, 40.0, 40.0, WHITE));
+//! world.attach_quad(sprite, blob);
+//! ```
+
+use std::ops::Range;
+
+use macroquad::math::{Vec2, vec2};
+
+use crate::basics::BodyType;
+use crate::objects::point::Point;
+use crate::objects::quad::Quad;
+
+/// How much more a fixed point counts than a moving one when fitting a rigid body, so it acts as a pivot
+const PINNED_WEIGHT: f32 = 1.0e4;
+
+/// Position of a point as a vector
+fn position(point: &Point) -> Vec2 {
+    vec2(point.position.0, point.position.1)
+}
+
+/// Velocity of a point as a vector
+fn velocity(point: &Point) -> Vec2 {
+    vec2(point.velocity.0, point.velocity.1)
+}
+
+/// Mass-weighted average position of the points
+pub fn center_of_mass(points: &[Point]) -> Vec2 {
+    weighted_average(points, |point| point.mass, position)
+}
+
+/// Mass-weighted average velocity of the points
+pub fn linear_velocity(points: &[Point]) -> Vec2 {
+    weighted_average(points, |point| point.mass, velocity)
+}
+
+/// How hard the points are to spin around their center of mass
+pub fn moment_of_inertia(points: &[Point]) -> f32 {
+    let center = center_of_mass(points);
+    points.iter().map(|point| point.mass * (position(point) - center).length_squared()).sum()
+}
+
+/// How fast the points spin around their center of mass, in radians per second clockwise on screen
+pub fn angular_velocity(points: &[Point]) -> f32 {
+    spin(points, |point| point.mass, center_of_mass(points), linear_velocity(points))
+}
+
+/// Add forces to the points that spin them around their center of mass without moving it
+///
+/// Like other forces, the torque acts on the next update of the points.
+/// Positive torque spins clockwise on screen.
+pub fn apply_torque(points: &mut [Point], torque: f32) {
+    let inertia = moment_of_inertia(points);
+    if inertia <= 0.0 {
+        return;
+    }
+    let center = center_of_mass(points);
+    let acceleration = torque / inertia;
+    for point in points.iter_mut().filter(|point| !point.is_fixed()) {
+        let force = (position(point) - center).perp() * acceleration * point.mass;
+        point.force.0 += force.x;
+        point.force.1 += force.y;
+    }
+}
+
+/// Average of `value` over the points, weighted by `weight`
+fn weighted_average(points: &[Point], weight: impl Fn(&Point) -> f32, value: impl Fn(&Point) -> Vec2) -> Vec2 {
+    let total: f32 = points.iter().map(&weight).sum();
+    if total <= 0.0 {
+        return Vec2::ZERO;
+    }
+    points.iter().map(|point| value(point) * weight(point)).sum::<Vec2>() / total
+}
+
+/// Angular velocity of the points around `center`, once `linear` is taken out of their velocities
+fn spin(points: &[Point], weight: impl Fn(&Point) -> f32, center: Vec2, linear: Vec2) -> f32 {
+    let (momentum, inertia) = points.iter().fold((0.0, 0.0), |(momentum, inertia), point| {
+        let offset = position(point) - center;
+        let w = weight(point);
+        (momentum + w * offset.perp_dot(velocity(point) - linear), inertia + w * offset.length_squared())
+    });
+    if inertia > 0.0 { momentum / inertia } else { 0.0 }
+}
+
+/// A set of points that moves as one solid piece
+///
+/// The shape the points have when the body is created is kept as its rest
+/// shape. Each `solve` finds the position and rotation that best match
+/// where the points have been pushed, then puts every point back in the rest
+/// shape there, moving together at one linear and angular velocity. Fixed
+/// points stay put and act as pivots.
+///
+/// # Fields
+///
+/// - `points`: Indices of the body's points.
+/// - `offsets`: Rest position of each point relative to the body's rest center.
+/// - `center`: Center of the body as of the last `solve` or `fit`.
+/// - `rotation`: Angle in radians, clockwise on screen, from the rest shape as of the last `solve` or `fit`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RigidPointBody {
+    pub points: Range<usize>,
+    pub offsets: Vec<Vec2>,
+    pub center: Vec2,
+    pub rotation: f32,
+}
+
+impl RigidPointBody {
+    /// Creates a rigid body from the points at `range`, using their current shape as the rest shape.
+    ///
+    /// # Parameters
+    /// - `points`: Every point, such as `PhysicsWorld::points`.
+    /// - `range`: Indices of the body's points, such as returned by `PhysicsWorld::add_body`.
+    ///
+    /// # Returns
+    /// A new `RigidPointBody` instance.
+    pub fn new(points: &[Point], range: Range<usize>) -> Self {
+        let (offsets, center) = rest_shape(&points[range.clone()]);
+        Self {
+            points: range,
+            offsets,
+            center,
+            rotation: 0.0,
+        }
+    }
+
+    /// Find the position and rotation that best match where the points are now
+    ///
+    /// # Returns
+    /// The center and rotation, also stored in `center` and `rotation`
+    pub fn fit(&mut self, points: &[Point]) -> (Vec2, f32) {
+        (self.center, self.rotation) = fit_shape(&points[self.points.clone()], &self.offsets, self.rotation);
+        (self.center, self.rotation)
+    }
+
+    /// Where the point with the given offset belongs for the last fit
+    pub fn rest_position(&self, offset: Vec2) -> Vec2 {
+        self.center + Vec2::from_angle(self.rotation).rotate(offset)
+    }
+
+    /// Put every moving point back in the rest shape, moving as one body.
+    pub fn solve(&mut self, points: &mut [Point]) {
+        self.fit(points);
+        let body = &points[self.points.clone()];
+        let linear = weighted_average(body, fit_weight, velocity);
+        let angular = spin(body, fit_weight, self.center, linear);
+        let rotation = Vec2::from_angle(self.rotation);
+        for (point, offset) in points[self.points.clone()].iter_mut().zip(&self.offsets) {
+            if point.is_fixed() {
+                continue;
+            }
+            let offset = rotation.rotate(*offset);
+            let target = self.center + offset;
+            let velocity = linear + offset.perp() * angular;
+            point.position = (target.x, target.y);
+            point.velocity = (velocity.x, velocity.y);
+        }
+    }
+}
+
+/// A soft body's rest shape that its points are pulled back toward
+///
+/// Constraints only keep the distances they join, so a square hit hard
+/// enough can fold into a rhombus and stay that way. Shape matching finds
+/// where the whole rest shape best fits the points and moves each point part
+/// of the way back to its spot in it, whatever happened to the constraints.
+///
+/// # Fields
+///
+/// - `points`: Indices of the body's points.
+/// - `offsets`: Rest position of each point relative to the body's rest center.
+/// - `stiffness`: Fraction of the way back to the rest shape each point moves per solve, from 0.0 to 1.0.
+/// - `center`: Center of the best fit as of the last `solve`.
+/// - `rotation`: Angle in radians, clockwise on screen, from the rest shape as of the last `solve`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeMatch {
+    pub points: Range<usize>,
+    pub offsets: Vec<Vec2>,
+    pub stiffness: f32,
+    pub center: Vec2,
+    pub rotation: f32,
+}
+
+impl ShapeMatch {
+    /// Creates a shape match for the points at `range`, using their current shape as the rest shape.
+    ///
+    /// # Parameters
+    /// - `points`: Every point, such as `PhysicsWorld::points`.
+    /// - `range`: Indices of the body's points, such as returned by `PhysicsWorld::add_body`.
+    /// - `stiffness`: Fraction of the way back to the rest shape each point moves per solve.
+    ///
+    /// # Returns
+    /// A new `ShapeMatch` instance.
+    pub fn new(points: &[Point], range: Range<usize>, stiffness: f32) -> Self {
+        let (offsets, center) = rest_shape(&points[range.clone()]);
+        Self {
+            points: range,
+            offsets,
+            stiffness: stiffness.clamp(0.0, 1.0),
+            center,
+            rotation: 0.0,
+        }
+    }
+
+    /// Move every moving point part of the way back to its spot in the best-fitting rest shape.
+    pub fn solve(&mut self, points: &mut [Point]) {
+        (self.center, self.rotation) = fit_shape(&points[self.points.clone()], &self.offsets, self.rotation);
+        let rotation = Vec2::from_angle(self.rotation);
+        for (point, offset) in points[self.points.clone()].iter_mut().zip(&self.offsets) {
+            if point.is_fixed() {
+                continue;
+            }
+            let target = self.center + rotation.rotate(*offset);
+            let moved = position(point).lerp(target, self.stiffness);
+            point.position = (moved.x, moved.y);
+        }
+    }
+}
+
+/// Keeps a quad on a body made of points, following its center and rotation
+///
+/// The points drag the quad along but aren't pushed by it, so a quad
+/// carrying a sprite can sit on a blob from `create_circle` or
+/// `create_polygon` without averaging its points every frame. The quad keeps
+/// the offset and rotation it had from the points when it was attached.
+///
+/// # Fields
+///
+/// - `quad`: Index of the quad that follows the points.
+/// - `body`: Best fit of the points, tracking their center and rotation.
+/// - `offset`: Quad center relative to the points' center, in their rest shape.
+/// - `rotation`: Quad rotation when the points are in their rest shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoftBodyAnchor {
+    pub quad: usize,
+    pub body: RigidPointBody,
+    pub offset: Vec2,
+    pub rotation: f32,
+}
+
+impl SoftBodyAnchor {
+    /// Creates an anchor keeping `quad` where it is now relative to the points at `range`.
+    ///
+    /// # Parameters
+    /// - `points`: Every point, such as `PhysicsWorld::points`.
+    /// - `range`: Indices of the body's points, such as returned by `PhysicsWorld::add_body`.
+    /// - `index`: Index of the quad, such as returned by `PhysicsWorld::add_quad`.
+    /// - `quad`: The quad at `index`.
+    ///
+    /// # Returns
+    /// A new `SoftBodyAnchor` instance.
+    pub fn new(points: &[Point], range: Range<usize>, index: usize, quad: &Quad) -> Self {
+        let body = RigidPointBody::new(points, range);
+        Self {
+            quad: index,
+            offset: quad.center() - body.center,
+            rotation: quad.rotation,
+            body,
+        }
+    }
+
+    /// Move `quad` to its spot on the points, matching their velocity and spin.
+    pub fn follow(&mut self, points: &[Point], quad: &mut Quad) {
+        let (center, rotation) = self.body.fit(points);
+        let body = &points[self.body.points.clone()];
+        let target = center + Vec2::from_angle(rotation).rotate(self.offset);
+        let size = vec2(quad.size.0, quad.size.1);
+        let pivot = size * vec2(quad.pivot.0, quad.pivot.1);
+        quad.rotation = self.rotation + rotation;
+        let position = target - pivot - Vec2::from_angle(quad.rotation).rotate(size * 0.5 - pivot);
+        let velocity = linear_velocity(body);
+        quad.position = (position.x, position.y);
+        (quad.velocity_x, quad.velocity_y) = (velocity.x, velocity.y);
+        quad.angular_velocity = angular_velocity(body);
+        quad.body_type = BodyType::Kinematic;
+    }
+}
+
+/// Offsets of the points from their center, and that center, to use as a rest shape
+fn rest_shape(points: &[Point]) -> (Vec<Vec2>, Vec2) {
+    let center = weighted_average(points, fit_weight, position);
+    (points.iter().map(|point| position(point) - center).collect(), center)
+}
+
+/// Center and rotation of the rest shape given by `offsets` that best fit the points
+///
+/// Keeps `rotation` when the points give no rotation at all, such as when
+/// they have all collapsed onto their center.
+fn fit_shape(points: &[Point], offsets: &[Vec2], rotation: f32) -> (Vec2, f32) {
+    let center = weighted_average(points, fit_weight, position);
+    let (cross, dot) = points.iter().zip(offsets).fold((0.0, 0.0), |(cross, dot), (point, offset)| {
+        let current = position(point) - center;
+        let w = fit_weight(point);
+        (cross + w * offset.perp_dot(current), dot + w * offset.dot(current))
+    });
+    if cross != 0.0 || dot != 0.0 { (center, cross.atan2(dot)) } else { (center, rotation) }
+}
+
+/// How much a point counts when fitting a rest shape
+fn fit_weight(point: &Point) -> f32 {
+    if point.is_fixed() { point.mass * PINNED_WEIGHT } else { point.mass }
+}
@@ -0,0 +1,205 @@
+//! Object pooling
+//!
+//! This module provides a pool that keeps objects around once they are done
+//! with, so bullets, particles and other short-lived objects are reused
+//! instead of built, with their boxed components, every time one appears.
+//!
+//! # Features
+//! - Objects built on demand, or ahead of time with `prewarm`
+//! - Hooks run on every object as it is spawned and as it is recycled
+//! - Stable indices, so an object keeps its slot while it is active
+//! - An optional cap on how many objects the pool builds
+//!
+//! Bodies in a `PhysicsWorld` are pooled by the world itself, see
+//! `PhysicsWorld::recycle` and `PhysicsWorld::respawn_quad`.
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::pool::Pool;
+//!
+//! let mut sparks = Pool::new(|| Point::builder().radius(2.0).color(YELLOW).build())
+//!     .with_on_recycle(|spark: &mut Point| spark.reset_components())
+//!     .with_max_size(500);
+//! sparks.prewarm(100);
+//!
+//! if let Some(index) = sparks.spawn_with(|spark| spark.position = (x, y)) {
+//!     sparks.get_mut(index).unwrap().velocity = (0.0, -200.0);
+//! }
+//! sparks.recycle_where(|spark| spark.position.1 > screen_height());
+//! ```
+
+/// Code run on a pooled object as it is spawned or recycled
+type Hook<T> = Box<dyn FnMut(&mut T)>;
+
+/// Objects kept for reuse, each either active or waiting to be spawned again
+///
+/// # Fields
+///
+/// - `items`: Every object the pool built, active or not, by index.
+/// - `active`: Whether the object at each index is in use.
+/// - `free`: Indices of objects waiting to be spawned again.
+/// - `create`: Builds a new object when none is waiting.
+/// - `on_spawn`: Runs on an object each time it is spawned, if set.
+/// - `on_recycle`: Runs on an object each time it is recycled, if set.
+/// - `max_size`: Most objects the pool builds, if capped.
+pub struct Pool<T> {
+    items: Vec<T>,
+    active: Vec<bool>,
+    free: Vec<usize>,
+    create: Box<dyn FnMut() -> T>,
+    on_spawn: Option<Hook<T>>,
+    on_recycle: Option<Hook<T>>,
+    pub max_size: Option<usize>,
+}
+
+impl<T> Pool<T> {
+    /// Creates an empty pool that builds objects with `create`.
+    pub fn new(create: impl FnMut() -> T + 'static) -> Self {
+        Self {
+            items: Vec::new(),
+            active: Vec::new(),
+            free: Vec::new(),
+            create: Box::new(create),
+            on_spawn: None,
+            on_recycle: None,
+            max_size: None,
+        }
+    }
+
+    /// Run `hook` on an object each time it is spawned, new or reused
+    pub fn with_on_spawn(mut self, hook: impl FnMut(&mut T) + 'static) -> Self {
+        self.on_spawn = Some(Box::new(hook));
+        self
+    }
+
+    /// Run `hook` on an object each time it is recycled, to reset it for its next use
+    pub fn with_on_recycle(mut self, hook: impl FnMut(&mut T) + 'static) -> Self {
+        self.on_recycle = Some(Box::new(hook));
+        self
+    }
+
+    /// Build at most `max_size` objects; spawning fails once all of them are active
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Build objects ahead of time until the pool holds `count`, so spawning doesn't have to
+    pub fn prewarm(&mut self, count: usize) {
+        let count = self.max_size.map_or(count, |max| count.min(max));
+        while self.items.len() < count {
+            self.items.push((self.create)());
+            self.active.push(false);
+            self.free.push(self.items.len() - 1);
+        }
+    }
+
+    /// Take an object out of the pool, building one if none is waiting, and return its index
+    ///
+    /// Returns `None` if every object is active and the pool is at its `max_size`.
+    pub fn spawn(&mut self) -> Option<usize> {
+        self.spawn_with(|_| {})
+    }
+
+    /// Take an object out of the pool like `spawn`, then set it up with `setup` after the spawn hook
+    pub fn spawn_with(&mut self, setup: impl FnOnce(&mut T)) -> Option<usize> {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None if self.max_size.is_some_and(|max| self.items.len() >= max) => return None,
+            None => {
+                self.items.push((self.create)());
+                self.active.push(false);
+                self.items.len() - 1
+            }
+        };
+        self.active[index] = true;
+        let item = &mut self.items[index];
+        if let Some(hook) = &mut self.on_spawn {
+            hook(item);
+        }
+        setup(item);
+        Some(index)
+    }
+
+    /// Put an active object back in the pool, returning false if it wasn't active
+    ///
+    /// Its index may be handed out again by the next spawn, so forget it.
+    pub fn recycle(&mut self, index: usize) -> bool {
+        if !self.is_active(index) {
+            return false;
+        }
+        self.active[index] = false;
+        if let Some(hook) = &mut self.on_recycle {
+            hook(&mut self.items[index]);
+        }
+        self.free.push(index);
+        true
+    }
+
+    /// Recycle every active object `predicate` returns true for, returning how many were recycled
+    pub fn recycle_where(&mut self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        let done: Vec<usize> = self.iter().filter(|(_, item)| predicate(item)).map(|(index, _)| index).collect();
+        for &index in &done {
+            self.recycle(index);
+        }
+        done.len()
+    }
+
+    /// Recycle every active object
+    pub fn recycle_all(&mut self) {
+        self.recycle_where(|_| true);
+    }
+
+    /// Check if the object at `index` is in use
+    pub fn is_active(&self, index: usize) -> bool {
+        self.active.get(index).is_some_and(|&active| active)
+    }
+
+    /// The active object at `index`, if any
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.is_active(index).then(|| &self.items[index])
+    }
+
+    /// The active object at `index` mutably, if any
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if !self.is_active(index) {
+            return None;
+        }
+        Some(&mut self.items[index])
+    }
+
+    /// Every active object with its index
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.items.iter().enumerate().filter(|(index, _)| self.active[*index])
+    }
+
+    /// Every active object mutably with its index
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.items
+            .iter_mut()
+            .zip(&self.active)
+            .enumerate()
+            .filter(|(_, (_, active))| **active)
+            .map(|(index, (item, _))| (index, item))
+    }
+
+    /// Number of objects in use
+    pub fn active_count(&self) -> usize {
+        self.items.len() - self.free.len()
+    }
+
+    /// Number of objects waiting to be spawned again
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Number of objects the pool has built, active or not
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if the pool hasn't built any objects yet
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
@@ -0,0 +1,85 @@
+//! Draw ordering
+//!
+//! This module provides a renderer that draws objects by their draw layer
+//! instead of the order the game code happens to call them in, so
+//! backgrounds, actors and foreground overlays stack the same way every frame.
+//!
+//! # Features
+//! - Lower draw layers drawn first, so higher ones end up on top
+//! - Optional sorting by the bottom edge within a layer, for top-down games
+//! - Objects on the same layer and row keep the order they were added in
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::render::Renderer;
+//!
+//! let background = Quad::builder().size(800.0, 600.0).color(DARKGRAY).build();
+//! let mut player = Quad::builder().pos(400.0, 300.0).size(32.0, 32.0).color(BLUE).build();
+//! player.draw_layer = 1;
+//!
+//! let mut renderer = Renderer::new().with_y_sort(true);
+//! renderer.push(&player);
+//! renderer.push(&background);
+//! renderer.draw();
+//! ```
+
+/// Objects a `Renderer` can draw in order
+pub trait Renderable {
+    /// Layer the object is drawn on, higher layers on top
+    fn draw_layer(&self) -> i32;
+
+    /// Y coordinate of the object's bottom edge, which decides the order within a layer when sorting by y
+    fn sort_y(&self) -> f32;
+
+    /// Draw the object
+    fn render(&self);
+}
+
+/// Collects objects for one frame and draws them sorted by layer
+///
+/// # Fields
+///
+/// - `sort_by_y`: Also sort objects on the same layer by their bottom edge, so lower ones overlap higher ones.
+/// - `queue`: Objects waiting for `draw`.
+#[derive(Default)]
+pub struct Renderer<'a> {
+    pub sort_by_y: bool,
+    queue: Vec<&'a dyn Renderable>,
+}
+
+impl<'a> Renderer<'a> {
+    /// Creates a renderer that sorts by layer only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort objects on the same layer by their bottom edge
+    pub fn with_y_sort(mut self, sort_by_y: bool) -> Self {
+        self.sort_by_y = sort_by_y;
+        self
+    }
+
+    /// Queue an object for the next `draw`
+    pub fn push(&mut self, object: &'a dyn Renderable) {
+        self.queue.push(object);
+    }
+
+    /// Queue every object in `objects` for the next `draw`
+    pub fn extend<T: Renderable>(&mut self, objects: &'a [T]) {
+        self.queue.extend(objects.iter().map(|object| object as &dyn Renderable));
+    }
+
+    /// Draws the queued objects by layer, and by y if `sort_by_y` is set, then empties the queue.
+    pub fn draw(&mut self) {
+        if self.sort_by_y {
+            self.queue.sort_by(|a, b| {
+                a.draw_layer().cmp(&b.draw_layer()).then(a.sort_y().total_cmp(&b.sort_y()))
+            });
+        } else {
+            self.queue.sort_by_key(|object| object.draw_layer());
+        }
+        for object in self.queue.drain(..) {
+            object.render();
+        }
+    }
+}
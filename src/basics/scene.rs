@@ -0,0 +1,1293 @@
+//! Scene files
+//!
+//! This module provides a description of a `PhysicsWorld` and the state of
+//! a `UiManager` that can be saved to a file and built again, so level
+//! editors can keep their work and games can ship levels as data.
+//!
+//! # Features
+//! - Points, constraints, quads, circles, polygons, hinges and terrain, with their transforms and settings
+//! - Parented quads, anchors, motors, attractors, point bodies, soft body anchors, fills and fluids
+//! - The world's timestep, sleep and draw order settings
+//! - The parameters of the built-in components: gravity, friction, drag, force and collision
+//! - The physics configuration the world runs with
+//! - Position, visibility and value of every named UI element
+//! - TOML or JSON files, picked by extension, with colors written as hex strings
+//!
+//! Textures, gradients, fonts and custom components aren't saved; attach
+//! them again after building the world. Bodies recycled for reuse come back
+//! recycled. Building checks that every index in the scene points at an
+//! object it holds, so a hand-edited or truncated file fails to build
+//! instead of panicking later.
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::scene::Scene;
+//!
+//! Scene::capture(&world).with_ui(&mut ui).save("levels/level1.toml")?;
+//!
+//! let scene = Scene::load("levels/level1.toml")?;
+//! let mut world = scene.build()?;
+//! scene.apply_ui(&mut ui);
+//! ```
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::ops::Range;
+use std::path::Path;
+
+use macroquad::color::Color;
+use macroquad::math::{Rect, Vec2, vec2};
+use serde::{Deserialize, Serialize};
+
+use crate::basics::attractor::{Attractor, Falloff};
+use crate::basics::collision::Collision;
+use crate::basics::drag::Drag;
+use crate::basics::force::Force;
+use crate::basics::friction::Friction;
+use crate::basics::gravity::Gravity;
+use crate::basics::physics_config::PhysicsConfig;
+use crate::basics::physics_world::{BodyHandle, PhysicsWorld, QuadAnchor, QuadChild};
+use crate::basics::point_body::{RigidPointBody, ShapeMatch, SoftBodyAnchor};
+use crate::basics::transform::Transform;
+use crate::basics::{BodyType, Component, ComponentOwner};
+use crate::objects::circle::CircleBody;
+use crate::objects::constraint::{Constraint, ConstraintMode, Motor};
+use crate::objects::fluid::{Fluid, FluidParticle};
+use crate::objects::joint::HingeJoint;
+use crate::objects::point::Point;
+use crate::objects::polygon::PolygonCollider;
+use crate::objects::quad::{Outline, Quad};
+use crate::objects::shapes::{FillTriangulation, ShapeFill};
+use crate::objects::terrain::TerrainCollider;
+use crate::objects::ui::{CheckState, UiCheckbox, UiDropdown, UiElement, UiInput, UiManager, UiPanel, UiProgressBar, UiSlider};
+use crate::utils::color::hex;
+use crate::utils::data_file;
+
+/// Everything needed to build a world and restore a UI again
+///
+/// Objects keep their order, so indices in constraints and hinges, and the
+/// handles game code holds, point at the same objects once built.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scene {
+    pub config: PhysicsConfig,
+    pub points: Vec<PointData>,
+    pub constraints: Vec<ConstraintData>,
+    pub quads: Vec<QuadData>,
+    pub circles: Vec<CircleData>,
+    pub polygons: Vec<PolygonData>,
+    pub hinges: Vec<HingeData>,
+    pub terrains: Vec<TerrainData>,
+    pub quad_children: Vec<QuadChildData>,
+    pub anchors: Vec<AnchorData>,
+    pub motors: Vec<MotorData>,
+    pub attractors: Vec<AttractorData>,
+    pub rigid_bodies: Vec<PointBodyData>,
+    pub shape_matches: Vec<ShapeMatchData>,
+    pub soft_anchors: Vec<SoftAnchorData>,
+    pub fills: Vec<FillData>,
+    pub fluids: Vec<FluidData>,
+    pub settings: WorldSettings,
+    /// State of each named UI element, by name
+    pub ui: BTreeMap<String, UiElementState>,
+}
+
+impl Scene {
+    /// Describe every object in `world`
+    pub fn capture(world: &PhysicsWorld) -> Self {
+        Self {
+            config: world.config.clone(),
+            points: world.points.iter().map(PointData::capture).collect(),
+            constraints: world.constraints.iter().map(ConstraintData::capture).collect(),
+            quads: world.quads.iter().map(QuadData::capture).collect(),
+            circles: world.circles.iter().map(CircleData::capture).collect(),
+            polygons: world.polygons.iter().map(PolygonData::capture).collect(),
+            hinges: world.hinges.iter().map(HingeData::capture).collect(),
+            terrains: world.terrains.iter().map(TerrainData::capture).collect(),
+            quad_children: world.quad_children.iter().map(QuadChildData::capture).collect(),
+            anchors: world.anchors.iter().map(AnchorData::capture).collect(),
+            motors: world.motors.iter().map(MotorData::capture).collect(),
+            attractors: world.attractors.iter().map(AttractorData::capture).collect(),
+            rigid_bodies: world.rigid_bodies.iter().map(PointBodyData::capture).collect(),
+            shape_matches: world.shape_matches.iter().map(ShapeMatchData::capture).collect(),
+            soft_anchors: world.soft_anchors.iter().map(SoftAnchorData::capture).collect(),
+            fills: world.fills.iter().map(FillData::capture).collect(),
+            fluids: world.fluids.iter().map(FluidData::capture).collect(),
+            settings: WorldSettings::capture(world),
+            ui: BTreeMap::new(),
+        }
+    }
+
+    /// Also describe the named elements of `ui`
+    pub fn with_ui(mut self, ui: &mut UiManager) -> Self {
+        self.capture_ui(ui);
+        self
+    }
+
+    /// Describe the named elements of `ui`, replacing any UI state already in the scene
+    pub fn capture_ui(&mut self, ui: &mut UiManager) {
+        self.ui = ui
+            .element_names()
+            .into_iter()
+            .filter_map(|name| {
+                let state = UiElementState::capture(ui.get_named_element(&name)?);
+                Some((name, state))
+            })
+            .collect();
+    }
+
+    /// Build a new world holding the scene's objects
+    ///
+    /// Fails if an index in the scene points past the objects it holds.
+    pub fn build(&self) -> Result<PhysicsWorld, Box<dyn Error>> {
+        self.validate()?;
+        let mut world = PhysicsWorld::new().with_config(self.config.clone());
+        self.settings.apply(&mut world);
+        for point in &self.points {
+            world.add_point(point.build());
+        }
+        for constraint in &self.constraints {
+            world.add_constraint(constraint.build());
+        }
+        for quad in &self.quads {
+            let index = world.add_quad(quad.build());
+            if !quad.active {
+                world.recycle(BodyHandle::Quad(index));
+            }
+        }
+        for circle in &self.circles {
+            let index = world.add_circle(circle.build());
+            if !circle.active {
+                world.recycle(BodyHandle::Circle(index));
+            }
+        }
+        for polygon in &self.polygons {
+            world.add_polygon(polygon.build());
+        }
+        for hinge in &self.hinges {
+            world.add_hinge(hinge.build());
+        }
+        for terrain in &self.terrains {
+            world.add_terrain(terrain.build());
+        }
+        world.quad_children.extend(self.quad_children.iter().map(QuadChildData::build));
+        world.anchors.extend(self.anchors.iter().map(AnchorData::build));
+        world.motors.extend(self.motors.iter().map(MotorData::build));
+        world.attractors.extend(self.attractors.iter().map(AttractorData::build));
+        world.rigid_bodies.extend(self.rigid_bodies.iter().map(PointBodyData::build));
+        world.shape_matches.extend(self.shape_matches.iter().map(ShapeMatchData::build));
+        world.soft_anchors.extend(self.soft_anchors.iter().map(SoftAnchorData::build));
+        world.fills.extend(self.fills.iter().map(FillData::build));
+        world.fluids.extend(self.fluids.iter().map(FluidData::build));
+        Ok(world)
+    }
+
+    /// Check that every index in the scene points at a point or quad it holds
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        let (points, quads) = (self.points.len(), self.quads.len());
+        let point = |what: &str, index: usize| -> Result<(), Box<dyn Error>> {
+            if index >= points {
+                return Err(format!("{what} uses point {index} but the scene has {points} points").into());
+            }
+            Ok(())
+        };
+        let quad = |what: &str, index: usize| -> Result<(), Box<dyn Error>> {
+            if index >= quads {
+                return Err(format!("{what} uses quad {index} but the scene has {quads} quads").into());
+            }
+            Ok(())
+        };
+        let body = |what: &str, range: &Range<usize>, offsets: usize| -> Result<(), Box<dyn Error>> {
+            if range.start > range.end || range.end > points {
+                return Err(format!("{what} uses points {range:?} but the scene has {points} points").into());
+            }
+            if offsets != range.len() {
+                return Err(format!("{what} has {offsets} offsets for {} points", range.len()).into());
+            }
+            Ok(())
+        };
+
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            let what = format!("constraint {i}");
+            point(&what, constraint.point1)?;
+            point(&what, constraint.point2)?;
+        }
+        for (i, hinge) in self.hinges.iter().enumerate() {
+            let what = format!("hinge {i}");
+            quad(&what, hinge.quad_a)?;
+            quad(&what, hinge.quad_b)?;
+            if hinge.quad_a == hinge.quad_b {
+                return Err(format!("{what} joins quad {} to itself", hinge.quad_a).into());
+            }
+        }
+        for (i, link) in self.quad_children.iter().enumerate() {
+            let what = format!("quad child {i}");
+            quad(&what, link.child)?;
+            quad(&what, link.parent)?;
+            if link.child == link.parent {
+                return Err(format!("{what} parents quad {} to itself", link.child).into());
+            }
+        }
+        for (i, anchor) in self.anchors.iter().enumerate() {
+            let what = format!("anchor {i}");
+            point(&what, anchor.point)?;
+            quad(&what, anchor.quad)?;
+        }
+        for (i, motor) in self.motors.iter().enumerate() {
+            let what = format!("motor {i}");
+            for &index in motor.hub.iter().chain(&motor.rim) {
+                point(&what, index)?;
+            }
+        }
+        for (i, rigid) in self.rigid_bodies.iter().enumerate() {
+            body(&format!("rigid body {i}"), &rigid.points, rigid.offsets.len())?;
+        }
+        for (i, shape) in self.shape_matches.iter().enumerate() {
+            body(&format!("shape match {i}"), &shape.points, shape.offsets.len())?;
+        }
+        for (i, anchor) in self.soft_anchors.iter().enumerate() {
+            let what = format!("soft anchor {i}");
+            quad(&what, anchor.quad)?;
+            body(&what, &anchor.body.points, anchor.body.offsets.len())?;
+        }
+        for (i, fill) in self.fills.iter().enumerate() {
+            let range = &fill.points;
+            if range.start > range.end || range.end > points {
+                return Err(format!("fill {i} uses points {range:?} but the scene has {points} points").into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Put the named elements of `ui` back into their saved state
+    ///
+    /// Elements the scene doesn't mention, and saved ones `ui` doesn't have, are left alone.
+    pub fn apply_ui(&self, ui: &mut UiManager) {
+        for (name, state) in &self.ui {
+            if let Some(element) = ui.get_named_element(name) {
+                state.apply(element);
+            }
+        }
+    }
+
+    /// Load a scene from a `.toml` or `.json` file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Scene, Box<dyn Error>> {
+        data_file::load(path)
+    }
+
+    /// Save the scene to a `.toml` or `.json` file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        data_file::save(self, path)
+    }
+}
+
+/// Parameters of a built-in component
+///
+/// Components of other types can't be described and are left out of the scene.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ComponentData {
+    Gravity { strength: f32, direction: (f32, f32), enabled: bool },
+    Friction { coefficient: f32, stop_speed: f32, ground_only: bool, enabled: bool },
+    Drag { linear: f32, quadratic: f32, enabled: bool },
+    Force { force: (f32, f32), enabled: bool },
+    Collision { bounce: f32, slope_friction: f32, enabled: bool },
+}
+
+impl ComponentData {
+    /// Describe `component`, or `None` if it isn't one of the built-in components
+    pub fn capture<T>(component: &dyn Component<T>) -> Option<Self> {
+        let component = component as &dyn Any;
+        if let Some(gravity) = component.downcast_ref::<Gravity>() {
+            return Some(Self::Gravity {
+                strength: gravity.strength,
+                direction: gravity.direction.into(),
+                enabled: gravity.enabled,
+            });
+        }
+        if let Some(friction) = component.downcast_ref::<Friction>() {
+            return Some(Self::Friction {
+                coefficient: friction.coefficient,
+                stop_speed: friction.stop_speed,
+                ground_only: friction.ground_only,
+                enabled: friction.enabled,
+            });
+        }
+        if let Some(drag) = component.downcast_ref::<Drag>() {
+            return Some(Self::Drag {
+                linear: drag.linear,
+                quadratic: drag.quadratic,
+                enabled: drag.enabled,
+            });
+        }
+        if let Some(force) = component.downcast_ref::<Force>() {
+            return Some(Self::Force {
+                force: force.force.into(),
+                enabled: force.enabled,
+            });
+        }
+        let collision = component.downcast_ref::<Collision>()?;
+        Some(Self::Collision {
+            bounce: collision.bounce(),
+            slope_friction: collision.slope_friction(),
+            enabled: collision.enabled,
+        })
+    }
+
+    /// Build the component described
+    pub fn build<T>(&self) -> Box<dyn Component<T>>
+    where
+        Gravity: Component<T>,
+        Friction: Component<T>,
+        Drag: Component<T>,
+        Force: Component<T>,
+        Collision: Component<T>,
+    {
+        match *self {
+            Self::Gravity { strength, direction, enabled } => {
+                let mut gravity = Gravity::new(strength).with_direction(Vec2::from(direction));
+                gravity.enabled = enabled;
+                Box::new(gravity)
+            }
+            Self::Friction { coefficient, stop_speed, ground_only, enabled } => {
+                let mut friction = Friction::new(coefficient).with_stop_speed(stop_speed);
+                friction.ground_only = ground_only;
+                friction.enabled = enabled;
+                Box::new(friction)
+            }
+            Self::Drag { linear, quadratic, enabled } => {
+                let mut drag = Drag::new(linear, quadratic);
+                drag.enabled = enabled;
+                Box::new(drag)
+            }
+            Self::Force { force, enabled } => {
+                let mut force = Force::new(Vec2::from(force));
+                force.enabled = enabled;
+                Box::new(force)
+            }
+            Self::Collision { bounce, slope_friction, enabled } => {
+                let mut collision = Collision::new(bounce, slope_friction);
+                collision.enabled = enabled;
+                Box::new(collision)
+            }
+        }
+    }
+}
+
+/// Describe the built-in components attached to `owner`
+fn capture_components<T: ComponentOwner>(owner: &T) -> Vec<ComponentData> {
+    owner.components().iter().filter_map(|component| ComponentData::capture(component.as_ref())).collect()
+}
+
+/// Attach the components described in `components` to `owner`
+fn attach_components<T: ComponentOwner>(owner: &mut T, components: &[ComponentData])
+where
+    Gravity: Component<T>,
+    Friction: Component<T>,
+    Drag: Component<T>,
+    Force: Component<T>,
+    Collision: Component<T>,
+{
+    for component in components {
+        owner.add_component(component.build());
+    }
+}
+
+/// Description of a Point
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PointData {
+    pub position: (f32, f32),
+    pub velocity: (f32, f32),
+    pub mass: f32,
+    pub gravity_scale: f32,
+    pub radius: f32,
+    #[serde(with = "hex")]
+    pub color: Color,
+    pub draw_layer: i32,
+    pub body_type: BodyType,
+    pub layer: Option<String>,
+    pub collision_group: Option<u32>,
+    pub tags: Vec<String>,
+    pub components: Vec<ComponentData>,
+}
+
+impl Default for PointData {
+    fn default() -> Self {
+        Self::capture(&Point::new(0.0, 0.0, 1.0, 5.0, Color::new(1.0, 1.0, 1.0, 1.0)))
+    }
+}
+
+impl PointData {
+    /// Describe `point`
+    pub fn capture(point: &Point) -> Self {
+        Self {
+            position: point.position,
+            velocity: point.velocity,
+            mass: point.mass,
+            gravity_scale: point.gravity_scale,
+            radius: point.radius,
+            color: point.color,
+            draw_layer: point.draw_layer,
+            body_type: point.body_type,
+            layer: point.layer.clone(),
+            collision_group: point.collision_group,
+            tags: point.tags.clone(),
+            components: capture_components(point),
+        }
+    }
+
+    /// Build the point described
+    pub fn build(&self) -> Point {
+        let mut point = Point::new(self.position.0, self.position.1, self.mass, self.radius, self.color);
+        point.velocity = self.velocity;
+        point.gravity_scale = self.gravity_scale;
+        point.draw_layer = self.draw_layer;
+        point.body_type = self.body_type;
+        point.layer = self.layer.clone();
+        point.collision_group = self.collision_group;
+        point.tags = self.tags.clone();
+        attach_components(&mut point, &self.components);
+        point
+    }
+}
+
+/// Description of a Constraint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintData {
+    pub point1: usize,
+    pub point2: usize,
+    pub rest_length: f32,
+    pub stiffness: f32,
+    #[serde(default)]
+    pub mode: ConstraintMode,
+    #[serde(with = "hex")]
+    pub color: Color,
+}
+
+impl ConstraintData {
+    /// Describe `constraint`
+    pub fn capture(constraint: &Constraint) -> Self {
+        Self {
+            point1: constraint.point1,
+            point2: constraint.point2,
+            rest_length: constraint.rest_length,
+            stiffness: constraint.stiffness,
+            mode: constraint.mode,
+            color: constraint.color,
+        }
+    }
+
+    /// Build the constraint described
+    pub fn build(&self) -> Constraint {
+        Constraint::new(self.point1, self.point2, self.rest_length, self.stiffness, self.color).with_mode(self.mode)
+    }
+}
+
+/// Description of the border of a Quad
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OutlineData {
+    #[serde(with = "hex")]
+    pub color: Color,
+    pub thickness: f32,
+}
+
+/// Description of a Quad
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuadData {
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    #[serde(with = "hex")]
+    pub color: Color,
+    pub corner_radius: f32,
+    pub outline: Option<OutlineData>,
+    pub filled: bool,
+    pub draw_layer: i32,
+    pub velocity: (f32, f32),
+    pub rotation: f32,
+    pub angular_velocity: f32,
+    pub pivot: (f32, f32),
+    pub mass: f32,
+    pub restitution: f32,
+    pub gravity_scale: f32,
+    pub body_type: BodyType,
+    pub is_trigger: bool,
+    pub can_sleep: bool,
+    pub active: bool,
+    pub layer: Option<String>,
+    pub tags: Vec<String>,
+    pub components: Vec<ComponentData>,
+}
+
+impl Default for QuadData {
+    fn default() -> Self {
+        Self::capture(&Quad::new(0.0, 0.0, 0.0, 0.0, Color::new(1.0, 1.0, 1.0, 1.0)))
+    }
+}
+
+impl QuadData {
+    /// Describe `quad`
+    pub fn capture(quad: &Quad) -> Self {
+        Self {
+            position: quad.position,
+            size: quad.size,
+            color: quad.color,
+            corner_radius: quad.corner_radius,
+            outline: quad.outline.map(|outline| OutlineData {
+                color: outline.color,
+                thickness: outline.thickness,
+            }),
+            filled: quad.filled,
+            draw_layer: quad.draw_layer,
+            velocity: (quad.velocity_x, quad.velocity_y),
+            rotation: quad.rotation,
+            angular_velocity: quad.angular_velocity,
+            pivot: quad.pivot,
+            mass: quad.mass,
+            restitution: quad.restitution,
+            gravity_scale: quad.gravity_scale,
+            body_type: quad.body_type,
+            is_trigger: quad.is_trigger,
+            can_sleep: quad.can_sleep,
+            active: quad.active,
+            layer: quad.layer.clone(),
+            tags: quad.tags.clone(),
+            components: capture_components(quad),
+        }
+    }
+
+    /// Build the quad described, active whatever `active` says
+    pub fn build(&self) -> Quad {
+        let mut quad = Quad::new(self.position.0, self.position.1, self.size.0, self.size.1, self.color);
+        quad.corner_radius = self.corner_radius;
+        quad.outline = self.outline.map(|outline| Outline::new(outline.color, outline.thickness));
+        quad.filled = self.filled;
+        quad.draw_layer = self.draw_layer;
+        (quad.velocity_x, quad.velocity_y) = self.velocity;
+        quad.rotation = self.rotation;
+        quad.angular_velocity = self.angular_velocity;
+        quad.pivot = self.pivot;
+        quad.mass = self.mass;
+        quad.restitution = self.restitution;
+        quad.gravity_scale = self.gravity_scale;
+        quad.body_type = self.body_type;
+        quad.is_trigger = self.is_trigger;
+        quad.can_sleep = self.can_sleep;
+        quad.layer = self.layer.clone();
+        quad.tags = self.tags.clone();
+        attach_components(&mut quad, &self.components);
+        quad
+    }
+}
+
+/// Description of a CircleBody
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CircleData {
+    pub position: (f32, f32),
+    pub radius: f32,
+    #[serde(with = "hex")]
+    pub color: Color,
+    pub draw_layer: i32,
+    pub velocity: (f32, f32),
+    pub mass: f32,
+    pub restitution: f32,
+    pub gravity_scale: f32,
+    pub body_type: BodyType,
+    pub is_trigger: bool,
+    pub can_sleep: bool,
+    pub active: bool,
+    pub layer: Option<String>,
+    pub tags: Vec<String>,
+    pub components: Vec<ComponentData>,
+}
+
+impl Default for CircleData {
+    fn default() -> Self {
+        Self::capture(&CircleBody::new(0.0, 0.0, 0.0, Color::new(1.0, 1.0, 1.0, 1.0)))
+    }
+}
+
+impl CircleData {
+    /// Describe `circle`
+    pub fn capture(circle: &CircleBody) -> Self {
+        Self {
+            position: circle.position,
+            radius: circle.radius,
+            color: circle.color,
+            draw_layer: circle.draw_layer,
+            velocity: (circle.velocity_x, circle.velocity_y),
+            mass: circle.mass,
+            restitution: circle.restitution,
+            gravity_scale: circle.gravity_scale,
+            body_type: circle.body_type,
+            is_trigger: circle.is_trigger,
+            can_sleep: circle.can_sleep,
+            active: circle.active,
+            layer: circle.layer.clone(),
+            tags: circle.tags.clone(),
+            components: capture_components(circle),
+        }
+    }
+
+    /// Build the circle described, active whatever `active` says
+    pub fn build(&self) -> CircleBody {
+        let mut circle = CircleBody::new(self.position.0, self.position.1, self.radius, self.color);
+        circle.draw_layer = self.draw_layer;
+        (circle.velocity_x, circle.velocity_y) = self.velocity;
+        circle.mass = self.mass;
+        circle.restitution = self.restitution;
+        circle.gravity_scale = self.gravity_scale;
+        circle.body_type = self.body_type;
+        circle.is_trigger = self.is_trigger;
+        circle.can_sleep = self.can_sleep;
+        circle.layer = self.layer.clone();
+        circle.tags = self.tags.clone();
+        attach_components(&mut circle, &self.components);
+        circle
+    }
+}
+
+/// Description of a PolygonCollider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolygonData {
+    pub position: (f32, f32),
+    pub vertices: Vec<(f32, f32)>,
+    pub rotation: f32,
+    pub angular_velocity: f32,
+    #[serde(with = "hex")]
+    pub color: Color,
+    pub draw_layer: i32,
+    pub velocity: (f32, f32),
+    pub mass: f32,
+    pub restitution: f32,
+    pub gravity_scale: f32,
+    pub body_type: BodyType,
+    pub is_trigger: bool,
+    pub can_sleep: bool,
+    pub layer: Option<String>,
+    pub tags: Vec<String>,
+    pub components: Vec<ComponentData>,
+}
+
+impl Default for PolygonData {
+    fn default() -> Self {
+        Self::capture(&PolygonCollider::new(0.0, 0.0, Vec::new(), Color::new(1.0, 1.0, 1.0, 1.0)))
+    }
+}
+
+impl PolygonData {
+    /// Describe `polygon`
+    pub fn capture(polygon: &PolygonCollider) -> Self {
+        Self {
+            position: polygon.position,
+            vertices: polygon.vertices.iter().map(|&vertex| vertex.into()).collect(),
+            rotation: polygon.rotation,
+            angular_velocity: polygon.angular_velocity,
+            color: polygon.color,
+            draw_layer: polygon.draw_layer,
+            velocity: (polygon.velocity_x, polygon.velocity_y),
+            mass: polygon.mass,
+            restitution: polygon.restitution,
+            gravity_scale: polygon.gravity_scale,
+            body_type: polygon.body_type,
+            is_trigger: polygon.is_trigger,
+            can_sleep: polygon.can_sleep,
+            layer: polygon.layer.clone(),
+            tags: polygon.tags.clone(),
+            components: capture_components(polygon),
+        }
+    }
+
+    /// Build the polygon described
+    pub fn build(&self) -> PolygonCollider {
+        let vertices = self.vertices.iter().map(|&vertex| Vec2::from(vertex)).collect();
+        let mut polygon = PolygonCollider::new(self.position.0, self.position.1, vertices, self.color);
+        polygon.rotation = self.rotation;
+        polygon.angular_velocity = self.angular_velocity;
+        polygon.draw_layer = self.draw_layer;
+        (polygon.velocity_x, polygon.velocity_y) = self.velocity;
+        polygon.mass = self.mass;
+        polygon.restitution = self.restitution;
+        polygon.gravity_scale = self.gravity_scale;
+        polygon.body_type = self.body_type;
+        polygon.is_trigger = self.is_trigger;
+        polygon.can_sleep = self.can_sleep;
+        polygon.layer = self.layer.clone();
+        polygon.tags = self.tags.clone();
+        attach_components(&mut polygon, &self.components);
+        polygon
+    }
+}
+
+/// Description of a HingeJoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HingeData {
+    pub quad_a: usize,
+    pub quad_b: usize,
+    pub anchor_a: (f32, f32),
+    pub anchor_b: (f32, f32),
+    #[serde(default)]
+    pub reference_angle: f32,
+    #[serde(default)]
+    pub limits: Option<(f32, f32)>,
+    #[serde(default)]
+    pub motor: Option<(f32, f32)>,
+    #[serde(with = "hex")]
+    pub color: Color,
+}
+
+impl HingeData {
+    /// Describe `hinge`
+    pub fn capture(hinge: &HingeJoint) -> Self {
+        Self {
+            quad_a: hinge.quad_a,
+            quad_b: hinge.quad_b,
+            anchor_a: hinge.anchor_a.into(),
+            anchor_b: hinge.anchor_b.into(),
+            reference_angle: hinge.reference_angle,
+            limits: hinge.limits,
+            motor: hinge.motor,
+            color: hinge.color,
+        }
+    }
+
+    /// Build the hinge described
+    pub fn build(&self) -> HingeJoint {
+        let mut hinge = HingeJoint::new(self.quad_a, Vec2::from(self.anchor_a), self.quad_b, Vec2::from(self.anchor_b));
+        hinge.reference_angle = self.reference_angle;
+        hinge.limits = self.limits;
+        hinge.motor = self.motor;
+        hinge.color = self.color;
+        hinge
+    }
+}
+
+/// Description of a TerrainCollider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerrainData {
+    pub vertices: Vec<(f32, f32)>,
+    pub bottom: f32,
+    #[serde(with = "hex")]
+    pub color: Color,
+    #[serde(default)]
+    pub restitution: f32,
+}
+
+impl TerrainData {
+    /// Describe `terrain`
+    pub fn capture(terrain: &TerrainCollider) -> Self {
+        Self {
+            vertices: terrain.vertices.iter().map(|&vertex| vertex.into()).collect(),
+            bottom: terrain.bottom,
+            color: terrain.color,
+            restitution: terrain.restitution,
+        }
+    }
+
+    /// Build the terrain described
+    pub fn build(&self) -> TerrainCollider {
+        TerrainCollider {
+            vertices: self.vertices.iter().map(|&(x, y)| vec2(x, y)).collect(),
+            bottom: self.bottom,
+            color: self.color,
+            restitution: self.restitution,
+        }
+    }
+}
+
+/// Settings of a world that aren't part of its `PhysicsConfig`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldSettings {
+    pub timestep: f32,
+    pub max_steps: usize,
+    pub sleep_velocity: f32,
+    pub sleep_steps: u32,
+    pub sort_by_y: bool,
+}
+
+impl Default for WorldSettings {
+    fn default() -> Self {
+        Self::capture(&PhysicsWorld::new())
+    }
+}
+
+impl WorldSettings {
+    /// Describe the settings of `world`
+    pub fn capture(world: &PhysicsWorld) -> Self {
+        Self {
+            timestep: world.timestep,
+            max_steps: world.max_steps,
+            sleep_velocity: world.sleep_velocity,
+            sleep_steps: world.sleep_steps,
+            sort_by_y: world.sort_by_y,
+        }
+    }
+
+    /// Give `world` the settings described
+    pub fn apply(&self, world: &mut PhysicsWorld) {
+        world.timestep = self.timestep;
+        world.max_steps = self.max_steps;
+        world.sleep_velocity = self.sleep_velocity;
+        world.sleep_steps = self.sleep_steps;
+        world.sort_by_y = self.sort_by_y;
+    }
+}
+
+/// Description of a QuadChild
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuadChildData {
+    pub child: usize,
+    pub parent: usize,
+    /// Child's pivot point relative to the parent's
+    pub position: (f32, f32),
+    pub rotation: f32,
+    #[serde(default = "unit_scale")]
+    pub scale: (f32, f32),
+}
+
+fn unit_scale() -> (f32, f32) {
+    (1.0, 1.0)
+}
+
+impl QuadChildData {
+    /// Describe `link`
+    pub fn capture(link: &QuadChild) -> Self {
+        Self {
+            child: link.child,
+            parent: link.parent,
+            position: link.local.position.into(),
+            rotation: link.local.rotation,
+            scale: link.local.scale.into(),
+        }
+    }
+
+    /// Build the link described
+    pub fn build(&self) -> QuadChild {
+        QuadChild {
+            child: self.child,
+            parent: self.parent,
+            local: Transform {
+                position: Vec2::from(self.position),
+                rotation: self.rotation,
+                scale: Vec2::from(self.scale),
+            },
+        }
+    }
+}
+
+/// Description of a QuadAnchor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnchorData {
+    pub point: usize,
+    pub quad: usize,
+    pub offset: (f32, f32),
+}
+
+impl AnchorData {
+    /// Describe `anchor`
+    pub fn capture(anchor: &QuadAnchor) -> Self {
+        Self {
+            point: anchor.point,
+            quad: anchor.quad,
+            offset: anchor.offset.into(),
+        }
+    }
+
+    /// Build the anchor described
+    pub fn build(&self) -> QuadAnchor {
+        QuadAnchor {
+            point: self.point,
+            quad: self.quad,
+            offset: Vec2::from(self.offset),
+        }
+    }
+}
+
+/// Description of a Motor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MotorData {
+    #[serde(default)]
+    pub hub: Option<usize>,
+    pub rim: Vec<usize>,
+    pub speed: f32,
+    pub torque: f32,
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl MotorData {
+    /// Describe `motor`
+    pub fn capture(motor: &Motor) -> Self {
+        Self {
+            hub: motor.hub,
+            rim: motor.rim.clone(),
+            speed: motor.speed,
+            torque: motor.torque,
+            enabled: motor.enabled,
+        }
+    }
+
+    /// Build the motor described
+    pub fn build(&self) -> Motor {
+        let mut motor = Motor::new(self.hub, self.rim.clone(), self.speed, self.torque);
+        motor.enabled = self.enabled;
+        motor
+    }
+}
+
+/// Description of an Attractor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttractorData {
+    pub position: (f32, f32),
+    pub strength: f32,
+    pub radius: f32,
+    #[serde(default)]
+    pub falloff: Falloff,
+    #[serde(default = "core_radius")]
+    pub core_radius: f32,
+}
+
+fn core_radius() -> f32 {
+    Attractor::new(Vec2::ZERO, 0.0, 0.0).core_radius
+}
+
+impl AttractorData {
+    /// Describe `attractor`
+    pub fn capture(attractor: &Attractor) -> Self {
+        Self {
+            position: attractor.position.into(),
+            strength: attractor.strength,
+            radius: attractor.radius,
+            falloff: attractor.falloff,
+            core_radius: attractor.core_radius,
+        }
+    }
+
+    /// Build the attractor described
+    pub fn build(&self) -> Attractor {
+        Attractor::new(Vec2::from(self.position), self.strength, self.radius)
+            .with_falloff(self.falloff)
+            .with_core_radius(self.core_radius)
+    }
+}
+
+/// Description of a RigidPointBody
+///
+/// The rest shape is saved rather than measured again, so bodies bent out of
+/// shape when saved still spring back to their original shape.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PointBodyData {
+    pub points: Range<usize>,
+    /// Rest offset of each point from the body's center
+    pub offsets: Vec<(f32, f32)>,
+    pub center: (f32, f32),
+    #[serde(default)]
+    pub rotation: f32,
+}
+
+impl PointBodyData {
+    /// Describe `body`
+    pub fn capture(body: &RigidPointBody) -> Self {
+        Self {
+            points: body.points.clone(),
+            offsets: body.offsets.iter().map(|&offset| offset.into()).collect(),
+            center: body.center.into(),
+            rotation: body.rotation,
+        }
+    }
+
+    /// Build the body described
+    pub fn build(&self) -> RigidPointBody {
+        RigidPointBody {
+            points: self.points.clone(),
+            offsets: self.offsets.iter().map(|&offset| Vec2::from(offset)).collect(),
+            center: Vec2::from(self.center),
+            rotation: self.rotation,
+        }
+    }
+}
+
+/// Description of a ShapeMatch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShapeMatchData {
+    pub points: Range<usize>,
+    /// Rest offset of each point from the shape's center
+    pub offsets: Vec<(f32, f32)>,
+    pub stiffness: f32,
+    pub center: (f32, f32),
+    #[serde(default)]
+    pub rotation: f32,
+}
+
+impl ShapeMatchData {
+    /// Describe `shape`
+    pub fn capture(shape: &ShapeMatch) -> Self {
+        Self {
+            points: shape.points.clone(),
+            offsets: shape.offsets.iter().map(|&offset| offset.into()).collect(),
+            stiffness: shape.stiffness,
+            center: shape.center.into(),
+            rotation: shape.rotation,
+        }
+    }
+
+    /// Build the shape match described
+    pub fn build(&self) -> ShapeMatch {
+        ShapeMatch {
+            points: self.points.clone(),
+            offsets: self.offsets.iter().map(|&offset| Vec2::from(offset)).collect(),
+            stiffness: self.stiffness.clamp(0.0, 1.0),
+            center: Vec2::from(self.center),
+            rotation: self.rotation,
+        }
+    }
+}
+
+/// Description of a SoftBodyAnchor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoftAnchorData {
+    pub quad: usize,
+    pub body: PointBodyData,
+    /// Quad's center relative to the body's
+    pub offset: (f32, f32),
+    #[serde(default)]
+    pub rotation: f32,
+}
+
+impl SoftAnchorData {
+    /// Describe `anchor`
+    pub fn capture(anchor: &SoftBodyAnchor) -> Self {
+        Self {
+            quad: anchor.quad,
+            body: PointBodyData::capture(&anchor.body),
+            offset: anchor.offset.into(),
+            rotation: anchor.rotation,
+        }
+    }
+
+    /// Build the anchor described
+    pub fn build(&self) -> SoftBodyAnchor {
+        SoftBodyAnchor {
+            quad: self.quad,
+            body: self.body.build(),
+            offset: Vec2::from(self.offset),
+            rotation: self.rotation,
+        }
+    }
+}
+
+/// Description of a ShapeFill, without its gradient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FillData {
+    pub points: Range<usize>,
+    #[serde(with = "hex")]
+    pub color: Color,
+    #[serde(default)]
+    pub triangulation: FillTriangulation,
+}
+
+impl FillData {
+    /// Describe `fill`
+    pub fn capture(fill: &ShapeFill) -> Self {
+        Self {
+            points: fill.points.clone(),
+            color: fill.color,
+            triangulation: fill.triangulation,
+        }
+    }
+
+    /// Build the fill described, filled with its color
+    pub fn build(&self) -> ShapeFill {
+        ShapeFill::new(self.points.clone(), self.color).with_triangulation(self.triangulation)
+    }
+}
+
+/// Description of a Fluid and its particles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FluidData {
+    pub interaction_radius: f32,
+    pub particle_radius: f32,
+    pub rest_density: f32,
+    pub stiffness: f32,
+    pub near_stiffness: f32,
+    pub viscosity: f32,
+    pub gravity: (f32, f32),
+    /// Box the particles are kept in as x, y, width and height, if any
+    #[serde(default)]
+    pub bounds: Option<(f32, f32, f32, f32)>,
+    #[serde(with = "hex")]
+    pub color: Color,
+    /// Position and velocity of each particle
+    #[serde(default)]
+    pub particles: Vec<((f32, f32), (f32, f32))>,
+}
+
+impl FluidData {
+    /// Describe `fluid`
+    pub fn capture(fluid: &Fluid) -> Self {
+        Self {
+            interaction_radius: fluid.interaction_radius,
+            particle_radius: fluid.particle_radius,
+            rest_density: fluid.rest_density,
+            stiffness: fluid.stiffness,
+            near_stiffness: fluid.near_stiffness,
+            viscosity: fluid.viscosity,
+            gravity: fluid.gravity.into(),
+            bounds: fluid.bounds.map(|rect| (rect.x, rect.y, rect.w, rect.h)),
+            color: fluid.color,
+            particles: fluid
+                .particles
+                .iter()
+                .map(|particle| (particle.position.into(), particle.velocity.into()))
+                .collect(),
+        }
+    }
+
+    /// Build the fluid described
+    pub fn build(&self) -> Fluid {
+        let mut fluid = Fluid::new(self.interaction_radius, self.color);
+        fluid.particle_radius = self.particle_radius;
+        fluid.rest_density = self.rest_density;
+        fluid.stiffness = self.stiffness;
+        fluid.near_stiffness = self.near_stiffness;
+        fluid.viscosity = self.viscosity;
+        fluid.gravity = Vec2::from(self.gravity);
+        fluid.bounds = self.bounds.map(|(x, y, w, h)| Rect::new(x, y, w, h));
+        fluid.particles = self
+            .particles
+            .iter()
+            .map(|&(position, velocity)| FluidParticle {
+                position: Vec2::from(position),
+                velocity: Vec2::from(velocity),
+            })
+            .collect();
+        fluid
+    }
+}
+
+/// Saved state of a named UI element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiElementState {
+    /// Top-left corner of the element
+    pub position: (f32, f32),
+    pub visible: bool,
+    pub enabled: bool,
+    /// What the user entered or picked, for elements that hold a value
+    #[serde(default)]
+    pub value: Option<UiValue>,
+}
+
+/// Value held by an input element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum UiValue {
+    /// Value of a `UiSlider`
+    Slider(f32),
+    /// Checked and indeterminate flags of a `UiCheckbox`
+    Checkbox(bool, bool),
+    /// Text of a `UiInput`
+    Text(String),
+    /// Selected option of a `UiDropdown`
+    Selected(usize),
+    /// Progress of a `UiProgressBar`, from 0.0 to 1.0
+    Progress(f32),
+    /// Whether a `UiPanel` is minimized
+    Minimized(bool),
+}
+
+impl UiElementState {
+    /// Describe `element`
+    pub fn capture(element: &dyn UiElement) -> Self {
+        let (x, y, _, _) = element.get_bounds();
+        Self {
+            position: (x, y),
+            visible: element.is_visible(),
+            enabled: element.is_enabled(),
+            value: UiValue::capture(element),
+        }
+    }
+
+    /// Put `element` back into this state, without firing its callbacks
+    pub fn apply(&self, element: &mut dyn UiElement) {
+        element.set_position(self.position.0, self.position.1);
+        element.set_visible(self.visible);
+        element.set_enabled(self.enabled);
+        if let Some(value) = &self.value {
+            value.apply(element);
+        }
+    }
+}
+
+impl UiValue {
+    /// The value `element` holds, or `None` if it isn't an input element
+    pub fn capture(element: &dyn UiElement) -> Option<Self> {
+        let element = element.as_any();
+        if let Some(slider) = element.downcast_ref::<UiSlider>() {
+            return Some(Self::Slider(slider.value));
+        }
+        if let Some(checkbox) = element.downcast_ref::<UiCheckbox>() {
+            return Some(Self::Checkbox(checkbox.checked, checkbox.indeterminate));
+        }
+        if let Some(input) = element.downcast_ref::<UiInput>() {
+            return Some(Self::Text(input.text.clone()));
+        }
+        if let Some(dropdown) = element.downcast_ref::<UiDropdown>() {
+            return Some(Self::Selected(dropdown.selected_index));
+        }
+        if let Some(progress) = element.downcast_ref::<UiProgressBar>() {
+            return Some(Self::Progress(progress.progress));
+        }
+        let panel = element.downcast_ref::<UiPanel>()?;
+        Some(Self::Minimized(panel.minimized))
+    }
+
+    /// Put the value back into `element`, if it is the kind of element that holds it
+    pub fn apply(&self, element: &mut dyn UiElement) {
+        let element = element.as_any_mut();
+        match self {
+            Self::Slider(value) => {
+                if let Some(slider) = element.downcast_mut::<UiSlider>() {
+                    slider.value = value.clamp(slider.min, slider.max);
+                }
+            }
+            Self::Checkbox(checked, indeterminate) => {
+                if let Some(checkbox) = element.downcast_mut::<UiCheckbox>() {
+                    checkbox.set_state(match (checked, indeterminate) {
+                        (_, true) => CheckState::Indeterminate,
+                        (true, false) => CheckState::Checked,
+                        (false, false) => CheckState::Unchecked,
+                    });
+                }
+            }
+            Self::Text(text) => {
+                if let Some(input) = element.downcast_mut::<UiInput>() {
+                    input.set_text(text);
+                }
+            }
+            Self::Selected(index) => {
+                if let Some(dropdown) = element.downcast_mut::<UiDropdown>() {
+                    dropdown.selected_index = (*index).min(dropdown.options.len().saturating_sub(1));
+                }
+            }
+            Self::Progress(progress) => {
+                if let Some(bar) = element.downcast_mut::<UiProgressBar>() {
+                    bar.set_progress(*progress);
+                }
+            }
+            Self::Minimized(minimized) => {
+                if let Some(panel) = element.downcast_mut::<UiPanel>() {
+                    panel.set_minimized(*minimized);
+                }
+            }
+        }
+    }
+}
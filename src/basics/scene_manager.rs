@@ -0,0 +1,266 @@
+//! Scene stack
+//!
+//! This module provides a stack of scenes, such as a menu, a level and a
+//! pause screen, with one game loop that updates and draws whichever is on
+//! top, so flows between them are transitions instead of nested loops.
+//!
+//! # Features
+//! - Scenes pushed over others, popped back off, or replaced outright
+//! - Hooks as scenes enter, exit, are covered by another scene and come back
+//! - Overlay scenes, like pause menus, drawn over the scene beneath them
+//! - Named values shared between scenes, to pass scores, settings and results along
+//!
+//! Saving levels to files is done by `basics::scene::Scene`, which a scene
+//! here can load in its `on_enter`. The two share a name, so bring in the
+//! one you need by its module path.
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::scene_manager::{Scene, SceneData, SceneManager, SceneTransition};
+//!
+//! struct Level { world: PhysicsWorld, score: u32 }
+//!
+//! impl Scene for Level {
+//!     fn update(&mut self, dt: f32) -> SceneTransition {
+//!         self.world.step(dt);
+//!         if is_key_pressed(KeyCode::Escape) {
+//!             return SceneTransition::Push(Box::new(PauseMenu::new()));
+//!         }
+//!         SceneTransition::None
+//!     }
+//!
+//!     fn draw(&self) {
+//!         self.world.draw();
+//!     }
+//!
+//!     fn on_exit(&mut self, data: &mut SceneData) {
+//!         data.insert("score", self.score);
+//!     }
+//! }
+//!
+//! let mut scenes = SceneManager::new();
+//! scenes.push(Box::new(MainMenu::new()));
+//! scenes.run().await;
+//! ```
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use macroquad::time::get_frame_time;
+use macroquad::window::next_frame;
+
+/// What the scene manager should do after a scene's update
+pub enum SceneTransition {
+    /// Stay on the current scene
+    None,
+    /// Cover the current scene with a new one, which it comes back to once that is popped
+    Push(Box<dyn Scene>),
+    /// Leave the current scene and go back to the one beneath it
+    Pop,
+    /// Leave the current scene for a new one
+    Replace(Box<dyn Scene>),
+    /// Leave every scene, ending `SceneManager::run`
+    Quit,
+}
+
+/// A screen of the game with its own update and draw
+///
+/// Every hook but `update` and `draw` does nothing unless implemented.
+pub trait Scene {
+    /// Called when the scene is pushed or replaces another, before its first update
+    fn on_enter(&mut self, _data: &mut SceneData) {}
+
+    /// Update the scene, `dt` seconds after the last update, and say what should happen next
+    fn update(&mut self, dt: f32) -> SceneTransition;
+
+    /// Draw the scene
+    fn draw(&self);
+
+    /// Called when the scene is popped, replaced or quit
+    fn on_exit(&mut self, _data: &mut SceneData) {}
+
+    /// Called when another scene is pushed over this one
+    fn on_pause(&mut self, _data: &mut SceneData) {}
+
+    /// Called when the scene is on top again after the one over it was popped
+    fn on_resume(&mut self, _data: &mut SceneData) {}
+
+    /// Whether the scene beneath this one is still drawn, under it
+    fn is_overlay(&self) -> bool {
+        false
+    }
+}
+
+/// Named values scenes leave for each other
+#[derive(Default)]
+pub struct SceneData {
+    values: HashMap<String, Box<dyn Any>>,
+}
+
+impl SceneData {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `value` under `key`, replacing whatever was there
+    pub fn insert<T: Any>(&mut self, key: &str, value: T) {
+        self.values.insert(key.to_string(), Box::new(value));
+    }
+
+    /// The value under `key`, if there is one of type `T`
+    pub fn get<T: Any>(&self, key: &str) -> Option<&T> {
+        self.values.get(key)?.downcast_ref()
+    }
+
+    /// The value under `key` mutably, if there is one of type `T`
+    pub fn get_mut<T: Any>(&mut self, key: &str) -> Option<&mut T> {
+        self.values.get_mut(key)?.downcast_mut()
+    }
+
+    /// Remove and return the value under `key`, if there is one of type `T`
+    ///
+    /// A value of another type is left where it is.
+    pub fn take<T: Any>(&mut self, key: &str) -> Option<T> {
+        if !self.values.get(key)?.is::<T>() {
+            return None;
+        }
+        self.values.remove(key)?.downcast().ok().map(|value| *value)
+    }
+
+    /// Remove the value under `key`, returning false if there wasn't one
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.values.remove(key).is_some()
+    }
+
+    /// Check if there is a value under `key`
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Remove every value
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+/// Stack of scenes, the top one updated and drawn each frame
+///
+/// # Fields
+///
+/// - `scenes`: The scenes, from the bottom of the stack to the top.
+/// - `data`: Values shared between scenes, handed to their hooks.
+#[derive(Default)]
+pub struct SceneManager {
+    scenes: Vec<Box<dyn Scene>>,
+    pub data: SceneData,
+}
+
+impl SceneManager {
+    /// Creates a manager without scenes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with `data` already shared between scenes
+    pub fn with_data(mut self, data: SceneData) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Cover the current scene, if any, with `scene`
+    pub fn push(&mut self, mut scene: Box<dyn Scene>) {
+        if let Some(top) = self.scenes.last_mut() {
+            top.on_pause(&mut self.data);
+        }
+        scene.on_enter(&mut self.data);
+        self.scenes.push(scene);
+    }
+
+    /// Leave the current scene and go back to the one beneath it, returning the scene left
+    pub fn pop(&mut self) -> Option<Box<dyn Scene>> {
+        let mut scene = self.scenes.pop()?;
+        scene.on_exit(&mut self.data);
+        if let Some(top) = self.scenes.last_mut() {
+            top.on_resume(&mut self.data);
+        }
+        Some(scene)
+    }
+
+    /// Leave the current scene, if any, for `scene`, returning the scene left
+    pub fn replace(&mut self, mut scene: Box<dyn Scene>) -> Option<Box<dyn Scene>> {
+        let mut old = self.scenes.pop();
+        if let Some(old) = &mut old {
+            old.on_exit(&mut self.data);
+        }
+        scene.on_enter(&mut self.data);
+        self.scenes.push(scene);
+        old
+    }
+
+    /// Leave every scene, top first
+    pub fn clear(&mut self) {
+        while let Some(mut scene) = self.scenes.pop() {
+            scene.on_exit(&mut self.data);
+        }
+    }
+
+    /// Update the top scene and carry out the transition it asks for
+    pub fn update(&mut self, dt: f32) {
+        let Some(top) = self.scenes.last_mut() else {
+            return;
+        };
+        match top.update(dt) {
+            SceneTransition::None => {}
+            SceneTransition::Push(scene) => self.push(scene),
+            SceneTransition::Pop => {
+                self.pop();
+            }
+            SceneTransition::Replace(scene) => {
+                self.replace(scene);
+            }
+            SceneTransition::Quit => self.clear(),
+        }
+    }
+
+    /// Draw the top scene, over the scenes beneath it for as long as they are covered by overlays
+    pub fn draw(&self) {
+        let first = self
+            .scenes
+            .iter()
+            .rposition(|scene| !scene.is_overlay())
+            .unwrap_or(0);
+        for scene in &self.scenes[first..] {
+            scene.draw();
+        }
+    }
+
+    /// Update and draw the scenes every frame until none are left
+    pub async fn run(&mut self) {
+        while !self.scenes.is_empty() {
+            self.update(get_frame_time());
+            self.draw();
+            next_frame().await;
+        }
+    }
+
+    /// The scene on top, if any
+    pub fn current(&self) -> Option<&dyn Scene> {
+        self.scenes.last().map(|scene| scene.as_ref())
+    }
+
+    /// The scene on top mutably, if any
+    pub fn current_mut(&mut self) -> Option<&mut (dyn Scene + 'static)> {
+        self.scenes.last_mut().map(|scene| scene.as_mut())
+    }
+
+    /// Number of scenes on the stack
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Check if there are no scenes left
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+}
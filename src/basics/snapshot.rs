@@ -0,0 +1,177 @@
+//! Physics snapshots
+//!
+//! This module provides a copy of the moving state of a `PhysicsWorld` that
+//! can be restored later, for rewind mechanics, undo in editors and rollback
+//! networking.
+//!
+//! # Features
+//! - Positions, velocities, rotations and sleep state of every object
+//! - Constraints, anchors, motors and hinges, so cut ropes come back
+//! - Contacts and the fixed-step accumulator, so events carry on as before
+//!
+//! Components aren't part of a snapshot, so any state they keep, like a
+//! jump in progress, stays as it is when a snapshot is restored.
+//!
+//! # Examples
+//! ```rust
+//! let checkpoint = world.snapshot();
+//! world.advance(get_frame_time());
+//! if is_key_pressed(KeyCode::R) {
+//!     world.restore(&checkpoint)?;
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use macroquad::math::Vec2;
+
+use crate::basics::BodyType;
+use crate::basics::physics_world::{BodyHandle, QuadAnchor};
+use crate::objects::circle::CircleBody;
+use crate::objects::constraint::{Constraint, Motor};
+use crate::objects::joint::HingeJoint;
+use crate::objects::point::Point;
+use crate::objects::polygon::PolygonCollider;
+use crate::objects::quad::Quad;
+
+/// Moving state of one Point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointState {
+    pub position: (f32, f32),
+    pub velocity: (f32, f32),
+    pub force: (f32, f32),
+    pub body_type: BodyType,
+}
+
+impl PointState {
+    /// Copy the moving state of `point`
+    pub fn capture(point: &Point) -> Self {
+        Self {
+            position: point.position,
+            velocity: point.velocity,
+            force: point.force,
+            body_type: point.body_type,
+        }
+    }
+
+    /// Put `point` back into this state
+    pub fn apply(&self, point: &mut Point) {
+        point.position = self.position;
+        point.velocity = self.velocity;
+        point.force = self.force;
+        point.body_type = self.body_type;
+    }
+}
+
+/// Moving state of one Quad, circle or polygon
+///
+/// Circles don't rotate, so their `rotation` and `angular_velocity` are always 0.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodyState {
+    pub position: (f32, f32),
+    pub velocity: (f32, f32),
+    pub rotation: f32,
+    pub angular_velocity: f32,
+    pub body_type: BodyType,
+    pub sleeping: bool,
+    pub idle_steps: u32,
+}
+
+impl BodyState {
+    /// Copy the moving state of `quad`
+    pub fn of_quad(quad: &Quad) -> Self {
+        Self {
+            position: quad.position,
+            velocity: (quad.velocity_x, quad.velocity_y),
+            rotation: quad.rotation,
+            angular_velocity: quad.angular_velocity,
+            body_type: quad.body_type,
+            sleeping: quad.sleeping,
+            idle_steps: quad.idle_steps,
+        }
+    }
+
+    /// Copy the moving state of `circle`
+    pub fn of_circle(circle: &CircleBody) -> Self {
+        Self {
+            position: circle.position,
+            velocity: (circle.velocity_x, circle.velocity_y),
+            rotation: 0.0,
+            angular_velocity: 0.0,
+            body_type: circle.body_type,
+            sleeping: circle.sleeping,
+            idle_steps: circle.idle_steps,
+        }
+    }
+
+    /// Copy the moving state of `polygon`
+    pub fn of_polygon(polygon: &PolygonCollider) -> Self {
+        Self {
+            position: polygon.position,
+            velocity: (polygon.velocity_x, polygon.velocity_y),
+            rotation: polygon.rotation,
+            angular_velocity: polygon.angular_velocity,
+            body_type: polygon.body_type,
+            sleeping: polygon.sleeping,
+            idle_steps: polygon.idle_steps,
+        }
+    }
+
+    /// Put `quad` back into this state
+    pub fn apply_to_quad(&self, quad: &mut Quad) {
+        quad.position = self.position;
+        (quad.velocity_x, quad.velocity_y) = self.velocity;
+        quad.rotation = self.rotation;
+        quad.angular_velocity = self.angular_velocity;
+        quad.body_type = self.body_type;
+        quad.sleeping = self.sleeping;
+        quad.idle_steps = self.idle_steps;
+    }
+
+    /// Put `circle` back into this state
+    pub fn apply_to_circle(&self, circle: &mut CircleBody) {
+        circle.position = self.position;
+        (circle.velocity_x, circle.velocity_y) = self.velocity;
+        circle.body_type = self.body_type;
+        circle.sleeping = self.sleeping;
+        circle.idle_steps = self.idle_steps;
+    }
+
+    /// Put `polygon` back into this state
+    pub fn apply_to_polygon(&self, polygon: &mut PolygonCollider) {
+        polygon.position = self.position;
+        (polygon.velocity_x, polygon.velocity_y) = self.velocity;
+        polygon.rotation = self.rotation;
+        polygon.angular_velocity = self.angular_velocity;
+        polygon.body_type = self.body_type;
+        polygon.sleeping = self.sleeping;
+        polygon.idle_steps = self.idle_steps;
+    }
+}
+
+/// Moving state of a whole `PhysicsWorld`, taken by `PhysicsWorld::snapshot`
+///
+/// Objects are stored by index, so a snapshot can only be restored into a
+/// world holding the same number of points, quads, circles and polygons.
+/// Constraints, anchors, motors and hinges are stored whole and replace the
+/// world's own, so ropes cut after the snapshot are tied again.
+///
+/// # Fields
+///
+/// - `points`, `quads`, `circles`, `polygons`: State of each object, in the world's order.
+/// - `constraints`, `anchors`, `motors`, `hinges`: Copies of the world's links between objects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicsSnapshot {
+    pub points: Vec<PointState>,
+    pub quads: Vec<BodyState>,
+    pub circles: Vec<BodyState>,
+    pub polygons: Vec<BodyState>,
+    pub constraints: Vec<Constraint>,
+    pub anchors: Vec<QuadAnchor>,
+    pub motors: Vec<Motor>,
+    pub hinges: Vec<HingeJoint>,
+    pub(crate) accumulator: f32,
+    pub(crate) contacts: HashSet<(BodyHandle, BodyHandle)>,
+    pub(crate) trigger_contacts: HashSet<(BodyHandle, BodyHandle)>,
+    pub(crate) ground_normals: HashMap<usize, Vec2>,
+}
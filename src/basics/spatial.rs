@@ -0,0 +1,244 @@
+//! Spatial Partitioning
+//!
+//! This module provides a quadtree for finding objects by location, both for
+//! the collision broad-phase and for gameplay queries such as "every enemy
+//! within 200 pixels".
+//!
+//! # Features
+//! - Insert, remove and move objects by their bounding rectangle
+//! - Rectangle and circle region queries
+//! - Nearest-neighbor lookup
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::spatial::Quadtree;
+//!
+//! let mut tree = Quadtree::new(Rect::new(0.0, 0.0, 1280.0, 720.0));
+//! tree.insert(7, Rect::new(100.0, 100.0, 32.0, 32.0));
+//! tree.update(7, Rect::new(120.0, 100.0, 32.0, 32.0));
+//!
+//! let nearby = tree.query_circle(vec2(110.0, 110.0), 200.0);
+//! let closest = tree.nearest(vec2(0.0, 0.0));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use macroquad::prelude::*;
+
+/// Quadtree storing items by their bounding rectangles
+///
+/// Items are any small copyable id, such as an index into a `Vec` of quads.
+/// Items that straddle a split stay in the larger node, and items outside the
+/// tree's bounds are kept in the root, so nothing is ever lost.
+pub struct Quadtree<T> {
+    /// Items a node holds before splitting
+    pub max_items: usize,
+    /// Deepest level nodes split to
+    pub max_depth: usize,
+    root: Node<T>,
+    /// Bounds of every item, to find it again on removal
+    bounds: HashMap<T, Rect>,
+}
+
+struct Node<T> {
+    bounds: Rect,
+    depth: usize,
+    items: Vec<(T, Rect)>,
+    children: Option<Box<[Node<T>; 4]>>,
+}
+
+impl<T: Copy + Eq + Hash> Quadtree<T> {
+    /// Create an empty quadtree covering `bounds`
+    pub fn new(bounds: Rect) -> Self {
+        Self {
+            max_items: 8,
+            max_depth: 8,
+            root: Node::new(bounds, 0),
+            bounds: HashMap::new(),
+        }
+    }
+
+    /// Number of items in the tree
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Check if the tree has no items
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Remove every item
+    pub fn clear(&mut self) {
+        self.root = Node::new(self.root.bounds, 0);
+        self.bounds.clear();
+    }
+
+    /// Insert an item, replacing it if it is already in the tree
+    pub fn insert(&mut self, item: T, bounds: Rect) {
+        self.remove(item);
+        self.bounds.insert(item, bounds);
+        self.root.insert(item, bounds, self.max_items, self.max_depth);
+    }
+
+    /// Remove an item, returning whether it was in the tree
+    pub fn remove(&mut self, item: T) -> bool {
+        match self.bounds.remove(&item) {
+            Some(bounds) => self.root.remove(item, bounds),
+            None => false,
+        }
+    }
+
+    /// Move an item to new bounds
+    pub fn update(&mut self, item: T, bounds: Rect) {
+        self.insert(item, bounds);
+    }
+
+    /// Get the bounds an item was stored with
+    pub fn bounds_of(&self, item: T) -> Option<Rect> {
+        self.bounds.get(&item).copied()
+    }
+
+    /// Items whose bounds overlap `region`
+    pub fn query_rect(&self, region: Rect) -> Vec<T> {
+        let mut found = Vec::new();
+        self.root.query(&|bounds| bounds.overlaps(&region), &mut found);
+        found
+    }
+
+    /// Items whose bounds touch the circle at `center` with `radius`
+    pub fn query_circle(&self, center: Vec2, radius: f32) -> Vec<T> {
+        let mut found = Vec::new();
+        self.root
+            .query(&|bounds| distance_to_rect(center, bounds) <= radius, &mut found);
+        found
+    }
+
+    /// Items whose bounds contain `point`
+    pub fn query_point(&self, point: Vec2) -> Vec<T> {
+        let mut found = Vec::new();
+        self.root.query(&|bounds| bounds.contains(point), &mut found);
+        found
+    }
+
+    /// Item closest to `point`, measured to the edge of its bounds
+    pub fn nearest(&self, point: Vec2) -> Option<T> {
+        self.nearest_within(point, f32::INFINITY)
+    }
+
+    /// Item closest to `point` no further than `max_distance` away
+    pub fn nearest_within(&self, point: Vec2, max_distance: f32) -> Option<T> {
+        let mut best = None;
+        let mut best_distance = max_distance;
+        self.root.nearest(point, &mut best, &mut best_distance);
+        best
+    }
+}
+
+impl<T: Copy + Eq> Node<T> {
+    fn new(bounds: Rect, depth: usize) -> Self {
+        Self {
+            bounds,
+            depth,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    /// Index of the child that fully contains `bounds`, if any
+    fn child_for(&self, bounds: Rect) -> Option<usize> {
+        let children = self.children.as_ref()?;
+        children.iter().position(|child| contains_rect(child.bounds, bounds))
+    }
+
+    fn insert(&mut self, item: T, bounds: Rect, max_items: usize, max_depth: usize) {
+        if let Some(index) = self.child_for(bounds) {
+            if let Some(children) = &mut self.children {
+                children[index].insert(item, bounds, max_items, max_depth);
+            }
+            return;
+        }
+        self.items.push((item, bounds));
+        if self.children.is_none() && self.items.len() > max_items && self.depth < max_depth {
+            self.split(max_items, max_depth);
+        }
+    }
+
+    /// Create four children and move down every item that fits in one
+    fn split(&mut self, max_items: usize, max_depth: usize) {
+        let Rect { x, y, w, h } = self.bounds;
+        let (hw, hh) = (w / 2.0, h / 2.0);
+        let depth = self.depth + 1;
+        self.children = Some(Box::new([
+            Node::new(Rect::new(x, y, hw, hh), depth),
+            Node::new(Rect::new(x + hw, y, hw, hh), depth),
+            Node::new(Rect::new(x, y + hh, hw, hh), depth),
+            Node::new(Rect::new(x + hw, y + hh, hw, hh), depth),
+        ]));
+        for (item, bounds) in std::mem::take(&mut self.items) {
+            self.insert(item, bounds, max_items, max_depth);
+        }
+    }
+
+    fn remove(&mut self, item: T, bounds: Rect) -> bool {
+        if let Some(index) = self.items.iter().position(|(stored, _)| *stored == item) {
+            self.items.swap_remove(index);
+            return true;
+        }
+        match (self.child_for(bounds), &mut self.children) {
+            (Some(index), Some(children)) => children[index].remove(item, bounds),
+            _ => false,
+        }
+    }
+
+    fn query(&self, matches: &dyn Fn(Rect) -> bool, found: &mut Vec<T>) {
+        if !matches(self.bounds) && self.depth > 0 {
+            return;
+        }
+        found.extend(self.items.iter().filter(|(_, bounds)| matches(*bounds)).map(|(item, _)| *item));
+        if let Some(children) = &self.children {
+            for child in children.iter() {
+                child.query(matches, found);
+            }
+        }
+    }
+
+    fn nearest(&self, point: Vec2, best: &mut Option<T>, best_distance: &mut f32) {
+        // The root also holds items outside its bounds, so it is always searched
+        if self.depth > 0 && distance_to_rect(point, self.bounds) > *best_distance {
+            return;
+        }
+        for (item, bounds) in &self.items {
+            let distance = distance_to_rect(point, *bounds);
+            if distance <= *best_distance {
+                *best = Some(*item);
+                *best_distance = distance;
+            }
+        }
+        if let Some(children) = &self.children {
+            // Visit the closest child first so the others are more likely to be pruned
+            let mut order: Vec<&Node<T>> = children.iter().collect();
+            order.sort_by(|a, b| {
+                distance_to_rect(point, a.bounds).total_cmp(&distance_to_rect(point, b.bounds))
+            });
+            for child in order {
+                child.nearest(point, best, best_distance);
+            }
+        }
+    }
+}
+
+/// Check if `outer` fully contains `inner`
+fn contains_rect(outer: Rect, inner: Rect) -> bool {
+    inner.x >= outer.x && inner.y >= outer.y && inner.right() <= outer.right() && inner.bottom() <= outer.bottom()
+}
+
+/// Distance from a point to the nearest edge of a rectangle, zero inside it
+fn distance_to_rect(point: Vec2, rect: Rect) -> f32 {
+    let closest = vec2(
+        point.x.clamp(rect.x, rect.right()),
+        point.y.clamp(rect.y, rect.bottom()),
+    );
+    point.distance(closest)
+}
@@ -0,0 +1,293 @@
+//! Sprite animation
+//!
+//! This module provides a component that plays animations from a sprite
+//! sheet on a textured Quad, by moving the part of the texture it draws.
+//!
+//! # Features
+//! - Sprite sheets laid out as a grid of equally sized frames
+//! - Named clips such as "run" or "jump", each with its own frames and speed
+//! - Looping clips, and clips that stop on their last frame
+//! - Events fired when a clip reaches a given frame, for footsteps and hit frames
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::sprite_animation::{AnimationClip, SpriteAnimation};
+//!
+//! let mut player = Quad::builder().size(48.0, 48.0).texture(sheet).build();
+//! player.add_component(Box::new(
+//!     SpriteAnimation::new(32.0, 32.0, 8)
+//!         .with_clip("run", AnimationClip::new(8..16, 12.0).with_event(2, "step").with_event(6, "step"))
+//!         .with_clip("jump", AnimationClip::new(16..20, 10.0).with_looping(false)),
+//! ));
+//!
+//! if let Some(animation) = player.get_component_mut::<SpriteAnimation>() {
+//!     animation.play("run");
+//!     for event in animation.drain_events() {
+//!         if event.name == "step" {
+//!             play_sound(&footstep, PlaySoundParams::default());
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use macroquad::math::Rect;
+
+use crate::basics::Component;
+use crate::objects::quad::Quad;
+
+/// A named animation made of frames of a sprite sheet
+///
+/// # Fields
+///
+/// - `frames`: Frames of the sheet shown in order, counted left to right and then top to bottom from 0.
+/// - `fps`: Frames shown per second.
+/// - `looping`: Whether the clip starts over after its last frame instead of stopping on it.
+/// - `events`: Names fired when the clip reaches the frame at each position in `frames`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationClip {
+    pub frames: Vec<usize>,
+    pub fps: f32,
+    pub looping: bool,
+    pub events: Vec<(usize, String)>,
+}
+
+impl AnimationClip {
+    /// Creates a looping clip showing `frames` of the sheet at `fps` frames per second.
+    pub fn new(frames: impl IntoIterator<Item = usize>, fps: f32) -> Self {
+        Self {
+            frames: frames.into_iter().collect(),
+            fps,
+            looping: true,
+            events: Vec::new(),
+        }
+    }
+
+    /// Set whether the clip starts over after its last frame
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Fire an event named `name` whenever the clip reaches position `frame` in its frames
+    pub fn with_event(mut self, frame: usize, name: impl Into<String>) -> Self {
+        self.events.push((frame, name.into()));
+        self
+    }
+}
+
+/// An event a clip fired on reaching one of its frames
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnimationEvent {
+    /// Name of the clip playing
+    pub clip: String,
+    /// Name given to the event with `AnimationClip::with_event`
+    pub name: String,
+    /// Position in the clip's frames that was reached
+    pub frame: usize,
+}
+
+/// Component that plays clips from a sprite sheet on a Quad's texture
+///
+/// Each update sets the Quad's `source_rect` to the current frame, so the
+/// Quad needs the sheet as its texture.
+///
+/// # Fields
+///
+/// - `frame_width`, `frame_height`: Size of one frame on the sheet, in pixels.
+/// - `columns`: Frames in each row of the sheet.
+/// - `clips`: Clips that can be played, by name.
+/// - `speed`: Multiplier on the speed of every clip; 1.0 by default.
+/// - `enabled`: Whether the animation advances.
+pub struct SpriteAnimation {
+    pub frame_width: f32,
+    pub frame_height: f32,
+    pub columns: usize,
+    pub clips: HashMap<String, AnimationClip>,
+    pub speed: f32,
+    pub enabled: bool,
+    current: Option<String>,
+    position: usize,
+    elapsed: f32,
+    finished: bool,
+    entered: bool,
+    events: Vec<AnimationEvent>,
+}
+
+impl SpriteAnimation {
+    /// Creates an animation without clips for a sheet of frames in rows of `columns`.
+    ///
+    /// # Parameters
+    /// - `frame_width`, `frame_height`: Size of one frame on the sheet, in pixels.
+    /// - `columns`: Frames in each row of the sheet.
+    ///
+    /// # Returns
+    /// A new `SpriteAnimation` instance.
+    pub fn new(frame_width: f32, frame_height: f32, columns: usize) -> Self {
+        Self {
+            frame_width,
+            frame_height,
+            columns: columns.max(1),
+            clips: HashMap::new(),
+            speed: 1.0,
+            enabled: true,
+            current: None,
+            position: 0,
+            elapsed: 0.0,
+            finished: false,
+            entered: false,
+            events: Vec::new(),
+        }
+    }
+
+    /// Add a clip, playing it if it is the first one
+    pub fn with_clip(mut self, name: impl Into<String>, clip: AnimationClip) -> Self {
+        self.add_clip(name, clip);
+        self
+    }
+
+    /// Set the multiplier on the speed of every clip
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Add or replace a clip, playing it if nothing is playing yet
+    pub fn add_clip(&mut self, name: impl Into<String>, clip: AnimationClip) {
+        let name = name.into();
+        self.clips.insert(name.clone(), clip);
+        if self.current.is_none() {
+            self.play(&name);
+        }
+    }
+
+    /// Switch to the clip named `name` from its first frame, unless it is already playing
+    ///
+    /// Unknown names are ignored.
+    pub fn play(&mut self, name: &str) {
+        if self.current.as_deref() != Some(name) {
+            self.restart(name);
+        }
+    }
+
+    /// Play the clip named `name` from its first frame, even if it is already playing
+    ///
+    /// Unknown names are ignored.
+    pub fn restart(&mut self, name: &str) {
+        if !self.clips.contains_key(name) {
+            return;
+        }
+        self.current = Some(name.to_string());
+        self.position = 0;
+        self.elapsed = 0.0;
+        self.finished = false;
+        self.entered = true;
+    }
+
+    /// Name of the clip playing, if any
+    pub fn current_clip(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Frame of the sheet shown right now, if a clip is playing
+    pub fn current_frame(&self) -> Option<usize> {
+        let clip = self.clips.get(self.current.as_deref()?)?;
+        clip.frames.get(self.position).copied()
+    }
+
+    /// Check if a clip that doesn't loop has reached its last frame
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Take the events fired since the last call
+    pub fn drain_events(&mut self) -> Vec<AnimationEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Returns the part of the sheet showing `frame`, in pixels.
+    pub fn frame_rect(&self, frame: usize) -> Rect {
+        let column = frame % self.columns;
+        let row = frame / self.columns;
+        Rect::new(
+            column as f32 * self.frame_width,
+            row as f32 * self.frame_height,
+            self.frame_width,
+            self.frame_height,
+        )
+    }
+
+    /// Moves the current clip on by `dt` seconds, firing the events of every frame it reaches.
+    pub fn advance(&mut self, dt: f32) {
+        let Some(clip) = self.current.as_ref().and_then(|name| self.clips.get(name)) else {
+            return;
+        };
+        if clip.frames.is_empty() {
+            return;
+        }
+        if self.entered {
+            self.entered = false;
+            fire_events(&mut self.events, self.current.as_deref().unwrap_or_default(), clip, self.position);
+        }
+        if self.finished || clip.fps <= 0.0 {
+            return;
+        }
+        let frame_time = 1.0 / clip.fps;
+        self.elapsed += dt * self.speed.max(0.0);
+        while self.elapsed >= frame_time {
+            self.elapsed -= frame_time;
+            if self.position + 1 < clip.frames.len() {
+                self.position += 1;
+            } else if clip.looping {
+                self.position = 0;
+            } else {
+                self.finished = true;
+                self.elapsed = 0.0;
+                break;
+            }
+            fire_events(&mut self.events, self.current.as_deref().unwrap_or_default(), clip, self.position);
+        }
+    }
+}
+
+/// Queue the events `clip` fires on reaching `position`
+fn fire_events(events: &mut Vec<AnimationEvent>, name: &str, clip: &AnimationClip, position: usize) {
+    for (frame, event) in &clip.events {
+        if *frame == position {
+            events.push(AnimationEvent {
+                clip: name.to_string(),
+                name: event.clone(),
+                frame: position,
+            });
+        }
+    }
+}
+
+impl Component<Quad> for SpriteAnimation {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Plays the current clip from its first frame and drops events not yet drained.
+    fn on_reset(&mut self, _quad: &mut Quad) {
+        if let Some(name) = self.current.clone() {
+            self.restart(&name);
+        }
+        self.events.clear();
+    }
+
+    /// Advances the current clip and shows its frame on the Quad.
+    fn update(&mut self, quad: &mut Quad, dt: f32) {
+        self.advance(dt);
+        if let Some(frame) = self.current_frame() {
+            quad.source_rect = Some(self.frame_rect(frame));
+        }
+    }
+
+    fn on_collide(&mut self, _me: &mut Quad, _other: &mut Quad) {}
+}
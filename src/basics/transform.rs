@@ -0,0 +1,116 @@
+//! Transforms
+//!
+//! This module provides the position, rotation and scale that place a
+//! `GameObject` and its drawable in the world.
+//!
+//! # Features
+//! - Position, clockwise rotation in radians and per-axis scale
+//! - Conversion of points between local and world coordinates
+//! - Nesting transforms inside a parent's, and back
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::transform::Transform;
+//!
+//! let transform = Transform::new(vec2(400.0, 300.0)).with_rotation(0.5).with_scale(vec2(2.0, 2.0));
+//! let muzzle = transform.to_world(vec2(20.0, 0.0));
+//!
+//! // A turret 10 pixels above the tank's origin, turned with it
+//! let turret = Transform::new(vec2(0.0, -10.0)).in_parent(&transform);
+//! ```
+
+use macroquad::math::{Vec2, vec2};
+
+/// Where an object is, how far it is turned and how much it is stretched
+///
+/// Local points are scaled first, then rotated, then moved by `position`.
+///
+/// # Fields
+///
+/// - `position`: World position of the object's origin.
+/// - `rotation`: Angle in radians, clockwise on screen.
+/// - `scale`: Stretch along the object's own x and y axes; `(1.0, 1.0)` keeps its size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub position: Vec2,
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Transform {
+    /// A transform at the origin that leaves points where they are
+    pub const IDENTITY: Transform = Transform {
+        position: Vec2::ZERO,
+        rotation: 0.0,
+        scale: Vec2::ONE,
+    };
+
+    /// Creates an unrotated, unscaled transform at `position`.
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            position,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Set the rotation in radians, clockwise on screen
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the stretch along the object's own x and y axes
+    pub fn with_scale(mut self, scale: Vec2) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Converts a point relative to the object into world coordinates.
+    pub fn to_world(&self, local: Vec2) -> Vec2 {
+        self.position + Vec2::from_angle(self.rotation).rotate(local * self.scale)
+    }
+
+    /// Converts a world position into a point relative to the object.
+    ///
+    /// Axes scaled to zero map every point onto them to zero.
+    pub fn to_local(&self, world: Vec2) -> Vec2 {
+        let unrotated = Vec2::from_angle(-self.rotation).rotate(world - self.position);
+        unscale(unrotated, self.scale)
+    }
+
+    /// Returns this transform, given relative to `parent`, in world coordinates.
+    ///
+    /// Rotations add up and scales multiply. A parent scaled unevenly along
+    /// its axes would shear a rotated child, which a `Transform` can't hold,
+    /// so the child only takes on the parent's scale along its own axes.
+    pub fn in_parent(&self, parent: &Transform) -> Transform {
+        Transform {
+            position: parent.to_world(self.position),
+            rotation: parent.rotation + self.rotation,
+            scale: parent.scale * self.scale,
+        }
+    }
+
+    /// Returns the transform relative to `parent` that puts an object at this world transform.
+    ///
+    /// The opposite of `in_parent`.
+    pub fn relative_to(&self, parent: &Transform) -> Transform {
+        Transform {
+            position: parent.to_local(self.position),
+            rotation: self.rotation - parent.rotation,
+            scale: unscale(self.scale, parent.scale),
+        }
+    }
+}
+
+/// Divides `value` by `scale` per axis, giving zero along axes scaled to zero
+fn unscale(value: Vec2, scale: Vec2) -> Vec2 {
+    let axis = |value: f32, scale: f32| if scale == 0.0 { 0.0 } else { value / scale };
+    vec2(axis(value.x, scale.x), axis(value.y, scale.y))
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
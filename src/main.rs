@@ -1,25 +1,32 @@
+
 pub mod basics;
 pub mod objects;
 pub mod utils;
 pub mod test;
 
-use macroquad::color::{Color, BLACK, WHITE};
+use std::cell::Cell;
+use std::rc::Rc;
+
+use macroquad::color::{BLACK, WHITE};
 use macroquad::input::{KeyCode, is_key_down};
 use macroquad::miniquad::window::set_window_size;
+use macroquad::time::get_frame_time;
 use macroquad::window::{clear_background, next_frame, set_fullscreen};
-use std::sync::{Arc, Mutex};
 
+use crate::basics::ComponentOwner;
 use crate::basics::collision::Collision;
+use crate::basics::drag::Drag;
 use crate::basics::friction::Friction;
 use crate::basics::gravity::Gravity;
 use crate::basics::physics_config::{PhysicsConfig, PhysicsPreset};
 use crate::objects::quad::Quad;
 use crate::objects::ui::{
-    Theme, UiText, UiButton, UiInput, UiSlider, UiCheckbox, 
-    UiPanel, UiProgressBar, UiDropdown, TextAlignment, UiElement
+    Theme, UiText, UiButton, UiInput, UiCheckbox,
+    UiPanel, UiProgressBar, UiDropdown, TextAlignment
 };
-use crate::test::gradient_test;
-use crate::test::ui_test::run_ui_example;
+use crate::basics::scene_manager::SceneManager;
+use crate::test::gradient_test::GradientTest;
+use crate::test::ui_test::UiExample;
 use crate::utils::screen;
 use crate::utils::font_text::FontText;
 use crate::utils::window::WindowBuilder;
@@ -28,7 +35,6 @@ use crate::objects::ui::UiManager;
 
 #[macroquad::main("Ruty Game Engine")]
 async fn main() {
-    gradient_test::run_gradient_test().await;
     // Configure the window
     set_window_size(1280, 720);
     set_fullscreen(false);
@@ -42,8 +48,8 @@ async fn main() {
         .await;
 
     // Configure physics
-    let physics_config = PhysicsConfig::new()
-        .gravity(9.81)
+    let mut physics_config = PhysicsConfig::new()
+        .gravity(588.6)
         .friction(0.8)
         .bounce(0.5)
         .air_resistance(0.1)
@@ -52,7 +58,12 @@ async fn main() {
         .add_preset("bouncy", PhysicsConfig::bouncy())
         .add_preset("space_like", PhysicsConfig::space_like());
 
-    run_ui_example().await;
+    // Show the gradient test, then the UI example once it is closed, before the game
+    let mut scenes = SceneManager::new();
+    scenes.push(Box::new(UiExample::load().await));
+    scenes.push(Box::new(GradientTest::new()));
+    scenes.run().await;
+
     // Run the point physics example
 //    crate::test::point_example::run_point_example().await;
 
@@ -64,9 +75,7 @@ async fn main() {
     // Player cube positioned somewhere near top-left
     let mut cube = Quad::new(200.0, 0.0, 50.0, 50.0, WHITE);
     // Add persistent components: gravity, collision, friction
-    cube.add_component(Box::new(Gravity::new(physics_config.gravity)));
-    cube.add_component(Box::new(Collision::new(physics_config.bounce, physics_config.friction)));
-    cube.add_component(Box::new(Friction::new(physics_config.friction)));
+    attach_physics(&mut cube, &physics_config);
 
     // Create UI elements
     let mut main_panel = UiPanel::new(
@@ -74,7 +83,6 @@ async fn main() {
         20.0,
         300.0,
         500.0,
-        theme.clone(),
         Some("Ruty Game Engine".to_string()),
     );
 
@@ -110,13 +118,13 @@ async fn main() {
         30.0,
         16,
         font_text.font.clone(),
-        theme.clone(),
         "Enter your name...",
         Some(Box::new(|text| println!("Input changed: {}", text))),
     );
     main_panel.add_element(Box::new(input));
 
     // Physics preset dropdown
+    let selected_preset = Rc::new(Cell::new(None));
     let physics_presets = vec![
         "Default".to_string(),
         "Low Gravity".to_string(),
@@ -130,19 +138,12 @@ async fn main() {
         220.0,
         30.0,
         physics_presets,
-        theme.clone(),
         font_text.font.clone(),
         16,
-        Some(Box::new(|index| {
-            // Apply physics preset based on selection
-            let preset = match index {
-                1 => PhysicsConfig::low_gravity(),
-                2 => PhysicsConfig::high_friction(),
-                3 => PhysicsConfig::bouncy(),
-                4 => PhysicsConfig::space_like(),
-                _ => PhysicsPreset::new(9.81, 0.8, 0.5, 0.1),
-            };
-            // TODO: Apply preset to physics system
+        Some(Box::new({
+            // Remember the selection, the game loop applies it to the cube
+            let selected_preset = selected_preset.clone();
+            move |index| selected_preset.set(Some(index))
         })),
     );
     main_panel.add_element(Box::new(dropdown));
@@ -153,11 +154,10 @@ async fn main() {
         220.0,
         20.0,
         false,
-        theme.clone(),
         Some(Box::new(|checked| println!("Checkbox: {}", checked))),
     );
     main_panel.add_element(Box::new(checkbox));
-    let mut checkbox_label = UiText::new(
+    let checkbox_label = UiText::new(
         "Enable features",
         70.0,
         225.0,
@@ -174,9 +174,8 @@ async fn main() {
         220.0,
         20.0,
         0.5,
-        theme.clone(),
     );
-    main_panel.add_element(Box::new(progress_bar));
+    main_panel.add_named_element("progress", Box::new(progress_bar));
 
     // Buttons
     let start_button = UiButton::new(
@@ -187,7 +186,6 @@ async fn main() {
         40.0,
         18,
         font_text.font.clone(),
-        theme.clone(),
         Some(Box::new(|| println!("Start game clicked!"))),
     );
     main_panel.add_element(Box::new(start_button));
@@ -200,7 +198,6 @@ async fn main() {
         40.0,
         18,
         font_text.font.clone(),
-        theme.clone(),
         Some(Box::new(|| println!("Settings clicked!"))),
     );
     main_panel.add_element(Box::new(settings_button));
@@ -213,7 +210,6 @@ async fn main() {
         40.0,
         18,
         font_text.font.clone(),
-        theme.clone(),
         Some(Box::new(|| std::process::exit(0))),
     );
     main_panel.add_element(Box::new(exit_button));
@@ -221,7 +217,7 @@ async fn main() {
     let mut on_ground = false;
     let mut progress = 0.0;
     let mut ui_manager = UiManager::new();
-    let panel_index = ui_manager.add_element(Box::new(main_panel));
+    ui_manager.add_element(Box::new(main_panel));
 
     loop {
         let ground_height = 50.0;
@@ -241,19 +237,25 @@ async fn main() {
 
         // Update progress bar
         progress = (progress + 0.001) % 1.0;
-        if let Some(element) = ui_manager.get_element_mut(panel_index) {
-            if let Some(panel) = element.as_any_mut().downcast_mut::<UiPanel>() {
-                for element in &mut panel.elements {
-                    if let Some(progress_bar) = element.as_any_mut().downcast_mut::<UiProgressBar>() {
-                        progress_bar.set_progress(progress);
-                        break;
-                    }
-                }
-            }
+        if let Some(progress_bar) = ui_manager.get_named::<UiProgressBar>("progress") {
+            progress_bar.set_progress(progress);
+        }
+
+        // Apply the physics preset picked in the dropdown
+        if let Some(index) = selected_preset.take() {
+            let preset = match index {
+                1 => PhysicsConfig::low_gravity(),
+                2 => PhysicsConfig::high_friction(),
+                3 => PhysicsConfig::bouncy(),
+                4 => PhysicsConfig::space_like(),
+                _ => PhysicsPreset::new(588.6, 0.8, 0.5, 0.1),
+            };
+            physics_config.apply_preset(&preset);
+            attach_physics(&mut cube, &physics_config);
         }
 
         // Update and draw the player cube
-        cube.update_components();
+        cube.update_components(get_frame_time());
         cube.draw();
 
         // Handle player movement
@@ -281,3 +283,15 @@ async fn main() {
         next_frame().await;
     }
 }
+
+/// Give the cube gravity, collision, friction and drag from `config`, replacing the ones it had
+fn attach_physics(cube: &mut Quad, config: &PhysicsConfig) {
+    cube.remove_component::<Gravity>();
+    cube.remove_component::<Collision>();
+    cube.remove_component::<Friction>();
+    cube.remove_component::<Drag>();
+    cube.add_component(Box::new(Gravity::new(config.gravity)));
+    cube.add_component(Box::new(Collision::new(config.bounce, config.friction)));
+    cube.add_component(Box::new(Friction::new(config.friction)));
+    cube.add_component(Box::new(Drag::new(config.air_resistance, 0.0)));
+}
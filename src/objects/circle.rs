@@ -0,0 +1,208 @@
+use macroquad::color::Color;
+use macroquad::math::Rect;
+use macroquad::shapes::draw_circle;
+
+use crate::basics::{BodyType, Component, ComponentOwner, Tagged};
+use crate::basics::physics_world::{BodyHandle, ContactPhase, TriggerPhase};
+use crate::basics::render::Renderable;
+use crate::objects::quad::Quad;
+
+/// Represents a solid circular game object in 2D space.
+///
+/// A cheaper alternative to a ring of points and constraints for balls,
+/// coins and other round objects. Like `Quad`, behavior comes from components.
+///
+/// # Fields
+///
+/// - `position`: The (x, y) coordinates of the circle's center.
+/// - `radius`: Radius of the circle.
+/// - `color`: Color used to draw the circle.
+/// - `draw_layer`: Layer a `Renderer` draws the circle on, higher on top; 0 by default.
+/// - `velocity_x`, `velocity_y`: Current velocity components along the X and Y axes.
+/// - `mass`: How hard the circle is to push in collisions; 1.0 by default.
+/// - `restitution`: How bouncy the circle is, from 0.0 to 1.0.
+/// - `gravity_scale`: Multiplier on the pull of `Gravity`; 1.0 by default.
+/// - `body_type`: Static, kinematic or dynamic; dynamic by default.
+/// - `is_trigger`: Report overlaps as trigger events instead of colliding.
+/// - `can_sleep`: Whether a `PhysicsWorld` may put the circle to sleep once it rests.
+/// - `sleeping`: Whether the circle is asleep and skipped by the simulation.
+/// - `active`: Whether the circle takes part in a `PhysicsWorld` at all; recycled bodies wait inactive to be reused.
+/// - `idle_steps`: How many steps in a row the circle has barely moved.
+/// - `layer`: Name of the `PhysicsConfig` layer whose overrides apply to the circle, if any.
+/// - `tags`: Names gameplay code finds the circle by, see `Tagged`.
+/// - `components`: A vector of boxed components attached to this circle.
+///
+/// # Example
+///
+/// ```rust
+/// let mut ball = CircleBody::new(100.0, 200.0, 16.0, WHITE);
+/// ball.add_component(Box::new(Gravity::new(30.0)));
+/// ball.add_component(Box::new(Collision::new(0.8, 0.0)));
+/// ```
+pub struct CircleBody {
+    pub position: (f32, f32),
+    pub radius: f32,
+    pub color: Color,
+    pub draw_layer: i32,
+    pub velocity_x: f32,
+    pub velocity_y: f32,
+    pub mass: f32,
+    pub restitution: f32,
+    pub gravity_scale: f32,
+    pub body_type: BodyType,
+    pub is_trigger: bool,
+    pub can_sleep: bool,
+    pub sleeping: bool,
+    pub active: bool,
+    pub idle_steps: u32,
+    pub layer: Option<String>,
+    pub tags: Vec<String>,
+    pub components: Vec<Box<dyn Component<CircleBody>>>,
+}
+
+impl CircleBody {
+    /// Creates a new circle centered at `x`, `y`.
+    ///
+    /// Velocity starts at zero and no components are attached by default.
+    pub fn new(x: f32, y: f32, radius: f32, color: Color) -> Self {
+        Self {
+            position: (x, y),
+            radius,
+            color,
+            draw_layer: 0,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            mass: 1.0,
+            restitution: 0.0,
+            gravity_scale: 1.0,
+            body_type: BodyType::Dynamic,
+            is_trigger: false,
+            can_sleep: true,
+            sleeping: false,
+            active: true,
+            idle_steps: 0,
+            layer: None,
+            tags: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+
+    /// Draws the circle on the screen.
+    pub fn draw(&self) {
+        draw_circle(self.position.0, self.position.1, self.radius, self.color);
+    }
+
+    /// Moves the circle by its velocity over `dt` seconds. Static circles never move.
+    pub fn update(&mut self, dt: f32) {
+        if !self.body_type.moves() {
+            return;
+        }
+        self.position.0 += self.velocity_x * dt;
+        self.position.1 += self.velocity_y * dt;
+    }
+
+    /// Wakes the circle so it is simulated again.
+    pub fn wake(&mut self) {
+        self.sleeping = false;
+        self.idle_steps = 0;
+    }
+
+    /// Counts steps moving slower than `threshold` and puts the circle to sleep after `steps` of them.
+    ///
+    /// `speed` is how fast the circle actually moved over the last step, which
+    /// can be slower than its velocity while collisions hold it in place.
+    /// Only dynamic circles with `can_sleep` set ever fall asleep.
+    pub fn update_sleep(&mut self, speed: f32, threshold: f32, steps: u32) {
+        if !self.can_sleep || !self.body_type.is_dynamic() || self.sleeping {
+            return;
+        }
+        let resting = speed < threshold;
+        self.idle_steps = if resting { self.idle_steps + 1 } else { 0 };
+        if self.idle_steps >= steps {
+            self.sleeping = true;
+            self.velocity_x = 0.0;
+            self.velocity_y = 0.0;
+        }
+    }
+
+    /// Returns the axis-aligned rectangle enclosing the circle.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(
+            self.position.0 - self.radius,
+            self.position.1 - self.radius,
+            self.radius * 2.0,
+            self.radius * 2.0,
+        )
+    }
+
+    /// Checks if this circle overlaps another circle.
+    pub fn is_colliding_with(&self, other: &CircleBody) -> bool {
+        crate::basics::collision::circle_overlap(self, other).is_some()
+    }
+
+    /// Checks if this circle overlaps a Quad, rotated or not.
+    pub fn is_colliding_with_quad(&self, quad: &Quad) -> bool {
+        crate::basics::collision::circle_quad_overlap(self, quad).is_some()
+    }
+
+    /// Calls every component's `on_collide` hook with `other`.
+    pub fn dispatch_collision(&mut self, other: &mut CircleBody) {
+        self.for_each_component(|comp, me| comp.on_collide(me, other));
+    }
+
+    /// Calls every component's `on_collide_quad` hook with `quad`.
+    pub fn dispatch_quad_collision(&mut self, quad: &mut Quad) {
+        self.for_each_component(|comp, me| comp.on_collide_quad(me, quad));
+    }
+
+    /// Calls every component's `on_collision_enter`, `on_collision_stay` or `on_collision_exit` hook with `other`.
+    pub fn dispatch_contact(&mut self, other: BodyHandle, phase: ContactPhase) {
+        self.for_each_component(|comp, me| match phase {
+            ContactPhase::Enter => comp.on_collision_enter(me, other),
+            ContactPhase::Stay => comp.on_collision_stay(me, other),
+            ContactPhase::Exit => comp.on_collision_exit(me, other),
+        });
+    }
+
+    /// Calls every component's `on_trigger_enter` or `on_trigger_exit` hook with `other`.
+    pub fn dispatch_trigger(&mut self, other: BodyHandle, phase: TriggerPhase) {
+        self.for_each_component(|comp, me| match phase {
+            TriggerPhase::Enter => comp.on_trigger_enter(me, other),
+            TriggerPhase::Exit => comp.on_trigger_exit(me, other),
+        });
+    }
+}
+
+impl ComponentOwner for CircleBody {
+    fn components(&self) -> &[Box<dyn Component<CircleBody>>] {
+        &self.components
+    }
+
+    fn components_mut(&mut self) -> &mut Vec<Box<dyn Component<CircleBody>>> {
+        &mut self.components
+    }
+}
+
+impl Renderable for CircleBody {
+    fn draw_layer(&self) -> i32 {
+        self.draw_layer
+    }
+
+    fn sort_y(&self) -> f32 {
+        self.position.1 + self.radius
+    }
+
+    fn render(&self) {
+        self.draw();
+    }
+}
+
+impl Tagged for CircleBody {
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
@@ -1,12 +1,48 @@
 use crate::objects::point::Point;
+use macroquad::math::{Vec2, vec2};
 use macroquad::shapes::draw_line;
+use serde::{Deserialize, Serialize};
+
+/// Which deviations from `rest_length` a constraint corrects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConstraintMode {
+    /// Keep the points exactly `rest_length` apart, like a rod
+    #[default]
+    Rigid,
+    /// Only keep the points from moving further than `rest_length` apart, like a rope
+    MaxLength,
+    /// Only keep the points from moving closer than `rest_length`, like a strut
+    MinLength,
+}
+
+/// What a constraint needs to know about a point it joins
+///
+/// A plain copy, so constraints can be solved away from the points themselves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstraintEnd {
+    pub position: Vec2,
+    pub mass: f32,
+    pub fixed: bool,
+}
+
+impl From<&Point> for ConstraintEnd {
+    fn from(point: &Point) -> Self {
+        Self {
+            position: vec2(point.position.0, point.position.1),
+            mass: point.mass,
+            fixed: point.is_fixed(),
+        }
+    }
+}
 
 /// Represents a distance constraint between two points
+#[derive(Debug, Clone, PartialEq)]
 pub struct Constraint {
     pub point1: usize,  // Index of first point
     pub point2: usize,  // Index of second point
     pub rest_length: f32,  // The desired distance between points
     pub stiffness: f32,    // How rigid the constraint is (0-1)
+    pub mode: ConstraintMode, // Whether it resists stretching, compression or both
     pub color: macroquad::color::Color,
 }
 
@@ -17,10 +53,22 @@ impl Constraint {
             point2,
             rest_length,
             stiffness,
+            mode: ConstraintMode::Rigid,
             color,
         }
     }
 
+    /// Create a constraint that only resists stretching beyond `rest_length`
+    pub fn rope(point1: usize, point2: usize, rest_length: f32, stiffness: f32, color: macroquad::color::Color) -> Self {
+        Self::new(point1, point2, rest_length, stiffness, color).with_mode(ConstraintMode::MaxLength)
+    }
+
+    /// Set which deviations from `rest_length` the constraint corrects
+    pub fn with_mode(mut self, mode: ConstraintMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     pub fn draw(&self, points: &[Point]) {
         if let (Some(p1), Some(p2)) = (points.get(self.point1), points.get(self.point2)) {
             draw_line(
@@ -35,42 +83,120 @@ impl Constraint {
     }
 
     pub fn solve(&self, points: &mut [Point]) {
-        // Get mutable references to both points
-        let (p1, p2) = if self.point1 < self.point2 {
-            let (left, right) = points.split_at_mut(self.point2);
-            (&mut left[self.point1], &mut right[0])
-        } else {
-            let (left, right) = points.split_at_mut(self.point1);
-            (&mut right[0], &mut left[self.point2])
-        };
+        if let Some((move1, move2)) = self.correction(points) {
+            let p1 = &mut points[self.point1];
+            p1.position.0 += move1.x;
+            p1.position.1 += move1.y;
+            let p2 = &mut points[self.point2];
+            p2.position.0 += move2.x;
+            p2.position.1 += move2.y;
+        }
+    }
+
+    /// How far `solve` would move the first and second point, or `None` if it has nothing to correct
+    pub fn correction(&self, points: &[Point]) -> Option<(Vec2, Vec2)> {
+        self.correction_between((&points[self.point1]).into(), (&points[self.point2]).into())
+    }
 
-        let dx = p2.position.0 - p1.position.0;
-        let dy = p2.position.1 - p1.position.1;
+    /// How far `solve` would move the first and second point, given copies of them
+    pub fn correction_between(&self, p1: ConstraintEnd, p2: ConstraintEnd) -> Option<(Vec2, Vec2)> {
+        let dx = p2.position.x - p1.position.x;
+        let dy = p2.position.y - p1.position.y;
         let distance = (dx * dx + dy * dy).sqrt();
 
         if distance == 0.0 {
-            return;
+            return None;
+        }
+
+        // Slack ropes and uncompressed struts have nothing to correct
+        let slack = match self.mode {
+            ConstraintMode::Rigid => false,
+            ConstraintMode::MaxLength => distance <= self.rest_length,
+            ConstraintMode::MinLength => distance >= self.rest_length,
+        };
+        if slack {
+            return None;
         }
 
         // Calculate the difference from the rest length
         let diff = (distance - self.rest_length) / distance;
 
         // Calculate the correction vector
-        let correction_x = dx * diff * self.stiffness;
-        let correction_y = dy * diff * self.stiffness;
+        let correction = vec2(dx, dy) * diff * self.stiffness;
 
         // Apply the correction based on mass ratios
         let total_mass = p1.mass + p2.mass;
         let ratio1 = p2.mass / total_mass;
         let ratio2 = p1.mass / total_mass;
 
-        if !p1.fixed {
-            p1.position.0 += correction_x * ratio1;
-            p1.position.1 += correction_y * ratio1;
+        let move1 = if p1.fixed { Vec2::ZERO } else { correction * ratio1 };
+        let move2 = if p2.fixed { Vec2::ZERO } else { -correction * ratio2 };
+        Some((move1, move2))
+    }
+}
+
+/// Spins a ring of points around a hub, like a powered wheel
+#[derive(Debug, Clone, PartialEq)]
+pub struct Motor {
+    pub hub: Option<usize>,  // Index of the hub point, or None to spin around the rim's center
+    pub rim: Vec<usize>,     // Indices of the points driven around the hub
+    pub speed: f32,          // Target angular velocity in radians per second, clockwise on screen
+    pub torque: f32,         // Most tangential speed the motor adds to a rim point per second
+    pub enabled: bool,
+}
+
+impl Motor {
+    pub fn new(hub: Option<usize>, rim: Vec<usize>, speed: f32, torque: f32) -> Self {
+        Self {
+            hub,
+            rim,
+            speed,
+            torque,
+            enabled: true,
+        }
+    }
+
+    /// Push the rim points' tangential velocity toward `speed` over `dt` seconds
+    pub fn solve(&self, points: &mut [Point], dt: f32) {
+        if !self.enabled || self.rim.is_empty() {
+            return;
         }
-        if !p2.fixed {
-            p2.position.0 -= correction_x * ratio2;
-            p2.position.1 -= correction_y * ratio2;
+        let position = |point: &Point| vec2(point.position.0, point.position.1);
+        let velocity = |point: &Point| vec2(point.velocity.0, point.velocity.1);
+        let (center, hub_velocity) = match self.hub.and_then(|hub| points.get(hub)) {
+            Some(hub) => (position(hub), velocity(hub)),
+            None => {
+                let rim = self.rim.iter().filter_map(|&index| points.get(index));
+                let (sum, velocity_sum, count) = rim.fold((Vec2::ZERO, Vec2::ZERO, 0), |(p, v, n), point| {
+                    (p + position(point), v + velocity(point), n + 1)
+                });
+                // Rim indices past the end of `points` don't count towards the center
+                if count == 0 {
+                    return;
+                }
+                (sum / count as f32, velocity_sum / count as f32)
+            }
+        };
+
+        let max_change = self.torque * dt;
+        for &index in &self.rim {
+            let Some(point) = points.get_mut(index) else {
+                continue;
+            };
+            if point.is_fixed() {
+                continue;
+            }
+            let offset = position(point) - center;
+            let radius = offset.length();
+            if radius == 0.0 {
+                continue;
+            }
+            // Direction of clockwise travel on screen, where y points down
+            let tangent = vec2(-offset.y, offset.x) / radius;
+            let current = (velocity(point) - hub_velocity).dot(tangent);
+            let change = (self.speed * radius - current).clamp(-max_change, max_change);
+            point.velocity.0 += tangent.x * change;
+            point.velocity.1 += tangent.y * change;
         }
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,358 @@
+//! Fluids
+//!
+//! This module provides particle-based liquid in the style of smoothed
+//! particle hydrodynamics. Particles push apart where they crowd together and
+//! pull together where they spread out, which makes them pool, slosh and
+//! splash like water.
+//!
+//! # Features
+//! - Density and near-density pressure, solved on positions so large steps stay stable
+//! - Viscosity, from runny water to thick goo
+//! - Boundaries against quads, circles, terrain and an optional containing rectangle
+//! - Plain particle drawing and a blobby metaball-style rendering
+//!
+//! # Examples
+//! ```rust
+//! use ruty::objects::fluid::Fluid;
+//!
+//! let mut water = Fluid::new(16.0, SKYBLUE).with_bounds(Rect::new(100.0, 100.0, 400.0, 400.0));
+//! water.add_block(Rect::new(120.0, 150.0, 160.0, 200.0));
+//! world.add_fluid(water);
+//!
+//! loop {
+//!     world.advance(get_frame_time());
+//!     world.fluids[0].draw_metaballs(4.0);
+//!     next_frame().await;
+//! }
+//! ```
+
+use macroquad::color::Color;
+use macroquad::math::{Rect, Vec2, vec2};
+use macroquad::shapes::{draw_circle, draw_rectangle};
+
+use crate::basics::collision::{SpatialHash, circle_overlap, circle_quad_overlap};
+use crate::objects::circle::CircleBody;
+use crate::objects::quad::Quad;
+use crate::objects::terrain::TerrainCollider;
+
+/// One drop of a `Fluid`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FluidParticle {
+    pub position: Vec2,
+    /// Velocity in pixels per second
+    pub velocity: Vec2,
+}
+
+/// A body of liquid made of particles
+///
+/// Pressure is solved with double density relaxation: each step particles
+/// move, then are nudged apart or together toward `rest_density`. Particles
+/// collide with the bodies passed to `step` but don't push them back.
+///
+/// # Fields
+///
+/// - `particles`: Every drop of the fluid.
+/// - `interaction_radius`: Distance within which particles affect each other.
+/// - `particle_radius`: Size of a particle against boundaries and when drawn.
+/// - `rest_density`: How tightly particles pack when the fluid is at rest.
+/// - `stiffness`: How hard crowded particles push apart, and spread ones pull together.
+/// - `near_stiffness`: How hard particles very close together push apart, keeping them from clumping.
+/// - `viscosity`: How much neighbouring particles match velocities; higher is thicker.
+/// - `gravity`: Acceleration in pixels per second squared.
+/// - `bounds`: Rectangle the particles are kept inside, if any.
+/// - `color`: Color used to draw the fluid.
+pub struct Fluid {
+    pub particles: Vec<FluidParticle>,
+    pub interaction_radius: f32,
+    pub particle_radius: f32,
+    pub rest_density: f32,
+    pub stiffness: f32,
+    pub near_stiffness: f32,
+    pub viscosity: f32,
+    pub gravity: Vec2,
+    pub bounds: Option<Rect>,
+    pub color: Color,
+    /// Neighbour search, rebuilt whenever particles move
+    grid: SpatialHash,
+}
+
+impl Fluid {
+    /// Creates an empty fluid that behaves like water.
+    ///
+    /// # Parameters
+    /// - `interaction_radius`: Distance within which particles affect each other;
+    ///   particles end up roughly half this far apart.
+    /// - `color`: Color used to draw the fluid.
+    ///
+    /// # Returns
+    /// A new `Fluid` instance.
+    pub fn new(interaction_radius: f32, color: Color) -> Self {
+        let interaction_radius = interaction_radius.max(1.0);
+        Self {
+            particles: Vec::new(),
+            interaction_radius,
+            particle_radius: interaction_radius * 0.25,
+            rest_density: 2.0,
+            stiffness: interaction_radius * 250.0,
+            near_stiffness: interaction_radius * 500.0,
+            viscosity: 2.0,
+            gravity: vec2(0.0, 980.0),
+            bounds: None,
+            color,
+            grid: SpatialHash::new(interaction_radius),
+        }
+    }
+
+    /// Set how hard particles push apart and pull together
+    ///
+    /// Both default to multiples of the interaction radius; values much higher
+    /// than the defaults make the fluid boil and then explode.
+    pub fn with_stiffness(mut self, stiffness: f32, near_stiffness: f32) -> Self {
+        self.stiffness = stiffness.max(0.0);
+        self.near_stiffness = near_stiffness.max(0.0);
+        self
+    }
+
+    /// Set how tightly particles pack at rest
+    pub fn with_rest_density(mut self, rest_density: f32) -> Self {
+        self.rest_density = rest_density.max(0.0);
+        self
+    }
+
+    /// Set how thick the fluid is
+    pub fn with_viscosity(mut self, viscosity: f32) -> Self {
+        self.viscosity = viscosity.max(0.0);
+        self
+    }
+
+    /// Set the acceleration pulling the particles
+    pub fn with_gravity(mut self, gravity: Vec2) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Keep the particles inside `bounds`
+    pub fn with_bounds(mut self, bounds: Rect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Add a particle at `position` moving at `velocity`
+    pub fn add_particle(&mut self, position: Vec2, velocity: Vec2) {
+        self.particles.push(FluidParticle { position, velocity });
+    }
+
+    /// Fill `area` with resting particles, half the interaction radius apart
+    ///
+    /// # Returns
+    /// How many particles were added
+    pub fn add_block(&mut self, area: Rect) -> usize {
+        let spacing = self.interaction_radius * 0.5;
+        let columns = (area.w / spacing).floor().max(0.0) as usize;
+        let rows = (area.h / spacing).floor().max(0.0) as usize;
+        for row in 0..rows {
+            for column in 0..columns {
+                let position = area.point() + vec2(column as f32 + 0.5, row as f32 + 0.5) * spacing;
+                self.add_particle(position, Vec2::ZERO);
+            }
+        }
+        rows * columns
+    }
+
+    /// Advance the fluid by `dt` seconds, colliding it with the given bodies and terrain.
+    ///
+    /// Pass empty slices for anything the fluid should ignore.
+    pub fn step(&mut self, dt: f32, quads: &[Quad], circles: &[CircleBody], terrains: &[TerrainCollider]) {
+        if dt <= 0.0 || self.particles.is_empty() {
+            return;
+        }
+        for particle in &mut self.particles {
+            particle.velocity += self.gravity * dt;
+        }
+        self.apply_viscosity(dt);
+
+        let previous: Vec<Vec2> = self.particles.iter().map(|particle| particle.position).collect();
+        for particle in &mut self.particles {
+            particle.position += particle.velocity * dt;
+        }
+        self.relax(dt);
+        for index in 0..self.particles.len() {
+            self.collide_particle(index, quads, circles, terrains);
+        }
+
+        for (particle, previous) in self.particles.iter_mut().zip(previous) {
+            particle.velocity = (particle.position - previous) / dt;
+        }
+    }
+
+    /// Pairs of particles closer than the interaction radius, with their distance as a fraction of it
+    fn neighbours(&mut self) -> Vec<(usize, usize, Vec2, f32)> {
+        let h = self.interaction_radius;
+        let extent = Vec2::splat(h * 0.5);
+        self.grid.clear();
+        for (index, particle) in self.particles.iter().enumerate() {
+            self.grid.insert(index, particle.position - extent, particle.position + extent);
+        }
+        self.grid
+            .candidate_pairs()
+            .into_iter()
+            .filter_map(|(i, j)| {
+                let offset = self.particles[j].position - self.particles[i].position;
+                let distance = offset.length();
+                // Particles on the same spot still need a direction to separate in
+                let direction = if distance > 0.0 { offset / distance } else { Vec2::from_angle(i as f32) };
+                (distance < h).then_some((i, j, direction, distance / h))
+            })
+            .collect()
+    }
+
+    /// Slow particles moving toward each other, so neighbours share their motion
+    fn apply_viscosity(&mut self, dt: f32) {
+        if self.viscosity <= 0.0 {
+            return;
+        }
+        for (i, j, direction, q) in self.neighbours() {
+            let inward = (self.particles[i].velocity - self.particles[j].velocity).dot(direction);
+            if inward > 0.0 {
+                let impulse = direction * (dt * (1.0 - q) * self.viscosity * inward).min(inward) * 0.5;
+                self.particles[i].velocity -= impulse;
+                self.particles[j].velocity += impulse;
+            }
+        }
+    }
+
+    /// Move particles apart where they are denser than `rest_density` and together where they are sparser
+    fn relax(&mut self, dt: f32) {
+        let h = self.interaction_radius;
+        let mut neighbours = vec![Vec::new(); self.particles.len()];
+        for (i, j, _, _) in self.neighbours() {
+            neighbours[i].push(j);
+            neighbours[j].push(i);
+        }
+
+        // Each particle is relaxed against its neighbours' latest positions, which converges far better than moving all at once
+        for (i, others) in neighbours.iter().enumerate() {
+            let position = self.particles[i].position;
+            let offsets: Vec<(usize, Vec2, f32)> = others
+                .iter()
+                .filter_map(|&j| {
+                    let offset = self.particles[j].position - position;
+                    let distance = offset.length();
+                    let direction = if distance > 0.0 { offset / distance } else { Vec2::from_angle(i as f32) };
+                    (distance < h).then_some((j, direction, 1.0 - distance / h))
+                })
+                .collect();
+            let density: f32 = offsets.iter().map(|(_, _, closeness)| closeness * closeness).sum();
+            let near_density: f32 = offsets.iter().map(|(_, _, closeness)| closeness.powi(3)).sum();
+            let pressure = self.stiffness * (density - self.rest_density);
+            let near_pressure = self.near_stiffness * near_density;
+
+            let mut moved = Vec2::ZERO;
+            for (j, direction, closeness) in offsets {
+                let push = direction * dt * dt * (pressure * closeness + near_pressure * closeness * closeness) * 0.5;
+                self.particles[j].position += push;
+                moved -= push;
+            }
+            self.particles[i].position += moved;
+        }
+    }
+
+    /// Push one particle out of the bounds, bodies and terrain it overlaps
+    fn collide_particle(&mut self, index: usize, quads: &[Quad], circles: &[CircleBody], terrains: &[TerrainCollider]) {
+        let radius = self.particle_radius;
+        let mut position = self.particles[index].position;
+
+        let mut drop = CircleBody::new(position.x, position.y, radius, self.color);
+        let bounds = Rect::new(position.x - radius, position.y - radius, radius * 2.0, radius * 2.0);
+        for quad in quads.iter().filter(|quad| quad.active && !quad.is_trigger && quad.bounds().overlaps(&bounds)) {
+            drop.position = (position.x, position.y);
+            if let Some(mtv) = circle_quad_overlap(&drop, quad) {
+                position -= mtv;
+            }
+        }
+        for circle in circles.iter().filter(|circle| circle.active && !circle.is_trigger && circle.bounds().overlaps(&bounds)) {
+            drop.position = (position.x, position.y);
+            if let Some(mtv) = circle_overlap(circle, &drop) {
+                position += mtv;
+            }
+        }
+        for terrain in terrains.iter().filter(|terrain| terrain.bounds().overlaps(&bounds)) {
+            if let Some((mtv, _)) = terrain.circle_overlap(position, radius) {
+                position += mtv;
+            }
+        }
+        if let Some(area) = self.bounds {
+            position.x = position.x.clamp(area.x + radius, (area.x + area.w - radius).max(area.x + radius));
+            position.y = position.y.clamp(area.y + radius, (area.y + area.h - radius).max(area.y + radius));
+        }
+        self.particles[index].position = position;
+    }
+
+    /// How much fluid is around `position`, 0.0 far from any particle
+    ///
+    /// Each particle adds up to 1.0, fading to nothing at the interaction radius.
+    pub fn field_at(&self, position: Vec2) -> f32 {
+        let h = self.interaction_radius;
+        self.particles
+            .iter()
+            .map(|particle| {
+                let q = (particle.position - position).length_squared() / (h * h);
+                if q < 1.0 { (1.0 - q) * (1.0 - q) } else { 0.0 }
+            })
+            .sum()
+    }
+
+    /// Draws every particle as a small circle.
+    pub fn draw(&self) {
+        for particle in &self.particles {
+            draw_circle(particle.position.x, particle.position.y, self.particle_radius, self.color);
+        }
+    }
+
+    /// Draws the fluid as one smooth blob, with nearby particles melting together.
+    ///
+    /// The area around the particles is split into square cells, and each cell
+    /// is filled by how much fluid surrounds it, fading out at the surface.
+    /// Smaller cells look smoother but cost more.
+    pub fn draw_metaballs(&self, cell_size: f32) {
+        let (Some(first), true) = (self.particles.first(), cell_size > 0.0) else {
+            return;
+        };
+        let h = self.interaction_radius;
+        let (min, max) = self.particles.iter().fold((first.position, first.position), |(min, max), particle| {
+            (min.min(particle.position), max.max(particle.position))
+        });
+        let origin = min - Vec2::splat(h);
+        let columns = ((max.x - min.x + 2.0 * h) / cell_size).ceil() as usize + 1;
+        let rows = ((max.y - min.y + 2.0 * h) / cell_size).ceil() as usize + 1;
+
+        // Each particle adds to the cells within its reach, rather than each cell searching for particles
+        let mut field = vec![0.0; columns * rows];
+        let reach = (h / cell_size).ceil() as i32;
+        for particle in &self.particles {
+            let cell = ((particle.position - origin) / cell_size).floor();
+            let (column, row) = (cell.x as i32, cell.y as i32);
+            for y in (row - reach).max(0)..=(row + reach).min(rows as i32 - 1) {
+                for x in (column - reach).max(0)..=(column + reach).min(columns as i32 - 1) {
+                    let center = origin + (vec2(x as f32, y as f32) + 0.5) * cell_size;
+                    let q = (particle.position - center).length_squared() / (h * h);
+                    if q < 1.0 {
+                        field[y as usize * columns + x as usize] += (1.0 - q) * (1.0 - q);
+                    }
+                }
+            }
+        }
+
+        // The surface sits where the field reaches this level, blending over the band below it
+        const SURFACE: f32 = 0.6;
+        const BAND: f32 = 0.3;
+        for (index, &amount) in field.iter().enumerate() {
+            let coverage = ((amount - (SURFACE - BAND)) / BAND).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                let corner = origin + vec2((index % columns) as f32, (index / columns) as f32) * cell_size;
+                let color = Color::new(self.color.r, self.color.g, self.color.b, self.color.a * coverage);
+                draw_rectangle(corner.x, corner.y, cell_size, cell_size, color);
+            }
+        }
+    }
+}
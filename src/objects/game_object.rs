@@ -0,0 +1,186 @@
+//! Game objects
+//!
+//! This module provides `GameObject`, a general object placed by a
+//! `Transform` whose behavior comes from components, for things that don't
+//! need a physics body of their own such as pickups, decorations and spawners.
+//!
+//! # Features
+//! - Position, rotation and scale through a `Transform`
+//! - Velocity and angular velocity, integrated by `update`
+//! - Components, attached and run like on every other object through `ComponentOwner`
+//! - An optional `Drawable`, drawn at the object's transform
+//! - Children placed relative to their parent, updated and drawn along with it
+//!
+//! # Examples
+//! ```rust
+//! use ruty::basics::ComponentOwner;
+//! use ruty::basics::transform::Transform;
+//! use ruty::objects::game_object::GameObject;
+//!
+//! let mut coin = GameObject::new(Transform::new(vec2(300.0, 200.0))).with_drawable(Box::new(CoinSprite));
+//! coin.add_component(Box::new(Gravity::new(600.0)));
+//!
+//! let dt = get_frame_time();
+//! coin.update_components(dt);
+//! coin.update(dt);
+//! coin.draw();
+//!
+//! // A turret riding on a tank, turning with it
+//! let mut tank = GameObject::new(Transform::new(vec2(400.0, 500.0))).with_drawable(Box::new(TankSprite));
+//! let turret = tank.add_child(GameObject::new(Transform::new(vec2(0.0, -12.0))).with_drawable(Box::new(TurretSprite)));
+//! tank.children[turret].transform.rotation = aim;
+//! tank.update_tree(dt);
+//! tank.draw();
+//! ```
+
+use macroquad::math::Vec2;
+
+use crate::basics::render::Renderable;
+use crate::basics::transform::Transform;
+use crate::basics::{Component, ComponentOwner, Drawable, Tagged};
+
+/// An object placed by a transform, with components, an optional drawable and children
+///
+/// A child's transform and velocities are relative to its parent, so it
+/// moves, turns and scales with it.
+///
+/// # Fields
+///
+/// - `transform`: Position, rotation and scale of the object, relative to its parent if it has one.
+/// - `velocity`: Change in position per second, in the parent's coordinates.
+/// - `angular_velocity`: Change in rotation per second.
+/// - `gravity_scale`: Multiplier on the pull of `Gravity`; 1.0 by default.
+/// - `components`: A vector of boxed components attached to this object.
+/// - `drawable`: What `draw` draws at the object's transform, if anything.
+/// - `draw_layer`: Layer a `Renderer` draws the object and its children on, higher on top; 0 by default.
+/// - `children`: Objects placed relative to this one.
+/// - `tags`: Names gameplay code finds the object by, see `Tagged`.
+pub struct GameObject {
+    pub transform: Transform,
+    pub velocity: Vec2,
+    pub angular_velocity: f32,
+    pub gravity_scale: f32,
+    pub components: Vec<Box<dyn Component<GameObject>>>,
+    pub drawable: Option<Box<dyn Drawable>>,
+    pub draw_layer: i32,
+    pub children: Vec<GameObject>,
+    pub tags: Vec<String>,
+}
+
+impl GameObject {
+    /// Creates a still object at `transform` with no components and nothing to draw.
+    ///
+    /// # Parameters
+    /// - `transform`: Where the object starts.
+    ///
+    /// # Returns
+    /// A new `GameObject` instance.
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            transform,
+            velocity: Vec2::ZERO,
+            angular_velocity: 0.0,
+            gravity_scale: 1.0,
+            components: Vec::new(),
+            drawable: None,
+            draw_layer: 0,
+            children: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Set what the object draws at its transform
+    pub fn with_drawable(mut self, drawable: Box<dyn Drawable>) -> Self {
+        self.drawable = Some(drawable);
+        self
+    }
+
+    /// Add a child placed relative to this object
+    pub fn with_child(mut self, child: GameObject) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add a child placed relative to this object, returning its index in `children`
+    pub fn add_child(&mut self, child: GameObject) -> usize {
+        self.children.push(child);
+        self.children.len() - 1
+    }
+
+    /// Moves and turns the object by its velocities over `dt` seconds, leaving its children as they are.
+    pub fn update(&mut self, dt: f32) {
+        self.transform.position += self.velocity * dt;
+        self.transform.rotation += self.angular_velocity * dt;
+    }
+
+    /// Updates the components of the object, then moves it, then does the same for every child in turn.
+    pub fn update_tree(&mut self, dt: f32) {
+        self.update_components(dt);
+        self.update(dt);
+        for child in &mut self.children {
+            child.update_tree(dt);
+        }
+    }
+
+    /// Returns the object's transform in world coordinates, given its parent's world transform.
+    ///
+    /// Pass `Transform::IDENTITY` for an object without a parent.
+    pub fn world_transform(&self, parent: &Transform) -> Transform {
+        self.transform.in_parent(parent)
+    }
+
+    /// Places the object at `world`, given its parent's world transform.
+    pub fn set_world_transform(&mut self, world: &Transform, parent: &Transform) {
+        self.transform = world.relative_to(parent);
+    }
+
+    /// Draws the object's drawable at its transform, if it has one, then its children on top.
+    pub fn draw(&self) {
+        self.draw_in(&Transform::IDENTITY);
+    }
+
+    /// Draws the object and its children, given its parent's world transform.
+    pub fn draw_in(&self, parent: &Transform) {
+        let world = self.world_transform(parent);
+        if let Some(drawable) = &self.drawable {
+            drawable.draw(&world);
+        }
+        for child in &self.children {
+            child.draw_in(&world);
+        }
+    }
+}
+
+impl ComponentOwner for GameObject {
+    fn components(&self) -> &[Box<dyn Component<GameObject>>] {
+        &self.components
+    }
+
+    fn components_mut(&mut self) -> &mut Vec<Box<dyn Component<GameObject>>> {
+        &mut self.components
+    }
+}
+
+impl Renderable for GameObject {
+    fn draw_layer(&self) -> i32 {
+        self.draw_layer
+    }
+
+    fn sort_y(&self) -> f32 {
+        self.transform.position.y
+    }
+
+    fn render(&self) {
+        self.draw();
+    }
+}
+
+impl Tagged for GameObject {
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
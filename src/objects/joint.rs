@@ -0,0 +1,214 @@
+use macroquad::color::{Color, WHITE};
+use macroquad::math::{Mat2, Vec2, vec2};
+use macroquad::shapes::draw_circle;
+
+use crate::objects::quad::Quad;
+
+/// Fraction of the joint's drift corrected each step
+const BAUMGARTE: f32 = 0.2;
+
+/// Pins two Quads together at an anchor so they can only rotate around it.
+///
+/// Doors, flippers and ragdoll limbs can be built from rectangles with it.
+/// Hinged Quads never collide with each other in a `PhysicsWorld`.
+/// Anchors are offsets from each Quad's unrotated top-left corner, and each
+/// Quad turns around its pivot, which should stay at its center for the
+/// rotation response to be right.
+///
+/// # Fields
+///
+/// - `quad_a`, `quad_b`: Indices of the joined Quads.
+/// - `anchor_a`, `anchor_b`: Where the joint sits on each Quad.
+/// - `reference_angle`: Rotation of `quad_b` minus `quad_a` that counts as zero.
+/// - `limits`: Smallest and largest allowed angle, relative to `reference_angle`.
+/// - `motor`: Target relative angular velocity and the most torque used to reach it.
+/// - `color`: Color used to draw the anchor.
+///
+/// # Example
+///
+/// ```rust
+/// let hinge = world.hinge_at(frame, door, vec2(200.0, 100.0))
+///     .with_limits(-1.5, 1.5);
+/// world.add_hinge(hinge);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HingeJoint {
+    pub quad_a: usize,
+    pub quad_b: usize,
+    pub anchor_a: Vec2,
+    pub anchor_b: Vec2,
+    pub reference_angle: f32,
+    pub limits: Option<(f32, f32)>,
+    pub motor: Option<(f32, f32)>,
+    pub color: Color,
+}
+
+impl HingeJoint {
+    /// Creates a hinge from anchors already in each Quad's local space.
+    ///
+    /// The current relative angle is zero; use `PhysicsWorld::hinge_at` to
+    /// join Quads at a world position instead.
+    pub fn new(quad_a: usize, anchor_a: Vec2, quad_b: usize, anchor_b: Vec2) -> Self {
+        Self {
+            quad_a,
+            quad_b,
+            anchor_a,
+            anchor_b,
+            reference_angle: 0.0,
+            limits: None,
+            motor: None,
+            color: WHITE,
+        }
+    }
+
+    /// Keeps the relative angle between `min` and `max` radians.
+    pub fn with_limits(mut self, min: f32, max: f32) -> Self {
+        self.limits = Some((min.min(max), min.max(max)));
+        self
+    }
+
+    /// Drives the relative angular velocity toward `speed` radians per second.
+    ///
+    /// `max_torque` caps how much angular velocity per second the motor can add.
+    pub fn with_motor(mut self, speed: f32, max_torque: f32) -> Self {
+        self.motor = Some((speed, max_torque));
+        self
+    }
+
+    /// Returns the rotation of `quad_b` relative to `quad_a`, minus the reference angle.
+    pub fn angle(&self, quads: &[Quad]) -> f32 {
+        match (quads.get(self.quad_a), quads.get(self.quad_b)) {
+            (Some(a), Some(b)) => b.rotation - a.rotation - self.reference_angle,
+            _ => 0.0,
+        }
+    }
+
+    /// Draws the anchor as seen on `quad_a`.
+    pub fn draw(&self, quads: &[Quad]) {
+        if let Some(quad) = quads.get(self.quad_a) {
+            let anchor = quad.to_world(self.anchor_a);
+            draw_circle(anchor.x, anchor.y, 3.0, self.color);
+        }
+    }
+
+    /// Checks that the hinge joins two different Quads out of `quad_count`.
+    pub fn is_valid(&self, quad_count: usize) -> bool {
+        self.quad_a != self.quad_b && self.quad_a < quad_count && self.quad_b < quad_count
+    }
+
+    /// Returns the two joined Quads, or `None` if the hinge isn't valid for `quads`.
+    fn quads_mut<'a>(&self, quads: &'a mut [Quad]) -> Option<(&'a mut Quad, &'a mut Quad)> {
+        if !self.is_valid(quads.len()) {
+            return None;
+        }
+        Some(if self.quad_a < self.quad_b {
+            let (left, right) = quads.split_at_mut(self.quad_b);
+            (&mut left[self.quad_a], &mut right[0])
+        } else {
+            let (left, right) = quads.split_at_mut(self.quad_a);
+            (&mut right[0], &mut left[self.quad_b])
+        })
+    }
+
+    /// Runs the motor for one step, adding at most `max_torque * dt` of relative angular velocity.
+    ///
+    /// Call it once per step, before the `solve` iterations, so the motor's
+    /// strength doesn't depend on the number of iterations.
+    ///
+    /// # Parameters
+    /// - `quads`: All Quads, indexed by `quad_a` and `quad_b`.
+    /// - `dt`: Time step in seconds.
+    pub fn solve_motor(&self, quads: &mut [Quad], dt: f32) {
+        let Some((speed, max_torque)) = self.motor else {
+            return;
+        };
+        let Some((a, b)) = self.quads_mut(quads) else {
+            return;
+        };
+        let (_, inertia_a) = inverse_mass(a);
+        let (_, inertia_b) = inverse_mass(b);
+        let angular_mass = inertia_a + inertia_b;
+        if angular_mass <= 0.0 || dt <= 0.0 {
+            return;
+        }
+        let relative_spin = b.angular_velocity - a.angular_velocity;
+        let max_impulse = max_torque * dt / angular_mass;
+        let impulse = ((speed - relative_spin) / angular_mass).clamp(-max_impulse, max_impulse);
+        a.angular_velocity -= inertia_a * impulse;
+        b.angular_velocity += inertia_b * impulse;
+    }
+
+    /// Applies impulses so the anchors meet and the limits hold.
+    ///
+    /// Hinges whose Quads are the same or out of range are skipped.
+    ///
+    /// # Parameters
+    /// - `quads`: All Quads, indexed by `quad_a` and `quad_b`.
+    /// - `dt`: Time step in seconds.
+    pub fn solve(&self, quads: &mut [Quad], dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+        let Some((a, b)) = self.quads_mut(quads) else {
+            return;
+        };
+        let (mass_a, inertia_a) = inverse_mass(a);
+        let (mass_b, inertia_b) = inverse_mass(b);
+
+        // Keep the relative angle inside the limits
+        let angular_mass = inertia_a + inertia_b;
+        if let (true, Some((min, max))) = (angular_mass > 0.0, self.limits) {
+            let spin = b.angular_velocity - a.angular_velocity;
+            let angle = b.rotation - a.rotation - self.reference_angle;
+            let impulse = if angle <= min {
+                (-(spin + BAUMGARTE * (angle - min) / dt) / angular_mass).max(0.0)
+            } else if angle >= max {
+                (-(spin + BAUMGARTE * (angle - max) / dt) / angular_mass).min(0.0)
+            } else {
+                0.0
+            };
+            a.angular_velocity -= inertia_a * impulse;
+            b.angular_velocity += inertia_b * impulse;
+        }
+
+        // Pull the anchors together
+        let (offset_a, offset_b) = (
+            a.to_world(self.anchor_a) - a.pivot_point(),
+            b.to_world(self.anchor_b) - b.pivot_point(),
+        );
+        let anchor_velocity = |quad: &Quad, offset: Vec2| {
+            vec2(quad.velocity_x, quad.velocity_y) + vec2(-offset.y, offset.x) * quad.angular_velocity
+        };
+        let error = (b.pivot_point() + offset_b) - (a.pivot_point() + offset_a);
+        let relative = anchor_velocity(b, offset_b) - anchor_velocity(a, offset_a);
+        let arm = |offset: Vec2, inertia: f32| {
+            Mat2::from_cols(
+                vec2(offset.y * offset.y, -offset.x * offset.y),
+                vec2(-offset.x * offset.y, offset.x * offset.x),
+            ) * inertia
+        };
+        let effective_mass = Mat2::from_diagonal(Vec2::splat(mass_a + mass_b))
+            + arm(offset_a, inertia_a)
+            + arm(offset_b, inertia_b);
+        if effective_mass.determinant() == 0.0 {
+            return;
+        }
+        let impulse = effective_mass.inverse() * -(relative + error * (BAUMGARTE / dt));
+        a.velocity_x -= impulse.x * mass_a;
+        a.velocity_y -= impulse.y * mass_a;
+        a.angular_velocity -= inertia_a * offset_a.perp_dot(impulse);
+        b.velocity_x += impulse.x * mass_b;
+        b.velocity_y += impulse.y * mass_b;
+        b.angular_velocity += inertia_b * offset_b.perp_dot(impulse);
+    }
+}
+
+/// Inverse mass and inverse moment of inertia, zero for Quads that can't be moved
+fn inverse_mass(quad: &Quad) -> (f32, f32) {
+    if !quad.body_type.is_dynamic() || quad.sleeping || quad.mass <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let (w, h) = quad.size;
+    let inertia = quad.mass * (w * w + h * h) / 12.0;
+    (1.0 / quad.mass, if inertia > 0.0 { 1.0 / inertia } else { 0.0 })
+}
@@ -1,11 +1,32 @@
 pub mod point;
+pub mod circle;
 pub mod constraint;
+pub mod fluid;
+pub mod game_object;
+pub mod joint;
 pub mod quad;
+pub mod polygon;
+pub mod polyline;
+pub mod registry;
 pub mod shapes;
+pub mod terrain;
 pub mod ui;
+pub mod ui_animator;
+pub mod world_text;
 pub use ui::{UiText, UiButton, UiElement};
 
 pub use point::Point;
+pub use circle::CircleBody;
 pub use constraint::Constraint;
+pub use fluid::Fluid;
+pub use game_object::GameObject;
+pub use joint::HingeJoint;
 pub use quad::Quad;
-pub use shapes::{create_triangle, create_square, create_circle, create_line};
+pub use polygon::{Polygon, PolygonCollider};
+pub use polyline::Polyline;
+pub use terrain::TerrainCollider;
+pub use world_text::WorldText;
+pub use shapes::{
+    create_triangle, create_square, create_circle, create_regular_polygon, create_star, create_capsule, create_from_vertices, create_line,
+    create_rope, create_bridge, create_ragdoll, weld,
+};
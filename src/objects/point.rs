@@ -1,6 +1,10 @@
-use macroquad::color::Color;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use macroquad::color::{Color, WHITE};
 use macroquad::shapes::draw_circle;
-use crate::basics::Component;
+use crate::basics::{BodyType, Component, ComponentOwner, Tagged};
+use crate::basics::physics_world::{BodyHandle, ContactPhase};
+use crate::basics::render::Renderable;
 
 /// Represents a physics point that can be connected to other points via constraints
 pub struct Point {
@@ -8,10 +12,23 @@ pub struct Point {
     pub velocity: (f32, f32),
     pub force: (f32, f32),
     pub mass: f32,
+    pub gravity_scale: f32, // Multiplier on the pull of Gravity
     pub radius: f32,
     pub color: Color,
+    pub draw_layer: i32, // Layer a Renderer draws the point on, higher on top
     pub components: Vec<Box<dyn Component<Point>>>,
-    pub fixed: bool, // Whether the point is fixed in space
+    pub body_type: BodyType, // Static points are fixed in space
+    pub layer: Option<String>, // PhysicsConfig layer whose overrides apply to the point
+    pub collision_group: Option<u32>, // Points sharing a group don't collide with each other
+    pub tags: Vec<String>, // Names gameplay code finds the point by
+}
+
+/// Next group handed out by `next_collision_group`, high enough not to clash with hand-picked groups
+static NEXT_COLLISION_GROUP: AtomicU32 = AtomicU32::new(1 << 31);
+
+/// Get a collision group no other caller has been given, for keeping one shape's points apart
+pub fn next_collision_group() -> u32 {
+    NEXT_COLLISION_GROUP.fetch_add(1, Ordering::Relaxed)
 }
 
 impl Point {
@@ -21,13 +38,37 @@ impl Point {
             velocity: (0.0, 0.0),
             force: (0.0, 0.0),
             mass,
+            gravity_scale: 1.0,
             radius,
             color,
+            draw_layer: 0,
             components: Vec::new(),
-            fixed: false,
+            body_type: BodyType::Dynamic,
+            layer: None,
+            collision_group: None,
+            tags: Vec::new(),
         }
     }
 
+    /// Start building a point, for setting only the properties that differ from the defaults
+    ///
+    /// ```rust
+    /// let ball = Point::builder().pos(400.0, 100.0).radius(10.0).color(RED).with(Gravity::new(600.0)).build();
+    /// ```
+    pub fn builder() -> PointBuilder {
+        PointBuilder::default()
+    }
+
+    /// Whether physics can't move the point, because it is static or kinematic
+    pub fn is_fixed(&self) -> bool {
+        !self.body_type.is_dynamic()
+    }
+
+    /// Pin the point in place, or make it dynamic again
+    pub fn set_fixed(&mut self, fixed: bool) {
+        self.body_type = if fixed { BodyType::Static } else { BodyType::Dynamic };
+    }
+
     pub fn draw(&self) {
         draw_circle(
             self.position.0,
@@ -37,16 +78,18 @@ impl Point {
         );
     }
 
-    pub fn add_component(&mut self, comp: Box<dyn Component<Point>>) {
-        self.components.push(comp);
+    /// Call every component's `on_collide` hook with `other`
+    pub fn dispatch_collision(&mut self, other: &mut Point) {
+        self.for_each_component(|comp, me| comp.on_collide(me, other));
     }
 
-    pub fn update_components(&mut self) {
-        let mut comps = std::mem::take(&mut self.components);
-        for comp in comps.iter_mut() {
-            comp.update(self);
-        }
-        self.components = comps;
+    /// Call every component's `on_collision_enter`, `on_collision_stay` or `on_collision_exit` hook with `other`
+    pub fn dispatch_contact(&mut self, other: BodyHandle, phase: ContactPhase) {
+        self.for_each_component(|comp, me| match phase {
+            ContactPhase::Enter => comp.on_collision_enter(me, other),
+            ContactPhase::Stay => comp.on_collision_stay(me, other),
+            ContactPhase::Exit => comp.on_collision_exit(me, other),
+        });
     }
 
     pub fn apply_force(&mut self, fx: f32, fy: f32) {
@@ -55,13 +98,15 @@ impl Point {
     }
 
     pub fn update(&mut self, dt: f32) {
-        if self.fixed {
+        if !self.body_type.moves() {
             return;
         }
 
-        // Update velocity using forces (F = ma)
-        self.velocity.0 += (self.force.0 / self.mass) * dt;
-        self.velocity.1 += (self.force.1 / self.mass) * dt;
+        // Update velocity using forces (F = ma), which kinematic points ignore
+        if self.body_type.is_dynamic() {
+            self.velocity.0 += (self.force.0 / self.mass) * dt;
+            self.velocity.1 += (self.force.1 / self.mass) * dt;
+        }
 
         // Update position
         self.position.0 += self.velocity.0 * dt;
@@ -71,6 +116,11 @@ impl Point {
         self.force = (0.0, 0.0);
     }
 
+    /// Check if the points may collide at all, which they can't when they share a collision group
+    pub fn can_collide_with(&self, other: &Point) -> bool {
+        self.collision_group.is_none() || self.collision_group != other.collision_group
+    }
+
     pub fn is_colliding_with(&self, other: &Point) -> bool {
         let dx = self.position.0 - other.position.0;
         let dy = self.position.1 - other.position.1;
@@ -79,7 +129,7 @@ impl Point {
     }
 
     pub fn resolve_collision(&mut self, other: &mut Point) {
-        if self.fixed && other.fixed {
+        if self.is_fixed() && other.is_fixed() {
             return;
         }
 
@@ -123,11 +173,11 @@ impl Point {
         let impulse_x = impulse_scalar * nx;
         let impulse_y = impulse_scalar * ny;
 
-        if !self.fixed {
+        if !self.is_fixed() {
             self.velocity.0 -= impulse_x / self.mass;
             self.velocity.1 -= impulse_y / self.mass;
         }
-        if !other.fixed {
+        if !other.is_fixed() {
             other.velocity.0 += impulse_x / other.mass;
             other.velocity.1 += impulse_y / other.mass;
         }
@@ -138,13 +188,142 @@ impl Point {
         let correction_x = nx * correction;
         let correction_y = ny * correction;
 
-        if !self.fixed {
+        if !self.is_fixed() {
             self.position.0 -= correction_x;
             self.position.1 -= correction_y;
         }
-        if !other.fixed {
+        if !other.is_fixed() {
             other.position.0 += correction_x;
             other.position.1 += correction_y;
         }
     }
-} 
\ No newline at end of file
+}
+
+impl ComponentOwner for Point {
+    fn components(&self) -> &[Box<dyn Component<Point>>] {
+        &self.components
+    }
+
+    fn components_mut(&mut self) -> &mut Vec<Box<dyn Component<Point>>> {
+        &mut self.components
+    }
+}
+
+impl Renderable for Point {
+    fn draw_layer(&self) -> i32 {
+        self.draw_layer
+    }
+
+    fn sort_y(&self) -> f32 {
+        self.position.1 + self.radius
+    }
+
+    fn render(&self) {
+        self.draw();
+    }
+}
+
+impl Tagged for Point {
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
+
+/// Builds a `Point` one property at a time, attaching components as it goes
+///
+/// Starts as a white, dynamic point at the origin with a mass of 1.0 and a
+/// radius of 5.0. Components are attached by `build` once every property is set.
+pub struct PointBuilder {
+    point: Point,
+    components: Vec<Box<dyn Component<Point>>>,
+}
+
+impl Default for PointBuilder {
+    fn default() -> Self {
+        Self {
+            point: Point::new(0.0, 0.0, 1.0, 5.0, WHITE),
+            components: Vec::new(),
+        }
+    }
+}
+
+impl PointBuilder {
+    /// Set the position
+    pub fn pos(mut self, x: f32, y: f32) -> Self {
+        self.point.position = (x, y);
+        self
+    }
+
+    /// Set the collision radius, which is also the drawn size
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.point.radius = radius;
+        self
+    }
+
+    /// Set the mass
+    pub fn mass(mut self, mass: f32) -> Self {
+        self.point.mass = mass;
+        self
+    }
+
+    /// Set the color
+    pub fn color(mut self, color: Color) -> Self {
+        self.point.color = color;
+        self
+    }
+
+    /// Set the layer a `Renderer` draws the point on
+    pub fn draw_layer(mut self, draw_layer: i32) -> Self {
+        self.point.draw_layer = draw_layer;
+        self
+    }
+
+    /// Set the starting velocity
+    pub fn velocity(mut self, x: f32, y: f32) -> Self {
+        self.point.velocity = (x, y);
+        self
+    }
+
+    /// Set whether the point is static, kinematic or dynamic
+    pub fn body_type(mut self, body_type: BodyType) -> Self {
+        self.point.body_type = body_type;
+        self
+    }
+
+    /// Set the `PhysicsConfig` layer whose overrides apply to the point
+    pub fn layer(mut self, layer: impl Into<String>) -> Self {
+        self.point.layer = Some(layer.into());
+        self
+    }
+
+    /// Set the collision group, whose points don't collide with each other
+    pub fn collision_group(mut self, group: u32) -> Self {
+        self.point.collision_group = Some(group);
+        self
+    }
+
+    /// Add a tag
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.point.add_tag(tag);
+        self
+    }
+
+    /// Attach a component when the point is built
+    pub fn with(mut self, component: impl Component<Point>) -> Self {
+        self.components.push(Box::new(component));
+        self
+    }
+
+    /// Finish the point, attaching its components
+    pub fn build(self) -> Point {
+        let mut point = self.point;
+        for component in self.components {
+            point.add_component(component);
+        }
+        point
+    }
+}
@@ -0,0 +1,511 @@
+use macroquad::color::Color;
+use macroquad::math::{Rect, Vec2, vec2};
+use macroquad::models::{Mesh, Vertex, draw_mesh};
+use macroquad::shapes::{draw_circle, draw_line, draw_triangle};
+
+use crate::basics::{BodyType, Component, ComponentOwner, Tagged};
+use crate::basics::physics_world::{BodyHandle, ContactPhase, TriggerPhase};
+use crate::basics::render::Renderable;
+use crate::objects::circle::CircleBody;
+use crate::objects::quad::{Outline, Quad};
+use crate::utils::gradient::{Gradient, GradientDirection};
+
+/// Represents a solid convex polygon in 2D space.
+///
+/// Irregular level geometry such as ramps and rocks can be one collider
+/// instead of dozens of fixed points. Vertices are relative to `position`,
+/// which is also the point the polygon rotates around.
+///
+/// Only convex polygons collide correctly; split concave shapes into several.
+///
+/// # Fields
+///
+/// - `position`: The (x, y) coordinates of the polygon's origin.
+/// - `vertices`: Corners relative to `position`, in order around the outline.
+/// - `rotation`: Angle in radians, clockwise on screen, around `position`.
+/// - `angular_velocity`: Change in rotation per second.
+/// - `color`: Color used to draw the polygon.
+/// - `draw_layer`: Layer a `Renderer` draws the polygon on, higher on top; 0 by default.
+/// - `velocity_x`, `velocity_y`: Current velocity components along the X and Y axes.
+/// - `mass`: How hard the polygon is to push in collisions; 1.0 by default.
+/// - `restitution`: How bouncy the polygon is, from 0.0 to 1.0.
+/// - `gravity_scale`: Multiplier on the pull of `Gravity`; 1.0 by default.
+/// - `body_type`: Static, kinematic or dynamic; dynamic by default.
+/// - `is_trigger`: Report overlaps as trigger events instead of colliding.
+/// - `can_sleep`: Whether a `PhysicsWorld` may put the polygon to sleep once it rests.
+/// - `sleeping`: Whether the polygon is asleep and skipped by the simulation.
+/// - `idle_steps`: How many steps in a row the polygon has barely moved.
+/// - `layer`: Name of the `PhysicsConfig` layer whose overrides apply to the polygon, if any.
+/// - `tags`: Names gameplay code finds the polygon by, see `Tagged`.
+/// - `components`: A vector of boxed components attached to this polygon.
+///
+/// # Example
+///
+/// ```rust
+/// let ramp = PolygonCollider::new(
+///     400.0,
+///     500.0,
+///     vec![vec2(0.0, 0.0), vec2(200.0, -80.0), vec2(200.0, 0.0)],
+///     GRAY,
+/// );
+/// ```
+pub struct PolygonCollider {
+    pub position: (f32, f32),
+    pub vertices: Vec<Vec2>,
+    pub rotation: f32,
+    pub angular_velocity: f32,
+    pub color: Color,
+    pub draw_layer: i32,
+    pub velocity_x: f32,
+    pub velocity_y: f32,
+    pub mass: f32,
+    pub restitution: f32,
+    pub gravity_scale: f32,
+    pub body_type: BodyType,
+    pub is_trigger: bool,
+    pub can_sleep: bool,
+    pub sleeping: bool,
+    pub idle_steps: u32,
+    pub layer: Option<String>,
+    pub tags: Vec<String>,
+    pub components: Vec<Box<dyn Component<PolygonCollider>>>,
+}
+
+impl PolygonCollider {
+    /// Creates a new polygon with its origin at `x`, `y`.
+    ///
+    /// Velocity starts at zero and no components are attached by default.
+    pub fn new(x: f32, y: f32, vertices: Vec<Vec2>, color: Color) -> Self {
+        Self {
+            position: (x, y),
+            vertices,
+            rotation: 0.0,
+            angular_velocity: 0.0,
+            color,
+            draw_layer: 0,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            mass: 1.0,
+            restitution: 0.0,
+            gravity_scale: 1.0,
+            body_type: BodyType::Dynamic,
+            is_trigger: false,
+            can_sleep: true,
+            sleeping: false,
+            idle_steps: 0,
+            layer: None,
+            tags: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+
+    /// Creates a regular polygon with `sides` corners centered at `x`, `y`.
+    pub fn regular(x: f32, y: f32, radius: f32, sides: usize, color: Color) -> Self {
+        let sides = sides.max(3);
+        let vertices = (0..sides)
+            .map(|i| Vec2::from_angle(i as f32 * std::f32::consts::TAU / sides as f32) * radius)
+            .collect();
+        Self::new(x, y, vertices, color)
+    }
+
+    /// Returns the vertices in world coordinates, after rotation.
+    pub fn world_vertices(&self) -> Vec<Vec2> {
+        let origin = vec2(self.position.0, self.position.1);
+        let rotation = Vec2::from_angle(self.rotation);
+        self.vertices.iter().map(|v| origin + rotation.rotate(*v)).collect()
+    }
+
+    /// Returns the average of the world vertices.
+    pub fn center(&self) -> Vec2 {
+        let vertices = self.world_vertices();
+        vertices.iter().copied().sum::<Vec2>() / vertices.len().max(1) as f32
+    }
+
+    /// Returns the axis-aligned rectangle enclosing the polygon.
+    pub fn bounds(&self) -> Rect {
+        bounding_rect(&self.world_vertices()).unwrap_or(Rect::new(self.position.0, self.position.1, 0.0, 0.0))
+    }
+
+    /// Draws the polygon as a triangle fan.
+    pub fn draw(&self) {
+        let vertices = self.world_vertices();
+        for i in 1..vertices.len().saturating_sub(1) {
+            draw_triangle(vertices[0], vertices[i], vertices[i + 1], self.color);
+        }
+    }
+
+    /// Moves the polygon by its velocity over `dt` seconds. Static polygons never move.
+    pub fn update(&mut self, dt: f32) {
+        if !self.body_type.moves() {
+            return;
+        }
+        self.position.0 += self.velocity_x * dt;
+        self.position.1 += self.velocity_y * dt;
+        self.rotation += self.angular_velocity * dt;
+    }
+
+    /// Wakes the polygon so it is simulated again.
+    pub fn wake(&mut self) {
+        self.sleeping = false;
+        self.idle_steps = 0;
+    }
+
+    /// Counts steps moving slower than `threshold` and puts the polygon to sleep after `steps` of them.
+    ///
+    /// `speed` is how fast the polygon actually moved over the last step, which
+    /// can be slower than its velocity while collisions hold it in place.
+    /// Only dynamic polygons with `can_sleep` set ever fall asleep.
+    pub fn update_sleep(&mut self, speed: f32, threshold: f32, steps: u32) {
+        if !self.can_sleep || !self.body_type.is_dynamic() || self.sleeping {
+            return;
+        }
+        let resting = speed < threshold && self.angular_velocity == 0.0;
+        self.idle_steps = if resting { self.idle_steps + 1 } else { 0 };
+        if self.idle_steps >= steps {
+            self.sleeping = true;
+            self.velocity_x = 0.0;
+            self.velocity_y = 0.0;
+        }
+    }
+
+    /// Checks if this polygon overlaps another polygon.
+    pub fn is_colliding_with(&self, other: &PolygonCollider) -> bool {
+        crate::basics::collision::polygon_overlap(self, other).is_some()
+    }
+
+    /// Checks if this polygon overlaps a Quad, rotated or not.
+    pub fn is_colliding_with_quad(&self, quad: &Quad) -> bool {
+        crate::basics::collision::polygon_quad_overlap(self, quad).is_some()
+    }
+
+    /// Checks if this polygon overlaps a circle.
+    pub fn is_colliding_with_circle(&self, circle: &CircleBody) -> bool {
+        crate::basics::collision::polygon_circle_overlap(self, circle).is_some()
+    }
+
+    /// Calls every component's `on_collide` hook with `other`.
+    pub fn dispatch_collision(&mut self, other: &mut PolygonCollider) {
+        self.for_each_component(|comp, me| comp.on_collide(me, other));
+    }
+
+    /// Calls every component's `on_collide_quad` hook with `quad`.
+    pub fn dispatch_quad_collision(&mut self, quad: &mut Quad) {
+        self.for_each_component(|comp, me| comp.on_collide_quad(me, quad));
+    }
+
+    /// Calls every component's `on_collide_circle` hook with `circle`.
+    pub fn dispatch_circle_collision(&mut self, circle: &mut CircleBody) {
+        self.for_each_component(|comp, me| comp.on_collide_circle(me, circle));
+    }
+
+    /// Calls every component's `on_collision_enter`, `on_collision_stay` or `on_collision_exit` hook with `other`.
+    pub fn dispatch_contact(&mut self, other: BodyHandle, phase: ContactPhase) {
+        self.for_each_component(|comp, me| match phase {
+            ContactPhase::Enter => comp.on_collision_enter(me, other),
+            ContactPhase::Stay => comp.on_collision_stay(me, other),
+            ContactPhase::Exit => comp.on_collision_exit(me, other),
+        });
+    }
+
+    /// Calls every component's `on_trigger_enter` or `on_trigger_exit` hook with `other`.
+    pub fn dispatch_trigger(&mut self, other: BodyHandle, phase: TriggerPhase) {
+        self.for_each_component(|comp, me| match phase {
+            TriggerPhase::Enter => comp.on_trigger_enter(me, other),
+            TriggerPhase::Exit => comp.on_trigger_exit(me, other),
+        });
+    }
+}
+
+impl ComponentOwner for PolygonCollider {
+    fn components(&self) -> &[Box<dyn Component<PolygonCollider>>] {
+        &self.components
+    }
+
+    fn components_mut(&mut self) -> &mut Vec<Box<dyn Component<PolygonCollider>>> {
+        &mut self.components
+    }
+}
+
+impl Renderable for PolygonCollider {
+    fn draw_layer(&self) -> i32 {
+        self.draw_layer
+    }
+
+    fn sort_y(&self) -> f32 {
+        self.bounds().bottom()
+    }
+
+    fn render(&self) {
+        self.draw();
+    }
+}
+
+impl Tagged for PolygonCollider {
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
+
+/// A filled shape of any outline, convex or not, for level geometry and decorations
+///
+/// Unlike `PolygonCollider` it takes no part in physics by itself; get
+/// colliders that match its shape from `colliders`. Vertices are relative to
+/// `position`, which is also the point the polygon rotates around. The
+/// outline must not cross itself.
+///
+/// # Fields
+///
+/// - `position`: The (x, y) coordinates of the polygon's origin.
+/// - `vertices`: Corners relative to `position`, in order around the outline.
+/// - `rotation`: Angle in radians, clockwise on screen, around `position`.
+/// - `color`: Color used to fill the polygon.
+/// - `gradient`: Colors the inside is filled with instead of `color`, if any.
+/// - `gradient_direction`: Axis the gradient runs along, across the polygon's unrotated bounds.
+/// - `outline`: Border drawn centered on the edges, if any.
+/// - `filled`: Whether the inside is drawn; turn it off with an outline to draw only the border.
+/// - `draw_layer`: Layer a `Renderer` draws the polygon on, higher on top; 0 by default.
+/// - `tags`: Names gameplay code finds the polygon by, see `Tagged`.
+///
+/// # Example
+///
+/// ```rust
+/// let hill = Polygon::new(
+///     300.0,
+///     500.0,
+///     vec![vec2(0.0, 0.0), vec2(80.0, -60.0), vec2(140.0, -20.0), vec2(220.0, -90.0), vec2(300.0, 0.0)],
+///     DARKGREEN,
+/// )
+/// .with_outline(BLACK, 2.0);
+/// for collider in hill.colliders() {
+///     world.add_polygon(collider);
+/// }
+/// ```
+pub struct Polygon {
+    pub position: (f32, f32),
+    pub vertices: Vec<Vec2>,
+    pub rotation: f32,
+    pub color: Color,
+    pub gradient: Option<Gradient>,
+    pub gradient_direction: GradientDirection,
+    pub outline: Option<Outline>,
+    pub filled: bool,
+    pub draw_layer: i32,
+    pub tags: Vec<String>,
+}
+
+impl Polygon {
+    /// Creates a filled polygon with its origin at `x`, `y`.
+    pub fn new(x: f32, y: f32, vertices: Vec<Vec2>, color: Color) -> Self {
+        Self {
+            position: (x, y),
+            vertices,
+            rotation: 0.0,
+            color,
+            gradient: None,
+            gradient_direction: GradientDirection::Vertical,
+            outline: None,
+            filled: true,
+            draw_layer: 0,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Set the angle in radians, clockwise on screen, around the origin
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Fill the inside with a gradient running along `direction` instead of the color
+    pub fn with_gradient(mut self, gradient: Gradient, direction: GradientDirection) -> Self {
+        self.gradient = Some(gradient);
+        self.gradient_direction = direction;
+        self
+    }
+
+    /// Draw a border centered on the edges
+    pub fn with_outline(mut self, color: Color, thickness: f32) -> Self {
+        self.outline = Some(Outline::new(color, thickness));
+        self
+    }
+
+    /// Set whether the inside is drawn, so a polygon with an outline can be drawn as a border only
+    pub fn with_filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Returns the vertices in world coordinates, after rotation.
+    pub fn world_vertices(&self) -> Vec<Vec2> {
+        let origin = vec2(self.position.0, self.position.1);
+        let rotation = Vec2::from_angle(self.rotation);
+        self.vertices.iter().map(|v| origin + rotation.rotate(*v)).collect()
+    }
+
+    /// Returns the axis-aligned rectangle enclosing the polygon.
+    pub fn bounds(&self) -> Rect {
+        bounding_rect(&self.world_vertices()).unwrap_or(Rect::new(self.position.0, self.position.1, 0.0, 0.0))
+    }
+
+    /// Static colliders covering the polygon, one if it is convex or else one per triangle
+    ///
+    /// The colliders share the polygon's origin, rotation, color and tags.
+    pub fn colliders(&self) -> Vec<PolygonCollider> {
+        let pieces = if is_convex(&self.vertices) {
+            vec![self.vertices.clone()]
+        } else {
+            triangulate(&self.vertices)
+                .into_iter()
+                .map(|triangle| triangle.map(|i| self.vertices[i]).to_vec())
+                .collect()
+        };
+        pieces
+            .into_iter()
+            .map(|vertices| {
+                let mut collider = PolygonCollider::new(self.position.0, self.position.1, vertices, self.color);
+                collider.rotation = self.rotation;
+                collider.body_type = BodyType::Static;
+                collider.tags = self.tags.clone();
+                collider
+            })
+            .collect()
+    }
+
+    /// Draws the inside as a triangulated mesh, then the outline on top.
+    ///
+    /// A gradient colors each vertex by where it lies across the polygon's
+    /// bounds and blends straight across each triangle, so stops falling
+    /// between vertices are smoothed over.
+    pub fn draw(&self) {
+        let world = self.world_vertices();
+        if world.len() < 3 {
+            return;
+        }
+        if self.filled {
+            let bounds = bounding_rect(&self.vertices).unwrap_or_default();
+            let color = |local: Vec2| match &self.gradient {
+                Some(gradient) => gradient.get_color(match self.gradient_direction {
+                    GradientDirection::Horizontal => (local.x - bounds.x) / bounds.w.max(f32::EPSILON),
+                    GradientDirection::Vertical => (local.y - bounds.y) / bounds.h.max(f32::EPSILON),
+                }),
+                None => self.color,
+            };
+            let vertices = world
+                .iter()
+                .zip(&self.vertices)
+                .map(|(point, &local)| Vertex::new(point.x, point.y, 0.0, 0.0, 0.0, color(local)))
+                .collect();
+            let indices = triangulate(&self.vertices).into_iter().flatten().map(|i| i as u16).collect();
+            draw_mesh(&Mesh {
+                vertices,
+                indices,
+                texture: None,
+            });
+        }
+        if let Some(outline) = self.outline {
+            for (i, &start) in world.iter().enumerate() {
+                let end = world[(i + 1) % world.len()];
+                draw_line(start.x, start.y, end.x, end.y, outline.thickness, outline.color);
+                draw_circle(start.x, start.y, outline.thickness * 0.5, outline.color);
+            }
+        }
+    }
+}
+
+impl Renderable for Polygon {
+    fn draw_layer(&self) -> i32 {
+        self.draw_layer
+    }
+
+    fn sort_y(&self) -> f32 {
+        self.bounds().bottom()
+    }
+
+    fn render(&self) {
+        self.draw();
+    }
+}
+
+impl Tagged for Polygon {
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
+
+/// Splits an outline that doesn't cross itself into triangles, as indices into `vertices`
+///
+/// Uses ear clipping, so concave outlines work in either winding. Whatever
+/// is left once no ear can be found, as happens with crossing outlines, is
+/// split as a fan.
+pub fn triangulate(vertices: &[Vec2]) -> Vec<[usize; 3]> {
+    if vertices.len() < 3 {
+        return Vec::new();
+    }
+    let mut remaining: Vec<usize> = (0..vertices.len()).collect();
+    if signed_area(vertices) < 0.0 {
+        remaining.reverse();
+    }
+    let mut triangles = Vec::with_capacity(vertices.len() - 2);
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let corner = |i: usize| [remaining[(i + count - 1) % count], remaining[i], remaining[(i + 1) % count]];
+        let Some(ear) = (0..count).find(|&i| is_ear(vertices, corner(i), &remaining)) else {
+            break;
+        };
+        triangles.push(corner(ear));
+        remaining.remove(ear);
+    }
+    for i in 1..remaining.len() - 1 {
+        triangles.push([remaining[0], remaining[i], remaining[i + 1]]);
+    }
+    triangles
+}
+
+/// Twice the area enclosed by the outline, positive when it winds counterclockwise in y-up terms
+fn signed_area(vertices: &[Vec2]) -> f32 {
+    (0..vertices.len())
+        .map(|i| vertices[i].perp_dot(vertices[(i + 1) % vertices.len()]))
+        .sum()
+}
+
+/// Check if the corner `b` between `a` and `c` can be cut off without cutting through the rest of the outline
+fn is_ear(vertices: &[Vec2], [a, b, c]: [usize; 3], remaining: &[usize]) -> bool {
+    let (pa, pb, pc) = (vertices[a], vertices[b], vertices[c]);
+    if (pb - pa).perp_dot(pc - pb) <= 0.0 {
+        return false;
+    }
+    remaining.iter().filter(|&&i| i != a && i != b && i != c).all(|&i| {
+        let p = vertices[i];
+        let inside = (pb - pa).perp_dot(p - pa) >= 0.0
+            && (pc - pb).perp_dot(p - pb) >= 0.0
+            && (pa - pc).perp_dot(p - pc) >= 0.0;
+        !inside || p == pa || p == pb || p == pc
+    })
+}
+
+/// Check if every corner of the outline turns the same way
+fn is_convex(vertices: &[Vec2]) -> bool {
+    let turns: Vec<f32> = (0..vertices.len())
+        .map(|i| {
+            let [a, b, c] = [0, 1, 2].map(|k| vertices[(i + k) % vertices.len()]);
+            (b - a).perp_dot(c - b)
+        })
+        .collect();
+    turns.iter().all(|&turn| turn >= 0.0) || turns.iter().all(|&turn| turn <= 0.0)
+}
+
+/// Smallest rectangle holding every point, if there are any
+pub(crate) fn bounding_rect(points: &[Vec2]) -> Option<Rect> {
+    let &first = points.first()?;
+    let min = points.iter().fold(first, |min, p| min.min(*p));
+    let max = points.iter().fold(first, |max, p| max.max(*p));
+    Some(Rect::new(min.x, min.y, max.x - min.x, max.y - min.y))
+}
@@ -0,0 +1,432 @@
+//! Thick lines
+//!
+//! This module provides a line drawn as a mesh, for paths, trails, laser
+//! beams and debug overlays. Unlike `draw_line`, thick lines meet cleanly
+//! at corners instead of leaving gaps.
+//!
+//! # Features
+//! - Thickness set for the whole line or at each point, tapering in between
+//! - Miter, round and bevel joins, with miters beveled past a limit
+//! - Butt, square and round caps
+//! - Dashes, which can be shifted along the line to animate them
+//! - Gradients running along the length of the line
+//!
+//! # Examples
+//! ```rust
+//! use ruty::objects::polyline::{LineCap, LineJoin, Polyline};
+//!
+//! let path = Polyline::new(vec![vec2(100.0, 100.0), vec2(300.0, 160.0), vec2(200.0, 320.0)], 12.0, ORANGE)
+//!     .with_join(LineJoin::Round)
+//!     .with_cap(LineCap::Round)
+//!     .with_dash(24.0, 12.0);
+//! path.draw();
+//!
+//! let trail = Polyline::new(positions, 8.0, WHITE)
+//!     .with_widths(vec![1.0, 4.0, 8.0])
+//!     .with_gradient(Gradient::new(Color::new(1.0, 1.0, 1.0, 0.0), WHITE));
+//! trail.draw();
+//! ```
+
+use macroquad::color::Color;
+use macroquad::math::{Rect, Vec2};
+use macroquad::models::{Mesh, Vertex, draw_mesh};
+
+use crate::basics::Tagged;
+use crate::basics::render::Renderable;
+use crate::objects::polygon::bounding_rect;
+use crate::utils::gradient::Gradient;
+
+/// Most vertices drawn in one mesh, below what macroquad takes in a single draw call
+const MAX_BATCH_VERTICES: usize = 4000;
+/// Most indices drawn in one mesh, below what macroquad takes in a single draw call
+const MAX_BATCH_INDICES: usize = 4800;
+/// Triangles in half a turn of a round join or cap
+const ROUND_SEGMENTS: usize = 8;
+
+/// How a `Polyline` fills the outside of its corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    /// Extend the edges until they meet in a point, or bevel if that is past the miter limit
+    #[default]
+    Miter,
+    /// Round the corner off with an arc
+    Round,
+    /// Cut the corner off straight
+    Bevel,
+}
+
+/// How a `Polyline` ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// End flat at the end point
+    #[default]
+    Butt,
+    /// End flat half the thickness past the end point
+    Square,
+    /// End in a half circle around the end point
+    Round,
+}
+
+/// A thick line through a list of points
+///
+/// # Fields
+///
+/// - `points`: Points the line runs through, in world coordinates.
+/// - `thickness`: Width of the line in pixels, at points without their own width.
+/// - `widths`: Width of the line at each point, tapering along the segments in between.
+/// - `color`: Color used to draw the line.
+/// - `gradient`: Colors running from the start to the end of the line instead of `color`, if any.
+/// - `join`: How segments meet at corners.
+/// - `cap`: How the line, and each of its dashes, ends.
+/// - `miter_limit`: Longest miter, in multiples of half the width, before the corner is beveled instead; 4.0 by default.
+/// - `dash`: Length of each dash and of the gap after it, if the line is dashed.
+/// - `dash_offset`: Distance the dashes are shifted along the line; move it over time to animate them.
+/// - `closed`: Whether the last point joins back to the first.
+/// - `draw_layer`: Layer a `Renderer` draws the line on, higher on top; 0 by default.
+/// - `tags`: Names gameplay code finds the line by, see `Tagged`.
+#[derive(Clone)]
+pub struct Polyline {
+    pub points: Vec<Vec2>,
+    pub thickness: f32,
+    pub widths: Vec<f32>,
+    pub color: Color,
+    pub gradient: Option<Gradient>,
+    pub join: LineJoin,
+    pub cap: LineCap,
+    pub miter_limit: f32,
+    pub dash: Option<(f32, f32)>,
+    pub dash_offset: f32,
+    pub closed: bool,
+    pub draw_layer: i32,
+    pub tags: Vec<String>,
+}
+
+impl Polyline {
+    /// Creates a solid line through `points` with mitered corners and flat ends.
+    pub fn new(points: Vec<Vec2>, thickness: f32, color: Color) -> Self {
+        Self {
+            points,
+            thickness,
+            widths: Vec::new(),
+            color,
+            gradient: None,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: 4.0,
+            dash: None,
+            dash_offset: 0.0,
+            closed: false,
+            draw_layer: 0,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Creates a straight line from `start` to `end`.
+    pub fn line(start: Vec2, end: Vec2, thickness: f32, color: Color) -> Self {
+        Self::new(vec![start, end], thickness, color)
+    }
+
+    /// Set the width at each point, in order; points past the end of `widths` use the thickness
+    pub fn with_widths(mut self, widths: Vec<f32>) -> Self {
+        self.widths = widths;
+        self
+    }
+
+    /// Color the line with a gradient running from its start to its end
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// Set how segments meet at corners
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Set how the line and its dashes end
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Set the longest miter, in multiples of half the width, before corners are beveled
+    pub fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Draw the line as dashes `length` long with `gap` between them
+    pub fn with_dash(mut self, length: f32, gap: f32) -> Self {
+        self.dash = Some((length, gap));
+        self
+    }
+
+    /// Set whether the last point joins back to the first
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Width of the line at point `index`
+    pub fn width_at(&self, index: usize) -> f32 {
+        self.widths.get(index).copied().unwrap_or(self.thickness)
+    }
+
+    /// Returns the length of the line along all its segments.
+    pub fn length(&self) -> f32 {
+        let path = self.path();
+        path.last().map_or(0.0, |stop| stop.distance)
+    }
+
+    /// Returns the axis-aligned rectangle enclosing the line, joins and caps aside.
+    pub fn bounds(&self) -> Rect {
+        let widest = (0..self.points.len()).map(|i| self.width_at(i)).fold(0.0, f32::max);
+        match bounding_rect(&self.points) {
+            Some(rect) => Rect::new(
+                rect.x - widest * 0.5,
+                rect.y - widest * 0.5,
+                rect.w + widest,
+                rect.h + widest,
+            ),
+            None => Rect::default(),
+        }
+    }
+
+    /// Draws the line, dashed if `dash` is set, as triangle meshes.
+    ///
+    /// Overlapping pieces of a translucent line, such as the inside of sharp
+    /// corners, are blended twice and show up darker.
+    pub fn draw(&self) {
+        let path = self.path();
+        let Some(length) = path.last().map(|stop| stop.distance) else {
+            return;
+        };
+        if path.len() < 2 {
+            return;
+        }
+        let color = |distance: f32| match &self.gradient {
+            Some(gradient) => gradient.get_color(distance / length.max(f32::EPSILON)),
+            None => self.color,
+        };
+        let mut batch = MeshBatch::default();
+        match self.dash {
+            Some((dash, gap)) if dash > 0.0 => {
+                let period = dash + gap.max(0.0);
+                let mut start = -self.dash_offset.rem_euclid(period);
+                while start < length {
+                    let run = cut(&path, start.max(0.0), (start + dash).min(length));
+                    self.draw_run(&mut batch, &run, false, &color);
+                    start += period;
+                }
+            }
+            _ => self.draw_run(&mut batch, &path, self.closed, &color),
+        }
+        batch.flush();
+    }
+
+    /// Points of the line with their width and distance along it, skipping repeats
+    ///
+    /// A closed line ends with its first point again.
+    fn path(&self) -> Vec<Stop> {
+        let mut path: Vec<Stop> = Vec::with_capacity(self.points.len() + 1);
+        let closing = self.closed.then_some(0).filter(|_| self.points.len() > 2);
+        for (index, &position) in self.points.iter().enumerate().chain(closing.map(|i| (i, &self.points[i]))) {
+            let distance = match path.last() {
+                Some(last) if last.position.distance(position) <= f32::EPSILON => continue,
+                Some(last) => last.distance + last.position.distance(position),
+                None => 0.0,
+            };
+            path.push(Stop {
+                position,
+                width: self.width_at(index),
+                distance,
+            });
+        }
+        path
+    }
+
+    /// Draws one unbroken stretch of the line, with caps at its ends unless it is a closed loop.
+    fn draw_run(&self, batch: &mut MeshBatch, run: &[Stop], looped: bool, color: &impl Fn(f32) -> Color) {
+        if run.len() < 2 {
+            return;
+        }
+        for pair in run.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let normal = (b.position - a.position).normalize().perp();
+            let (ca, cb) = (color(a.distance), color(b.distance));
+            batch.polygon(&[
+                (a.position + normal * a.width * 0.5, ca),
+                (b.position + normal * b.width * 0.5, cb),
+                (b.position - normal * b.width * 0.5, cb),
+                (a.position - normal * a.width * 0.5, ca),
+            ]);
+        }
+        for i in 1..run.len() - 1 {
+            self.draw_join(batch, run[i - 1].position, run[i], run[i + 1].position, color(run[i].distance));
+        }
+        if looped && run.len() > 2 {
+            // The last stop repeats the first, so the seam joins the last segment to the first
+            let seam = run[run.len() - 1];
+            self.draw_join(batch, run[run.len() - 2].position, seam, run[1].position, color(seam.distance));
+        } else {
+            let (first, last) = (run[0], run[run.len() - 1]);
+            self.draw_cap(batch, first, (first.position - run[1].position).normalize(), color(first.distance));
+            self.draw_cap(batch, last, (last.position - run[run.len() - 2].position).normalize(), color(last.distance));
+        }
+    }
+
+    /// Fills the outside of the corner at `corner` between the segments from `before` and to `after`.
+    fn draw_join(&self, batch: &mut MeshBatch, before: Vec2, corner: Stop, after: Vec2, color: Color) {
+        let incoming = (corner.position - before).normalize();
+        let outgoing = (after - corner.position).normalize();
+        let turn = incoming.perp_dot(outgoing);
+        if turn.abs() <= f32::EPSILON && incoming.dot(outgoing) > 0.0 {
+            return;
+        }
+        // The outside of the corner is on the side the line turns away from
+        let side = if turn > 0.0 { -1.0 } else { 1.0 };
+        let (from, to) = (incoming.perp() * side, outgoing.perp() * side);
+        let half = corner.width * 0.5;
+        let center = corner.position;
+        match self.join {
+            LineJoin::Round => {
+                let sweep = from.perp_dot(to).atan2(from.dot(to));
+                let mut fan = vec![(center, color)];
+                fan.extend(arc(center, half, from.to_angle(), sweep).map(|point| (point, color)));
+                batch.polygon(&fan);
+            }
+            LineJoin::Miter | LineJoin::Bevel => {
+                let bisector = (from + to).normalize_or_zero();
+                let reach = bisector.dot(from);
+                let miter = (self.join == LineJoin::Miter && reach > 1.0 / self.miter_limit.max(1.0))
+                    .then(|| center + bisector * half / reach);
+                let mut corners = vec![(center, color), (center + from * half, color)];
+                corners.extend(miter.map(|tip| (tip, color)));
+                corners.push((center + to * half, color));
+                batch.polygon(&corners);
+            }
+        }
+    }
+
+    /// Draws the cap at `end`, where the line leaves in `direction`.
+    fn draw_cap(&self, batch: &mut MeshBatch, end: Stop, direction: Vec2, color: Color) {
+        let half = end.width * 0.5;
+        let normal = direction.perp();
+        match self.cap {
+            LineCap::Butt => {}
+            LineCap::Square => batch.polygon(&[
+                (end.position + normal * half, color),
+                (end.position + (normal + direction) * half, color),
+                (end.position + (direction - normal) * half, color),
+                (end.position - normal * half, color),
+            ]),
+            LineCap::Round => {
+                let mut fan = vec![(end.position, color)];
+                fan.extend(
+                    arc(end.position, half, (-normal).to_angle(), std::f32::consts::PI).map(|point| (point, color)),
+                );
+                batch.polygon(&fan);
+            }
+        }
+    }
+}
+
+impl Renderable for Polyline {
+    fn draw_layer(&self) -> i32 {
+        self.draw_layer
+    }
+
+    fn sort_y(&self) -> f32 {
+        self.bounds().bottom()
+    }
+
+    fn render(&self) {
+        self.draw();
+    }
+}
+
+impl Tagged for Polyline {
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
+
+/// A point of a line with the width there and its distance from the start
+#[derive(Debug, Clone, Copy)]
+struct Stop {
+    position: Vec2,
+    width: f32,
+    distance: f32,
+}
+
+/// The part of `path` between distances `start` and `end` along it
+fn cut(path: &[Stop], start: f32, end: f32) -> Vec<Stop> {
+    let at = |distance: f32| {
+        let next = path.iter().position(|stop| stop.distance >= distance).unwrap_or(path.len() - 1).max(1);
+        let (a, b) = (path[next - 1], path[next]);
+        let t = ((distance - a.distance) / (b.distance - a.distance).max(f32::EPSILON)).clamp(0.0, 1.0);
+        Stop {
+            position: a.position.lerp(b.position, t),
+            width: a.width + (b.width - a.width) * t,
+            distance,
+        }
+    };
+    let mut run = vec![at(start)];
+    run.extend(path.iter().filter(|stop| stop.distance > start && stop.distance < end).copied());
+    run.push(at(end));
+    run
+}
+
+/// Points along an arc of `radius` around `center`, from angle `start` turning by `sweep`
+fn arc(center: Vec2, radius: f32, start: f32, sweep: f32) -> impl Iterator<Item = Vec2> {
+    let steps = ((sweep.abs() / std::f32::consts::PI * ROUND_SEGMENTS as f32).ceil() as usize).max(1);
+    (0..=steps).map(move |i| center + Vec2::from_angle(start + sweep * i as f32 / steps as f32) * radius)
+}
+
+/// Triangles collected into as few meshes as macroquad can draw
+#[derive(Default)]
+struct MeshBatch {
+    vertices: Vec<Vertex>,
+    indices: Vec<u16>,
+}
+
+impl MeshBatch {
+    /// Add a convex polygon, fanned out from its first corner
+    fn polygon(&mut self, corners: &[(Vec2, Color)]) {
+        if corners.len() < 3 {
+            return;
+        }
+        let triangles = corners.len() - 2;
+        if self.vertices.len() + corners.len() > MAX_BATCH_VERTICES
+            || self.indices.len() + triangles * 3 > MAX_BATCH_INDICES
+        {
+            self.flush();
+        }
+        let first = self.vertices.len() as u16;
+        self.vertices.extend(
+            corners
+                .iter()
+                .map(|&(point, color)| Vertex::new(point.x, point.y, 0.0, 0.0, 0.0, color)),
+        );
+        for i in 1..=triangles as u16 {
+            self.indices.extend([first, first + i, first + i + 1]);
+        }
+    }
+
+    /// Draw everything added so far and start over
+    fn flush(&mut self) {
+        if self.indices.is_empty() {
+            return;
+        }
+        draw_mesh(&Mesh {
+            vertices: std::mem::take(&mut self.vertices),
+            indices: std::mem::take(&mut self.indices),
+            texture: None,
+        });
+    }
+}
@@ -1,7 +1,17 @@
-use macroquad::color::Color;
-use macroquad::shapes::draw_rectangle;
+use macroquad::color::{Color, WHITE};
+use std::f32::consts::{FRAC_PI_2, PI};
 
-use crate::basics::Component;
+use macroquad::math::{Rect, Vec2, vec2};
+use macroquad::models::{Mesh, Vertex, draw_mesh};
+use macroquad::shapes::{DrawRectangleParams, draw_rectangle, draw_rectangle_ex};
+use macroquad::texture::{DrawTextureParams, Texture2D, draw_texture_ex};
+
+use crate::basics::collision::Segment;
+use crate::basics::{BodyType, Component, ComponentOwner, Tagged};
+use crate::basics::physics_world::{BodyHandle, ContactPhase, TriggerPhase};
+use crate::basics::render::Renderable;
+use crate::basics::transform::Transform;
+use crate::utils::gradient::{Gradient, GradientDirection};
 
 /// Represents a basic rectangular game object in 2D space.
 ///
@@ -16,22 +26,67 @@ use crate::basics::Component;
 ///
 /// - `position`: The (x, y) coordinates of the top-left corner of the Quad.
 /// - `size`: Width and height of the Quad.
-/// - `color`: Color used to draw the Quad.
+/// - `color`: Color used to draw the Quad, or to tint its texture.
+/// - `texture`: Image stretched over the Quad instead of a plain rectangle, if any.
+/// - `source_rect`: Part of the texture drawn, in pixels, or all of it if `None`.
+/// - `corner_radius`: Radius of the rounded corners, at most half the shorter side; 0.0 for square corners.
+/// - `gradient`: Colors the inside is filled with instead of `color`, if any; a texture still wins.
+/// - `gradient_direction`: Axis of the Quad the gradient runs along; top to bottom by default.
+/// - `outline`: Border drawn just inside the Quad's edges, if any.
+/// - `filled`: Whether the inside is drawn; turn it off with an outline to draw only the border.
+/// - `draw_layer`: Layer a `Renderer` draws the Quad on, higher on top; 0 by default.
 /// - `velocity_x`, `velocity_y`: Current velocity components along the X and Y axes.
+/// - `rotation`: Angle in radians, clockwise on screen, around the pivot.
+/// - `angular_velocity`: Change in rotation per second.
+/// - `pivot`: Point the Quad rotates around, relative to its size, so
+///   `(0.5, 0.5)` is the center and `(0.0, 0.0)` the top-left corner.
+/// - `mass`: How hard the Quad is to push in collisions; 1.0 by default.
+/// - `restitution`: How bouncy the Quad is, from 0.0 to 1.0.
+/// - `gravity_scale`: Multiplier on the pull of `Gravity`; 1.0 by default.
+/// - `body_type`: Static, kinematic or dynamic; dynamic by default.
+/// - `is_trigger`: Report overlaps as trigger events instead of colliding.
+/// - `can_sleep`: Whether a `PhysicsWorld` may put the Quad to sleep once it rests.
+/// - `sleeping`: Whether the Quad is asleep and skipped by the simulation.
+/// - `active`: Whether the Quad takes part in a `PhysicsWorld` at all; recycled bodies wait inactive to be reused.
+/// - `idle_steps`: How many steps in a row the Quad has barely moved.
+/// - `layer`: Name of the `PhysicsConfig` layer whose overrides apply to the Quad, if any.
+/// - `tags`: Names gameplay code finds the Quad by, see `Tagged`.
 /// - `components`: A vector of boxed components attached to this Quad.
 ///
 /// # Example
 ///
 /// ```rust
 /// let mut quad = Quad::new(100.0, 200.0, 50.0, 50.0, WHITE);
-/// quad.add_component(Box::new(Gravity::new(0.5)));
+/// quad.add_component(Box::new(Gravity::new(30.0)));
 /// ```
 pub struct Quad {
     pub position: (f32, f32),
     pub size: (f32, f32),
     pub color: Color,
+    pub texture: Option<Texture2D>,
+    pub source_rect: Option<Rect>,
+    pub corner_radius: f32,
+    pub gradient: Option<Gradient>,
+    pub gradient_direction: GradientDirection,
+    pub outline: Option<Outline>,
+    pub filled: bool,
+    pub draw_layer: i32,
     pub velocity_x: f32,
     pub velocity_y: f32,
+    pub rotation: f32,
+    pub angular_velocity: f32,
+    pub pivot: (f32, f32),
+    pub mass: f32,
+    pub restitution: f32,
+    pub gravity_scale: f32,
+    pub body_type: BodyType,
+    pub is_trigger: bool,
+    pub can_sleep: bool,
+    pub sleeping: bool,
+    pub active: bool,
+    pub idle_steps: u32,
+    pub layer: Option<String>,
+    pub tags: Vec<String>,
     pub components: Vec<Box<dyn Component<Quad>>>,
 }
 
@@ -52,54 +107,414 @@ impl Quad {
             position: (x, y),
             size: (w, h),
             color,
+            texture: None,
+            source_rect: None,
+            corner_radius: 0.0,
+            gradient: None,
+            gradient_direction: GradientDirection::Vertical,
+            outline: None,
+            filled: true,
+            draw_layer: 0,
             velocity_x: 0.0,
             velocity_y: 0.0,
+            rotation: 0.0,
+            angular_velocity: 0.0,
+            pivot: (0.5, 0.5),
+            mass: 1.0,
+            restitution: 0.0,
+            gravity_scale: 1.0,
+            body_type: BodyType::Dynamic,
+            is_trigger: false,
+            can_sleep: true,
+            sleeping: false,
+            active: true,
+            idle_steps: 0,
+            layer: None,
+            tags: Vec::new(),
             components: Vec::new(),
         }
     }
 
-    /// Draws the Quad on the screen using macroquad's rectangle drawing.
+    /// Starts building a Quad, for setting only the properties that differ from the defaults.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let crate_box = Quad::builder()
+    ///     .pos(100.0, 200.0)
+    ///     .size(50.0, 50.0)
+    ///     .color(RED)
+    ///     .with(Gravity::new(600.0))
+    ///     .with(Collision::new(0.5, 0.8))
+    ///     .build();
+    /// ```
+    pub fn builder() -> QuadBuilder {
+        QuadBuilder::default()
+    }
+
+    /// Set the angle in radians, clockwise on screen, around the pivot
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the point the Quad rotates around, relative to its size
+    pub fn with_pivot(mut self, x: f32, y: f32) -> Self {
+        self.pivot = (x, y);
+        self
+    }
+
+    /// Draw an image stretched over the Quad, tinted by its color
+    pub fn with_texture(mut self, texture: Texture2D) -> Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// Round the corners off with `radius`
+    pub fn with_corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
+    /// Fill the inside with a gradient running along `direction` instead of the color
+    pub fn with_gradient(mut self, gradient: Gradient, direction: GradientDirection) -> Self {
+        self.gradient = Some(gradient);
+        self.gradient_direction = direction;
+        self
+    }
+
+    /// Draw a border just inside the Quad's edges
+    pub fn with_outline(mut self, color: Color, thickness: f32) -> Self {
+        self.outline = Some(Outline::new(color, thickness));
+        self
+    }
+
+    /// Set whether the inside is drawn, so a Quad with an outline can be drawn as a border only
+    pub fn with_filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draws the Quad on the screen, turned by its rotation around its pivot.
     ///
-    /// This is a basic render method — you can extend it later to support sprites,
-    /// animations, or GUI elements.
+    /// Quads with a texture draw it stretched to their size and tinted by
+    /// their color, so use `WHITE` to keep the image's own colors. Without
+    /// one, the gradient fills the Quad if set. Rounded corners clip all of
+    /// these, and the outline, if any, is drawn on top following them.
     pub fn draw(&self) {
-        draw_rectangle(
-            self.position.0,
-            self.position.1,
+        if self.size.0 <= 0.0 || self.size.1 <= 0.0 {
+            return;
+        }
+        if self.filled {
+            self.draw_fill();
+        }
+        if let Some(outline) = self.outline {
+            self.draw_outline(outline);
+        }
+    }
+
+    /// Draws the inside of the Quad, its texture if it has one, else its gradient, else its color
+    fn draw_fill(&self) {
+        let radius = self.rounding();
+        if let (None, Some(gradient)) = (&self.texture, &self.gradient) {
+            self.draw_gradient(gradient, radius);
+            return;
+        }
+        if radius > 0.0 {
+            self.draw_rounded(radius);
+            return;
+        }
+        if let Some(texture) = &self.texture {
+            draw_texture_ex(
+                texture,
+                self.position.0,
+                self.position.1,
+                self.color,
+                DrawTextureParams {
+                    dest_size: Some(vec2(self.size.0, self.size.1)),
+                    rotation: self.rotation,
+                    source: self.source_rect,
+                    pivot: Some(self.pivot_point()),
+                    ..Default::default()
+                },
+            );
+            return;
+        }
+        if self.rotation == 0.0 {
+            draw_rectangle(
+                self.position.0,
+                self.position.1,
+                self.size.0,
+                self.size.1,
+                self.color,
+            );
+            return;
+        }
+        let pivot = self.pivot_point();
+        draw_rectangle_ex(
+            pivot.x,
+            pivot.y,
             self.size.0,
             self.size.1,
-            self.color,
+            DrawRectangleParams {
+                offset: vec2(self.pivot.0, self.pivot.1),
+                rotation: self.rotation,
+                color: self.color,
+            },
         );
     }
 
-    /// Adds a component to the Quad's component list.
+    /// Draws the Quad's shape filled with its color or texture, as a fan from the center
+    fn draw_rounded(&self, radius: f32) {
+        let (w, h) = self.size;
+        let uv_rect = match (&self.texture, self.source_rect) {
+            (Some(texture), Some(source)) => Rect::new(
+                source.x / texture.width(),
+                source.y / texture.height(),
+                source.w / texture.width(),
+                source.h / texture.height(),
+            ),
+            _ => Rect::new(0.0, 0.0, 1.0, 1.0),
+        };
+        let vertex = |local: Vec2| {
+            let world = self.to_world(local);
+            let u = uv_rect.x + local.x / w * uv_rect.w;
+            let v = uv_rect.y + local.y / h * uv_rect.h;
+            Vertex::new(world.x, world.y, 0.0, u, v, self.color)
+        };
+        let outline = self.local_outline(0.0, radius, CORNER_SEGMENTS);
+        let mut vertices = vec![vertex(vec2(w, h) * 0.5)];
+        vertices.extend(outline.iter().map(|&local| vertex(local)));
+        let n = outline.len() as u16;
+        let indices = (0..n).flat_map(|i| [0, i + 1, (i + 1) % n + 1]).collect();
+        draw_mesh(&Mesh {
+            vertices,
+            indices,
+            texture: self.texture.clone(),
+        });
+    }
+
+    /// Draws the Quad's shape in bands across the gradient's direction, each edge colored from the gradient
+    fn draw_gradient(&self, gradient: &Gradient, radius: f32) {
+        let (w, h) = self.size;
+        let (length, across) = match self.gradient_direction {
+            GradientDirection::Horizontal => (w, h),
+            GradientDirection::Vertical => (h, w),
+        };
+        let bands = (length.ceil() as usize).clamp(1, MAX_GRADIENT_BANDS);
+        let mut vertices = Vec::with_capacity((bands + 1) * 2);
+        for i in 0..=bands {
+            let along = length * i as f32 / bands as f32;
+            let inset = corner_inset(along, length, radius);
+            let color = gradient.get_color(along / length);
+            for side in [inset, across - inset] {
+                let local = match self.gradient_direction {
+                    GradientDirection::Horizontal => vec2(along, side),
+                    GradientDirection::Vertical => vec2(side, along),
+                };
+                let world = self.to_world(local);
+                vertices.push(Vertex::new(world.x, world.y, 0.0, 0.0, 0.0, color));
+            }
+        }
+        let indices = (0..bands as u16)
+            .flat_map(|i| {
+                let a = i * 2;
+                [a, a + 1, a + 3, a, a + 3, a + 2]
+            })
+            .collect();
+        draw_mesh(&Mesh {
+            vertices,
+            indices,
+            texture: None,
+        });
+    }
+
+    /// Draws a border just inside the edges, rotated with the Quad and following its rounded corners
+    fn draw_outline(&self, outline: Outline) {
+        let (w, h) = self.size;
+        let thickness = outline.thickness.min(w * 0.5).min(h * 0.5).max(0.0);
+        let radius = self.rounding();
+        let segments = if radius > 0.0 { CORNER_SEGMENTS } else { 0 };
+        let outer = self.local_outline(0.0, radius, segments);
+        let inner = self.local_outline(thickness, (radius - thickness).max(0.0), segments);
+        let vertices = outer
+            .iter()
+            .chain(&inner)
+            .map(|&local| {
+                let world = self.to_world(local);
+                Vertex::new(world.x, world.y, 0.0, 0.0, 0.0, outline.color)
+            })
+            .collect();
+        let n = outer.len() as u16;
+        let indices = (0..n)
+            .flat_map(|a| {
+                let b = (a + 1) % n;
+                [a, b, n + b, a, n + b, n + a]
+            })
+            .collect();
+        draw_mesh(&Mesh {
+            vertices,
+            indices,
+            texture: None,
+        });
+    }
+
+    /// Returns the corner radius, limited to half the shorter side.
+    fn rounding(&self) -> f32 {
+        self.corner_radius.clamp(0.0, self.size.0.min(self.size.1) * 0.5)
+    }
+
+    /// Outline of the Quad shrunk by `inset`, with corners of `radius` made of `segments` pieces each
     ///
-    /// Components implement custom behavior, such as physics or input handling.
-    /// They are stored as trait objects (`Box<dyn Component>`) for dynamic dispatch.
+    /// Points go clockwise on screen from the left end of the top-left
+    /// corner, relative to the unrotated top-left corner of the Quad.
+    fn local_outline(&self, inset: f32, radius: f32, segments: usize) -> Vec<Vec2> {
+        let (w, h) = self.size;
+        let offset = inset + radius;
+        let centers = [
+            vec2(offset, offset),
+            vec2(w - offset, offset),
+            vec2(w - offset, h - offset),
+            vec2(offset, h - offset),
+        ];
+        centers
+            .into_iter()
+            .enumerate()
+            .flat_map(|(corner, center)| {
+                (0..=segments).map(move |i| {
+                    let angle = PI + FRAC_PI_2 * (corner as f32 + i as f32 / segments.max(1) as f32);
+                    center + Vec2::from_angle(angle) * radius
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the point the Quad rotates around, in world coordinates.
+    pub fn pivot_point(&self) -> Vec2 {
+        vec2(
+            self.position.0 + self.size.0 * self.pivot.0,
+            self.position.1 + self.size.1 * self.pivot.1,
+        )
+    }
+
+    /// Returns the Quad's pivot point and rotation as a transform.
+    pub fn transform(&self) -> Transform {
+        Transform::new(self.pivot_point()).with_rotation(self.rotation)
+    }
+
+    /// Moves the Quad so its pivot point and rotation match `transform`, ignoring its scale.
+    pub fn set_transform(&mut self, transform: &Transform) {
+        let pivot = vec2(self.size.0 * self.pivot.0, self.size.1 * self.pivot.1);
+        let position = transform.position - pivot;
+        self.position = (position.x, position.y);
+        self.rotation = transform.rotation;
+    }
+
+    /// Converts a point relative to the unrotated top-left corner into world coordinates.
+    pub fn to_world(&self, local: Vec2) -> Vec2 {
+        let point = vec2(self.position.0, self.position.1) + local;
+        if self.rotation == 0.0 {
+            return point;
+        }
+        let pivot = self.pivot_point();
+        pivot + Vec2::from_angle(self.rotation).rotate(point - pivot)
+    }
+
+    /// Converts a world position into a point relative to the unrotated top-left corner.
+    pub fn to_local(&self, world: Vec2) -> Vec2 {
+        let pivot = self.pivot_point();
+        let point = pivot + Vec2::from_angle(-self.rotation).rotate(world - pivot);
+        point - vec2(self.position.0, self.position.1)
+    }
+
+    /// Returns the four corners after rotation, clockwise from the top-left.
+    pub fn corners(&self) -> [Vec2; 4] {
+        let (x, y, w, h) = (self.position.0, self.position.1, self.size.0, self.size.1);
+        let corners = [vec2(x, y), vec2(x + w, y), vec2(x + w, y + h), vec2(x, y + h)];
+        if self.rotation == 0.0 {
+            return corners;
+        }
+        let pivot = self.pivot_point();
+        let rotation = Vec2::from_angle(self.rotation);
+        corners.map(|corner| pivot + rotation.rotate(corner - pivot))
+    }
+
+    /// Returns the center of the Quad after rotation.
+    pub fn center(&self) -> Vec2 {
+        let [top_left, _, bottom_right, _] = self.corners();
+        (top_left + bottom_right) * 0.5
+    }
+
+    /// Returns the axis-aligned rectangle enclosing the rotated Quad.
+    pub fn bounds(&self) -> Rect {
+        if self.rotation == 0.0 {
+            return Rect::new(self.position.0, self.position.1, self.size.0, self.size.1);
+        }
+        let corners = self.corners();
+        let min = corners.iter().fold(corners[0], |min, c| min.min(*c));
+        let max = corners.iter().fold(corners[0], |max, c| max.max(*c));
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    /// Moves the Quad by its velocity over `dt` seconds. Static Quads never move.
     ///
     /// # Parameters
-    /// - `comp`: A boxed component to add.
-    pub fn add_component(&mut self, comp: Box<dyn Component<Quad>>) {
-        self.components.push(comp);
+    /// - `dt`: Time step in seconds.
+    pub fn update(&mut self, dt: f32) {
+        if !self.body_type.moves() {
+            return;
+        }
+        self.position.0 += self.velocity_x * dt;
+        self.position.1 += self.velocity_y * dt;
+        self.rotation += self.angular_velocity * dt;
     }
 
-    /// Updates all attached components by calling their `update` method.
-    ///
-    /// This allows components to modify the Quad's state (e.g., velocity, position)
-    /// each frame.
+    /// Wakes the Quad so it is simulated again.
+    pub fn wake(&mut self) {
+        self.sleeping = false;
+        self.idle_steps = 0;
+    }
+
+    /// Counts steps moving slower than `threshold` and puts the Quad to sleep after `steps` of them.
     ///
-    /// Usually called once per game loop iteration.
-    pub fn update_components(&mut self) {
-        let mut comps = std::mem::take(&mut self.components);
-        for comp in comps.iter_mut() {
-            comp.update(self);
+    /// `speed` is how fast the Quad actually moved over the last step, which
+    /// can be slower than its velocity while collisions hold it in place.
+    /// Only dynamic Quads with `can_sleep` set ever fall asleep.
+    pub fn update_sleep(&mut self, speed: f32, threshold: f32, steps: u32) {
+        if !self.can_sleep || !self.body_type.is_dynamic() || self.sleeping {
+            return;
         }
-        self.components = comps;
+        let resting = speed < threshold && self.angular_velocity == 0.0;
+        self.idle_steps = if resting { self.idle_steps + 1 } else { 0 };
+        if self.idle_steps >= steps {
+            self.sleeping = true;
+            self.velocity_x = 0.0;
+            self.velocity_y = 0.0;
+        }
+    }
+
+    /// Calls every component's `on_collide` hook with `other`.
+    ///
+    /// Components are taken out while they run, just like in `update_components`.
+    ///
+    /// # Parameters
+    /// - `other`: The Quad this one collided with.
+    pub fn dispatch_collision(&mut self, other: &mut Quad) {
+        self.for_each_component(|comp, me| comp.on_collide(me, other));
+    }
+
+    /// Calls every component's `on_collide_segment` hook with `segment`.
+    ///
+    /// # Parameters
+    /// - `segment`: The segment of terrain this Quad touched.
+    pub fn dispatch_segment_collision(&mut self, segment: &Segment) {
+        self.for_each_component(|comp, me| comp.on_collide_segment(me, segment));
     }
 
     /// Checks if this Quad is colliding with another Quad.
     ///
-    /// Collision detection is based on Axis-Aligned Bounding Box (AABB) overlap.
+    /// Collision detection is based on Axis-Aligned Bounding Box (AABB) overlap,
+    /// or on the Separating Axis Theorem when either Quad is rotated.
     ///
     /// # Parameters
     /// - `other`: The other Quad to check collision against.
@@ -107,6 +522,9 @@ impl Quad {
     /// # Returns
     /// `true` if the bounding boxes overlap, else `false`.
     pub fn is_colliding_with(&self, other: &Quad) -> bool {
+        if self.rotation != 0.0 || other.rotation != 0.0 {
+            return crate::basics::collision::obb_overlap(self, other).is_some();
+        }
         let (x1, y1, w1, h1) = (self.position.0, self.position.1, self.size.0, self.size.1);
         let (x2, y2, w2, h2) = (
             other.position.0,
@@ -118,15 +536,229 @@ impl Quad {
         x1 < x2 + w2 && x1 + w1 > x2 && y1 < y2 + h2 && y1 + h1 > y2
     }
 
-    /// Removes a component of a specific type from the Quad.
-    ///
-    /// Uses Rust's type system and `TypeId` to identify the component to remove.
-    ///
-    /// # Type Parameters
-    /// - `T`: The component type to remove.
-    pub fn remove_component<T: 'static>(&mut self) {
-        self.components.retain(|comp| {
-            std::any::TypeId::of::<T>() != std::any::TypeId::of::<Box<dyn Component<Quad>>>()
+    /// Calls every component's `on_collision_enter`, `on_collision_stay` or `on_collision_exit` hook with `other`.
+    pub fn dispatch_contact(&mut self, other: BodyHandle, phase: ContactPhase) {
+        self.for_each_component(|comp, me| match phase {
+            ContactPhase::Enter => comp.on_collision_enter(me, other),
+            ContactPhase::Stay => comp.on_collision_stay(me, other),
+            ContactPhase::Exit => comp.on_collision_exit(me, other),
+        });
+    }
+
+    /// Calls every component's `on_trigger_enter` or `on_trigger_exit` hook with `other`.
+    pub fn dispatch_trigger(&mut self, other: BodyHandle, phase: TriggerPhase) {
+        self.for_each_component(|comp, me| match phase {
+            TriggerPhase::Enter => comp.on_trigger_enter(me, other),
+            TriggerPhase::Exit => comp.on_trigger_exit(me, other),
         });
     }
 }
+
+impl ComponentOwner for Quad {
+    fn components(&self) -> &[Box<dyn Component<Quad>>] {
+        &self.components
+    }
+
+    fn components_mut(&mut self) -> &mut Vec<Box<dyn Component<Quad>>> {
+        &mut self.components
+    }
+}
+
+impl Renderable for Quad {
+    fn draw_layer(&self) -> i32 {
+        self.draw_layer
+    }
+
+    fn sort_y(&self) -> f32 {
+        self.bounds().bottom()
+    }
+
+    fn render(&self) {
+        self.draw();
+    }
+}
+
+impl Tagged for Quad {
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
+
+/// Pieces each rounded corner of a Quad is drawn with
+const CORNER_SEGMENTS: usize = 8;
+
+/// Most bands a gradient fill is drawn in, keeping it within one draw call
+const MAX_GRADIENT_BANDS: usize = 256;
+
+/// How far the side of a shape with corners of `radius` is pulled in, `along` its `length`
+fn corner_inset(along: f32, length: f32, radius: f32) -> f32 {
+    let into_corner = (radius - along).max(along - (length - radius)).max(0.0);
+    radius - (radius * radius - into_corner * into_corner).max(0.0).sqrt()
+}
+
+/// Border drawn along a shape's edges
+///
+/// Quads draw it just inside their edges, polygons centered on theirs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Outline {
+    pub color: Color,
+    /// Width of the border in pixels, at most half the shorter side of a Quad
+    pub thickness: f32,
+}
+
+impl Outline {
+    /// Create an outline
+    pub fn new(color: Color, thickness: f32) -> Self {
+        Self { color, thickness }
+    }
+}
+
+/// Builds a `Quad` one property at a time, attaching components as it goes
+///
+/// Starts as a white, dynamic Quad of size zero at the origin. Components
+/// are attached by `build` in the order given, once every property is set,
+/// so their `on_attach` hooks see the finished Quad.
+pub struct QuadBuilder {
+    quad: Quad,
+    components: Vec<Box<dyn Component<Quad>>>,
+}
+
+impl Default for QuadBuilder {
+    fn default() -> Self {
+        Self {
+            quad: Quad::new(0.0, 0.0, 0.0, 0.0, WHITE),
+            components: Vec::new(),
+        }
+    }
+}
+
+impl QuadBuilder {
+    /// Set the position of the top-left corner
+    pub fn pos(mut self, x: f32, y: f32) -> Self {
+        self.quad.position = (x, y);
+        self
+    }
+
+    /// Set the width and height
+    pub fn size(mut self, w: f32, h: f32) -> Self {
+        self.quad.size = (w, h);
+        self
+    }
+
+    /// Set the color, or the tint of the texture
+    pub fn color(mut self, color: Color) -> Self {
+        self.quad.color = color;
+        self
+    }
+
+    /// Set the image stretched over the Quad
+    pub fn texture(mut self, texture: Texture2D) -> Self {
+        self.quad.texture = Some(texture);
+        self
+    }
+
+    /// Round the corners off with `radius`
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.quad.corner_radius = radius;
+        self
+    }
+
+    /// Fill the inside with a gradient running along `direction` instead of the color
+    pub fn gradient(mut self, gradient: Gradient, direction: GradientDirection) -> Self {
+        self.quad.gradient = Some(gradient);
+        self.quad.gradient_direction = direction;
+        self
+    }
+
+    /// Draw a border just inside the Quad's edges
+    pub fn outline(mut self, color: Color, thickness: f32) -> Self {
+        self.quad.outline = Some(Outline::new(color, thickness));
+        self
+    }
+
+    /// Set whether the inside is drawn, so a Quad with an outline can be drawn as a border only
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.quad.filled = filled;
+        self
+    }
+
+    /// Set the layer a `Renderer` draws the Quad on
+    pub fn draw_layer(mut self, draw_layer: i32) -> Self {
+        self.quad.draw_layer = draw_layer;
+        self
+    }
+
+    /// Set the starting velocity
+    pub fn velocity(mut self, x: f32, y: f32) -> Self {
+        self.quad.velocity_x = x;
+        self.quad.velocity_y = y;
+        self
+    }
+
+    /// Set the angle in radians, clockwise on screen, around the pivot
+    pub fn rotation(mut self, rotation: f32) -> Self {
+        self.quad.rotation = rotation;
+        self
+    }
+
+    /// Set the point the Quad rotates around, relative to its size
+    pub fn pivot(mut self, x: f32, y: f32) -> Self {
+        self.quad.pivot = (x, y);
+        self
+    }
+
+    /// Set how hard the Quad is to push in collisions
+    pub fn mass(mut self, mass: f32) -> Self {
+        self.quad.mass = mass;
+        self
+    }
+
+    /// Set how bouncy the Quad is, from 0.0 to 1.0
+    pub fn restitution(mut self, restitution: f32) -> Self {
+        self.quad.restitution = restitution;
+        self
+    }
+
+    /// Set whether the Quad is static, kinematic or dynamic
+    pub fn body_type(mut self, body_type: BodyType) -> Self {
+        self.quad.body_type = body_type;
+        self
+    }
+
+    /// Report overlaps as trigger events instead of colliding
+    pub fn trigger(mut self, is_trigger: bool) -> Self {
+        self.quad.is_trigger = is_trigger;
+        self
+    }
+
+    /// Set the `PhysicsConfig` layer whose overrides apply to the Quad
+    pub fn layer(mut self, layer: impl Into<String>) -> Self {
+        self.quad.layer = Some(layer.into());
+        self
+    }
+
+    /// Add a tag
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.quad.add_tag(tag);
+        self
+    }
+
+    /// Attach a component when the Quad is built
+    pub fn with(mut self, component: impl Component<Quad>) -> Self {
+        self.components.push(Box::new(component));
+        self
+    }
+
+    /// Finish the Quad, attaching its components
+    pub fn build(self) -> Quad {
+        let mut quad = self.quad;
+        for component in self.components {
+            quad.add_component(component);
+        }
+        quad
+    }
+}
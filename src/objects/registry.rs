@@ -0,0 +1,309 @@
+//! Entity registry
+//!
+//! This module provides a registry of entities whose components are kept in
+//! one column per component type, for games with too many objects for a
+//! boxed component list on each. Small projects can keep using `Quad`,
+//! `Point` and `GameObject` with their own components.
+//!
+//! # Features
+//! - Entity ids that go stale once the entity is despawned, even after the slot is reused
+//! - Any `'static` type as a component, at most one of each type per entity
+//! - Typed queries over every entity that has all the asked components, shared or mutable
+//!
+//! # Examples
+//! ```rust
+//! use ruty::objects::registry::Registry;
+//!
+//! let mut registry = Registry::new();
+//! let player = registry.spawn();
+//! registry.insert(player, Quad::new(100.0, 100.0, 32.0, 32.0, WHITE));
+//! registry.insert(player, Gravity::new(600.0));
+//!
+//! let dt = get_frame_time();
+//! for (_, (quad, gravity)) in registry.query::<(&mut Quad, &Gravity)>() {
+//!     quad.velocity_y += gravity.acceleration().y * dt;
+//! }
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Id of an entity in a `Registry`
+///
+/// Ids of despawned entities stay invalid even once their slot is given to a new entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    /// Slot of the entity in the registry's component columns
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+}
+
+/// A column of one component type, indexed by entity slot
+trait Column: Any {
+    /// Drop the component in `index`, if any
+    fn clear(&mut self, index: usize);
+}
+
+impl<T: 'static> Column for Vec<Option<T>> {
+    fn clear(&mut self, index: usize) {
+        if let Some(slot) = self.get_mut(index) {
+            *slot = None;
+        }
+    }
+}
+
+/// Entities and the components attached to them
+///
+/// # Fields
+///
+/// - `generations`: Generation of the entity in each slot, bumped when it is despawned.
+/// - `alive`: Whether each slot holds a live entity.
+/// - `free`: Slots of despawned entities, reused by `spawn`.
+/// - `columns`: One column per component type.
+#[derive(Default)]
+pub struct Registry {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<u32>,
+    columns: HashMap<TypeId, Box<dyn Column>>,
+}
+
+impl Registry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an entity without components
+    pub fn spawn(&mut self) -> Entity {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.generations.push(0);
+                self.alive.push(false);
+                (self.generations.len() - 1) as u32
+            }
+        };
+        self.alive[index as usize] = true;
+        Entity {
+            index,
+            generation: self.generations[index as usize],
+        }
+    }
+
+    /// Remove an entity and drop all its components
+    ///
+    /// Returns false if the entity was already despawned.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        let index = entity.index();
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(entity.index);
+        for column in self.columns.values_mut() {
+            column.clear(index);
+        }
+        true
+    }
+
+    /// Check if an entity was spawned and not despawned yet
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.alive.get(entity.index()).is_some_and(|&alive| alive)
+            && self.generations[entity.index()] == entity.generation
+    }
+
+    /// Every live entity, in slot order
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.alive
+            .iter()
+            .zip(&self.generations)
+            .enumerate()
+            .filter(|(_, (alive, _))| **alive)
+            .map(|(index, (_, &generation))| Entity {
+                index: index as u32,
+                generation,
+            })
+    }
+
+    /// Attach a component to an entity, returning the one of the same type it replaces
+    ///
+    /// Despawned entities can't get components, so the component is dropped.
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) -> Option<T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        let column = self.column_mut::<T>();
+        if column.len() <= entity.index() {
+            column.resize_with(entity.index() + 1, || None);
+        }
+        column[entity.index()].replace(component)
+    }
+
+    /// Take a component off an entity
+    pub fn remove<T: 'static>(&mut self, entity: Entity) -> Option<T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.column_mut::<T>().get_mut(entity.index())?.take()
+    }
+
+    /// The entity's component of type `T`, if it has one
+    pub fn get<T: 'static>(&self, entity: Entity) -> Option<&T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.columns
+            .get(&TypeId::of::<T>())
+            .and_then(|column| (column.as_ref() as &dyn Any).downcast_ref::<Vec<Option<T>>>())?
+            .get(entity.index())?
+            .as_ref()
+    }
+
+    /// The entity's component of type `T` mutably, if it has one
+    pub fn get_mut<T: 'static>(&mut self, entity: Entity) -> Option<&mut T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.column_mut::<T>().get_mut(entity.index())?.as_mut()
+    }
+
+    /// Check if the entity has a component of type `T`
+    pub fn has<T: 'static>(&self, entity: Entity) -> bool {
+        self.get::<T>(entity).is_some()
+    }
+
+    /// Every live entity that has all the components in `Q`, with them
+    ///
+    /// `Q` is a reference to a component type or a tuple of up to four,
+    /// such as `&Gravity` or `(&mut Quad, &Gravity)`.
+    ///
+    /// # Panics
+    /// If `Q` asks for the same component type twice.
+    pub fn query<Q: Query>(&mut self) -> impl Iterator<Item = (Entity, Q::Item<'_>)> + '_ {
+        Q::iter(self)
+    }
+
+    /// The column of `T`, created empty if no entity had a `T` yet
+    fn column_mut<T: 'static>(&mut self) -> &mut Vec<Option<T>> {
+        let column = self
+            .columns
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Vec::<Option<T>>::new()));
+        (column.as_mut() as &mut dyn Any)
+            .downcast_mut()
+            .expect("columns are keyed by their component type")
+    }
+
+    /// Generation of every slot with its liveness, and the columns of the given types, borrowed together
+    fn split<const N: usize>(
+        &mut self,
+        types: [TypeId; N],
+    ) -> (impl Iterator<Item = Option<Entity>> + '_, [Option<&mut dyn Any>; N]) {
+        let entities = self.alive.iter().zip(&self.generations).enumerate().map(|(index, (&alive, &generation))| {
+            alive.then_some(Entity {
+                index: index as u32,
+                generation,
+            })
+        });
+        let columns = self
+            .columns
+            .get_disjoint_mut(types.each_ref())
+            .map(|column| column.map(|column| column.as_mut() as &mut dyn Any));
+        (entities, columns)
+    }
+}
+
+/// A component asked for by a query, shared as `&T` or mutable as `&mut T`
+pub trait Fetch {
+    /// What the query hands out for each entity
+    type Item<'a>;
+
+    /// Type of the component asked for
+    fn component() -> TypeId;
+
+    /// Each slot's component in `column`, if it has one
+    fn fetch<'a>(column: Option<&'a mut dyn Any>) -> impl Iterator<Item = Option<Self::Item<'a>>> + 'a;
+}
+
+/// Every slot of `column`, or none if no entity has a `T`
+fn slots<T: 'static>(column: Option<&mut dyn Any>) -> &mut [Option<T>] {
+    match column.and_then(|column| column.downcast_mut::<Vec<Option<T>>>()) {
+        Some(column) => column,
+        None => &mut [],
+    }
+}
+
+impl<T: 'static> Fetch for &T {
+    type Item<'a> = &'a T;
+
+    fn component() -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn fetch<'a>(column: Option<&'a mut dyn Any>) -> impl Iterator<Item = Option<&'a T>> + 'a {
+        slots::<T>(column).iter().map(Option::as_ref)
+    }
+}
+
+impl<T: 'static> Fetch for &mut T {
+    type Item<'a> = &'a mut T;
+
+    fn component() -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn fetch<'a>(column: Option<&'a mut dyn Any>) -> impl Iterator<Item = Option<&'a mut T>> + 'a {
+        slots::<T>(column).iter_mut().map(Option::as_mut)
+    }
+}
+
+/// Components a `Registry::query` asks for
+pub trait Query {
+    /// What the query hands out for each matching entity
+    type Item<'a>;
+
+    /// Every live entity in `registry` that has all the components, with them
+    fn iter<'a>(registry: &'a mut Registry) -> impl Iterator<Item = (Entity, Self::Item<'a>)> + 'a;
+}
+
+impl<F: Fetch> Query for F {
+    type Item<'a> = F::Item<'a>;
+
+    fn iter<'a>(registry: &'a mut Registry) -> impl Iterator<Item = (Entity, F::Item<'a>)> + 'a {
+        <(F,)>::iter(registry).map(|(entity, (item,))| (entity, item))
+    }
+}
+
+/// Implement `Query` for a tuple of `Fetch` types, walking their columns side by side
+macro_rules! tuple_query {
+    ($($fetch:ident),+) => {
+        impl<$($fetch: Fetch),+> Query for ($($fetch,)+) {
+            type Item<'a> = ($($fetch::Item<'a>,)+);
+
+            #[allow(non_snake_case)]
+            fn iter<'a>(registry: &'a mut Registry) -> impl Iterator<Item = (Entity, Self::Item<'a>)> + 'a {
+                let (mut entities, [$($fetch),+]) = registry.split([$($fetch::component()),+]);
+                let ($(mut $fetch,)+) = ($($fetch::fetch($fetch),)+);
+                std::iter::from_fn(move || loop {
+                    let entity = entities.next()?;
+                    if let (Some(entity), $(Some($fetch),)+) = (entity, $($fetch.next()?,)+) {
+                        return Some((entity, ($($fetch,)+)));
+                    }
+                })
+            }
+        }
+    };
+}
+
+tuple_query!(A);
+tuple_query!(A, B);
+tuple_query!(A, B, C);
+tuple_query!(A, B, C, D);
@@ -1,16 +1,21 @@
-use crate::objects::point::Point;
-use crate::objects::constraint::Constraint;
+use crate::objects::point::{Point, next_collision_group};
+use crate::objects::constraint::{Constraint, ConstraintMode};
+use crate::basics::ComponentOwner;
 use crate::basics::gravity::Gravity;
 use crate::basics::friction::Friction;
 use crate::basics::collision::Collision;
+use crate::objects::polygon::triangulate;
+use crate::utils::gradient::{Gradient, GradientDirection};
+use std::ops::Range;
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Configuration for creating shapes
 #[derive(Clone, Copy)]
 pub struct ShapeConfig {
     /// Color of the shape
     pub color: Color,
-    /// Gravity force applied to the shape
+    /// Gravity applied to the shape, in pixels per second squared
     pub gravity: f32,
     /// Friction coefficient (0.0 to 1.0)
     pub friction: f32,
@@ -26,13 +31,19 @@ pub struct ShapeConfig {
     pub constraint_stiffness: f32,
     /// Whether the shape is fixed in place
     pub fixed: bool,
+    /// Whether the shape's points collide with each other
+    pub self_collision: bool,
+    /// Collision group for the shape's points, or `None` for a new group per shape
+    ///
+    /// Share a group between shapes to keep them from colliding with each other too.
+    pub collision_group: Option<u32>,
 }
 
 impl Default for ShapeConfig {
     fn default() -> Self {
         Self {
             color: WHITE,
-            gravity: 10.0,
+            gravity: 600.0,
             friction: 0.95,
             bounce: 0.2,
             slope_friction: 0.85,
@@ -40,15 +51,96 @@ impl Default for ShapeConfig {
             point_mass: 1.0,
             constraint_stiffness: 0.95,
             fixed: false,
+            self_collision: false,
+            collision_group: None,
         }
     }
 }
 
+impl ShapeConfig {
+    /// Create a new shape configuration with the default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the color of the shape
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set gravity applied to the shape
+    pub fn gravity(mut self, gravity: f32) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Set friction coefficient
+    pub fn friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Set bounce coefficient
+    pub fn bounce(mut self, bounce: f32) -> Self {
+        self.bounce = bounce;
+        self
+    }
+
+    /// Set slope friction coefficient
+    pub fn slope_friction(mut self, slope_friction: f32) -> Self {
+        self.slope_friction = slope_friction;
+        self
+    }
+
+    /// Set point radius for collision detection
+    pub fn point_radius(mut self, point_radius: f32) -> Self {
+        self.point_radius = point_radius;
+        self
+    }
+
+    /// Set point mass
+    pub fn point_mass(mut self, point_mass: f32) -> Self {
+        self.point_mass = point_mass;
+        self
+    }
+
+    /// Set constraint stiffness
+    pub fn constraint_stiffness(mut self, constraint_stiffness: f32) -> Self {
+        self.constraint_stiffness = constraint_stiffness;
+        self
+    }
+
+    /// Set whether the shape is fixed in place
+    pub fn fixed(mut self, fixed: bool) -> Self {
+        self.fixed = fixed;
+        self
+    }
+
+    /// Set whether the shape's points collide with each other
+    pub fn self_collision(mut self, self_collision: bool) -> Self {
+        self.self_collision = self_collision;
+        self
+    }
+
+    /// Put the shape's points in a collision group shared with other shapes
+    pub fn collision_group(mut self, group: u32) -> Self {
+        self.collision_group = Some(group);
+        self
+    }
+
+    /// Collision group for the points of one new shape, or `None` if they collide with each other
+    fn shape_collision_group(&self) -> Option<u32> {
+        (!self.self_collision).then(|| self.collision_group.unwrap_or_else(next_collision_group))
+    }
+}
+
 /// Creates a triangle with the given center and size point
 /// size_point determines the distance and angle of the first vertex from center
 pub fn create_triangle(center: Vec2, size_point: Vec2, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
     let mut points = Vec::new();
     let mut constraints = Vec::new();
+    let collision_group = config.shape_collision_group();
     
     // Calculate radius and angle from center to size point
     let radius = (size_point - center).length();
@@ -60,7 +152,8 @@ pub fn create_triangle(center: Vec2, size_point: Vec2, config: ShapeConfig) -> (
         let x = center.x + radius * vertex_angle.cos();
         let y = center.y + radius * vertex_angle.sin();
         let mut point = Point::new(x, y, config.point_mass, config.point_radius, config.color);
-        point.fixed = config.fixed;
+        point.set_fixed(config.fixed);
+        point.collision_group = collision_group;
         points.push(point);
     }
     
@@ -87,6 +180,7 @@ pub fn create_triangle(center: Vec2, size_point: Vec2, config: ShapeConfig) -> (
 pub fn create_square(center: Vec2, size_point: Vec2, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
     let mut points = Vec::new();
     let mut constraints = Vec::new();
+    let collision_group = config.shape_collision_group();
     
     // Calculate radius and angle from center to size point
     let radius = (size_point - center).length();
@@ -98,7 +192,8 @@ pub fn create_square(center: Vec2, size_point: Vec2, config: ShapeConfig) -> (Ve
         let x = center.x + radius * vertex_angle.cos();
         let y = center.y + radius * vertex_angle.sin();
         let mut point = Point::new(x, y, config.point_mass, config.point_radius, config.color);
-        point.fixed = config.fixed;
+        point.set_fixed(config.fixed);
+        point.collision_group = collision_group;
         points.push(point);
     }
     
@@ -111,7 +206,7 @@ pub fn create_square(center: Vec2, size_point: Vec2, config: ShapeConfig) -> (Ve
     }
     
     // Add cross-bracing for stability
-    let diagonal = radius * 2.0_f32.sqrt();
+    let diagonal = radius * 2.0;
     constraints.push(Constraint::new(0, 2, diagonal, config.constraint_stiffness, config.color));
     constraints.push(Constraint::new(1, 3, diagonal, config.constraint_stiffness, config.color));
     
@@ -130,6 +225,7 @@ pub fn create_square(center: Vec2, size_point: Vec2, config: ShapeConfig) -> (Ve
 pub fn create_circle(center: Vec2, size_point: Vec2, num_points: usize, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
     let mut points = Vec::new();
     let mut constraints = Vec::new();
+    let collision_group = config.shape_collision_group();
     
     // Calculate radius from center to size point
     let radius = (size_point - center).length();
@@ -140,7 +236,8 @@ pub fn create_circle(center: Vec2, size_point: Vec2, num_points: usize, config:
         let x = center.x + radius * angle.cos();
         let y = center.y + radius * angle.sin();
         let mut point = Point::new(x, y, config.point_mass, config.point_radius, config.color);
-        point.fixed = config.fixed;
+        point.set_fixed(config.fixed);
+        point.collision_group = collision_group;
         points.push(point);
     }
     
@@ -170,10 +267,332 @@ pub fn create_circle(center: Vec2, size_point: Vec2, num_points: usize, config:
     (points, constraints)
 }
 
+/// Creates a regular polygon with `sides` corners and the given center and size point
+/// size_point determines the distance and angle of the first vertex from center
+///
+/// Every corner is braced to the one opposite it, so the polygon keeps its shape.
+pub fn create_regular_polygon(center: Vec2, size_point: Vec2, sides: usize, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
+    let sides = sides.max(3);
+    let radius = (size_point - center).length();
+    let angle = (size_point - center).to_angle();
+    let positions: Vec<Vec2> = (0..sides)
+        .map(|i| center + Vec2::from_angle(angle + i as f32 * std::f32::consts::TAU / sides as f32) * radius)
+        .collect();
+    let (points, mut constraints) = create_ring(&positions, config);
+
+    // Brace each corner to the opposite one, once per pair
+    if sides > 3 {
+        for i in 0..sides.div_ceil(2) {
+            constraints.push(brace(&points, i, (i + sides / 2) % sides, config));
+        }
+    }
+
+    (points, constraints)
+}
+
+/// Creates a star with `spikes` tips and the given center and size point
+/// size_point determines the distance and angle of the first tip from center
+///
+/// The inner corners sit at `inner_ratio` of the tip distance. Each spike is
+/// braced across its base and the inner corners across the middle, so the
+/// tips don't fold in.
+pub fn create_star(
+    center: Vec2,
+    size_point: Vec2,
+    spikes: usize,
+    inner_ratio: f32,
+    config: ShapeConfig,
+) -> (Vec<Point>, Vec<Constraint>) {
+    let spikes = spikes.max(2);
+    let count = spikes * 2;
+    let radius = (size_point - center).length();
+    let inner_radius = radius * inner_ratio.clamp(0.05, 1.0);
+    let angle = (size_point - center).to_angle();
+    let positions: Vec<Vec2> = (0..count)
+        .map(|i| {
+            let distance = if i % 2 == 0 { radius } else { inner_radius };
+            center + Vec2::from_angle(angle + i as f32 * std::f32::consts::TAU / count as f32) * distance
+        })
+        .collect();
+    let (points, mut constraints) = create_ring(&positions, config);
+
+    // Brace each spike across its base, between the inner corners beside its tip
+    for tip in (0..count).step_by(2) {
+        constraints.push(brace(&points, (tip + count - 1) % count, tip + 1, config));
+    }
+    // Brace the inner corners to the opposite ones
+    let inner: Vec<usize> = (1..count).step_by(2).collect();
+    for i in 0..spikes.div_ceil(2) {
+        constraints.push(brace(&points, inner[i], inner[(i + spikes / 2) % spikes], config));
+    }
+
+    (points, constraints)
+}
+
+/// Creates a capsule, a rectangle with round ends, around the segment from `start` to `end`
+///
+/// Each round end is made of `cap_points` points, at least 2, at `radius`
+/// from its end of the segment. Every point is braced to the one opposite it.
+pub fn create_capsule(start: Vec2, end: Vec2, radius: f32, cap_points: usize, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
+    let cap_points = cap_points.max(2);
+    let axis = (end - start).try_normalize().unwrap_or(Vec2::X).to_angle();
+    let cap = |center: Vec2, from: f32| {
+        (0..cap_points).map(move |i| {
+            let turn = from + std::f32::consts::PI * i as f32 / (cap_points - 1) as f32;
+            center + Vec2::from_angle(axis + turn) * radius
+        })
+    };
+    let half_turn = std::f32::consts::FRAC_PI_2;
+    let positions: Vec<Vec2> = cap(end, -half_turn).chain(cap(start, half_turn)).collect();
+    let (points, mut constraints) = create_ring(&positions, config);
+
+    // Brace each point to the opposite one, once per pair
+    let count = positions.len();
+    for i in 0..count / 2 {
+        constraints.push(brace(&points, i, i + count / 2, config));
+    }
+
+    (points, constraints)
+}
+
+/// Creates a shape with a point at each of `vertices`, traced around its outline
+///
+/// The outline may be concave and wound either way, but must not cross
+/// itself. Besides the edges, the inside is braced along a triangulation of
+/// the outline, so the shape holds its form. Fewer than three vertices make
+/// no shape.
+pub fn create_from_vertices(vertices: &[Vec2], config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
+    if vertices.len() < 3 {
+        return (Vec::new(), Vec::new());
+    }
+    let (points, mut constraints) = create_ring(vertices, config);
+
+    // Brace along the sides of each triangle that aren't outline edges, once per pair
+    let count = vertices.len();
+    let mut braced = Vec::new();
+    for [a, b, c] in triangulate(vertices) {
+        for (i, j) in [(a, b), (b, c), (c, a)] {
+            let pair = (i.min(j), i.max(j));
+            let is_edge = pair.1 - pair.0 == 1 || (pair.0 == 0 && pair.1 == count - 1);
+            if !is_edge && !braced.contains(&pair) {
+                braced.push(pair);
+                constraints.push(brace(&points, pair.0, pair.1, config));
+            }
+        }
+    }
+
+    (points, constraints)
+}
+
+/// Joins two shapes into one, tying together their points that lie within `contact_tolerance` of each other
+///
+/// The points and constraints of `shape_b` follow those of `shape_a`, with
+/// its constraint indices moved along to match. Each pair of nearby points
+/// gets a rigid constraint, as stiff as the stiffest constraint of either
+/// shape. One pair makes a hinge; two or more hold the parts together rigidly.
+///
+/// Unless `shape_b`'s points collide with each other, they join `shape_a`'s
+/// collision group, so the parts don't push each other apart where they overlap.
+pub fn weld(
+    shape_a: (Vec<Point>, Vec<Constraint>),
+    shape_b: (Vec<Point>, Vec<Constraint>),
+    contact_tolerance: f32,
+) -> (Vec<Point>, Vec<Constraint>) {
+    let (mut points, mut constraints) = shape_a;
+    let (points_b, constraints_b) = shape_b;
+    let offset = points.len();
+    let stiffness = constraints
+        .iter()
+        .chain(&constraints_b)
+        .map(|constraint| constraint.stiffness)
+        .reduce(f32::max)
+        .unwrap_or(1.0);
+    let group_a = points.first().and_then(|point| point.collision_group);
+
+    constraints.extend(constraints_b.into_iter().map(|mut constraint| {
+        constraint.point1 += offset;
+        constraint.point2 += offset;
+        constraint
+    }));
+    points.extend(points_b.into_iter().map(|mut point| {
+        if point.collision_group.is_some() && group_a.is_some() {
+            point.collision_group = group_a;
+        }
+        point
+    }));
+
+    // Tie each point of the first shape to every point of the second close enough to it
+    for i in 0..offset {
+        for j in offset..points.len() {
+            let distance = (points[j].position.0 - points[i].position.0).hypot(points[j].position.1 - points[i].position.1);
+            if distance <= contact_tolerance {
+                constraints.push(Constraint::new(i, j, distance, stiffness, points[i].color));
+            }
+        }
+    }
+
+    (points, constraints)
+}
+
+/// Creates points at `positions` with the config's physics components, linked in a closed loop
+fn create_ring(positions: &[Vec2], config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
+    let collision_group = config.shape_collision_group();
+    let points: Vec<Point> = positions
+        .iter()
+        .map(|&position| shape_point(position, config.point_radius, collision_group, config))
+        .collect();
+    let constraints = (0..points.len()).map(|i| brace(&points, i, (i + 1) % points.len(), config)).collect();
+    (points, constraints)
+}
+
+/// Point of a shape at `position` with the config's physics components
+fn shape_point(position: Vec2, radius: f32, collision_group: Option<u32>, config: ShapeConfig) -> Point {
+    let mut point = Point::new(position.x, position.y, config.point_mass, radius, config.color);
+    point.set_fixed(config.fixed);
+    point.collision_group = collision_group;
+    point.add_component(Box::new(Gravity::new(config.gravity)));
+    point.add_component(Box::new(Friction::new(config.friction)));
+    point.add_component(Box::new(Collision::new(config.bounce, config.slope_friction)));
+    point
+}
+
+/// Constraint holding points `a` and `b` at their current distance
+fn brace(points: &[Point], a: usize, b: usize, config: ShapeConfig) -> Constraint {
+    let distance = vec2(points[a].position.0 - points[b].position.0, points[a].position.1 - points[b].position.1).length();
+    Constraint::new(a, b, distance, config.constraint_stiffness, config.color)
+}
+
+/// Creates a rope hanging from `start` to `end` made of `segments` links
+///
+/// Links only resist stretching, so the rope hangs and folds instead of acting
+/// like a rigid rod. Point radii shrink to fit the links so neighbouring points
+/// can collide with the world without pushing each other apart. Tie the ends
+/// down with `PhysicsWorld::add_rope`.
+pub fn create_rope(start: Vec2, end: Vec2, segments: usize, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
+    let segments = segments.max(1);
+    let link_length = (end - start).length() / segments as f32;
+    let radius = config.point_radius.min(link_length * 0.5);
+    let mut points = Vec::new();
+    let mut constraints = Vec::new();
+    let collision_group = config.shape_collision_group();
+
+    // Create points along the rope
+    for i in 0..=segments {
+        let position = start.lerp(end, i as f32 / segments as f32);
+        let mut point = Point::new(position.x, position.y, config.point_mass, radius, config.color);
+        point.set_fixed(config.fixed);
+        point.collision_group = collision_group;
+        point.add_component(Box::new(Gravity::new(config.gravity)));
+        point.add_component(Box::new(Friction::new(config.friction)));
+        point.add_component(Box::new(Collision::new(config.bounce, config.slope_friction)));
+        points.push(point);
+    }
+
+    // Link neighbouring points with rope constraints
+    for i in 0..segments {
+        constraints.push(Constraint::rope(i, i + 1, link_length, config.constraint_stiffness, config.color));
+    }
+
+    (points, constraints)
+}
+
+/// Creates a bridge of `planks` planks hanging from `start` to `end`
+///
+/// Each plank is a pair of points held rigidly apart, and neighbouring
+/// planks hang from each other by short ropes. The first and last points
+/// are anchors pinned at `start` and `end`, whatever `config.fixed` says.
+/// Point radii shrink to fit the planks, like `create_rope`.
+///
+/// Points are ordered from `start` to `end`: the start anchor, the two ends
+/// of each plank, then the end anchor.
+pub fn create_bridge(start: Vec2, end: Vec2, planks: usize, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
+    let planks = planks.max(1);
+    let span = (end - start).length() / planks as f32;
+    // Each plank leaves a tenth of its span free on either side for the ropes
+    let gap = 0.1;
+    let radius = config.point_radius.min(span * (1.0 - 2.0 * gap) * 0.5);
+    let collision_group = config.shape_collision_group();
+    let mut points = Vec::new();
+    let mut constraints = Vec::new();
+
+    let anchor = |position: Vec2| {
+        let mut point = shape_point(position, radius, collision_group, config);
+        point.set_fixed(true);
+        point
+    };
+    points.push(anchor(start));
+    for i in 0..planks {
+        let at = |t: f32| start.lerp(end, (i as f32 + t) / planks as f32);
+        points.push(shape_point(at(gap), radius, collision_group, config));
+        points.push(shape_point(at(1.0 - gap), radius, collision_group, config));
+    }
+    points.push(anchor(end));
+
+    // Join the anchors and planks in a chain, alternating ropes and planks
+    for i in 0..points.len() - 1 {
+        let distance = (points[i + 1].position.0 - points[i].position.0).hypot(points[i + 1].position.1 - points[i].position.1);
+        let constraint = if i % 2 == 0 {
+            Constraint::rope(i, i + 1, distance, config.constraint_stiffness, config.color)
+        } else {
+            Constraint::new(i, i + 1, distance, config.constraint_stiffness, config.color)
+        };
+        constraints.push(constraint);
+    }
+
+    (points, constraints)
+}
+
+/// Creates a ragdoll standing with its head at `position`, about 100 pixels tall at a `scale` of 1
+///
+/// Bones are rigid constraints between joints. Struts that only resist
+/// squashing keep the head off the hips and stop elbows and knees from
+/// folding all the way. Point radii shrink to fit the body.
+///
+/// Points are ordered head, neck, pelvis, left elbow, left hand, right
+/// elbow, right hand, left knee, left foot, right knee, right foot.
+pub fn create_ragdoll(position: Vec2, scale: f32, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
+    let joints = [
+        vec2(0.0, 0.0),    // head
+        vec2(0.0, 14.0),   // neck
+        vec2(0.0, 50.0),   // pelvis
+        vec2(-16.0, 28.0), // left elbow
+        vec2(-20.0, 46.0), // left hand
+        vec2(16.0, 28.0),  // right elbow
+        vec2(20.0, 46.0),  // right hand
+        vec2(-8.0, 74.0),  // left knee
+        vec2(-10.0, 98.0), // left foot
+        vec2(8.0, 74.0),   // right knee
+        vec2(10.0, 98.0),  // right foot
+    ];
+    let bones = [(0, 1), (1, 2), (1, 3), (3, 4), (1, 5), (5, 6), (2, 7), (7, 8), (2, 9), (9, 10)];
+    let struts = [(0, 2), (1, 4), (1, 6), (2, 8), (2, 10), (3, 5), (7, 9)];
+
+    let collision_group = config.shape_collision_group();
+    let points: Vec<Point> = joints
+        .iter()
+        .enumerate()
+        .map(|(i, &offset)| {
+            let radius = if i == 0 { 8.0 } else { 4.0 } * scale;
+            shape_point(position + offset * scale, config.point_radius.min(radius), collision_group, config)
+        })
+        .collect();
+
+    let mut constraints: Vec<Constraint> = bones.iter().map(|&(a, b)| brace(&points, a, b, config)).collect();
+    // Struts allow some give, so they rest a little shorter than the standing pose
+    constraints.extend(struts.iter().map(|&(a, b)| {
+        let mut strut = brace(&points, a, b, config).with_mode(ConstraintMode::MinLength);
+        strut.rest_length *= 0.6;
+        strut
+    }));
+
+    (points, constraints)
+}
+
 /// Creates a line segment between two points
 pub fn create_line(start: Vec2, end: Vec2, num_points: usize, config: ShapeConfig) -> (Vec<Point>, Vec<Constraint>) {
     let mut points = Vec::new();
     let mut constraints = Vec::new();
+    let collision_group = config.shape_collision_group();
     
     // Create points along the line
     for i in 0..num_points {
@@ -181,7 +600,8 @@ pub fn create_line(start: Vec2, end: Vec2, num_points: usize, config: ShapeConfi
         let x = start.x + (end.x - start.x) * t;
         let y = start.y + (end.y - start.y) * t;
         let mut point = Point::new(x, y, config.point_mass, config.point_radius, config.color);
-        point.fixed = config.fixed;
+        point.set_fixed(config.fixed);
+        point.collision_group = collision_group;
         points.push(point);
     }
     
@@ -202,4 +622,95 @@ pub fn create_line(start: Vec2, end: Vec2, num_points: usize, config: ShapeConfi
     }
     
     (points, constraints)
-} 
\ No newline at end of file
+} 
+
+/// How a `ShapeFill` splits its outline into triangles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FillTriangulation {
+    /// Fan out from the average of the points; fast, and stays whole when the shape is squashed inside out
+    #[default]
+    Fan,
+    /// Ear clipping, for shapes that bend in far enough to poke out of a fan
+    EarClipping,
+}
+
+/// Surface drawn inside a soft body, following its points as they move
+///
+/// The points must go around the shape's outline in order, like the ones
+/// `create_triangle`, `create_square` and `create_circle` make.
+///
+/// # Fields
+///
+/// - `points`: Indices of the outline's points in the world.
+/// - `color`: Color of the surface.
+/// - `gradient`: Colors the surface is filled with instead of `color`, if any, spread across its current bounds.
+/// - `gradient_direction`: Axis the gradient runs along; top to bottom by default.
+/// - `triangulation`: How the outline is split into triangles.
+#[derive(Clone)]
+pub struct ShapeFill {
+    pub points: Range<usize>,
+    pub color: Color,
+    pub gradient: Option<Gradient>,
+    pub gradient_direction: GradientDirection,
+    pub triangulation: FillTriangulation,
+}
+
+impl ShapeFill {
+    /// Create a solid fill of the outline made by the points at `points`
+    pub fn new(points: Range<usize>, color: Color) -> Self {
+        Self {
+            points,
+            color,
+            gradient: None,
+            gradient_direction: GradientDirection::Vertical,
+            triangulation: FillTriangulation::Fan,
+        }
+    }
+
+    /// Fill with a gradient running along `direction` instead of the color
+    pub fn with_gradient(mut self, gradient: Gradient, direction: GradientDirection) -> Self {
+        self.gradient = Some(gradient);
+        self.gradient_direction = direction;
+        self
+    }
+
+    /// Set how the outline is split into triangles
+    pub fn with_triangulation(mut self, triangulation: FillTriangulation) -> Self {
+        self.triangulation = triangulation;
+        self
+    }
+
+    /// Draw the surface at the points' current positions
+    pub fn draw(&self, points: &[Point]) {
+        let Some(outline) = points.get(self.points.clone()) else {
+            return;
+        };
+        if outline.len() < 3 {
+            return;
+        }
+        let mut positions: Vec<Vec2> = outline.iter().map(|p| vec2(p.position.0, p.position.1)).collect();
+        let triangles = match self.triangulation {
+            FillTriangulation::Fan => {
+                let center = positions.iter().copied().sum::<Vec2>() / positions.len() as f32;
+                let count = positions.len();
+                positions.push(center);
+                (0..count).map(|i| [count, i, (i + 1) % count]).collect()
+            }
+            FillTriangulation::EarClipping => triangulate(&positions),
+        };
+        let min = positions.iter().fold(positions[0], |min, p| min.min(*p));
+        let size = (positions.iter().fold(positions[0], |max, p| max.max(*p)) - min).max(Vec2::splat(f32::EPSILON));
+        let color = |position: Vec2| match &self.gradient {
+            Some(gradient) => gradient.get_color(match self.gradient_direction {
+                GradientDirection::Horizontal => (position.x - min.x) / size.x,
+                GradientDirection::Vertical => (position.y - min.y) / size.y,
+            }),
+            None => self.color,
+        };
+        draw_mesh(&Mesh {
+            vertices: positions.iter().map(|&p| Vertex::new(p.x, p.y, 0.0, 0.0, 0.0, color(p))).collect(),
+            indices: triangles.into_iter().flatten().map(|i| i as u16).collect(),
+            texture: None,
+        });
+    }
+}
@@ -0,0 +1,257 @@
+use std::ops::Range;
+
+use macroquad::color::Color;
+use macroquad::math::{Rect, Vec2, vec2};
+use macroquad::shapes::draw_triangle;
+
+use crate::basics::collision::Segment;
+use crate::objects::circle::CircleBody;
+use crate::objects::point::Point;
+use crate::objects::quad::Quad;
+
+/// Static ground shaped by a line of vertices and solid from its surface down to `bottom`.
+///
+/// One terrain replaces the hundreds of fixed points a slope would otherwise
+/// need. Vertices are kept sorted left to right, so the few segments under an
+/// object are found by a binary search on x; overhangs and caves need
+/// several terrains or polygons instead.
+///
+/// # Fields
+///
+/// - `vertices`: Surface points in world space, sorted by x.
+/// - `bottom`: Y coordinate the filled shape extends down to.
+/// - `color`: Color used to fill the terrain.
+/// - `restitution`: How bouncy the surface is, from 0.0 to 1.0.
+///
+/// # Example
+///
+/// ```rust
+/// let hills = TerrainCollider::from_heightmap(vec2(0.0, 600.0), 40.0, &[80.0, 120.0, 60.0, 140.0], GREEN);
+/// world.add_terrain(hills);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainCollider {
+    pub vertices: Vec<Vec2>,
+    pub bottom: f32,
+    pub color: Color,
+    pub restitution: f32,
+}
+
+impl TerrainCollider {
+    /// Creates a terrain whose surface runs through `vertices`.
+    ///
+    /// # Parameters
+    /// - `vertices`: Surface points in world space; they are sorted by x.
+    /// - `bottom`: Y coordinate the filled shape extends down to.
+    /// - `color`: Color used to fill the terrain.
+    pub fn from_polyline(mut vertices: Vec<Vec2>, bottom: f32, color: Color) -> Self {
+        vertices.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Self {
+            vertices,
+            bottom,
+            color,
+            restitution: 0.0,
+        }
+    }
+
+    /// Creates a terrain from evenly spaced heights.
+    ///
+    /// # Parameters
+    /// - `origin`: Bottom-left corner of the terrain.
+    /// - `spacing`: Horizontal distance between samples.
+    /// - `heights`: Height of the surface above `origin` at each sample.
+    /// - `color`: Color used to fill the terrain.
+    pub fn from_heightmap(origin: Vec2, spacing: f32, heights: &[f32], color: Color) -> Self {
+        let vertices = heights
+            .iter()
+            .enumerate()
+            .map(|(i, height)| vec2(origin.x + i as f32 * spacing, origin.y - height))
+            .collect();
+        Self::from_polyline(vertices, origin.y, color)
+    }
+
+    /// Set how bouncy the surface is
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Indices of the segments spanning any part of `min_x..=max_x`
+    ///
+    /// Segment `i` runs from `vertices[i]` to `vertices[i + 1]`.
+    pub fn segments_between(&self, min_x: f32, max_x: f32) -> Range<usize> {
+        if self.vertices.len() < 2 {
+            return 0..0;
+        }
+        let start = self.vertices[1..].partition_point(|vertex| vertex.x < min_x);
+        let end = self.vertices.partition_point(|vertex| vertex.x <= max_x).min(self.vertices.len() - 1);
+        start..end.max(start)
+    }
+
+    /// The segment from `vertices[index]` to `vertices[index + 1]`
+    pub fn segment(&self, index: usize) -> Segment {
+        Segment::new(self.vertices[index], self.vertices[index + 1], 0.0)
+    }
+
+    /// Y coordinate of the surface at `x`, or `None` beyond either end
+    pub fn height_at(&self, x: f32) -> Option<f32> {
+        let index = self.segments_between(x, x).next()?;
+        let (a, b) = (self.vertices[index], self.vertices[index + 1]);
+        if b.x == a.x {
+            return Some(a.y.min(b.y));
+        }
+        Some(a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x))
+    }
+
+    /// Rectangle enclosing the surface and the filled area below it
+    pub fn bounds(&self) -> Rect {
+        let (Some(first), Some(last)) = (self.vertices.first(), self.vertices.last()) else {
+            return Rect::new(0.0, self.bottom, 0.0, 0.0);
+        };
+        let top = self.vertices.iter().fold(self.bottom, |top, vertex| top.min(vertex.y));
+        Rect::new(first.x, top, last.x - first.x, self.bottom - top)
+    }
+
+    /// Draws the terrain filled down to `bottom`.
+    pub fn draw(&self) {
+        for pair in self.vertices.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let (below_a, below_b) = (vec2(a.x, self.bottom), vec2(b.x, self.bottom));
+            draw_triangle(a, b, below_b, self.color);
+            draw_triangle(a, below_b, below_a, self.color);
+        }
+    }
+
+    /// Minimum translation vector pushing a Quad out of the terrain
+    ///
+    /// # Returns
+    /// How far `quad` must move to stop overlapping and the index of the
+    /// segment it is pushed out of, or `None` if they don't overlap
+    pub fn quad_overlap(&self, quad: &Quad) -> Option<(Vec2, usize)> {
+        let bounds = quad.bounds();
+        let corners = quad.corners();
+        let segments = self.segments_between(bounds.x, bounds.x + bounds.w);
+        let mut deepest: Option<(f32, Vec2, usize)> = None;
+        let mut consider = |depth: f32, normal: Vec2, index: usize| {
+            if depth > 0.0 && deepest.is_none_or(|(best, _, _)| depth > best) {
+                deepest = Some((depth, normal, index));
+            }
+        };
+
+        for index in segments.clone() {
+            let (a, b) = (self.vertices[index], self.vertices[index + 1]);
+            let up = up_normal(a, b);
+            for corner in corners.iter().filter(|corner| (a.x..=b.x).contains(&corner.x)) {
+                consider((a - *corner).dot(up), up, index);
+            }
+        }
+        // Peaks between the corners poke into the Quad from below
+        let bottom = bounds.y + bounds.h;
+        for index in segments.skip(1) {
+            let peak = self.vertices[index];
+            if peak.x > bounds.x && peak.x < bounds.x + bounds.w {
+                consider(bottom - peak.y, vec2(0.0, -1.0), index);
+            }
+        }
+        deepest.map(|(depth, normal, index)| (normal * depth, index))
+    }
+
+    /// Minimum translation vector pushing a circle out of the terrain
+    ///
+    /// # Returns
+    /// How far the circle must move to stop overlapping and the index of the
+    /// segment it is pushed out of, or `None` if they don't overlap
+    pub fn circle_overlap(&self, center: Vec2, radius: f32) -> Option<(Vec2, usize)> {
+        let mut deepest: Option<(f32, Vec2, usize)> = None;
+        for index in self.segments_between(center.x - radius, center.x + radius) {
+            let (a, b) = (self.vertices[index], self.vertices[index + 1]);
+            let up = up_normal(a, b);
+            let offset = center - self.segment(index).closest_point(center);
+            let distance = offset.length();
+            let (depth, normal) = if (a.x..=b.x).contains(&center.x) && (center - a).dot(up) < 0.0 {
+                // Center below the surface: come back out through the top
+                (radius + (a - center).dot(up), up)
+            } else if distance < radius {
+                (radius - distance, if distance > 0.0 { offset / distance } else { up })
+            } else {
+                continue;
+            };
+            if deepest.is_none_or(|(best, _, _)| depth > best) {
+                deepest = Some((depth, normal, index));
+            }
+        }
+        deepest.map(|(depth, normal, index)| (normal * depth, index))
+    }
+
+    /// Pushes a dynamic Quad out of the terrain and stops it moving into the surface.
+    ///
+    /// # Returns
+    /// The index of the segment the Quad was pushed out of and the push
+    /// direction, or `None` if they don't touch
+    pub fn collide_quad(&self, quad: &mut Quad) -> Option<(usize, Vec2)> {
+        if quad.is_trigger || !quad.body_type.is_dynamic() {
+            return None;
+        }
+        let (mtv, index) = self.quad_overlap(quad)?;
+        let normal = mtv.normalize_or_zero();
+        quad.position.0 += mtv.x;
+        quad.position.1 += mtv.y;
+        let velocity = self.bounce(vec2(quad.velocity_x, quad.velocity_y), normal, quad.restitution);
+        (quad.velocity_x, quad.velocity_y) = (velocity.x, velocity.y);
+        Some((index, normal))
+    }
+
+    /// Pushes a dynamic circle out of the terrain and stops it moving into the surface.
+    ///
+    /// # Returns
+    /// The index of the segment the circle was pushed out of and the push
+    /// direction, or `None` if they don't touch
+    pub fn collide_circle(&self, circle: &mut CircleBody) -> Option<(usize, Vec2)> {
+        if circle.is_trigger || !circle.body_type.is_dynamic() {
+            return None;
+        }
+        let (mtv, index) = self.circle_overlap(vec2(circle.position.0, circle.position.1), circle.radius)?;
+        let normal = mtv.normalize_or_zero();
+        circle.position.0 += mtv.x;
+        circle.position.1 += mtv.y;
+        let velocity = self.bounce(vec2(circle.velocity_x, circle.velocity_y), normal, circle.restitution);
+        (circle.velocity_x, circle.velocity_y) = (velocity.x, velocity.y);
+        Some((index, normal))
+    }
+
+    /// Pushes a moving Point out of the terrain and stops it moving into the surface.
+    ///
+    /// # Returns
+    /// The index of the segment the Point was pushed out of and the push
+    /// direction, or `None` if they don't touch
+    pub fn collide_point(&self, point: &mut Point) -> Option<(usize, Vec2)> {
+        if point.is_fixed() {
+            return None;
+        }
+        let (mtv, index) = self.circle_overlap(vec2(point.position.0, point.position.1), point.radius)?;
+        let normal = mtv.normalize_or_zero();
+        point.position.0 += mtv.x;
+        point.position.1 += mtv.y;
+        let velocity = self.bounce(vec2(point.velocity.0, point.velocity.1), normal, 0.0);
+        point.velocity = (velocity.x, velocity.y);
+        Some((index, normal))
+    }
+
+    /// Removes the part of `velocity` heading into the surface, bouncing by the bouncier of the two
+    fn bounce(&self, velocity: Vec2, normal: Vec2, restitution: f32) -> Vec2 {
+        let into = velocity.dot(normal);
+        if into >= 0.0 {
+            return velocity;
+        }
+        let bounce = self.restitution.max(restitution).clamp(0.0, 1.0);
+        velocity - normal * into * (1.0 + bounce)
+    }
+}
+
+/// Unit normal of the segment from `a` to `b` pointing out of the ground
+///
+/// `a` must be left of `b`; vertical steps face the lower side.
+fn up_normal(a: Vec2, b: Vec2) -> Vec2 {
+    let along = b - a;
+    vec2(along.y, -along.x).normalize_or_zero()
+}
@@ -7,10 +7,10 @@
 //! # Examples
 //! 
 //! ```rust
-//! use ruty::objects::ui::{Theme, UiButton, UiPanel};
+//! use ruty::objects::ui::{Theme, UiButton, UiManager, UiPanel};
 //! 
-//! let theme = Theme::default();
-//! let mut panel = UiPanel::new(20.0, 20.0, 300.0, 500.0, theme.clone(), Some("My Panel".to_string()));
+//! let mut ui = UiManager::with_theme(Theme::default());
+//! let mut panel = UiPanel::new(20.0, 20.0, 300.0, 500.0, Some("My Panel".to_string()));
 //! 
 //! let button = UiButton::new(
 //!     "Click Me",
@@ -20,33 +20,55 @@
 //!     40.0,
 //!     18,
 //!     font.clone(),
-//!     theme.clone(),
 //!     Some(Box::new(|| println!("Button clicked!"))),
 //! );
 //! panel.add_element(Box::new(button));
+//! ui.add_element(Box::new(panel));
 //! ```
 
+use macroquad::color::{hsl_to_rgb, rgb_to_hsl};
+use macroquad::miniquad::CursorIcon;
+use macroquad::miniquad::window::set_mouse_cursor;
 use macroquad::prelude::*;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::path::Path;
+
+use crate::objects::ui_animator::Easing;
+use crate::utils::color::hex;
+use crate::utils::data_file;
+use crate::utils::localization::Localization;
 
 /// Theme for UI components
-#[derive(Clone)]
+///
+/// Themes can be stored as TOML or JSON, with colors written as hex strings.
+/// Missing fields fall back to the default theme.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Theme {
     /// Primary color for main elements
+    #[serde(with = "hex")]
     pub primary: Color,
     /// Secondary color for less prominent elements
+    #[serde(with = "hex")]
     pub secondary: Color,
     /// Accent color for highlights and interactive elements
+    #[serde(with = "hex")]
     pub accent: Color,
     /// Background color for panels and containers
+    #[serde(with = "hex")]
     pub background: Color,
     /// Text color
+    #[serde(with = "hex")]
     pub text: Color,
     /// Error state color
+    #[serde(with = "hex")]
     pub error: Color,
     /// Success state color
+    #[serde(with = "hex")]
     pub success: Color,
     /// Border radius for rounded corners
     pub border_radius: f32,
@@ -73,6 +95,145 @@ impl Default for Theme {
     }
 }
 
+/// Blend two colors, `t` = 0.0 gives `a` and 1.0 gives `b`
+fn mix_color(a: Color, b: Color, t: f32) -> Color {
+    Color::new(
+        a.r + (b.r - a.r) * t,
+        a.g + (b.g - a.g) * t,
+        a.b + (b.b - a.b) * t,
+        a.a + (b.a - a.a) * t,
+    )
+}
+
+impl Theme {
+    /// Dark theme, same as `Theme::default()`
+    pub fn dark() -> Theme {
+        Theme::default()
+    }
+
+    /// Light theme with dark text on pale surfaces
+    pub fn light() -> Theme {
+        Theme {
+            primary: Color::from_rgba(0, 120, 215, 255),
+            secondary: Color::from_rgba(225, 225, 225, 255),
+            accent: Color::from_rgba(0, 153, 204, 255),
+            background: Color::from_rgba(245, 245, 245, 255),
+            text: Color::from_rgba(20, 20, 20, 255),
+            error: Color::from_rgba(215, 40, 30, 255),
+            success: Color::from_rgba(40, 160, 70, 255),
+            ..Theme::default()
+        }
+    }
+
+    /// High-contrast theme with white text on black and a bright yellow accent
+    pub fn high_contrast() -> Theme {
+        Theme {
+            primary: Color::from_rgba(255, 255, 255, 255),
+            secondary: Color::from_rgba(90, 90, 90, 255),
+            accent: Color::from_rgba(255, 220, 0, 255),
+            background: Color::from_rgba(0, 0, 0, 255),
+            text: Color::from_rgba(255, 255, 255, 255),
+            error: Color::from_rgba(255, 80, 80, 255),
+            success: Color::from_rgba(80, 255, 120, 255),
+            ..Theme::default()
+        }
+    }
+
+    /// Dark theme whose surfaces and accent are derived from `primary`
+    pub fn from_primary(primary: Color) -> Theme {
+        let (h, s, l) = rgb_to_hsl(primary);
+        Theme {
+            primary,
+            secondary: hsl_to_rgb(h, s * 0.25, 0.18),
+            accent: hsl_to_rgb((h + 0.08) % 1.0, s, (l + 0.1).min(0.7)),
+            background: hsl_to_rgb(h, s * 0.2, 0.12),
+            text: hsl_to_rgb(h, s * 0.1, 0.95),
+            ..Theme::dark()
+        }
+    }
+
+    /// Light theme whose surfaces and accent are derived from `primary`
+    pub fn from_primary_light(primary: Color) -> Theme {
+        let (h, s, l) = rgb_to_hsl(primary);
+        Theme {
+            primary,
+            secondary: hsl_to_rgb(h, s * 0.25, 0.88),
+            accent: hsl_to_rgb((h + 0.08) % 1.0, s, (l - 0.1).max(0.3)),
+            background: hsl_to_rgb(h, s * 0.2, 0.96),
+            text: hsl_to_rgb(h, s * 0.1, 0.1),
+            ..Theme::light()
+        }
+    }
+
+    /// Copy of the theme with a different primary color
+    pub fn with_primary(&self, primary: Color) -> Theme {
+        Theme {
+            primary,
+            ..self.clone()
+        }
+    }
+
+    /// Copy of the theme with a different accent color
+    pub fn with_accent(&self, accent: Color) -> Theme {
+        Theme {
+            accent,
+            ..self.clone()
+        }
+    }
+
+    /// Load a theme from a `.toml` or `.json` file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Theme, Box<dyn Error>> {
+        data_file::load(path)
+    }
+
+    /// Save the theme to a `.toml` or `.json` file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        data_file::save(self, path)
+    }
+
+    /// Load every `.toml` and `.json` theme in a directory
+    ///
+    /// Themes are keyed by file name without the extension.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<HashMap<String, Theme>, Box<dyn Error>> {
+        let mut themes = HashMap::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if let (true, Some(name)) = (data_file::is_data_file(&path), path.file_stem().and_then(|stem| stem.to_str())) {
+                themes.insert(name.to_string(), Theme::from_file(&path)?);
+            }
+        }
+        Ok(themes)
+    }
+
+    /// Copy of the theme with every color's alpha scaled by `opacity`
+    pub fn with_opacity(&self, opacity: f32) -> Theme {
+        let fade = |color: Color| Color::new(color.r, color.g, color.b, color.a * opacity);
+        Theme {
+            primary: fade(self.primary),
+            secondary: fade(self.secondary),
+            accent: fade(self.accent),
+            background: fade(self.background),
+            text: fade(self.text),
+            error: fade(self.error),
+            success: fade(self.success),
+            ..self.clone()
+        }
+    }
+
+    /// Greyed-out variant of the theme used for disabled elements
+    pub fn disabled(&self) -> Theme {
+        let grey = self.secondary;
+        Theme {
+            primary: mix_color(self.primary, grey, 0.7),
+            accent: mix_color(self.accent, grey, 0.7),
+            text: mix_color(self.text, grey, 0.5),
+            error: mix_color(self.error, grey, 0.7),
+            success: mix_color(self.success, grey, 0.7),
+            ..self.clone()
+        }
+    }
+}
+
 /// Animation helper for smooth transitions
 #[derive(Clone)]
 pub struct Animation {
@@ -91,8 +252,12 @@ impl Animation {
         }
     }
 
-    /// Update the animation
+    /// Update the animation, jumping straight to the target when motion is reduced
     pub fn update(&mut self) {
+        if reduced_motion() {
+            self.current = self.target;
+            return;
+        }
         self.current += (self.target - self.current) * self.speed;
     }
 
@@ -158,6 +323,11 @@ pub enum UiState {
 pub trait UiElement {
     /// Draw the UI element
     fn draw(&self, theme: &Theme);
+
+    /// Draw what pops up over every other element, like an open dropdown list
+    ///
+    /// Called after every element has been drawn, in the same order.
+    fn draw_overlay(&self, _theme: &Theme) {}
     
     /// Update the UI element's state
     fn update(&mut self, theme: &Theme, manager: Option<&mut UiManager>);
@@ -176,6 +346,163 @@ pub trait UiElement {
     
     /// Get a mutable reference to the element as Any
     fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Check if the element is drawn
+    fn is_visible(&self) -> bool {
+        true
+    }
+
+    /// Show or hide the element
+    fn set_visible(&mut self, _visible: bool) {}
+
+    /// Check if the element reacts to input
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    /// Enable or disable the element
+    fn set_enabled(&mut self, _enabled: bool) {}
+
+    /// Move the element so its bounds start at (x, y)
+    fn set_position(&mut self, _x: f32, _y: f32) {}
+
+    /// Resize the element's bounds
+    fn set_size(&mut self, _w: f32, _h: f32) {}
+
+    /// Get the element's opacity (0.0 to 1.0)
+    fn opacity(&self) -> f32 {
+        1.0
+    }
+
+    /// Set the element's opacity (0.0 to 1.0)
+    fn set_opacity(&mut self, _opacity: f32) {}
+
+    /// Set the element's main color, for elements that have one
+    fn set_color(&mut self, _color: Color) {}
+
+    /// Check if gamepad navigation can move focus to the element
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Mouse cursor to show while the pointer is at `point` over the element
+    fn cursor_icon(&self, _point: Vec2) -> Option<CursorIcon> {
+        None
+    }
+
+    /// Refresh text that refers to localization keys
+    fn localize(&mut self, _localization: &Localization) {}
+
+    /// Check if the element has keyboard focus
+    fn has_focus(&self) -> bool {
+        false
+    }
+
+    /// Trigger the element from the confirm button while it has focus
+    fn activate(&mut self) {}
+
+    /// Handle a directional press while the element has focus
+    ///
+    /// Returns `true` if the element used it, so focus stays put.
+    fn navigate(&mut self, _direction: NavDirection) -> bool {
+        false
+    }
+
+    /// Called by the manager when the pointer moves onto the element
+    fn on_mouse_enter(&mut self) {}
+
+    /// Called by the manager when the pointer moves off the element
+    fn on_mouse_leave(&mut self) {}
+
+    /// Called by the manager when the element gains keyboard or gamepad focus
+    fn on_focus(&mut self) {}
+
+    /// Called by the manager when the element loses keyboard or gamepad focus
+    fn on_blur(&mut self) {}
+}
+
+/// Implement the `UiElement` accessors an element keeps in plain fields
+///
+/// `bounds` and `position` use `x`, `y`, `w` and `h`, `visible`, `enabled`
+/// and `opacity` use the fields of the same name.
+macro_rules! element_fields {
+    ($($part:ident),+ $(,)?) => {
+        $(element_fields!(@ $part);)+
+    };
+    (@ bounds) => {
+        fn get_bounds(&self) -> (f32, f32, f32, f32) {
+            (self.x, self.y, self.w, self.h)
+        }
+    };
+    (@ position) => {
+        fn set_position(&mut self, x: f32, y: f32) {
+            self.x = x;
+            self.y = y;
+        }
+
+        fn set_size(&mut self, w: f32, h: f32) {
+            self.w = w;
+            self.h = h;
+        }
+    };
+    (@ visible) => {
+        fn is_visible(&self) -> bool {
+            self.visible
+        }
+
+        fn set_visible(&mut self, visible: bool) {
+            self.visible = visible;
+        }
+    };
+    (@ enabled) => {
+        fn is_enabled(&self) -> bool {
+            self.enabled
+        }
+
+        fn set_enabled(&mut self, enabled: bool) {
+            self.enabled = enabled;
+        }
+    };
+    (@ opacity) => {
+        fn opacity(&self) -> f32 {
+            self.opacity
+        }
+
+        fn set_opacity(&mut self, opacity: f32) {
+            self.opacity = opacity.clamp(0.0, 1.0);
+        }
+    };
+}
+
+/// Draw an element, greyed out if disabled, faded by its opacity and skipped if hidden
+fn draw_element(element: &dyn UiElement, theme: &Theme) {
+    draw_faded(element, theme, |element, theme| element.draw(theme));
+}
+
+/// Draw an element's overlay the same way `draw_element` draws the element
+fn draw_element_overlay(element: &dyn UiElement, theme: &Theme) {
+    draw_faded(element, theme, |element, theme| element.draw_overlay(theme));
+}
+
+/// Run `draw` with the theme greyed out if the element is disabled and faded by its opacity
+fn draw_faded(element: &dyn UiElement, theme: &Theme, draw: impl Fn(&dyn UiElement, &Theme)) {
+    let opacity = element.opacity();
+    if !element.is_visible() || opacity <= 0.0 {
+        return;
+    }
+    if element.is_enabled() && opacity >= 1.0 {
+        draw(element, theme);
+        return;
+    }
+    let theme = if element.is_enabled() { theme.clone() } else { theme.disabled() };
+    draw(element, &theme.with_opacity(opacity));
+}
+
+/// Update an element if it is visible and enabled
+fn update_element(element: &mut dyn UiElement, theme: &Theme, manager: Option<&mut UiManager>) {
+    if element.is_visible() && element.is_enabled() {
+        element.update(theme, manager);
+    }
 }
 
 /// Helper function to draw rounded rectangles
@@ -192,9 +519,208 @@ fn draw_rounded_rectangle(x: f32, y: f32, width: f32, height: f32, radius: f32,
     draw_rectangle(x + width - diameter, y + height - diameter, diameter, diameter, color);
 }
 
+thread_local! {
+    /// Scale of the `UiManager` currently updating or drawing
+    static UI_SCALE: Cell<f32> = const { Cell::new(1.0) };
+    /// Smallest font size the current `UiManager` allows
+    static MIN_FONT_SIZE: Cell<u16> = const { Cell::new(0) };
+    /// Whether the current `UiManager` asked for reduced motion
+    static REDUCED_MOTION: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Interaction a `UiManager` sound hook can play a sound for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UiSound {
+    /// The pointer moved onto an interactive element
+    HoverEnter,
+    /// A button was clicked
+    Click,
+    /// A slider, checkbox, input, list or dropdown changed value
+    ValueChange,
+    /// A dropdown list opened
+    DropdownOpen,
+}
+
+thread_local! {
+    /// Sounds raised by elements since the manager last collected them
+    static PENDING_SOUNDS: RefCell<Vec<UiSound>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Queue a sound for the manager's sound hook
+fn emit_sound(sound: UiSound) {
+    PENDING_SOUNDS.with(|sounds| sounds.borrow_mut().push(sound));
+}
+
+/// What happened in a `UiEvent`
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiEventKind {
+    /// A button was clicked
+    Clicked,
+    /// A checkbox changed state
+    Checked(CheckState),
+    /// A slider moved
+    ValueChanged(f32),
+    /// An input's text changed
+    TextChanged(String),
+    /// A dropdown option or list row was selected
+    Selected(usize),
+    /// A multi-select dropdown's selection changed
+    MultiSelected(Vec<usize>),
+    /// A panel's close button was clicked
+    Closed,
+    /// A panel was minimized (`true`) or restored (`false`)
+    Minimized(bool),
+    /// The pointer moved onto the element
+    MouseEnter,
+    /// The pointer moved off the element
+    MouseLeave,
+    /// The element gained keyboard or gamepad focus
+    Focus,
+    /// The element lost keyboard or gamepad focus
+    Blur,
+}
+
+/// Event raised by an element, drained from the `UiManager` by the app
+///
+/// Handling events in the game loop gives access to app state without
+/// sharing it with callbacks through `Arc<Mutex<...>>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiEvent {
+    /// Index path of the element, through nested panels
+    pub path: Vec<usize>,
+    /// Name the element was added with in its manager or panel, if any
+    pub name: Option<String>,
+    pub kind: UiEventKind,
+}
+
+thread_local! {
+    /// Index path of the element the manager is currently running
+    static EVENT_PATH: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    /// Events raised since the manager last collected them
+    static PENDING_EVENTS: RefCell<Vec<(Vec<usize>, UiEventKind)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `f` with `index` appended to the current event path
+fn with_event_path<R>(index: usize, f: impl FnOnce() -> R) -> R {
+    EVENT_PATH.with(|path| path.borrow_mut().push(index));
+    let result = f();
+    EVENT_PATH.with(|path| path.borrow_mut().pop());
+    result
+}
+
+/// Queue an event for the element currently running
+///
+/// Elements used outside a `UiManager` have no path, so nothing is queued.
+fn emit_event(kind: UiEventKind) {
+    let path = EVENT_PATH.with(|path| path.borrow().clone());
+    if !path.is_empty() {
+        PENDING_EVENTS.with(|events| events.borrow_mut().push((path, kind)));
+    }
+}
+
+/// Raise a font size to the accessibility minimum
+fn ui_font_size(size: u16) -> u16 {
+    size.max(MIN_FONT_SIZE.with(|min| min.get()))
+}
+
+/// Check if animations should snap instead of easing
+fn reduced_motion() -> bool {
+    REDUCED_MOTION.with(|reduced| reduced.get())
+}
+
+/// Get the scale the UI is currently laid out at
+fn current_ui_scale() -> f32 {
+    UI_SCALE.with(|scale| scale.get())
+}
+
+/// Mouse position in unscaled UI coordinates
+fn ui_mouse_position() -> (f32, f32) {
+    let (x, y) = mouse_position();
+    let scale = current_ui_scale();
+    (x / scale, y / scale)
+}
+
+/// Screen size in unscaled UI coordinates
+fn ui_screen_size() -> (f32, f32) {
+    let scale = current_ui_scale();
+    (screen_width() / scale, screen_height() / scale)
+}
+
+/// Camera showing `width` by `height` UI units across the screen, with y pointing down
+///
+/// `Camera2D::from_display_rect` flips y for render targets, which drawing to
+/// the screen flips again, so the zoom is built here instead.
+fn ui_camera(width: f32, height: f32) -> Camera2D {
+    Camera2D {
+        target: vec2(width / 2.0, height / 2.0),
+        zoom: vec2(2.0 / width, 2.0 / height),
+        ..Default::default()
+    }
+}
+
+/// Get the operating system's display scale factor
+///
+/// Pass this to `UiManager::set_ui_scale` to keep the UI readable on high-DPI screens.
+pub fn system_ui_scale() -> f32 {
+    screen_dpi_scale()
+}
+
+thread_local! {
+    /// Nested clip rectangles, innermost last
+    static CLIP_STACK: RefCell<Vec<(f32, f32, f32, f32)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Restrict drawing to a screen rectangle, or lift the restriction with `None`
+fn set_clip_rect(rect: Option<(f32, f32, f32, f32)>) {
+    let scale = screen_dpi_scale() * current_ui_scale();
+    let clip = rect.map(|(x, y, w, h)| {
+        (
+            (x * scale) as i32,
+            (y * scale) as i32,
+            (w.max(0.0) * scale) as i32,
+            (h.max(0.0) * scale) as i32,
+        )
+    });
+    unsafe {
+        get_internal_gl().quad_gl.scissor(clip);
+    }
+}
+
+/// Clip drawing to a rectangle inside the current clip region until `pop_clip_rect`
+fn push_clip_rect(x: f32, y: f32, w: f32, h: f32) {
+    let rect = CLIP_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let rect = match stack.last() {
+            Some(&(px, py, pw, ph)) => {
+                let left = x.max(px);
+                let top = y.max(py);
+                let right = (x + w).min(px + pw);
+                let bottom = (y + h).min(py + ph);
+                (left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+            }
+            None => (x, y, w, h),
+        };
+        stack.push(rect);
+        rect
+    });
+    set_clip_rect(Some(rect));
+}
+
+/// Restore the clip region from before the last `push_clip_rect`
+fn pop_clip_rect() {
+    let rect = CLIP_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        stack.pop();
+        stack.last().copied()
+    });
+    set_clip_rect(rect);
+}
+
 /// Text UI element
 pub struct UiText {
     pub text: String,
+    /// Localization key the text is looked up from
+    pub text_key: Option<String>,
     pub x: f32,
     pub y: f32,
     pub font_size: u16,
@@ -202,6 +728,19 @@ pub struct UiText {
     pub font: Font,
     pub animation: Animation,
     pub alignment: TextAlignment,
+    /// Where the text block sits relative to `y`
+    pub vertical_alignment: VerticalAlignment,
+    /// Wrap words onto new lines past this width
+    pub max_width: Option<f32>,
+    /// Line height as a multiple of the font size
+    pub line_spacing: f32,
+    /// Draw with the theme's text color instead of `color`
+    pub use_theme_color: bool,
+    pub shadow: Option<TextShadow>,
+    pub outline: Option<TextOutline>,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
 }
 
 /// Text alignment options
@@ -212,11 +751,146 @@ pub enum TextAlignment {
     Right,
 }
 
+/// Vertical text alignment options
+#[derive(Clone, Copy, PartialEq)]
+pub enum VerticalAlignment {
+    /// `y` is the baseline of the first line
+    Baseline,
+    /// `y` is the top of the text block
+    Top,
+    /// `y` is the middle of the text block
+    Middle,
+    /// `y` is the bottom of the text block
+    Bottom,
+}
+
+/// Drop shadow drawn behind text
+#[derive(Clone, Copy)]
+pub struct TextShadow {
+    pub color: Color,
+    pub offset: Vec2,
+    /// Spread of the shadow in pixels, 0.0 for a hard shadow
+    pub blur: f32,
+}
+
+impl TextShadow {
+    /// Create a hard shadow
+    pub fn new(color: Color, offset: Vec2) -> Self {
+        Self { color, offset, blur: 0.0 }
+    }
+
+    /// Build a shadow from a style's shadow color, offset and blur
+    pub fn from_style(style: &UiStyle) -> Self {
+        Self {
+            color: style.shadow_color,
+            offset: style.shadow_offset,
+            blur: style.shadow_blur,
+        }
+    }
+}
+
+/// Outline drawn around text
+#[derive(Clone, Copy)]
+pub struct TextOutline {
+    pub color: Color,
+    pub thickness: f32,
+}
+
+impl TextOutline {
+    /// Create an outline
+    pub fn new(color: Color, thickness: f32) -> Self {
+        Self { color, thickness }
+    }
+}
+
+/// Offsets of the eight compass points at `radius`
+fn ring_offsets(radius: f32) -> [Vec2; 8] {
+    let d = radius * std::f32::consts::FRAC_1_SQRT_2;
+    [
+        vec2(radius, 0.0),
+        vec2(-radius, 0.0),
+        vec2(0.0, radius),
+        vec2(0.0, -radius),
+        vec2(d, d),
+        vec2(-d, d),
+        vec2(d, -d),
+        vec2(-d, -d),
+    ]
+}
+
+/// Draw text with an optional shadow and outline behind it
+///
+/// Shadow and outline colors are faded along with the text color's alpha.
+fn draw_text_with_effects(
+    text: &str,
+    x: f32,
+    y: f32,
+    params: TextParams,
+    shadow: Option<&TextShadow>,
+    outline: Option<&TextOutline>,
+) {
+    let fade = |color: Color| Color::new(color.r, color.g, color.b, color.a * params.color.a);
+    if let Some(shadow) = shadow {
+        let (x, y) = (x + shadow.offset.x, y + shadow.offset.y);
+        if shadow.blur > 0.0 {
+            // Approximate blur with faint copies spread around the offset
+            let mut color = fade(shadow.color);
+            color.a /= 9.0;
+            let params = TextParams { color, ..params.clone() };
+            draw_text_ex(text, x, y, params.clone());
+            for offset in ring_offsets(shadow.blur / 2.0) {
+                draw_text_ex(text, x + offset.x, y + offset.y, params.clone());
+            }
+        } else {
+            draw_text_ex(text, x, y, TextParams { color: fade(shadow.color), ..params.clone() });
+        }
+    }
+    if let Some(outline) = outline {
+        let params = TextParams { color: fade(outline.color), ..params.clone() };
+        for offset in ring_offsets(outline.thickness) {
+            draw_text_ex(text, x + offset.x, y + offset.y, params.clone());
+        }
+    }
+    draw_text_ex(text, x, y, params);
+}
+
+/// Break text into lines no wider than `max_width`, keeping explicit newlines
+///
+/// Words longer than a whole line are split between characters.
+fn wrap_text(text: &str, font: &Font, font_size: u16, max_width: f32) -> Vec<String> {
+    let width = |s: &str| measure_text(s, Some(font), font_size, 1.0).width;
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if width(&candidate) <= max_width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in word.chars() {
+                line.push(c);
+                if width(&line) > max_width && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::take(&mut line));
+                    line.push(c);
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
 impl UiText {
     /// Create a new text element
     pub fn new(text: &str, x: f32, y: f32, font_size: u16, color: Color, font: Font) -> Self {
         Self {
             text: text.to_string(),
+            text_key: None,
             x,
             y,
             font_size,
@@ -224,43 +898,141 @@ impl UiText {
             font,
             animation: Animation::new(1.0, 0.2),
             alignment: TextAlignment::Left,
+            vertical_alignment: VerticalAlignment::Baseline,
+            max_width: None,
+            line_spacing: 1.0,
+            use_theme_color: false,
+            shadow: None,
+            outline: None,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
         }
     }
 
+    /// Look the text up from a localization key whenever the language changes
+    pub fn set_text_key(&mut self, key: &str) {
+        self.text_key = Some(key.to_string());
+    }
+
     /// Set the text alignment
     pub fn set_alignment(&mut self, alignment: TextAlignment) {
         self.alignment = alignment;
     }
+
+    /// Follow the theme's text color so theme switches restyle the text
+    pub fn set_use_theme_color(&mut self, use_theme_color: bool) {
+        self.use_theme_color = use_theme_color;
+    }
+
+    /// Draw a drop shadow behind the text, or remove it with `None`
+    pub fn set_shadow(&mut self, shadow: Option<TextShadow>) {
+        self.shadow = shadow;
+    }
+
+    /// Draw an outline around the text, or remove it with `None`
+    pub fn set_outline(&mut self, outline: Option<TextOutline>) {
+        self.outline = outline;
+    }
+
+    /// Set the vertical alignment
+    pub fn set_vertical_alignment(&mut self, alignment: VerticalAlignment) {
+        self.vertical_alignment = alignment;
+    }
+
+    /// Wrap words past `max_width`, or keep each line unbounded with `None`
+    pub fn set_max_width(&mut self, max_width: Option<f32>) {
+        self.max_width = max_width;
+    }
+
+    /// Set the line height as a multiple of the font size
+    pub fn set_line_spacing(&mut self, line_spacing: f32) {
+        self.line_spacing = line_spacing;
+    }
+
+    /// Get the lines the text is drawn as, after wrapping
+    pub fn lines(&self) -> Vec<String> {
+        match self.max_width {
+            Some(max_width) => wrap_text(&self.text, &self.font, ui_font_size(self.font_size), max_width),
+            None => self.text.split('\n').map(str::to_string).collect(),
+        }
+    }
+
+    /// Distance between the baselines of two lines
+    fn line_height(&self) -> f32 {
+        ui_font_size(self.font_size) as f32 * self.line_spacing
+    }
+
+    /// Height from the top of a line to its baseline
+    fn ascent(&self) -> f32 {
+        measure_text("Ag", Some(&self.font), ui_font_size(self.font_size), 1.0).offset_y
+    }
+
+    /// Bounds of the text block laid out as `lines`
+    fn block_bounds(&self, lines: &[String]) -> (f32, f32, f32, f32) {
+        let width = lines
+            .iter()
+            .map(|line| measure_text(line, Some(&self.font), ui_font_size(self.font_size), 1.0).width)
+            .fold(0.0, f32::max);
+        let height = self.line_height() * lines.len() as f32;
+        let x = match self.alignment {
+            TextAlignment::Left => self.x,
+            TextAlignment::Center => self.x - width / 2.0,
+            TextAlignment::Right => self.x - width,
+        };
+        let y = match self.vertical_alignment {
+            VerticalAlignment::Baseline => self.y - self.ascent(),
+            VerticalAlignment::Top => self.y,
+            VerticalAlignment::Middle => self.y - height / 2.0,
+            VerticalAlignment::Bottom => self.y - height,
+        };
+        (x, y, width, height)
+    }
 }
 
 impl UiElement for UiText {
+    element_fields!(visible, enabled, opacity);
+
     fn draw(&self, theme: &Theme) {
         let alpha = self.animation.current;
+        // Disabled text takes the greyed-out theme color, which `draw_element` has already faded
+        let base = if self.enabled && !self.use_theme_color {
+            Color::new(self.color.r, self.color.g, self.color.b, self.color.a * self.opacity)
+        } else {
+            theme.text
+        };
         let color = Color::new(
-            self.color.r,
-            self.color.g,
-            self.color.b,
-            self.color.a * alpha as f32,
+            base.r,
+            base.g,
+            base.b,
+            base.a * alpha,
         );
 
-        let text_dim = measure_text(&self.text, Some(&self.font), self.font_size, 1.0);
-        let x = match self.alignment {
-            TextAlignment::Left => self.x,
-            TextAlignment::Center => self.x - text_dim.width / 2.0,
-            TextAlignment::Right => self.x - text_dim.width,
-        };
-
-        draw_text_ex(
-            &self.text,
-            x,
-            self.y,
-            TextParams {
-                font: Some(&self.font),
-                font_size: self.font_size,
-                color,
-                ..Default::default()
-            },
-        );
+        let lines = self.lines();
+        let (_, top, _, _) = self.block_bounds(&lines);
+        let ascent = self.ascent();
+        for (i, line) in lines.iter().enumerate() {
+            let line_width = measure_text(line, Some(&self.font), ui_font_size(self.font_size), 1.0).width;
+            let x = match self.alignment {
+                TextAlignment::Left => self.x,
+                TextAlignment::Center => self.x - line_width / 2.0,
+                TextAlignment::Right => self.x - line_width,
+            };
+
+            draw_text_with_effects(
+                line,
+                x,
+                top + ascent + self.line_height() * i as f32,
+                TextParams {
+                    font: Some(&self.font),
+                    font_size: ui_font_size(self.font_size),
+                    color,
+                    ..Default::default()
+                },
+                self.shadow.as_ref(),
+                self.outline.as_ref(),
+            );
+        }
     }
 
     fn update(&mut self, _theme: &Theme, _manager: Option<&mut UiManager>) {
@@ -268,8 +1040,7 @@ impl UiElement for UiText {
     }
 
     fn get_bounds(&self) -> (f32, f32, f32, f32) {
-        let text_dim = measure_text(&self.text, Some(&self.font), self.font_size, 1.0);
-        (self.x, self.y, text_dim.width, text_dim.height)
+        self.block_bounds(&self.lines())
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -279,26 +1050,74 @@ impl UiElement for UiText {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        // x and y are alignment anchors, so shift them by how far the bounds move
+        let (bx, by, _, _) = self.get_bounds();
+        self.x += x - bx;
+        self.y += y - by;
+    }
+
+    fn localize(&mut self, localization: &Localization) {
+        if let Some(key) = &self.text_key {
+            self.text = localization.get(key);
+        }
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+}
+
+/// Timing for buttons that keep firing while held
+#[derive(Clone, Copy)]
+pub struct ButtonRepeat {
+    /// Seconds held before the first repeat
+    pub delay: f32,
+    /// Seconds between repeats after that
+    pub interval: f32,
+}
+
+impl ButtonRepeat {
+    pub fn new(delay: f32, interval: f32) -> Self {
+        Self { delay, interval }
+    }
+}
+
+impl Default for ButtonRepeat {
+    fn default() -> Self {
+        Self::new(0.4, 0.08)
+    }
 }
 
 /// Button UI element
 pub struct UiButton {
     pub text: String,
+    /// Localization key the label is looked up from
+    pub text_key: Option<String>,
     pub x: f32,
     pub y: f32,
     pub w: f32,
     pub h: f32,
     pub font_size: u16,
     pub font: Font,
-    pub theme: Theme,
     pub on_click: Option<Box<dyn FnMut() + Send + Sync>>,
     pub hover_animation: Animation,
     pub press_animation: Animation,
     pub disabled: bool,
+    pub text_shadow: Option<TextShadow>,
+    pub text_outline: Option<TextOutline>,
+    /// Keep firing `on_click` while held
+    pub repeat: Option<ButtonRepeat>,
+    pub opacity: f32,
+    pub visible: bool,
+    held: bool,
+    repeat_timer: f32,
 }
 
 impl UiButton {
     /// Create a new button
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         text: &str,
         x: f32,
@@ -307,37 +1126,73 @@ impl UiButton {
         h: f32,
         font_size: u16,
         font: Font,
-        theme: Theme,
         on_click: Option<Box<dyn FnMut() + Send + Sync>>,
     ) -> Self {
         Self {
             text: text.to_string(),
+            text_key: None,
             x,
             y,
             w,
             h,
             font_size,
             font,
-            theme,
             on_click,
             hover_animation: Animation::new(0.0, 0.2),
             press_animation: Animation::new(0.0, 0.3),
             disabled: false,
+            text_shadow: None,
+            text_outline: None,
+            repeat: None,
+            opacity: 1.0,
+            visible: true,
+            held: false,
+            repeat_timer: 0.0,
         }
     }
 
-    pub fn is_mouse_over(&self) -> bool {
-        let (mx, my) = mouse_position();
-        mx >= self.x && mx <= self.x + self.w && my >= self.y && my <= self.y + self.h
+    /// Look the label up from a localization key whenever the language changes
+    pub fn set_text_key(&mut self, key: &str) {
+        self.text_key = Some(key.to_string());
     }
-}
 
-impl UiElement for UiButton {
-    fn draw(&self, theme: &Theme) {
-        let hover = self.hover_animation.current;
-        let press = self.press_animation.current;
-        
-        let base_color = if self.disabled {
+    /// Fire `on_click` repeatedly while the button is held, or only once with `None`
+    pub fn set_repeat(&mut self, repeat: Option<ButtonRepeat>) {
+        self.repeat = repeat;
+    }
+
+    fn click(&mut self) {
+        emit_sound(UiSound::Click);
+        emit_event(UiEventKind::Clicked);
+        if let Some(cb) = &mut self.on_click {
+            cb();
+        }
+    }
+
+    /// Draw a drop shadow behind the label, or remove it with `None`
+    pub fn set_text_shadow(&mut self, shadow: Option<TextShadow>) {
+        self.text_shadow = shadow;
+    }
+
+    /// Draw an outline around the label, or remove it with `None`
+    pub fn set_text_outline(&mut self, outline: Option<TextOutline>) {
+        self.text_outline = outline;
+    }
+
+    pub fn is_mouse_over(&self) -> bool {
+        let (mx, my) = ui_mouse_position();
+        mx >= self.x && mx <= self.x + self.w && my >= self.y && my <= self.y + self.h
+    }
+}
+
+impl UiElement for UiButton {
+    element_fields!(bounds, position, visible, opacity);
+
+    fn draw(&self, theme: &Theme) {
+        let hover = self.hover_animation.current;
+        let press = self.press_animation.current;
+        
+        let base_color = if self.disabled {
             theme.secondary
         } else {
             theme.primary
@@ -373,31 +1228,34 @@ impl UiElement for UiButton {
         }
 
         // Draw text
-        let text_dim = measure_text(&self.text, Some(&self.font), self.font_size, 1.0);
+        let text_dim = measure_text(&self.text, Some(&self.font), ui_font_size(self.font_size), 1.0);
         let tx = self.x + (self.w - text_dim.width) / 2.0;
         let ty = self.y + (self.h + text_dim.height) / 2.0 - 4.0;
         
         let text_color = if self.disabled {
-            Color::new(0.5, 0.5, 0.5, 1.0)
+            mix_color(theme.text, theme.secondary, 0.5)
         } else {
             theme.text
         };
 
-        draw_text_ex(
+        draw_text_with_effects(
             &self.text,
             tx,
             ty,
             TextParams {
                 font: Some(&self.font),
-                font_size: self.font_size,
+                font_size: ui_font_size(self.font_size),
                 color: text_color,
                 ..Default::default()
             },
+            self.text_shadow.as_ref(),
+            self.text_outline.as_ref(),
         );
     }
 
     fn update(&mut self, _theme: &Theme, _manager: Option<&mut UiManager>) {
         if self.disabled {
+            self.held = false;
             self.hover_animation.set_target(0.0);
             self.press_animation.set_target(0.0);
         } else {
@@ -407,8 +1265,18 @@ impl UiElement for UiButton {
             self.press_animation.set_target(if mouse_down && self.is_mouse_over() { 1.0 } else { 0.0 });
 
             if self.is_mouse_over() && is_mouse_button_pressed(MouseButton::Left) {
-                if let Some(cb) = &mut self.on_click {
-                    cb();
+                self.click();
+                self.held = true;
+                self.repeat_timer = self.repeat.map_or(0.0, |r| r.delay);
+            } else if self.held {
+                if !mouse_down || !self.is_mouse_over() {
+                    self.held = false;
+                } else if let Some(repeat) = self.repeat {
+                    self.repeat_timer -= get_frame_time();
+                    if self.repeat_timer <= 0.0 {
+                        self.click();
+                        self.repeat_timer = repeat.interval;
+                    }
                 }
             }
         }
@@ -417,10 +1285,6 @@ impl UiElement for UiButton {
         self.press_animation.update();
     }
 
-    fn get_bounds(&self) -> (f32, f32, f32, f32) {
-        (self.x, self.y, self.w, self.h)
-    }
-
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -428,8 +1292,75 @@ impl UiElement for UiButton {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn is_enabled(&self) -> bool {
+        !self.disabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.disabled = !enabled;
+    }
+
+    fn cursor_icon(&self, _point: Vec2) -> Option<CursorIcon> {
+        Some(if self.disabled { CursorIcon::NotAllowed } else { CursorIcon::Pointer })
+    }
+
+    fn localize(&mut self, localization: &Localization) {
+        if let Some(key) = &self.text_key {
+            self.text = localization.get(key);
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self) {
+        if !self.disabled {
+            emit_event(UiEventKind::Clicked);
+            if let Some(cb) = &mut self.on_click {
+                cb();
+            }
+        }
+    }
+}
+
+/// Character filter for input fields
+pub enum InputFilter {
+    /// Accept any printable character
+    Any,
+    /// Accept a decimal number with an optional leading minus sign
+    Numeric,
+    /// Accept a whole number with an optional leading minus sign
+    Integer,
+    /// Accept characters matching a custom predicate
+    Custom(Box<dyn Fn(char) -> bool + Send + Sync>),
+}
+
+impl InputFilter {
+    /// Check if `c` may be inserted at `position` in `text`
+    fn accepts(&self, text: &str, position: usize, c: char) -> bool {
+        match self {
+            InputFilter::Any => true,
+            InputFilter::Numeric => {
+                c.is_ascii_digit()
+                    || (c == '-' && position == 0 && !text.starts_with('-'))
+                    || (c == '.' && !text.contains('.') && !(position == 0 && text.starts_with('-')))
+            }
+            InputFilter::Integer => {
+                c.is_ascii_digit() || (c == '-' && position == 0 && !text.starts_with('-'))
+            }
+            InputFilter::Custom(predicate) => predicate(c),
+        }
+    }
 }
 
+/// Callback run with an input's text whenever the user changes it
+pub type ChangeCallback = Box<dyn FnMut(&str) + Send + Sync>;
+
+/// Check run on an input's text, returning the message to show when it is invalid
+pub type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
 /// Input field UI element
 pub struct UiInput {
     pub text: String,
@@ -439,16 +1370,33 @@ pub struct UiInput {
     pub h: f32,
     pub font_size: u16,
     pub font: Font,
-    pub theme: Theme,
     pub placeholder: String,
     pub focused: bool,
     pub cursor_position: usize,
     pub cursor_animation: Animation,
-    pub on_change: Option<Box<dyn FnMut(&str) + Send + Sync>>,
+    pub on_change: Option<ChangeCallback>,
+    /// Position where the current selection started, if any
+    pub selection_anchor: Option<usize>,
+    /// Validator run on every change
+    pub validator: Option<Validator>,
+    /// Error from the last failed validation
+    pub error_message: Option<String>,
+    /// Whether to draw the error message below the field
+    pub show_error_message: bool,
+    /// Filter applied to typed and pasted characters
+    pub filter: InputFilter,
+    /// Maximum number of characters, if limited
+    pub max_length: Option<usize>,
+    /// Horizontal scroll applied to the text so the cursor stays visible
+    pub scroll_offset: f32,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
 }
 
 impl UiInput {
     /// Create a new input field
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         x: f32,
         y: f32,
@@ -456,9 +1404,8 @@ impl UiInput {
         h: f32,
         font_size: u16,
         font: Font,
-        theme: Theme,
         placeholder: &str,
-        on_change: Option<Box<dyn FnMut(&str) + Send + Sync>>,
+        on_change: Option<ChangeCallback>,
     ) -> Self {
         Self {
             text: String::new(),
@@ -468,22 +1415,179 @@ impl UiInput {
             h,
             font_size,
             font,
-            theme,
             placeholder: placeholder.to_string(),
             focused: false,
             cursor_position: 0,
             cursor_animation: Animation::new(1.0, 0.1),
             on_change,
+            selection_anchor: None,
+            validator: None,
+            error_message: None,
+            show_error_message: false,
+            filter: InputFilter::Any,
+            max_length: None,
+            scroll_offset: 0.0,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
+        }
+    }
+
+    /// Width of the text up to a byte position
+    fn text_width(&self, position: usize) -> f32 {
+        measure_text(&self.text[..position], Some(&self.font), ui_font_size(self.font_size), 1.0).width
+    }
+
+    /// Scroll the text so the cursor stays inside the visible area
+    fn update_scroll(&mut self, padding: f32) {
+        let visible_width = (self.w - padding * 2.0).max(0.0);
+        let cursor_x = self.text_width(self.cursor_position);
+        let text_width = self.text_width(self.text.len());
+
+        if cursor_x - self.scroll_offset > visible_width {
+            self.scroll_offset = cursor_x - visible_width;
+        } else if cursor_x < self.scroll_offset {
+            self.scroll_offset = cursor_x;
         }
+        // Don't leave empty space after the text once it is shortened
+        self.scroll_offset = self.scroll_offset.min((text_width - visible_width).max(0.0));
+    }
+
+    /// Set the character filter
+    pub fn set_filter(&mut self, filter: InputFilter) {
+        self.filter = filter;
+    }
+
+    /// Set the maximum number of characters
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+    }
+
+    /// Set a validator and check the current text against it
+    pub fn set_validator(&mut self, validator: Validator) {
+        self.validator = Some(validator);
+        self.validate();
+    }
+
+    /// Set whether the error message is drawn below the field
+    pub fn set_show_error_message(&mut self, show: bool) {
+        self.show_error_message = show;
+    }
+
+    /// Run the validator against the current text
+    pub fn validate(&mut self) -> bool {
+        self.error_message = match &self.validator {
+            Some(validator) => validator(&self.text).err(),
+            None => None,
+        };
+        self.error_message.is_none()
+    }
+
+    /// Check if the last validation passed
+    pub fn is_valid(&self) -> bool {
+        self.error_message.is_none()
     }
 
     pub fn is_mouse_over(&self) -> bool {
-        let (mx, my) = mouse_position();
+        let (mx, my) = ui_mouse_position();
         mx >= self.x && mx <= self.x + self.w && my >= self.y && my <= self.y + self.h
     }
+
+    /// Get the selected byte range as (start, end), if anything is selected
+    pub fn selection_range(&self) -> Option<(usize, usize)> {
+        let anchor = self.selection_anchor?;
+        if anchor == self.cursor_position {
+            return None;
+        }
+        Some((anchor.min(self.cursor_position), anchor.max(self.cursor_position)))
+    }
+
+    /// Get the currently selected text
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection_range().map(|(start, end)| &self.text[start..end])
+    }
+
+    /// Select the whole text
+    pub fn select_all(&mut self) {
+        self.selection_anchor = Some(0);
+        self.cursor_position = self.text.len();
+    }
+
+    /// Clear the selection without changing the text
+    pub fn clear_selection(&mut self) {
+        self.selection_anchor = None;
+    }
+
+    /// Remove the selected text, returning true if anything was removed
+    pub fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.selection_range() else {
+            self.selection_anchor = None;
+            return false;
+        };
+        self.text.replace_range(start..end, "");
+        self.cursor_position = start;
+        self.selection_anchor = None;
+        true
+    }
+
+    /// Insert text at the cursor, replacing the selection if there is one
+    ///
+    /// Characters rejected by the filter or past the max length are dropped.
+    pub fn insert_text(&mut self, text: &str) {
+        self.delete_selection();
+        for c in text.chars() {
+            if !c.is_ascii() || c.is_control() {
+                continue;
+            }
+            if self.max_length.is_some_and(|max| self.text.len() >= max) {
+                break;
+            }
+            if self.filter.accepts(&self.text, self.cursor_position, c) {
+                self.text.insert(self.cursor_position, c);
+                self.cursor_position += 1;
+            }
+        }
+    }
+
+    /// Replace the text without running `on_change`
+    ///
+    /// The new text goes through the filter and max length like typed text,
+    /// the selection is cleared and the text is validated again.
+    pub fn set_text(&mut self, text: &str) {
+        let cursor = self.cursor_position;
+        self.text.clear();
+        self.cursor_position = 0;
+        self.selection_anchor = None;
+        self.insert_text(text);
+        self.cursor_position = cursor.min(self.text.len());
+        self.validate();
+    }
+
+    /// Move the cursor, extending the selection when `select` is true
+    fn move_cursor(&mut self, position: usize, select: bool) {
+        if select {
+            if self.selection_anchor.is_none() {
+                self.selection_anchor = Some(self.cursor_position);
+            }
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor_position = position;
+    }
+
+    fn notify_change(&mut self) {
+        emit_sound(UiSound::ValueChange);
+        self.validate();
+        emit_event(UiEventKind::TextChanged(self.text.clone()));
+        if let Some(cb) = &mut self.on_change {
+            cb(&self.text);
+        }
+    }
 }
 
 impl UiElement for UiInput {
+    element_fields!(bounds, position, visible, enabled, opacity);
+
     fn draw(&self, theme: &Theme) {
         // Draw background
         draw_rounded_rectangle(
@@ -493,17 +1597,19 @@ impl UiElement for UiInput {
             self.h,
             theme.border_radius,
             if self.focused {
-                Color::new(0.2, 0.2, 0.2, 1.0)
+                mix_color(theme.secondary, theme.text, 0.1)
             } else {
                 theme.secondary
             },
         );
 
         // Draw border
-        let border_color = if self.focused {
+        let border_color = if !self.is_valid() {
+            theme.error
+        } else if self.focused {
             theme.accent
         } else {
-            Color::new(0.3, 0.3, 0.3, 1.0)
+            mix_color(theme.secondary, theme.text, 0.2)
         };
         draw_rectangle_lines(
             self.x,
@@ -522,22 +1628,38 @@ impl UiElement for UiInput {
         };
 
         let text_color = if self.text.is_empty() && !self.focused {
-            Color::new(0.5, 0.5, 0.5, 1.0)
+            mix_color(theme.text, theme.secondary, 0.5)
         } else {
             theme.text
         };
 
-        let text_dim = measure_text(display_text, Some(&self.font), self.font_size, 1.0);
-        let tx = self.x + theme.padding;
+        let text_dim = measure_text(display_text, Some(&self.font), ui_font_size(self.font_size), 1.0);
+        let tx = self.x + theme.padding - self.scroll_offset;
         let ty = self.y + (self.h + text_dim.height) / 2.0 - 4.0;
 
+        // Clip text, selection and cursor to the field
+        push_clip_rect(self.x + 1.0, self.y + 1.0, self.w - 2.0, self.h - 2.0);
+
+        // Draw selection highlight
+        if self.focused && let Some((start, end)) = self.selection_range() {
+            let start_x = tx + self.text_width(start);
+            let end_x = tx + self.text_width(end);
+            draw_rectangle(
+                start_x,
+                self.y + theme.padding,
+                end_x - start_x,
+                self.h - theme.padding * 2.0,
+                Color::new(theme.accent.r, theme.accent.g, theme.accent.b, 0.4),
+            );
+        }
+
         draw_text_ex(
             display_text,
             tx,
             ty,
             TextParams {
                 font: Some(&self.font),
-                font_size: self.font_size,
+                font_size: ui_font_size(self.font_size),
                 color: text_color,
                 ..Default::default()
             },
@@ -545,12 +1667,7 @@ impl UiElement for UiInput {
 
         // Draw cursor if focused
         if self.focused {
-            let cursor_x = tx + measure_text(
-                &self.text[..self.cursor_position],
-                Some(&self.font),
-                self.font_size,
-                1.0,
-            ).width;
+            let cursor_x = tx + self.text_width(self.cursor_position);
 
             if self.cursor_animation.current > 0.5 {
                 draw_line(
@@ -563,11 +1680,30 @@ impl UiElement for UiInput {
                 );
             }
         }
+
+        pop_clip_rect();
+
+        // Draw error message below the field
+        if self.show_error_message && let Some(message) = &self.error_message {
+            let error_size = ui_font_size((self.font_size as f32 * 0.8) as u16);
+            draw_text_ex(
+                message,
+                self.x,
+                self.y + self.h + error_size as f32 + 2.0,
+                TextParams {
+                    font: Some(&self.font),
+                    font_size: error_size,
+                    color: theme.error,
+                    ..Default::default()
+                },
+            );
+        }
     }
 
     fn update(&mut self, theme: &Theme, _manager: Option<&mut UiManager>) {
         if is_mouse_button_pressed(MouseButton::Left) {
             self.focused = self.is_mouse_over();
+            self.selection_anchor = None;
         }
 
         if self.focused {
@@ -578,38 +1714,76 @@ impl UiElement for UiInput {
                 self.cursor_animation.set_target(0.0);
             }
 
+            let ctrl = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+            let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+
             // Handle text input
-            if let Some(key) = get_char_pressed() {
-                if key.is_ascii() && !key.is_control() {
-                    self.text.insert(self.cursor_position, key);
-                    self.cursor_position += 1;
-                    if let Some(cb) = &mut self.on_change {
-                        cb(&self.text);
-                    }
+            if let Some(key) = get_char_pressed() && !ctrl && key.is_ascii() && !key.is_control() {
+                let before = self.text.clone();
+                self.insert_text(&key.to_string());
+                if self.text != before {
+                    self.notify_change();
+                }
+            }
+
+            // Handle clipboard shortcuts
+            if ctrl {
+                if is_key_pressed(KeyCode::A) {
+                    self.select_all();
+                }
+                if (is_key_pressed(KeyCode::C) || is_key_pressed(KeyCode::X))
+                    && let Some(selected) = self.selected_text()
+                {
+                    miniquad::window::clipboard_set(selected);
+                }
+                if is_key_pressed(KeyCode::X) && self.delete_selection() {
+                    self.notify_change();
+                }
+                if is_key_pressed(KeyCode::V) && let Some(pasted) = miniquad::window::clipboard_get() {
+                    self.insert_text(&pasted);
+                    self.notify_change();
                 }
             }
 
             // Handle backspace
-            if is_key_pressed(KeyCode::Backspace) && self.cursor_position > 0 {
-                self.text.remove(self.cursor_position - 1);
-                self.cursor_position -= 1;
-                if let Some(cb) = &mut self.on_change {
-                    cb(&self.text);
+            if is_key_pressed(KeyCode::Backspace) {
+                if self.delete_selection() {
+                    self.notify_change();
+                } else if self.cursor_position > 0 {
+                    self.text.remove(self.cursor_position - 1);
+                    self.cursor_position -= 1;
+                    self.notify_change();
                 }
             }
 
-            // Handle arrow keys
-            if is_key_pressed(KeyCode::Left) && self.cursor_position > 0 {
-                self.cursor_position -= 1;
+            // Handle delete
+            if is_key_pressed(KeyCode::Delete) {
+                if self.delete_selection() {
+                    self.notify_change();
+                } else if self.cursor_position < self.text.len() {
+                    self.text.remove(self.cursor_position);
+                    self.notify_change();
+                }
             }
-            if is_key_pressed(KeyCode::Right) && self.cursor_position < self.text.len() {
-                self.cursor_position += 1;
+
+            // Handle arrow keys, extending the selection while shift is held
+            if is_key_pressed(KeyCode::Left) {
+                let position = match self.selection_range() {
+                    Some((start, _)) if !shift => start,
+                    _ => self.cursor_position.saturating_sub(1),
+                };
+                self.move_cursor(position, shift);
+            }
+            if is_key_pressed(KeyCode::Right) {
+                let position = match self.selection_range() {
+                    Some((_, end)) if !shift => end,
+                    _ => (self.cursor_position + 1).min(self.text.len()),
+                };
+                self.move_cursor(position, shift);
             }
-        }
-    }
 
-    fn get_bounds(&self) -> (f32, f32, f32, f32) {
-        (self.x, self.y, self.w, self.h)
+            self.update_scroll(theme.padding);
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -619,6 +1793,22 @@ impl UiElement for UiInput {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn cursor_icon(&self, _point: Vec2) -> Option<CursorIcon> {
+        Some(CursorIcon::Text)
+    }
+
+    fn has_focus(&self) -> bool {
+        self.focused
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self) {
+        self.focused = true;
+    }
 }
 
 /// Slider UI element
@@ -630,13 +1820,16 @@ pub struct UiSlider {
     pub value: f32,
     pub min: f32,
     pub max: f32,
-    pub theme: Theme,
     pub dragging: bool,
     pub on_change: Option<Box<dyn FnMut(f32) + Send + Sync>>,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
 }
 
 impl UiSlider {
     /// Create a new slider
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         x: f32,
         y: f32,
@@ -645,7 +1838,6 @@ impl UiSlider {
         min: f32,
         max: f32,
         initial: f32,
-        theme: Theme,
         on_change: Option<Box<dyn FnMut(f32) + Send + Sync>>,
     ) -> Self {
         Self {
@@ -656,14 +1848,16 @@ impl UiSlider {
             value: initial,
             min,
             max,
-            theme,
             dragging: false,
             on_change,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
         }
     }
 
     pub fn is_mouse_over_handle(&self) -> bool {
-        let (mx, my) = mouse_position();
+        let (mx, my) = ui_mouse_position();
         let handle_x = self.x + (self.value - self.min) / (self.max - self.min) * self.w;
         let handle_size = self.h * 1.5;
         mx >= handle_x - handle_size/2.0 && mx <= handle_x + handle_size/2.0 &&
@@ -672,6 +1866,8 @@ impl UiSlider {
 }
 
 impl UiElement for UiSlider {
+    element_fields!(visible, enabled, opacity);
+
     fn draw(&self, theme: &Theme) {
         // Draw track
         draw_rounded_rectangle(
@@ -715,13 +1911,15 @@ impl UiElement for UiSlider {
         }
 
         if self.dragging {
-            let (mx, _) = mouse_position();
+            let (mx, _) = ui_mouse_position();
             let new_value = ((mx - self.x) / self.w * (self.max - self.min) + self.min)
                 .max(self.min)
                 .min(self.max);
             
             if new_value != self.value {
                 self.value = new_value;
+                emit_sound(UiSound::ValueChange);
+                emit_event(UiEventKind::ValueChanged(self.value));
                 if let Some(cb) = &mut self.on_change {
                     cb(self.value);
                 }
@@ -740,6 +1938,52 @@ impl UiElement for UiSlider {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y + self.h / 2.0;
+    }
+
+    fn set_size(&mut self, w: f32, h: f32) {
+        self.w = w;
+        self.h = h / 2.0;
+    }
+
+    fn cursor_icon(&self, _point: Vec2) -> Option<CursorIcon> {
+        Some(CursorIcon::Pointer)
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn navigate(&mut self, direction: NavDirection) -> bool {
+        let step = (self.max - self.min) / 20.0;
+        let delta = match direction {
+            NavDirection::Left => -step,
+            NavDirection::Right => step,
+            _ => return false,
+        };
+        let new_value = (self.value + delta).clamp(self.min, self.max);
+        if new_value != self.value {
+            self.value = new_value;
+            emit_sound(UiSound::ValueChange);
+            emit_event(UiEventKind::ValueChanged(self.value));
+            if let Some(cb) = &mut self.on_change {
+                cb(self.value);
+            }
+        }
+        true
+    }
+}
+
+/// State of a checkbox
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    /// Partially checked, e.g. a "select all" box with only some items selected
+    Indeterminate,
 }
 
 /// Checkbox UI element
@@ -748,9 +1992,25 @@ pub struct UiCheckbox {
     pub y: f32,
     pub size: f32,
     pub checked: bool,
-    pub theme: Theme,
+    /// Drawn with a dash instead of a checkmark, overriding `checked`
+    pub indeterminate: bool,
+    /// Let clicks cycle through the indeterminate state too
+    pub tri_state: bool,
+    /// Text drawn to the right of the box, clicking it toggles the checkbox
+    pub label: Option<String>,
+    /// Localization key the label is looked up from
+    pub label_key: Option<String>,
+    pub font: Option<Font>,
+    pub font_size: u16,
+    /// Gap between the box and the label
+    pub label_spacing: f32,
     pub on_change: Option<Box<dyn FnMut(bool) + Send + Sync>>,
+    /// Called with the new state whenever the user changes it
+    pub on_state_change: Option<Box<dyn FnMut(CheckState) + Send + Sync>>,
     pub animation: Animation,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
 }
 
 impl UiCheckbox {
@@ -760,7 +2020,6 @@ impl UiCheckbox {
         y: f32,
         size: f32,
         checked: bool,
-        theme: Theme,
         on_change: Option<Box<dyn FnMut(bool) + Send + Sync>>,
     ) -> Self {
         Self {
@@ -768,20 +2027,101 @@ impl UiCheckbox {
             y,
             size,
             checked,
-            theme,
+            indeterminate: false,
+            tri_state: false,
+            label: None,
+            label_key: None,
+            font: None,
+            font_size: 16,
+            label_spacing: 8.0,
             on_change,
+            on_state_change: None,
             animation: Animation::new(if checked { 1.0 } else { 0.0 }, 0.2),
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
         }
     }
 
+    /// Check if the mouse is over the box or its label
     pub fn is_mouse_over(&self) -> bool {
-        let (mx, my) = mouse_position();
-        mx >= self.x && mx <= self.x + self.size &&
-        my >= self.y && my <= self.y + self.size
+        let (mx, my) = ui_mouse_position();
+        let (x, y, w, h) = self.get_bounds();
+        mx >= x && mx <= x + w &&
+        my >= y && my <= y + h
+    }
+
+    /// Draw a label next to the box
+    pub fn set_label(&mut self, label: &str, font: Font, font_size: u16) {
+        self.label = Some(label.to_string());
+        self.font = Some(font);
+        self.font_size = font_size;
+    }
+
+    /// Look the label up from a localization key whenever the language changes
+    pub fn set_label_key(&mut self, key: &str) {
+        self.label_key = Some(key.to_string());
+    }
+
+    /// Let clicks cycle unchecked, checked and indeterminate
+    pub fn set_tri_state(&mut self, tri_state: bool) {
+        self.tri_state = tri_state;
+    }
+
+    /// Get the current state
+    pub fn state(&self) -> CheckState {
+        if self.indeterminate {
+            CheckState::Indeterminate
+        } else if self.checked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        }
+    }
+
+    /// Set the state without firing callbacks
+    pub fn set_state(&mut self, state: CheckState) {
+        self.indeterminate = state == CheckState::Indeterminate;
+        self.checked = state == CheckState::Checked;
+    }
+
+    /// Move to the next state and fire callbacks
+    ///
+    /// Indeterminate boxes become checked, unless `tri_state` is set, in which
+    /// case clicks cycle unchecked, checked, indeterminate.
+    pub fn toggle(&mut self) {
+        let next = match (self.state(), self.tri_state) {
+            (CheckState::Unchecked, _) => CheckState::Checked,
+            (CheckState::Checked, true) => CheckState::Indeterminate,
+            (CheckState::Checked, false) | (CheckState::Indeterminate, true) => CheckState::Unchecked,
+            (CheckState::Indeterminate, false) => CheckState::Checked,
+        };
+        self.set_state(next);
+        emit_sound(UiSound::ValueChange);
+        emit_event(UiEventKind::Checked(next));
+        if let Some(cb) = &mut self.on_change {
+            cb(self.checked);
+        }
+        if let Some(cb) = &mut self.on_state_change {
+            cb(next);
+        }
+    }
+
+    /// Width of the label including the gap before it
+    fn label_width(&self) -> f32 {
+        match &self.label {
+            Some(label) => {
+                let size = measure_text(label, self.font.as_ref(), ui_font_size(self.font_size), 1.0);
+                self.label_spacing + size.width
+            }
+            None => 0.0,
+        }
     }
 }
 
 impl UiElement for UiCheckbox {
+    element_fields!(visible, enabled, opacity);
+
     fn draw(&self, theme: &Theme) {
         // Draw box
         draw_rounded_rectangle(
@@ -790,11 +2130,22 @@ impl UiElement for UiCheckbox {
             self.size,
             self.size,
             theme.border_radius,
-            if self.checked { theme.accent } else { theme.secondary },
+            if self.checked || self.indeterminate { theme.accent } else { theme.secondary },
         );
 
-        // Draw checkmark
-        if self.animation.current > 0.0 {
+        if self.indeterminate {
+            // Draw dash
+            let dash_size = self.size * 0.6;
+            draw_line(
+                self.x + (self.size - dash_size) / 2.0,
+                self.y + self.size / 2.0,
+                self.x + (self.size + dash_size) / 2.0,
+                self.y + self.size / 2.0,
+                dash_size * 0.15,
+                theme.text,
+            );
+        } else if self.animation.current > 0.0 {
+            // Draw checkmark
             let check_size = self.size * 0.6;
             let check_x = self.x + (self.size - check_size) / 2.0;
             let check_y = self.y + (self.size - check_size) / 2.0;
@@ -816,22 +2167,36 @@ impl UiElement for UiCheckbox {
                 theme.text,
             );
         }
+
+        // Draw label
+        if let Some(label) = &self.label {
+            let font_size = ui_font_size(self.font_size);
+            let text_size = measure_text(label, self.font.as_ref(), font_size, 1.0);
+            draw_text_ex(
+                label,
+                self.x + self.size + self.label_spacing,
+                self.y + (self.size + text_size.height) / 2.0,
+                TextParams {
+                    font: self.font.as_ref(),
+                    font_size,
+                    color: theme.text,
+                    ..Default::default()
+                },
+            );
+        }
     }
 
     fn update(&mut self, _theme: &Theme, _manager: Option<&mut UiManager>) {
-        self.animation.set_target(if self.checked { 1.0 } else { 0.0 });
+        self.animation.set_target(if self.checked || self.indeterminate { 1.0 } else { 0.0 });
         self.animation.update();
 
         if is_mouse_button_pressed(MouseButton::Left) && self.is_mouse_over() {
-            self.checked = !self.checked;
-            if let Some(cb) = &mut self.on_change {
-                cb(self.checked);
-            }
+            self.toggle();
         }
     }
 
     fn get_bounds(&self) -> (f32, f32, f32, f32) {
-        (self.x, self.y, self.size, self.size)
+        (self.x, self.y, self.size + self.label_width(), self.size)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -841,17 +2206,77 @@ impl UiElement for UiCheckbox {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    fn set_size(&mut self, w: f32, h: f32) {
+        // The label's width follows from the font, so only the height sizes a labelled box
+        self.size = if self.label.is_some() { h } else { w.min(h) };
+    }
+
+    fn cursor_icon(&self, _point: Vec2) -> Option<CursorIcon> {
+        Some(CursorIcon::Pointer)
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self) {
+        self.toggle();
+    }
+
+    fn localize(&mut self, localization: &Localization) {
+        if let Some(key) = &self.label_key {
+            self.label = Some(localization.get(key));
+        }
+    }
 }
 
+/// Size of the resize grip in the corner of resizable panels
+const PANEL_GRIP_SIZE: f32 = 14.0;
+
+/// Height of a panel's title bar
+const PANEL_TITLE_HEIGHT: f32 = 30.0;
+
+/// Size of the close and minimize buttons in a panel's title bar
+const PANEL_BUTTON_SIZE: f32 = 20.0;
+
 /// Panel UI element
 pub struct UiPanel {
     pub x: f32,
     pub y: f32,
     pub w: f32,
     pub h: f32,
-    pub theme: Theme,
     pub title: Option<String>,
+    /// Localization key the title is looked up from
+    pub title_key: Option<String>,
     pub elements: Vec<Box<dyn UiElement>>,
+    /// Indices of named elements
+    pub names: HashMap<String, usize>,
+    /// Cut off children that extend past the panel's bounds
+    pub clip_children: bool,
+    /// Let the user resize the panel by dragging its bottom-right grip
+    pub resizable: bool,
+    /// Smallest size the grip can shrink the panel to
+    pub min_size: Vec2,
+    resizing: bool,
+    /// Show a close button in the title bar that hides the panel
+    pub closable: bool,
+    /// Show a minimize button in the title bar that collapses the panel to its title
+    pub minimizable: bool,
+    /// Collapsed to the title bar, children are neither drawn nor updated
+    pub minimized: bool,
+    /// Called after the close button hides the panel
+    pub on_close: Option<Box<dyn FnMut() + Send + Sync>>,
+    /// Called with the new minimized state when the minimize button is clicked
+    pub on_minimize: Option<Box<dyn FnMut(bool) + Send + Sync>>,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
 }
 
 impl UiPanel {
@@ -861,7 +2286,6 @@ impl UiPanel {
         y: f32,
         w: f32,
         h: f32,
-        theme: Theme,
         title: Option<String>,
     ) -> Self {
         Self {
@@ -869,65 +2293,362 @@ impl UiPanel {
             y,
             w,
             h,
-            theme,
             title,
+            title_key: None,
             elements: Vec::new(),
+            names: HashMap::new(),
+            clip_children: false,
+            resizable: false,
+            min_size: vec2(60.0, 40.0),
+            resizing: false,
+            closable: false,
+            minimizable: false,
+            minimized: false,
+            on_close: None,
+            on_minimize: None,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
         }
     }
 
-    /// Add a UI element to the panel
-    pub fn add_element(&mut self, element: Box<dyn UiElement>) {
-        self.elements.push(element);
+    /// Let the user resize the panel by dragging its bottom-right grip
+    pub fn set_resizable(&mut self, resizable: bool) {
+        self.resizable = resizable;
     }
-}
 
-impl UiElement for UiPanel {
-    fn draw(&self, theme: &Theme) {
-        // Draw panel background
-        draw_rounded_rectangle(
-            self.x,
-            self.y,
-            self.w,
-            self.h,
-            theme.border_radius,
-            theme.background,
-        );
+    /// Show a close button in the title bar
+    pub fn set_closable(&mut self, closable: bool, on_close: Option<Box<dyn FnMut() + Send + Sync>>) {
+        self.closable = closable;
+        self.on_close = on_close;
+    }
 
-        // Draw title if present
-        if let Some(title) = &self.title {
-            draw_rectangle(
-                self.x,
-                self.y,
-                self.w,
-                30.0,
-                theme.secondary,
-            );
-            draw_text_ex(
-                title,
-                self.x + theme.padding,
-                self.y + 20.0,
-                TextParams {
-                    font_size: 16,
-                    color: theme.text,
-                    ..Default::default()
-                },
-            );
-        }
+    /// Show a minimize button in the title bar
+    pub fn set_minimizable(&mut self, minimizable: bool, on_minimize: Option<Box<dyn FnMut(bool) + Send + Sync>>) {
+        self.minimizable = minimizable;
+        self.on_minimize = on_minimize;
+    }
 
-        // Draw elements
-        for element in &self.elements {
-            element.draw(theme);
-        }
+    /// Collapse the panel to its title bar or expand it again, without firing `on_minimize`
+    pub fn set_minimized(&mut self, minimized: bool) {
+        self.minimized = minimized;
     }
 
-    fn update(&mut self, theme: &Theme, mut manager: Option<&mut UiManager>) {
-        for element in &mut self.elements {
-            element.update(theme, manager.as_deref_mut());
-        }
+    /// Check if the panel draws a title bar
+    ///
+    /// Panels with title bar buttons get one even without a title.
+    pub fn has_title_bar(&self) -> bool {
+        self.title.is_some() || self.closable || self.minimizable
+    }
+
+    /// Screen rectangle of the close button, if shown
+    pub fn close_button_rect(&self) -> Option<Rect> {
+        self.closable.then(|| self.title_button_rect(0))
+    }
+
+    /// Screen rectangle of the minimize button, if shown
+    pub fn minimize_button_rect(&self) -> Option<Rect> {
+        let slot = if self.closable { 1 } else { 0 };
+        self.minimizable.then(|| self.title_button_rect(slot))
+    }
+
+    /// Rectangle of the title bar button `slot` places from the right edge
+    fn title_button_rect(&self, slot: usize) -> Rect {
+        let margin = (PANEL_TITLE_HEIGHT - PANEL_BUTTON_SIZE) / 2.0;
+        Rect::new(
+            self.x + self.w - (margin + PANEL_BUTTON_SIZE) * (slot + 1) as f32,
+            self.y + margin,
+            PANEL_BUTTON_SIZE,
+            PANEL_BUTTON_SIZE,
+        )
+    }
+
+    /// Check if a point is over the resize grip
+    pub fn is_over_grip(&self, point: Vec2) -> bool {
+        self.resizable
+            && !self.minimized
+            && point.x >= self.x + self.w - PANEL_GRIP_SIZE
+            && point.x <= self.x + self.w
+            && point.y >= self.y + self.h - PANEL_GRIP_SIZE
+            && point.y <= self.y + self.h
+    }
+
+    /// Look the title up from a localization key whenever the language changes
+    pub fn set_title_key(&mut self, key: &str) {
+        self.title_key = Some(key.to_string());
+    }
+
+    /// Cut off children that extend past the panel's bounds
+    ///
+    /// Open dropdown lists are clipped too, so keep dropdowns clear of the bottom edge.
+    pub fn set_clip_children(&mut self, clip: bool) {
+        self.clip_children = clip;
+    }
+
+    /// Add a UI element to the panel
+    pub fn add_element(&mut self, element: Box<dyn UiElement>) {
+        self.elements.push(element);
+    }
+
+    /// Add a UI element that can later be looked up by name
+    pub fn add_named_element(&mut self, name: &str, element: Box<dyn UiElement>) {
+        self.names.insert(name.to_string(), self.elements.len());
+        self.elements.push(element);
+    }
+
+    /// Get a named element as its concrete type
+    ///
+    /// Nested panels are searched too, so widgets can be reached without
+    /// walking the hierarchy by hand.
+    pub fn get_named<T: 'static>(&mut self, name: &str) -> Option<&mut T> {
+        find_named(&mut self.elements, &self.names, name)
+    }
+
+    /// Get a named element as a trait object, searching nested panels too
+    pub fn get_named_element(&mut self, name: &str) -> Option<&mut dyn UiElement> {
+        find_named_element(&mut self.elements, &self.names, name)
+    }
+
+    /// Remove the element at `index`
+    ///
+    /// Elements after it move down one index.
+    pub fn remove_element(&mut self, index: usize) -> Option<Box<dyn UiElement>> {
+        if index >= self.elements.len() {
+            return None;
+        }
+        shift_names(&mut self.names, index);
+        Some(self.elements.remove(index))
+    }
+
+    /// Remove a named element
+    pub fn remove_named(&mut self, name: &str) -> Option<Box<dyn UiElement>> {
+        let index = *self.names.get(name)?;
+        self.remove_element(index)
+    }
+
+    /// Remove every element matching `predicate`, returning how many were removed
+    pub fn remove_where(&mut self, mut predicate: impl FnMut(&dyn UiElement) -> bool) -> usize {
+        let mut removed = 0;
+        for index in (0..self.elements.len()).rev() {
+            if predicate(self.elements[index].as_ref()) {
+                self.remove_element(index);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Remove all elements
+    pub fn clear(&mut self) {
+        self.elements.clear();
+        self.names.clear();
+    }
+}
+
+/// Drop an index path into a removed element or its children, and shift one into a later element
+fn shift_path(path: &mut Option<Vec<usize>>, removed: usize) {
+    if let Some(indices) = path && !indices.is_empty() {
+        match indices[0].cmp(&removed) {
+            std::cmp::Ordering::Equal => *path = None,
+            std::cmp::Ordering::Greater => indices[0] -= 1,
+            std::cmp::Ordering::Less => {}
+        }
+    }
+}
+
+/// Drop the name pointing at a removed index and shift the ones after it
+fn shift_names(names: &mut HashMap<String, usize>, removed: usize) {
+    names.retain(|_, index| *index != removed);
+    for index in names.values_mut() {
+        if *index > removed {
+            *index -= 1;
+        }
+    }
+}
+
+/// Look up a named element in `elements`, falling back to nested panels
+fn find_named_element<'a>(
+    elements: &'a mut [Box<dyn UiElement>],
+    names: &HashMap<String, usize>,
+    name: &str,
+) -> Option<&'a mut dyn UiElement> {
+    if let Some(&index) = names.get(name) && index < elements.len() {
+        return Some(elements[index].as_mut());
+    }
+    elements.iter_mut().find_map(|element| {
+        element
+            .as_any_mut()
+            .downcast_mut::<UiPanel>()
+            .and_then(|panel| panel.get_named_element(name))
+    })
+}
+
+/// Add the names of the elements in every panel among `elements`, nested ones too
+fn collect_panel_names(elements: &[Box<dyn UiElement>], names: &mut Vec<String>) {
+    for panel in elements.iter().filter_map(|element| element.as_any().downcast_ref::<UiPanel>()) {
+        names.extend(panel.names.keys().cloned());
+        collect_panel_names(&panel.elements, names);
+    }
+}
+
+/// Look up a named element and downcast it to `T`
+fn find_named<'a, T: 'static>(
+    elements: &'a mut [Box<dyn UiElement>],
+    names: &HashMap<String, usize>,
+    name: &str,
+) -> Option<&'a mut T> {
+    find_named_element(elements, names, name)?.as_any_mut().downcast_mut::<T>()
+}
+
+impl UiElement for UiPanel {
+    element_fields!(visible, enabled, opacity);
+
+    fn draw(&self, theme: &Theme) {
+        let (_, _, _, h) = self.get_bounds();
+
+        // Draw panel background
+        draw_rounded_rectangle(
+            self.x,
+            self.y,
+            self.w,
+            h,
+            theme.border_radius,
+            theme.background,
+        );
+
+        // Draw title bar if present
+        if self.has_title_bar() {
+            draw_rectangle(
+                self.x,
+                self.y,
+                self.w,
+                PANEL_TITLE_HEIGHT,
+                theme.secondary,
+            );
+        }
+        if let Some(title) = &self.title {
+            draw_text_ex(
+                title,
+                self.x + theme.padding,
+                self.y + 20.0,
+                TextParams {
+                    font_size: ui_font_size(16),
+                    color: theme.text,
+                    ..Default::default()
+                },
+            );
+        }
+
+        // Draw title bar buttons
+        let mouse = Vec2::from(ui_mouse_position());
+        if let Some(rect) = self.close_button_rect() {
+            if rect.contains(mouse) {
+                draw_rounded_rectangle(rect.x, rect.y, rect.w, rect.h, theme.border_radius, theme.accent);
+            }
+            let inset = rect.w * 0.3;
+            draw_line(rect.x + inset, rect.y + inset, rect.right() - inset, rect.bottom() - inset, 2.0, theme.text);
+            draw_line(rect.right() - inset, rect.y + inset, rect.x + inset, rect.bottom() - inset, 2.0, theme.text);
+        }
+        if let Some(rect) = self.minimize_button_rect() {
+            if rect.contains(mouse) {
+                draw_rounded_rectangle(rect.x, rect.y, rect.w, rect.h, theme.border_radius, theme.accent);
+            }
+            let inset = rect.w * 0.3;
+            if self.minimized {
+                // Restore icon
+                let size = rect.w - inset * 2.0;
+                draw_rectangle_lines(rect.x + inset, rect.y + inset, size, size, 2.0, theme.text);
+            } else {
+                let line_y = rect.bottom() - inset;
+                draw_line(rect.x + inset, line_y, rect.right() - inset, line_y, 2.0, theme.text);
+            }
+        }
+
+        if self.minimized {
+            return;
+        }
+
+        // Draw elements
+        if self.clip_children {
+            push_clip_rect(self.x, self.y, self.w, self.h);
+        }
+        for element in &self.elements {
+            draw_element(element.as_ref(), theme);
+        }
+        if self.clip_children {
+            pop_clip_rect();
+        }
+
+        if self.resizable {
+            let (right, bottom) = (self.x + self.w, self.y + self.h);
+            for i in 1..=3 {
+                let offset = PANEL_GRIP_SIZE * i as f32 / 4.0;
+                draw_line(right - offset, bottom - 2.0, right - 2.0, bottom - offset, 1.5, theme.secondary);
+            }
+        }
+    }
+
+    fn draw_overlay(&self, theme: &Theme) {
+        if self.minimized {
+            return;
+        }
+        for element in &self.elements {
+            draw_element_overlay(element.as_ref(), theme);
+        }
+    }
+
+    fn update(&mut self, theme: &Theme, mut manager: Option<&mut UiManager>) {
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let mouse = Vec2::from(ui_mouse_position());
+            if self.close_button_rect().is_some_and(|rect| rect.contains(mouse)) {
+                emit_sound(UiSound::Click);
+                emit_event(UiEventKind::Closed);
+                self.visible = false;
+                if let Some(cb) = &mut self.on_close {
+                    cb();
+                }
+                return;
+            }
+            if self.minimize_button_rect().is_some_and(|rect| rect.contains(mouse)) {
+                emit_sound(UiSound::Click);
+                self.minimized = !self.minimized;
+                emit_event(UiEventKind::Minimized(self.minimized));
+                if let Some(cb) = &mut self.on_minimize {
+                    cb(self.minimized);
+                }
+                return;
+            }
+        }
+
+        if self.minimized {
+            return;
+        }
+
+        if self.resizable {
+            let (mx, my) = ui_mouse_position();
+            if is_mouse_button_pressed(MouseButton::Left) && self.is_over_grip(vec2(mx, my)) {
+                self.resizing = true;
+            }
+            if !is_mouse_button_down(MouseButton::Left) {
+                self.resizing = false;
+            }
+            if self.resizing {
+                self.w = (mx - self.x).max(self.min_size.x);
+                self.h = (my - self.y).max(self.min_size.y);
+            }
+        }
+
+        for (index, element) in self.elements.iter_mut().enumerate() {
+            with_event_path(index, || update_element(element.as_mut(), theme, manager.as_deref_mut()));
+        }
     }
 
     fn get_bounds(&self) -> (f32, f32, f32, f32) {
-        (self.x, self.y, self.w, self.h)
+        if self.minimized {
+            (self.x, self.y, self.w, PANEL_TITLE_HEIGHT)
+        } else {
+            (self.x, self.y, self.w, self.h)
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -937,6 +2658,50 @@ impl UiElement for UiPanel {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        // Children use screen coordinates, so move them along with the panel
+        let (dx, dy) = (x - self.x, y - self.y);
+        for element in &mut self.elements {
+            let (ex, ey, _, _) = element.get_bounds();
+            element.set_position(ex + dx, ey + dy);
+        }
+        self.x = x;
+        self.y = y;
+    }
+
+    fn set_size(&mut self, w: f32, h: f32) {
+        self.w = w;
+        self.h = h;
+    }
+
+    fn cursor_icon(&self, point: Vec2) -> Option<CursorIcon> {
+        if self.is_over_grip(point) {
+            return Some(CursorIcon::NWSEResize);
+        }
+        let over_button = |rect: Option<Rect>| rect.is_some_and(|rect| rect.contains(point));
+        if over_button(self.close_button_rect()) || over_button(self.minimize_button_rect()) {
+            return Some(CursorIcon::Pointer);
+        }
+        if self.minimized {
+            return None;
+        }
+        // Children added later draw on top, so ask them first
+        self.elements
+            .iter()
+            .rev()
+            .find(|element| element.is_visible() && element.contains_point(point))
+            .and_then(|element| if element.is_enabled() { element.cursor_icon(point) } else { None })
+    }
+
+    fn localize(&mut self, localization: &Localization) {
+        if let Some(key) = &self.title_key {
+            self.title = Some(localization.get(key));
+        }
+        for element in &mut self.elements {
+            element.localize(localization);
+        }
+    }
 }
 
 /// Progress bar UI element
@@ -946,8 +2711,10 @@ pub struct UiProgressBar {
     pub w: f32,
     pub h: f32,
     pub progress: f32,
-    pub theme: Theme,
     pub animation: Animation,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
 }
 
 impl UiProgressBar {
@@ -958,27 +2725,30 @@ impl UiProgressBar {
         w: f32,
         h: f32,
         initial_progress: f32,
-        theme: Theme,
     ) -> Self {
         Self {
             x,
             y,
             w,
             h,
-            progress: initial_progress.max(0.0).min(1.0),
-            theme,
+            progress: initial_progress.clamp(0.0, 1.0),
             animation: Animation::new(initial_progress, 0.2),
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
         }
     }
 
     /// Set the progress value
     pub fn set_progress(&mut self, progress: f32) {
-        self.progress = progress.max(0.0).min(1.0);
+        self.progress = progress.clamp(0.0, 1.0);
         self.animation.set_target(self.progress);
     }
 }
 
 impl UiElement for UiProgressBar {
+    element_fields!(bounds, position, visible, enabled, opacity);
+
     fn draw(&self, theme: &Theme) {
         // Draw background
         draw_rounded_rectangle(
@@ -1006,10 +2776,6 @@ impl UiElement for UiProgressBar {
         self.animation.update();
     }
 
-    fn get_bounds(&self) -> (f32, f32, f32, f32) {
-        (self.x, self.y, self.w, self.h)
-    }
-
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -1019,129 +2785,1045 @@ impl UiElement for UiProgressBar {
     }
 }
 
-/// Dropdown menu UI element
-pub struct UiDropdown {
+/// How a `UiSpinner` shows progress
+#[derive(Clone, Copy, PartialEq)]
+pub enum SpinnerMode {
+    /// Endlessly rotating arc for work of unknown length
+    Indeterminate,
+    /// Ring that fills clockwise from the top as progress goes from 0.0 to 1.0
+    Radial,
+}
+
+/// Circular spinner and radial progress UI element
+pub struct UiSpinner {
+    /// Center of the spinner
     pub x: f32,
     pub y: f32,
-    pub width: f32,
-    pub height: f32,
-    pub options: Vec<String>,
-    pub selected_index: usize,
-    pub is_open: bool,
-    pub theme: Theme,
-    pub font: Font,
-    pub font_size: u16,
-    pub on_select: Option<Box<dyn Fn(usize)>>,
-    pub hover_index: Option<usize>,
-    // New customization options
-    pub hover_color: Color,
-    pub active_color: Color,
-    pub option_height: f32,
-    pub option_padding: f32,
-    pub animation_speed: f32,
-    pub hover_animation: Animation,
-    pub press_animation: Animation,
+    pub radius: f32,
+    /// Width of the ring
+    pub thickness: f32,
+    pub mode: SpinnerMode,
+    pub progress: f32,
+    /// Full turns per second in indeterminate mode
+    pub speed: f32,
+    /// Smoothed progress in radial mode
+    pub animation: Animation,
+    /// Arc length in degrees, growing and shrinking in indeterminate mode
+    pub sweep_animation: Animation,
+    pub rotation: f32,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
 }
 
-impl UiDropdown {
-    pub fn new(
-        x: f32,
-        y: f32,
-        width: f32,
-        height: f32,
-        options: Vec<String>,
-        theme: Theme,
-        font: Font,
-        font_size: u16,
-        on_select: Option<Box<dyn Fn(usize)>>,
-    ) -> Self {
+impl UiSpinner {
+    /// Create an indeterminate spinner centered on (x, y)
+    pub fn new(x: f32, y: f32, radius: f32) -> Self {
+        let mut sweep_animation = Animation::new(60.0, 0.05);
+        sweep_animation.set_target(270.0);
         Self {
             x,
             y,
-            width,
-            height,
-            options,
-            selected_index: 0,
-            is_open: false,
-            theme,
-            font,
-            font_size,
-            on_select,
-            hover_index: None,
-            hover_color: Color::from_rgba(60, 60, 60, 255),
-            active_color: Color::from_rgba(80, 80, 80, 255),
-            option_height: height,
-            option_padding: 5.0,
-            animation_speed: 0.2,
-            hover_animation: Animation::new(0.0, 0.2),
-            press_animation: Animation::new(0.0, 0.3),
+            radius,
+            thickness: (radius * 0.2).max(2.0),
+            mode: SpinnerMode::Indeterminate,
+            progress: 0.0,
+            speed: 1.0,
+            animation: Animation::new(0.0, 0.2),
+            sweep_animation,
+            rotation: 0.0,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
         }
     }
 
-    pub fn set_hover_color(&mut self, color: Color) {
-        self.hover_color = color;
-    }
-
-    pub fn set_active_color(&mut self, color: Color) {
-        self.active_color = color;
+    /// Create a radial progress ring centered on (x, y)
+    pub fn radial(x: f32, y: f32, radius: f32, initial_progress: f32) -> Self {
+        let mut spinner = Self::new(x, y, radius);
+        spinner.mode = SpinnerMode::Radial;
+        spinner.progress = initial_progress.clamp(0.0, 1.0);
+        spinner.animation = Animation::new(spinner.progress, 0.2);
+        spinner
     }
 
-    pub fn set_option_height(&mut self, height: f32) {
-        self.option_height = height;
+    /// Set the progress value for radial mode
+    pub fn set_progress(&mut self, progress: f32) {
+        self.progress = progress.clamp(0.0, 1.0);
+        self.animation.set_target(self.progress);
     }
 
-    pub fn set_option_padding(&mut self, padding: f32) {
-        self.option_padding = padding;
+    /// Set the width of the ring
+    pub fn set_thickness(&mut self, thickness: f32) {
+        self.thickness = thickness;
     }
 
-    pub fn set_animation_speed(&mut self, speed: f32) {
-        self.animation_speed = speed;
-        self.hover_animation.speed = speed;
-        self.press_animation.speed = speed;
+    /// Set how many full turns per second the indeterminate arc makes
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
     }
 }
 
-impl UiElement for UiDropdown {
-    fn draw(&self, theme: &Theme) {
-        let hover = self.hover_animation.current;
-        let press = self.press_animation.current;
-        
-        let button_color = if press > 0.0 {
-            Color::new(
-                self.active_color.r + (theme.primary.r - self.active_color.r) * press,
-                self.active_color.g + (theme.primary.g - self.active_color.g) * press,
-                self.active_color.b + (theme.primary.b - self.active_color.b) * press,
-                self.active_color.a,
-            )
-        } else {
-            Color::new(
-                theme.primary.r + (self.hover_color.r - theme.primary.r) * hover,
-                theme.primary.g + (self.hover_color.g - theme.primary.g) * hover,
-                theme.primary.b + (self.hover_color.b - theme.primary.b) * hover,
-                theme.primary.a,
-            )
-        };
+impl UiElement for UiSpinner {
+    element_fields!(visible, enabled, opacity);
 
-        // Draw button
-        draw_rounded_rectangle(
-            self.x,
-            self.y,
-            self.width,
-            self.height,
-            theme.border_radius,
-            button_color,
-        );
+    fn draw(&self, theme: &Theme) {
+        let inner = (self.radius - self.thickness).max(0.0);
+        let thickness = self.radius - inner;
+        match self.mode {
+            SpinnerMode::Indeterminate => {
+                draw_arc(
+                    self.x,
+                    self.y,
+                    48,
+                    inner,
+                    self.rotation,
+                    thickness,
+                    self.sweep_animation.current,
+                    theme.accent,
+                );
+            }
+            SpinnerMode::Radial => {
+                draw_arc(self.x, self.y, 48, inner, 0.0, thickness, 360.0, theme.secondary);
+                let sweep = 360.0 * self.animation.current;
+                if sweep > 0.0 {
+                    draw_arc(self.x, self.y, 48, inner, -90.0, thickness, sweep, theme.accent);
+                }
+            }
+        }
+    }
 
-        // Draw selected text
-        let text = &self.options[self.selected_index];
-        let text_size = measure_text(text, Some(&self.font), self.font_size, 1.0);
-        draw_text_ex(
-            text,
+    fn update(&mut self, _theme: &Theme, _manager: Option<&mut UiManager>) {
+        match self.mode {
+            SpinnerMode::Indeterminate => {
+                self.rotation = (self.rotation + 360.0 * self.speed * get_frame_time()) % 360.0;
+                // Swap the arc between growing and shrinking once it gets close
+                let sweep = &mut self.sweep_animation;
+                if (sweep.target - sweep.current).abs() < 5.0 {
+                    sweep.set_target(if sweep.target > 165.0 { 60.0 } else { 270.0 });
+                }
+                sweep.update();
+            }
+            SpinnerMode::Radial => self.animation.update(),
+        }
+    }
+
+    fn get_bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x - self.radius, self.y - self.radius, self.radius * 2.0, self.radius * 2.0)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        self.x = x + self.radius;
+        self.y = y + self.radius;
+    }
+
+    fn set_size(&mut self, w: f32, h: f32) {
+        // Keep the top-left corner of the bounds in place
+        let radius = w.min(h) / 2.0;
+        self.x += radius - self.radius;
+        self.y += radius - self.radius;
+        self.radius = radius;
+    }
+}
+
+/// UI element that runs custom drawing code inside its rectangle
+pub struct UiCanvas {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+    /// Called every draw with the canvas rectangle
+    pub on_draw: Box<dyn Fn(Rect)>,
+    /// Cut off drawing that strays outside the rectangle
+    pub clip: bool,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
+}
+
+impl UiCanvas {
+    /// Create a new canvas
+    pub fn new(x: f32, y: f32, w: f32, h: f32, on_draw: Box<dyn Fn(Rect)>) -> Self {
+        Self {
+            x,
+            y,
+            w,
+            h,
+            on_draw,
+            clip: true,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
+        }
+    }
+
+    /// Choose whether drawing outside the rectangle is cut off
+    pub fn set_clip(&mut self, clip: bool) {
+        self.clip = clip;
+    }
+
+    /// Get the canvas rectangle
+    pub fn rect(&self) -> Rect {
+        Rect::new(self.x, self.y, self.w, self.h)
+    }
+}
+
+impl UiElement for UiCanvas {
+    element_fields!(bounds, position, visible, enabled, opacity);
+
+    fn draw(&self, _theme: &Theme) {
+        if self.clip {
+            push_clip_rect(self.x, self.y, self.w, self.h);
+        }
+        (self.on_draw)(self.rect());
+        if self.clip {
+            pop_clip_rect();
+        }
+    }
+
+    fn update(&mut self, _theme: &Theme, _manager: Option<&mut UiManager>) {}
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// How a `UiGraph` draws its samples
+#[derive(Clone, Copy, PartialEq)]
+pub enum GraphStyle {
+    Line,
+    Bar,
+}
+
+/// Vertical range of a `UiGraph`
+#[derive(Clone, Copy, PartialEq)]
+pub enum GraphScale {
+    /// Fit the range to the samples currently shown
+    Auto,
+    /// Always show `min` to `max`
+    Fixed(f32, f32),
+}
+
+/// Graph UI element plotting the most recent samples of a value
+pub struct UiGraph {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+    /// Samples shown, oldest first
+    pub samples: VecDeque<f32>,
+    /// Number of samples kept before the oldest are dropped
+    pub capacity: usize,
+    pub style: GraphStyle,
+    pub scale: GraphScale,
+    /// Title drawn in the top-left corner
+    pub label: Option<String>,
+    /// Unit appended to the axis labels
+    pub unit: String,
+    pub font: Font,
+    pub font_size: u16,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
+}
+
+impl UiGraph {
+    /// Create a new line graph keeping `capacity` samples
+    pub fn new(x: f32, y: f32, w: f32, h: f32, capacity: usize, font: Font) -> Self {
+        Self {
+            x,
+            y,
+            w,
+            h,
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(2),
+            style: GraphStyle::Line,
+            scale: GraphScale::Auto,
+            label: None,
+            unit: String::new(),
+            font,
+            font_size: 14,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
+        }
+    }
+
+    /// Add a sample, dropping the oldest once full
+    pub fn push(&mut self, sample: f32) {
+        if self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Remove all samples
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Set whether samples are drawn as a line or bars
+    pub fn set_style(&mut self, style: GraphStyle) {
+        self.style = style;
+    }
+
+    /// Set the vertical range
+    pub fn set_scale(&mut self, scale: GraphScale) {
+        self.scale = scale;
+    }
+
+    /// Set the title and the unit shown on the axis labels
+    pub fn set_label(&mut self, label: &str, unit: &str) {
+        self.label = Some(label.to_string());
+        self.unit = unit.to_string();
+    }
+
+    /// Get the range currently plotted
+    pub fn range(&self) -> (f32, f32) {
+        match self.scale {
+            GraphScale::Fixed(min, max) => (min, max),
+            GraphScale::Auto => {
+                let min = self.samples.iter().copied().fold(f32::INFINITY, f32::min);
+                let max = self.samples.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                if !min.is_finite() || !max.is_finite() {
+                    return (0.0, 1.0);
+                }
+                // Keep zero in view so values read against a baseline
+                let min = min.min(0.0);
+                if max <= min { (min, min + 1.0) } else { (min, max) }
+            }
+        }
+    }
+
+    fn axis_label(&self, value: f32) -> String {
+        format!("{:.1}{}", value, self.unit)
+    }
+}
+
+impl UiElement for UiGraph {
+    element_fields!(bounds, position, visible, enabled, opacity);
+
+    fn draw(&self, theme: &Theme) {
+        draw_rounded_rectangle(self.x, self.y, self.w, self.h, theme.border_radius, theme.background);
+
+        let (min, max) = self.range();
+        let label_params = TextParams {
+            font: Some(&self.font),
+            font_size: ui_font_size(self.font_size),
+            color: mix_color(theme.text, theme.background, 0.3),
+            ..Default::default()
+        };
+        let max_label = self.axis_label(max);
+        let min_label = self.axis_label(min);
+        let label_width = measure_text(&max_label, Some(&self.font), ui_font_size(self.font_size), 1.0)
+            .width
+            .max(measure_text(&min_label, Some(&self.font), ui_font_size(self.font_size), 1.0).width);
+
+        // Plot area leaves room for the axis labels on the left and the title on top
+        let padding = theme.padding / 2.0;
+        let title_height = if self.label.is_some() { ui_font_size(self.font_size) as f32 + padding } else { 0.0 };
+        let px = self.x + padding * 2.0 + label_width;
+        let py = self.y + padding + title_height;
+        let pw = (self.x + self.w - padding - px).max(1.0);
+        let ph = (self.y + self.h - padding - py).max(1.0);
+
+        if let Some(label) = &self.label {
+            draw_text_ex(
+                label,
+                self.x + padding,
+                self.y + padding + ui_font_size(self.font_size) as f32 * 0.8,
+                TextParams { color: theme.text, ..label_params.clone() },
+            );
+        }
+        draw_text_ex(&max_label, self.x + padding, py + ui_font_size(self.font_size) as f32 * 0.8, label_params.clone());
+        draw_text_ex(&min_label, self.x + padding, py + ph, label_params);
+
+        let axis_color = mix_color(theme.secondary, theme.background, 0.3);
+        draw_line(px, py, px, py + ph, 1.0, axis_color);
+        draw_line(px, py + ph, px + pw, py + ph, 1.0, axis_color);
+
+        let span = max - min;
+        let to_y = |value: f32| py + ph - ((value - min) / span).clamp(0.0, 1.0) * ph;
+        let step = pw / (self.capacity - 1) as f32;
+        match self.style {
+            GraphStyle::Line => {
+                for (i, (a, b)) in self.samples.iter().zip(self.samples.iter().skip(1)).enumerate() {
+                    let x0 = px + i as f32 * step;
+                    draw_line(x0, to_y(*a), x0 + step, to_y(*b), 2.0, theme.accent);
+                }
+            }
+            GraphStyle::Bar => {
+                let bar_width = (pw / self.capacity as f32 - 1.0).max(1.0);
+                let base = to_y(min.max(0.0).min(max));
+                for (i, sample) in self.samples.iter().enumerate() {
+                    let bx = px + i as f32 * pw / self.capacity as f32;
+                    let top = to_y(*sample);
+                    draw_rectangle(bx, top.min(base), bar_width, (base - top).abs(), theme.accent);
+                }
+            }
+        }
+    }
+
+    fn update(&mut self, _theme: &Theme, _manager: Option<&mut UiManager>) {}
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Custom row drawing for a `UiList`, called with the item index, row rectangle, whether it is selected and the theme
+pub type RowRenderer = Box<dyn Fn(usize, Rect, bool, &Theme)>;
+
+/// Scrollable list UI element that only touches the rows on screen
+///
+/// Rows share one height, so the visible range is found without measuring
+/// anything and lists with thousands of items cost the same per frame as short ones.
+pub struct UiList {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+    pub items: Vec<String>,
+    pub row_height: f32,
+    /// Distance scrolled from the top in pixels
+    pub scroll_offset: f32,
+    pub selected_index: Option<usize>,
+    pub hover_index: Option<usize>,
+    pub font: Font,
+    pub font_size: u16,
+    pub on_select: Option<Box<dyn FnMut(usize) + Send + Sync>>,
+    /// Custom row drawing, called with the item index, row rectangle and whether it is selected
+    pub row_renderer: Option<RowRenderer>,
+    /// Whether the scrollbar thumb is being dragged
+    pub dragging_scrollbar: bool,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
+}
+
+/// Width of the `UiList` scrollbar
+const SCROLLBAR_WIDTH: f32 = 8.0;
+
+impl UiList {
+    /// Create a new list
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        items: Vec<String>,
+        row_height: f32,
+        font: Font,
+        font_size: u16,
+        on_select: Option<Box<dyn FnMut(usize) + Send + Sync>>,
+    ) -> Self {
+        Self {
+            x,
+            y,
+            w,
+            h,
+            items,
+            row_height: row_height.max(1.0),
+            scroll_offset: 0.0,
+            selected_index: None,
+            hover_index: None,
+            font,
+            font_size,
+            on_select,
+            row_renderer: None,
+            dragging_scrollbar: false,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
+        }
+    }
+
+    /// Replace the items, keeping the scroll position where possible
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        if self.selected_index.is_some_and(|i| i >= self.items.len()) {
+            self.selected_index = None;
+        }
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll());
+    }
+
+    /// Draw rows with custom code instead of the item text
+    pub fn set_row_renderer(&mut self, renderer: RowRenderer) {
+        self.row_renderer = Some(renderer);
+    }
+
+    /// Total height of all rows
+    pub fn content_height(&self) -> f32 {
+        self.items.len() as f32 * self.row_height
+    }
+
+    /// Furthest the list can scroll
+    pub fn max_scroll(&self) -> f32 {
+        (self.content_height() - self.h).max(0.0)
+    }
+
+    /// Indices of the rows at least partly on screen
+    pub fn visible_range(&self) -> std::ops::Range<usize> {
+        let first = (self.scroll_offset / self.row_height).floor() as usize;
+        let last = ((self.scroll_offset + self.h) / self.row_height).ceil() as usize;
+        first.min(self.items.len())..last.min(self.items.len())
+    }
+
+    /// Scroll just far enough to show the row at `index`
+    pub fn scroll_to(&mut self, index: usize) {
+        let top = index as f32 * self.row_height;
+        if top < self.scroll_offset {
+            self.scroll_offset = top;
+        } else if top + self.row_height > self.scroll_offset + self.h {
+            self.scroll_offset = top + self.row_height - self.h;
+        }
+        self.scroll_offset = self.scroll_offset.clamp(0.0, self.max_scroll());
+    }
+
+    /// Select the row at `index` and notify `on_select`
+    pub fn select(&mut self, index: usize) {
+        if index >= self.items.len() {
+            return;
+        }
+        self.selected_index = Some(index);
+        self.scroll_to(index);
+        emit_sound(UiSound::ValueChange);
+        emit_event(UiEventKind::Selected(index));
+        if let Some(cb) = &mut self.on_select {
+            cb(index);
+        }
+    }
+
+    /// Row under a screen point, if any
+    fn row_at(&self, point: Vec2) -> Option<usize> {
+        if point.x < self.x || point.x > self.x + self.w - SCROLLBAR_WIDTH || point.y < self.y || point.y > self.y + self.h {
+            return None;
+        }
+        let index = ((point.y - self.y + self.scroll_offset) / self.row_height) as usize;
+        (index < self.items.len()).then_some(index)
+    }
+
+    /// Scrollbar thumb position and height, if the list overflows
+    fn scrollbar_thumb(&self) -> Option<(f32, f32)> {
+        let max_scroll = self.max_scroll();
+        if max_scroll <= 0.0 {
+            return None;
+        }
+        let height = (self.h * self.h / self.content_height()).max(20.0).min(self.h);
+        let y = self.y + (self.h - height) * self.scroll_offset / max_scroll;
+        Some((y, height))
+    }
+}
+
+impl UiElement for UiList {
+    element_fields!(bounds, position, visible, enabled, opacity);
+
+    fn draw(&self, theme: &Theme) {
+        draw_rounded_rectangle(self.x, self.y, self.w, self.h, theme.border_radius, theme.background);
+
+        push_clip_rect(self.x, self.y, self.w, self.h);
+        let row_width = self.w - SCROLLBAR_WIDTH;
+        for index in self.visible_range() {
+            let row = Rect::new(
+                self.x,
+                self.y + index as f32 * self.row_height - self.scroll_offset,
+                row_width,
+                self.row_height,
+            );
+            let selected = self.selected_index == Some(index);
+            if selected {
+                draw_rectangle(row.x, row.y, row.w, row.h, theme.accent);
+            } else if self.hover_index == Some(index) {
+                draw_rectangle(row.x, row.y, row.w, row.h, mix_color(theme.background, theme.secondary, 0.5));
+            }
+
+            if let Some(renderer) = &self.row_renderer {
+                renderer(index, row, selected, theme);
+            } else {
+                let text_dim = measure_text(&self.items[index], Some(&self.font), ui_font_size(self.font_size), 1.0);
+                draw_text_ex(
+                    &self.items[index],
+                    row.x + theme.padding,
+                    row.y + (row.h + text_dim.height) / 2.0,
+                    TextParams {
+                        font: Some(&self.font),
+                        font_size: ui_font_size(self.font_size),
+                        color: theme.text,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+        pop_clip_rect();
+
+        if let Some((thumb_y, thumb_h)) = self.scrollbar_thumb() {
+            let bar_x = self.x + self.w - SCROLLBAR_WIDTH;
+            draw_rectangle(bar_x, self.y, SCROLLBAR_WIDTH, self.h, mix_color(theme.background, theme.secondary, 0.3));
+            draw_rectangle(bar_x + 1.0, thumb_y, SCROLLBAR_WIDTH - 2.0, thumb_h, theme.secondary);
+        }
+    }
+
+    fn update(&mut self, _theme: &Theme, _manager: Option<&mut UiManager>) {
+        let (mx, my) = ui_mouse_position();
+        let mouse = vec2(mx, my);
+        let over = self.contains_point(mouse);
+        self.hover_index = self.row_at(mouse);
+
+        if over {
+            let (_, wheel_y) = mouse_wheel();
+            if wheel_y != 0.0 {
+                self.scroll_offset -= wheel_y.signum() * self.row_height * 3.0;
+            }
+        }
+
+        if is_mouse_button_pressed(MouseButton::Left) && over {
+            if mx >= self.x + self.w - SCROLLBAR_WIDTH {
+                self.dragging_scrollbar = self.scrollbar_thumb().is_some();
+            } else if let Some(index) = self.hover_index {
+                self.select(index);
+            }
+        }
+        if is_mouse_button_released(MouseButton::Left) {
+            self.dragging_scrollbar = false;
+        }
+        if self.dragging_scrollbar && let Some((_, thumb_h)) = self.scrollbar_thumb() {
+            let t = ((my - self.y - thumb_h / 2.0) / (self.h - thumb_h)).clamp(0.0, 1.0);
+            self.scroll_offset = t * self.max_scroll();
+        }
+
+        self.scroll_offset = self.scroll_offset.clamp(0.0, self.max_scroll());
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn cursor_icon(&self, point: Vec2) -> Option<CursorIcon> {
+        self.row_at(point).map(|_| CursorIcon::Pointer)
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.items.is_empty()
+    }
+
+    fn activate(&mut self) {
+        if let Some(index) = self.selected_index {
+            self.select(index);
+        }
+    }
+
+    fn navigate(&mut self, direction: NavDirection) -> bool {
+        let last = self.items.len().saturating_sub(1);
+        let next = match (direction, self.selected_index) {
+            (NavDirection::Up, Some(0)) | (NavDirection::Left, _) | (NavDirection::Right, _) => return false,
+            (NavDirection::Down, Some(i)) if i >= last => return false,
+            (NavDirection::Up, Some(i)) => i - 1,
+            (NavDirection::Down, Some(i)) => i + 1,
+            (_, None) => 0,
+        };
+        self.selected_index = Some(next);
+        self.scroll_to(next);
+        true
+    }
+}
+
+/// Entry in a `UiDropdown` list
+#[derive(Clone)]
+pub struct DropdownOption {
+    pub label: String,
+    /// Image drawn before the label
+    pub icon: Option<Texture2D>,
+    /// Greyed out and not selectable
+    pub disabled: bool,
+    /// Divider line instead of a selectable entry
+    pub separator: bool,
+}
+
+impl DropdownOption {
+    /// Create a selectable option
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            icon: None,
+            disabled: false,
+            separator: false,
+        }
+    }
+
+    /// Create a divider between groups of options
+    pub fn separator() -> Self {
+        Self {
+            separator: true,
+            ..Self::new("")
+        }
+    }
+
+    /// Draw an icon before the label
+    pub fn with_icon(mut self, icon: Texture2D) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Grey the option out so it can't be selected
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Check if the option can be selected
+    pub fn is_selectable(&self) -> bool {
+        !self.disabled && !self.separator
+    }
+}
+
+impl From<&str> for DropdownOption {
+    fn from(label: &str) -> Self {
+        DropdownOption::new(label)
+    }
+}
+
+impl From<String> for DropdownOption {
+    fn from(label: String) -> Self {
+        DropdownOption::new(&label)
+    }
+}
+
+/// Callback a `UiDropdown` fires when its selection changes
+pub enum DropdownSelect {
+    /// Called with the chosen option
+    Single(Box<dyn Fn(usize)>),
+    /// Called with every selected option, in multi-select mode
+    Multi(Box<dyn Fn(Vec<usize>)>),
+}
+
+/// Dropdown menu UI element
+pub struct UiDropdown {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub options: Vec<DropdownOption>,
+    /// Localization keys the option labels are looked up from
+    pub option_keys: Option<Vec<String>>,
+    pub selected_index: usize,
+    pub is_open: bool,
+    pub font: Font,
+    pub font_size: u16,
+    pub on_select: Option<DropdownSelect>,
+    pub hover_index: Option<usize>,
+    // New customization options
+    pub hover_color: Color,
+    pub active_color: Color,
+    pub option_height: f32,
+    pub option_padding: f32,
+    pub animation_speed: f32,
+    pub hover_animation: Animation,
+    pub press_animation: Animation,
+    // Multi-select mode
+    pub multi_select: bool,
+    pub selected_indices: Vec<usize>,
+    /// Button text in multi-select mode when nothing is selected
+    pub none_selected_text: String,
+    /// Button text in multi-select mode when several options are selected, with `{count}` replaced
+    pub many_selected_text: String,
+    /// Localization keys the two texts above are looked up from
+    pub summary_keys: Option<(String, String)>,
+    /// Whether the dropdown receives keyboard input
+    pub focused: bool,
+    pub opacity: f32,
+    pub visible: bool,
+    pub enabled: bool,
+}
+
+impl UiDropdown {
+    /// Create a dropdown from plain option labels
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        options: Vec<String>,
+        font: Font,
+        font_size: u16,
+        on_select: Option<Box<dyn Fn(usize)>>,
+    ) -> Self {
+        let options = options.into_iter().map(DropdownOption::from).collect();
+        Self::with_options(x, y, width, height, options, font, font_size, on_select)
+    }
+
+    /// Create a dropdown from options with icons, disabled entries or separators
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        options: Vec<DropdownOption>,
+        font: Font,
+        font_size: u16,
+        on_select: Option<Box<dyn Fn(usize)>>,
+    ) -> Self {
+        let selected_index = options.iter().position(DropdownOption::is_selectable).unwrap_or(0);
+        Self {
+            x,
+            y,
+            width,
+            height,
+            options,
+            option_keys: None,
+            selected_index,
+            is_open: false,
+            font,
+            font_size,
+            on_select: on_select.map(DropdownSelect::Single),
+            hover_index: None,
+            hover_color: Color::from_rgba(60, 60, 60, 255),
+            active_color: Color::from_rgba(80, 80, 80, 255),
+            option_height: height,
+            option_padding: 5.0,
+            animation_speed: 0.2,
+            hover_animation: Animation::new(0.0, 0.2),
+            press_animation: Animation::new(0.0, 0.3),
+            multi_select: false,
+            selected_indices: Vec::new(),
+            none_selected_text: "None selected".to_string(),
+            many_selected_text: "{count} selected".to_string(),
+            summary_keys: None,
+            focused: false,
+            opacity: 1.0,
+            visible: true,
+            enabled: true,
+        }
+    }
+
+    /// Look the option labels up from localization keys whenever the language changes
+    ///
+    /// Keys pair up with options in order, so separators need a key too (it can be empty).
+    pub fn set_option_keys(&mut self, keys: Vec<String>) {
+        self.option_keys = Some(keys);
+    }
+
+    /// Set the button text shown in multi-select mode when nothing or several options are selected
+    ///
+    /// `{count}` in `many` is replaced by the number of selected options.
+    pub fn set_summary_text(&mut self, none: &str, many: &str) {
+        self.none_selected_text = none.to_string();
+        self.many_selected_text = many.to_string();
+    }
+
+    /// Look the multi-select summary texts up from localization keys whenever the language changes
+    pub fn set_summary_keys(&mut self, none_key: &str, many_key: &str) {
+        self.summary_keys = Some((none_key.to_string(), many_key.to_string()));
+    }
+
+    /// Select an option, or toggle it in multi-select mode
+    ///
+    /// Single selection closes the list and fires `on_select`.
+    /// Disabled options and separators are ignored.
+    pub fn select_option(&mut self, index: usize) {
+        if !self.options.get(index).is_some_and(DropdownOption::is_selectable) {
+            return;
+        }
+        if self.multi_select {
+            // Keep the list open while toggling
+            self.toggle_option(index);
+        } else {
+            self.selected_index = index;
+            self.is_open = false;
+            emit_sound(UiSound::ValueChange);
+            emit_event(UiEventKind::Selected(index));
+            if let Some(DropdownSelect::Single(callback)) = &self.on_select {
+                callback(index);
+            }
+        }
+    }
+
+    /// Handle Up/Down/Enter/Escape while focused or open
+    fn handle_keyboard(&mut self) {
+        if self.options.is_empty() {
+            return;
+        }
+        let enter = is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter);
+
+        if !self.is_open {
+            if enter || is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::Space) {
+                self.is_open = true;
+                emit_sound(UiSound::DropdownOpen);
+                self.hover_index = Some(self.selected_index);
+            }
+            return;
+        }
+
+        if is_key_pressed(KeyCode::Down) {
+            self.hover_index = self.step_hover(true);
+        }
+        if is_key_pressed(KeyCode::Up) {
+            self.hover_index = self.step_hover(false);
+        }
+        if enter && let Some(index) = self.hover_index {
+            self.select_option(index);
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            self.is_open = false;
+        }
+    }
+
+    /// Nearest selectable option after (or before) the hovered one
+    ///
+    /// Stays on the hovered option when there is nothing further that way.
+    fn step_hover(&self, forward: bool) -> Option<usize> {
+        let selectable = |i: &usize| self.options[*i].is_selectable();
+        let next = match (self.hover_index, forward) {
+            (Some(i), true) => (i + 1..self.options.len()).find(selectable),
+            (Some(i), false) => (0..i).rev().find(selectable),
+            (None, _) => (0..self.options.len()).find(selectable),
+        };
+        next.or(self.hover_index)
+    }
+
+    /// Switch the dropdown into multi-select mode
+    ///
+    /// Each option gets a checkbox, the list stays open while toggling and
+    /// `on_select` receives every selected index after each change, in
+    /// place of the single-select callback.
+    pub fn set_multi_select(&mut self, on_select: Option<Box<dyn Fn(Vec<usize>)>>) {
+        self.multi_select = true;
+        self.on_select = on_select.map(DropdownSelect::Multi);
+    }
+
+    /// Check if an option is selected
+    pub fn is_selected(&self, index: usize) -> bool {
+        if self.multi_select {
+            self.selected_indices.contains(&index)
+        } else {
+            self.selected_index == index
+        }
+    }
+
+    /// Toggle an option in multi-select mode and notify the callback
+    pub fn toggle_option(&mut self, index: usize) {
+        if !self.options.get(index).is_some_and(DropdownOption::is_selectable) {
+            return;
+        }
+        emit_sound(UiSound::ValueChange);
+        if let Some(pos) = self.selected_indices.iter().position(|&i| i == index) {
+            self.selected_indices.remove(pos);
+        } else {
+            self.selected_indices.push(index);
+            self.selected_indices.sort_unstable();
+        }
+        emit_event(UiEventKind::MultiSelected(self.selected_indices.clone()));
+        if let Some(DropdownSelect::Multi(callback)) = &self.on_select {
+            callback(self.selected_indices.clone());
+        }
+    }
+
+    /// Text shown on the dropdown button
+    fn summary_text(&self) -> String {
+        let label = |index: usize| self.options.get(index).map_or_else(String::new, |o| o.label.clone());
+        if !self.multi_select {
+            return label(self.selected_index);
+        }
+        match self.selected_indices.as_slice() {
+            [] => self.none_selected_text.clone(),
+            [index] => label(*index),
+            indices => self.many_selected_text.replace("{count}", &indices.len().to_string()),
+        }
+    }
+
+    pub fn set_hover_color(&mut self, color: Color) {
+        self.hover_color = color;
+    }
+
+    pub fn set_active_color(&mut self, color: Color) {
+        self.active_color = color;
+    }
+
+    pub fn set_option_height(&mut self, height: f32) {
+        self.option_height = height;
+    }
+
+    pub fn set_option_padding(&mut self, padding: f32) {
+        self.option_padding = padding;
+    }
+
+    pub fn set_animation_speed(&mut self, speed: f32) {
+        self.animation_speed = speed;
+        self.hover_animation.speed = speed;
+        self.press_animation.speed = speed;
+    }
+}
+
+impl UiElement for UiDropdown {
+    element_fields!(visible, enabled, opacity);
+
+    fn draw(&self, theme: &Theme) {
+        let hover = self.hover_animation.current;
+        let press = self.press_animation.current;
+        
+        let button_color = if press > 0.0 {
+            Color::new(
+                self.active_color.r + (theme.primary.r - self.active_color.r) * press,
+                self.active_color.g + (theme.primary.g - self.active_color.g) * press,
+                self.active_color.b + (theme.primary.b - self.active_color.b) * press,
+                self.active_color.a,
+            )
+        } else {
+            Color::new(
+                theme.primary.r + (self.hover_color.r - theme.primary.r) * hover,
+                theme.primary.g + (self.hover_color.g - theme.primary.g) * hover,
+                theme.primary.b + (self.hover_color.b - theme.primary.b) * hover,
+                theme.primary.a,
+            )
+        };
+
+        // Draw button
+        draw_rounded_rectangle(
+            self.x,
+            self.y,
+            self.width,
+            self.height,
+            theme.border_radius,
+            button_color,
+        );
+
+        // Draw focus outline
+        if self.focused {
+            draw_rectangle_lines(self.x, self.y, self.width, self.height, 1.0, theme.accent);
+        }
+
+        // Draw selected text
+        let text = self.summary_text();
+        let text_size = measure_text(&text, Some(&self.font), ui_font_size(self.font_size), 1.0);
+        draw_text_ex(
+            &text,
             self.x + 10.0,
             self.y + (self.height + text_size.height) / 2.0,
             TextParams {
                 font: Some(&self.font),
-                font_size: self.font_size,
+                font_size: ui_font_size(self.font_size),
                 color: theme.text,
                 ..Default::default()
             },
@@ -1167,67 +3849,125 @@ impl UiElement for UiDropdown {
         };
         
         draw_triangle(points[0], points[1], points[2], theme.text);
+    }
 
-        // If open, draw options
-        if self.is_open {
-            // Draw semi-transparent overlay
-            draw_rectangle(
-                0.0, 0.0,
-                screen_width(),
-                screen_height(),
-                Color::new(0.0, 0.0, 0.0, 0.3)
-            );
+    fn draw_overlay(&self, theme: &Theme) {
+        if !self.is_open {
+            return;
+        }
+
+        // Draw semi-transparent overlay
+        let (screen_w, screen_h) = ui_screen_size();
+        draw_rectangle(
+            0.0, 0.0,
+            screen_w,
+            screen_h,
+            Color::new(0.0, 0.0, 0.0, 0.3)
+        );
+        
+        // Draw options background
+        let total_height = self.option_height * (self.options.len() as f32);
+        draw_rounded_rectangle(
+            self.x,
+            self.y + self.height,
+            self.width,
+            total_height,
+            theme.border_radius,
+            theme.background,
+        );
+        
+        // Draw each option
+        for (i, option) in self.options.iter().enumerate() {
+            let option_y = self.y + self.height + (i as f32 * self.option_height);
+
+            if option.separator {
+                let line_y = option_y + self.option_height / 2.0;
+                draw_rectangle(self.x, option_y, self.width, self.option_height, theme.secondary);
+                draw_line(
+                    self.x + self.option_padding,
+                    line_y,
+                    self.x + self.width - self.option_padding,
+                    line_y,
+                    1.0,
+                    mix_color(theme.text, theme.secondary, 0.6),
+                );
+                continue;
+            }
+            
+            // Draw option background with hover effect
+            let bg_color = if Some(i) == self.hover_index {
+                self.hover_color
+            } else {
+                theme.secondary
+            };
             
-            // Draw options background
-            let total_height = self.option_height * (self.options.len() as f32);
             draw_rounded_rectangle(
                 self.x,
-                self.y + self.height,
+                option_y,
                 self.width,
-                total_height,
+                self.option_height,
                 theme.border_radius,
-                theme.background,
+                bg_color,
             );
-            
-            // Draw each option
-            for (i, option) in self.options.iter().enumerate() {
-                let option_y = self.y + self.height + (i as f32 * self.option_height);
-                
-                // Draw option background with hover effect
-                let bg_color = if Some(i) == self.hover_index {
-                    self.hover_color
-                } else {
-                    theme.secondary
-                };
-                
-                draw_rounded_rectangle(
-                    self.x,
-                    option_y,
-                    self.width,
-                    self.option_height,
-                    theme.border_radius,
-                    bg_color,
-                );
 
-                // Draw option text
-                let text_size = measure_text(option, Some(&self.font), self.font_size, 1.0);
-                draw_text_ex(
-                    option,
-                    self.x + self.option_padding,
-                    option_y + (self.option_height + text_size.height) / 2.0,
-                    TextParams {
-                        font: Some(&self.font),
-                        font_size: self.font_size,
-                        color: theme.text,
+            // Draw option checkbox in multi-select mode
+            let mut text_x = self.x + self.option_padding;
+            if self.multi_select {
+                let box_size = self.option_height * 0.5;
+                let box_y = option_y + (self.option_height - box_size) / 2.0;
+                draw_rectangle_lines(text_x, box_y, box_size, box_size, 1.0, theme.text);
+                if self.is_selected(i) {
+                    draw_rectangle(
+                        text_x + 3.0,
+                        box_y + 3.0,
+                        box_size - 6.0,
+                        box_size - 6.0,
+                        theme.accent,
+                    );
+                }
+                text_x += box_size + self.option_padding;
+            }
+
+            // Draw option icon
+            if let Some(icon) = &option.icon {
+                let icon_size = self.option_height - self.option_padding * 2.0;
+                let tint = if option.disabled { Color::new(1.0, 1.0, 1.0, 0.4) } else { WHITE };
+                draw_texture_ex(
+                    icon,
+                    text_x,
+                    option_y + self.option_padding,
+                    Color::new(tint.r, tint.g, tint.b, tint.a * theme.text.a),
+                    DrawTextureParams {
+                        dest_size: Some(vec2(icon_size, icon_size)),
                         ..Default::default()
                     },
                 );
+                text_x += icon_size + self.option_padding;
             }
+
+            // Draw option text, greyed out if disabled
+            let text_color = if option.disabled {
+                mix_color(theme.text, theme.secondary, 0.6)
+            } else {
+                theme.text
+            };
+            let text_size = measure_text(&option.label, Some(&self.font), ui_font_size(self.font_size), 1.0);
+            draw_text_ex(
+                &option.label,
+                text_x,
+                option_y + (self.option_height + text_size.height) / 2.0,
+                TextParams {
+                    font: Some(&self.font),
+                    font_size: ui_font_size(self.font_size),
+                    color: text_color,
+                    ..Default::default()
+                },
+            );
         }
     }
 
-    fn update(&mut self, theme: &Theme, manager: Option<&mut UiManager>) {
-        let (mouse_x, mouse_y) = mouse_position();
+    fn update(&mut self, _theme: &Theme, manager: Option<&mut UiManager>) {
+        let (mouse_x, mouse_y) = ui_mouse_position();
         
         // Check if mouse is over the main button
         let is_over_button = mouse_x >= self.x && mouse_x <= self.x + self.width &&
@@ -1242,71 +3982,499 @@ impl UiElement for UiDropdown {
         // Handle button click
         if is_over_button && is_mouse_button_pressed(MouseButton::Left) {
             println!("Dropdown button clicked, current is_open: {}", self.is_open);
+            self.focused = true;
             self.is_open = !self.is_open;
             println!("New is_open state: {}", self.is_open);
             
             if self.is_open {
+                emit_sound(UiSound::DropdownOpen);
                 // Bring to front when opened
-                if let Some(manager) = manager {
-                    if let Some(index) = manager.get_element_index(self) {
-                        manager.bring_to_front(index);
+                if let Some(manager) = manager && let Some(index) = manager.get_element_index(self) {
+                    manager.bring_to_front(index);
+                }
+            }
+            return; // Prevent immediate closing
+        }
+
+        // Handle options if dropdown is open
+        if self.is_open {
+            let total_height = self.option_height * (self.options.len() as f32);
+            
+            // Check if mouse is over the entire dropdown area (button + options)
+            let is_over_dropdown = mouse_x >= self.x && mouse_x <= self.x + self.width &&
+                                 mouse_y >= self.y && mouse_y <= self.y + self.height + total_height;
+
+            println!("is_over_dropdown: {}, total_height: {}", is_over_dropdown, total_height);
+
+            if is_over_dropdown {
+                // Check if mouse is over options area
+                let is_over_options = mouse_y > self.y + self.height;
+                
+                if is_over_options {
+                    let option_index = ((mouse_y - (self.y + self.height)) / self.option_height) as usize;
+                    if self.options.get(option_index).is_some_and(DropdownOption::is_selectable) {
+                        self.hover_index = Some(option_index);
+                        if is_mouse_button_pressed(MouseButton::Left) {
+                            println!("Option selected: {}", option_index);
+                            self.select_option(option_index);
+                        }
                     }
                 }
+            } else if is_mouse_button_pressed(MouseButton::Left) {
+                // Click outside closes the dropdown
+                println!("Click outside, closing dropdown");
+                self.is_open = false;
+                self.focused = false;
+            }
+        } else if is_mouse_button_pressed(MouseButton::Left) {
+            self.focused = false;
+        }
+
+        if self.focused || self.is_open {
+            self.handle_keyboard();
+        }
+    }
+
+    fn get_bounds(&self) -> (f32, f32, f32, f32) {
+        let total_height = if self.is_open {
+            self.height + (self.option_height * (self.options.len() as f32))
+        } else {
+            self.height
+        };
+        (self.x, self.y, self.width, total_height)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    fn set_size(&mut self, w: f32, h: f32) {
+        self.width = w;
+        self.height = h;
+    }
+
+    fn cursor_icon(&self, _point: Vec2) -> Option<CursorIcon> {
+        Some(CursorIcon::Pointer)
+    }
+
+    fn localize(&mut self, localization: &Localization) {
+        if let Some(keys) = &self.option_keys {
+            for (option, key) in self.options.iter_mut().zip(keys) {
+                if !option.separator {
+                    option.label = localization.get(key);
+                }
+            }
+        }
+        if let Some((none_key, many_key)) = &self.summary_keys {
+            self.none_selected_text = localization.get(none_key);
+            self.many_selected_text = localization.get(many_key);
+        }
+    }
+
+    fn has_focus(&self) -> bool {
+        self.focused
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.options.is_empty()
+    }
+
+    fn activate(&mut self) {
+        if self.is_open {
+            self.select_option(self.hover_index.unwrap_or(self.selected_index));
+        } else {
+            self.is_open = true;
+            self.hover_index = Some(self.selected_index);
+            emit_sound(UiSound::DropdownOpen);
+        }
+    }
+
+    fn navigate(&mut self, direction: NavDirection) -> bool {
+        if !self.is_open || self.options.is_empty() {
+            return false;
+        }
+        self.hover_index = self.hover_index.or(Some(self.selected_index));
+        match direction {
+            NavDirection::Up => self.hover_index = self.step_hover(false),
+            NavDirection::Down => self.hover_index = self.step_hover(true),
+            NavDirection::Left | NavDirection::Right => {}
+        }
+        true
+    }
+}
+
+/// Screen edge an element slides in from or out to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Animation played when an element is added to or removed from a `UiManager`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transition {
+    /// Fade the opacity in or out
+    Fade,
+    /// Slide in from, or out to, a screen edge
+    Slide(Edge),
+    /// Grow from, or shrink to, the element's center
+    Scale,
+}
+
+/// Transition currently playing on a manager element
+struct ActiveTransition {
+    index: usize,
+    kind: Transition,
+    duration: f32,
+    elapsed: f32,
+    exiting: bool,
+    /// Bounds and opacity the element rests at once it has entered
+    bounds: (f32, f32, f32, f32),
+    opacity: f32,
+}
+
+impl ActiveTransition {
+    fn new(index: usize, element: &dyn UiElement, kind: Transition, duration: f32, exiting: bool) -> Self {
+        Self {
+            index,
+            kind,
+            duration,
+            elapsed: 0.0,
+            exiting,
+            bounds: element.get_bounds(),
+            opacity: element.opacity(),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// How far the element is shown, from 0.0 (gone) to 1.0 (at rest)
+    fn progress(&self) -> f32 {
+        let t = if self.duration > 0.0 { self.elapsed / self.duration } else { 1.0 };
+        let t = Easing::EaseOut.apply(t);
+        if self.exiting { 1.0 - t } else { t }
+    }
+
+    /// Write the current state of the transition into the element
+    fn apply(&self, element: &mut dyn UiElement) {
+        let p = self.progress();
+        let (x, y, w, h) = self.bounds;
+        match self.kind {
+            Transition::Fade => element.set_opacity(self.opacity * p),
+            Transition::Slide(edge) => {
+                let (screen_w, screen_h) = ui_screen_size();
+                let (from_x, from_y) = match edge {
+                    Edge::Left => (-w, y),
+                    Edge::Right => (screen_w, y),
+                    Edge::Top => (x, -h),
+                    Edge::Bottom => (x, screen_h),
+                };
+                element.set_position(from_x + (x - from_x) * p, from_y + (y - from_y) * p);
+            }
+            Transition::Scale => {
+                element.set_size(w * p, h * p);
+                element.set_position(x + w * (1.0 - p) / 2.0, y + h * (1.0 - p) / 2.0);
+            }
+        }
+    }
+
+    /// Put the element back at its resting bounds and opacity
+    fn restore(&self, element: &mut dyn UiElement) {
+        let (x, y, w, h) = self.bounds;
+        match self.kind {
+            Transition::Fade => element.set_opacity(self.opacity),
+            Transition::Slide(_) => element.set_position(x, y),
+            Transition::Scale => {
+                element.set_size(w, h);
+                element.set_position(x, y);
+            }
+        }
+    }
+}
+
+/// Direction pressed on a d-pad, stick or arrow keys
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NavDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl NavDirection {
+    /// Convert an analog stick into a direction, ignoring movement inside `deadzone`
+    ///
+    /// `y` is expected to point down, like screen coordinates.
+    pub fn from_stick(x: f32, y: f32, deadzone: f32) -> Option<Self> {
+        if x.abs() < deadzone && y.abs() < deadzone {
+            None
+        } else if x.abs() > y.abs() {
+            Some(if x < 0.0 { NavDirection::Left } else { NavDirection::Right })
+        } else {
+            Some(if y < 0.0 { NavDirection::Up } else { NavDirection::Down })
+        }
+    }
+
+    /// Unit vector pointing in the direction
+    fn vector(&self) -> Vec2 {
+        match self {
+            NavDirection::Up => vec2(0.0, -1.0),
+            NavDirection::Down => vec2(0.0, 1.0),
+            NavDirection::Left => vec2(-1.0, 0.0),
+            NavDirection::Right => vec2(1.0, 0.0),
+        }
+    }
+}
+
+/// Navigation input for one frame, fed to `UiManager::navigate`
+///
+/// Fill this from whichever gamepad library the game uses. Both fields describe
+/// what is currently held, and the manager works out presses and repeats.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NavInput {
+    /// Direction held on the d-pad or stick
+    pub direction: Option<NavDirection>,
+    /// Whether the confirm (A) button is held
+    pub confirm: bool,
+}
+
+impl NavInput {
+    /// Read navigation from the arrow keys, with Enter or Space to confirm
+    pub fn from_keyboard() -> Self {
+        let direction = if is_key_down(KeyCode::Up) {
+            Some(NavDirection::Up)
+        } else if is_key_down(KeyCode::Down) {
+            Some(NavDirection::Down)
+        } else if is_key_down(KeyCode::Left) {
+            Some(NavDirection::Left)
+        } else if is_key_down(KeyCode::Right) {
+            Some(NavDirection::Right)
+        } else {
+            None
+        };
+        Self {
+            direction,
+            confirm: is_key_down(KeyCode::Enter) || is_key_down(KeyCode::Space),
+        }
+    }
+}
+
+/// Seconds a direction must be held before it starts repeating
+const NAV_REPEAT_DELAY: f32 = 0.4;
+/// Seconds between repeats while a direction stays held
+const NAV_REPEAT_INTERVAL: f32 = 0.1;
+
+/// Gamepad focus state of a `UiManager`
+#[derive(Default)]
+struct NavState {
+    /// Index path to the focused element, through nested panels
+    focus: Option<Vec<usize>>,
+    previous: NavInput,
+    repeat_timer: f32,
+}
+
+/// Draw a focus outline just outside an element's bounds
+fn draw_focus_outline(element: &dyn UiElement, thickness: f32, color: Color) {
+    let (x, y, w, h) = element.get_bounds();
+    let margin = 1.0 + thickness;
+    draw_rectangle_lines(x - margin, y - margin, w + margin * 2.0, h + margin * 2.0, thickness, color);
+}
+
+/// Outline every visible element with keyboard focus, including inside panels
+fn draw_keyboard_focus(element: &dyn UiElement, thickness: f32, color: Color) {
+    if !element.is_visible() {
+        return;
+    }
+    if element.has_focus() {
+        draw_focus_outline(element, thickness, color);
+    }
+    if let Some(panel) = element.as_any().downcast_ref::<UiPanel>() {
+        for child in &panel.elements {
+            draw_keyboard_focus(child.as_ref(), thickness, color);
+        }
+    }
+}
+
+/// Find the index path of the first element with keyboard focus, including inside panels
+fn find_keyboard_focus(elements: &[Box<dyn UiElement>], path: &mut Vec<usize>) -> Option<Vec<usize>> {
+    for (index, element) in elements.iter().enumerate() {
+        if !element.is_visible() {
+            continue;
+        }
+        path.push(index);
+        if element.has_focus() {
+            return Some(path.clone());
+        }
+        if let Some(panel) = element.as_any().downcast_ref::<UiPanel>()
+            && let Some(found) = find_keyboard_focus(&panel.elements, path)
+        {
+            return Some(found);
+        }
+        path.pop();
+    }
+    None
+}
+
+/// Check if a point is over a visible element or any of its panel children
+fn element_under_point(element: &dyn UiElement, point: Vec2) -> bool {
+    if !element.is_visible() || element.opacity() <= 0.0 {
+        return false;
+    }
+    if element.contains_point(point) {
+        return true;
+    }
+    // Open dropdowns cover the screen with an overlay that closes them on click
+    if let Some(dropdown) = element.as_any().downcast_ref::<UiDropdown>() {
+        return dropdown.is_open;
+    }
+    element
+        .as_any()
+        .downcast_ref::<UiPanel>()
+        .is_some_and(|panel| !panel.minimized && panel.elements.iter().any(|child| element_under_point(child.as_ref(), point)))
+}
+
+/// Index path and bounds of a focusable element
+type FocusTarget = (Vec<usize>, (f32, f32, f32, f32));
+
+/// Collect the index paths and bounds of every focusable element, including inside panels
+fn collect_focusable(
+    elements: &[Box<dyn UiElement>],
+    path: &mut Vec<usize>,
+    out: &mut Vec<FocusTarget>,
+) {
+    for (index, element) in elements.iter().enumerate() {
+        if !element.is_visible() || !element.is_enabled() {
+            continue;
+        }
+        path.push(index);
+        if let Some(panel) = element.as_any().downcast_ref::<UiPanel>() {
+            if !panel.minimized {
+                collect_focusable(&panel.elements, path, out);
             }
-            return; // Prevent immediate closing
+        } else if element.is_focusable() {
+            out.push((path.clone(), element.get_bounds()));
         }
+        path.pop();
+    }
+}
 
-        // Handle options if dropdown is open
-        if self.is_open {
-            let total_height = self.option_height * (self.options.len() as f32);
-            
-            // Check if mouse is over the entire dropdown area (button + options)
-            let is_over_dropdown = mouse_x >= self.x && mouse_x <= self.x + self.width &&
-                                 mouse_y >= self.y && mouse_y <= self.y + self.height + total_height;
+/// Follow an index path through nested panels
+fn element_at_path<'a>(elements: &'a [Box<dyn UiElement>], path: &[usize]) -> Option<&'a dyn UiElement> {
+    let (&first, rest) = path.split_first()?;
+    let element = elements.get(first)?;
+    if rest.is_empty() {
+        return Some(element.as_ref());
+    }
+    let panel = element.as_any().downcast_ref::<UiPanel>()?;
+    element_at_path(&panel.elements, rest)
+}
 
-            println!("is_over_dropdown: {}, total_height: {}", is_over_dropdown, total_height);
+/// Follow an index path through nested panels, mutably
+fn element_at_path_mut<'a>(elements: &'a mut [Box<dyn UiElement>], path: &[usize]) -> Option<&'a mut dyn UiElement> {
+    let (&first, rest) = path.split_first()?;
+    let element = elements.get_mut(first)?;
+    if rest.is_empty() {
+        return Some(element.as_mut());
+    }
+    let panel = element.as_any_mut().downcast_mut::<UiPanel>()?;
+    element_at_path_mut(&mut panel.elements, rest)
+}
 
-            if is_over_dropdown {
-                // Check if mouse is over options area
-                let is_over_options = mouse_y > self.y + self.height;
-                
-                if is_over_options {
-                    let option_index = ((mouse_y - (self.y + self.height)) / self.option_height) as usize;
-                    if option_index < self.options.len() {
-                        self.hover_index = Some(option_index);
-                        if is_mouse_button_pressed(MouseButton::Left) {
-                            println!("Option selected: {}", option_index);
-                            self.selected_index = option_index;
-                            self.is_open = false;
-                            if let Some(callback) = &self.on_select {
-                                callback(option_index);
-                            }
-                        }
-                    }
-                }
-            } else if is_mouse_button_pressed(MouseButton::Left) {
-                // Click outside closes the dropdown
-                println!("Click outside, closing dropdown");
-                self.is_open = false;
+/// Pick the focusable element nearest to `from` in `direction`
+///
+/// Candidates off to the side are penalised so focus prefers moving in a straight line.
+fn nearest_in_direction(
+    candidates: &[FocusTarget],
+    from: (f32, f32, f32, f32),
+    direction: NavDirection,
+) -> Option<Vec<usize>> {
+    let center = |(x, y, w, h): (f32, f32, f32, f32)| vec2(x + w / 2.0, y + h / 2.0);
+    let origin = center(from);
+    let axis = direction.vector();
+    candidates
+        .iter()
+        .filter_map(|(path, bounds)| {
+            let delta = center(*bounds) - origin;
+            let along = delta.dot(axis);
+            if along <= 0.0 {
+                return None;
             }
+            let across = (delta - axis * along).length();
+            Some((along + across * 2.0, path))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, path)| path.clone())
+}
+
+/// Named stacking layers for manager elements
+///
+/// Each layer is a z-index; elements with a higher z-index draw on top.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UiLayer {
+    Background,
+    Default,
+    Overlay,
+    Tooltip,
+}
+
+impl UiLayer {
+    /// Get the z-index the layer stands for
+    pub fn z_index(&self) -> i32 {
+        match self {
+            UiLayer::Background => -100,
+            UiLayer::Default => 0,
+            UiLayer::Overlay => 100,
+            UiLayer::Tooltip => 200,
         }
     }
+}
 
-    fn get_bounds(&self) -> (f32, f32, f32, f32) {
-        let total_height = if self.is_open {
-            self.height + (self.option_height * (self.options.len() as f32))
-        } else {
-            self.height
-        };
-        (self.x, self.y, self.width, total_height)
-    }
+/// Accessibility options for a `UiManager`
+#[derive(Clone)]
+pub struct Accessibility {
+    /// Text is never drawn smaller than this
+    pub min_font_size: u16,
+    /// Draw with `contrast_theme` instead of the manager's theme
+    pub high_contrast: bool,
+    pub contrast_theme: Theme,
+    /// Outline whichever element has keyboard or gamepad focus
+    pub always_show_focus: bool,
+    /// Snap hover, press and transition animations to their end state
+    pub reduced_motion: bool,
+}
 
-    fn as_any(&self) -> &dyn Any {
-        self
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            min_font_size: 0,
+            high_contrast: false,
+            contrast_theme: Theme::high_contrast(),
+            always_show_focus: false,
+            reduced_motion: false,
+        }
     }
+}
 
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
+/// Pick the theme a manager draws with: the accessibility override, the
+/// topmost pushed theme, or the base theme
+fn resolve_theme<'a>(base: &'a Theme, stack: &'a [Theme], accessibility: &'a Accessibility) -> &'a Theme {
+    if accessibility.high_contrast {
+        &accessibility.contrast_theme
+    } else {
+        stack.last().unwrap_or(base)
     }
 }
 
@@ -1314,27 +4482,620 @@ impl UiElement for UiDropdown {
 pub struct UiManager {
     elements: Vec<Box<dyn UiElement>>,
     styles: HashMap<String, UiStyle>,
-    active_element: Option<usize>,
     z_order: Vec<usize>,
+    /// Z-index of each element, parallel to `elements`
+    z_indices: Vec<i32>,
+    names: HashMap<String, usize>,
+    theme: Theme,
+    /// Themes pushed over `theme`, topmost last
+    theme_stack: Vec<Theme>,
+    transitions: Vec<ActiveTransition>,
+    ui_scale: f32,
+    nav: NavState,
+    /// Whether the last update saw a mouse press land on the UI
+    click_consumed: bool,
+    accessibility: Accessibility,
+    localization: Option<Localization>,
+    /// Whether the manager sets the system cursor
+    manage_cursor: bool,
+    cursor: CursorIcon,
+    on_sound: Option<Box<dyn FnMut(UiSound)>>,
+    /// Interactive element under the pointer last update, as an index path
+    hovered: Option<Vec<usize>>,
+    events: Vec<UiEvent>,
+    /// Events already present when the last update finished
+    stale_events: usize,
+    /// Element under the pointer last update, including non-interactive ones
+    pointer_path: Option<Vec<usize>>,
+    /// Element with keyboard or gamepad focus last update
+    focus_path: Option<Vec<usize>>,
+}
+
+impl Default for UiManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl UiManager {
     pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    /// Create a manager that renders with the given theme
+    pub fn with_theme(theme: Theme) -> Self {
         Self {
             elements: Vec::new(),
             styles: HashMap::new(),
-            active_element: None,
             z_order: Vec::new(),
+            z_indices: Vec::new(),
+            names: HashMap::new(),
+            theme,
+            theme_stack: Vec::new(),
+            transitions: Vec::new(),
+            ui_scale: 1.0,
+            nav: NavState::default(),
+            click_consumed: false,
+            accessibility: Accessibility::default(),
+            localization: None,
+            manage_cursor: true,
+            cursor: CursorIcon::Default,
+            on_sound: None,
+            hovered: None,
+            events: Vec::new(),
+            stale_events: 0,
+            pointer_path: None,
+            focus_path: None,
+        }
+    }
+
+    /// Switch the base theme used to update and draw every element
+    ///
+    /// Themes pushed with `push_theme` still take precedence.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Get the current theme, the topmost pushed one if any
+    pub fn theme(&self) -> &Theme {
+        self.theme_stack.last().unwrap_or(&self.theme)
+    }
+
+    /// Temporarily draw with another theme, e.g. while a modal dialog is open
+    pub fn push_theme(&mut self, theme: Theme) {
+        self.theme_stack.push(theme);
+    }
+
+    /// Go back to the theme below the topmost pushed one
+    ///
+    /// The base theme set with `set_theme` is never popped.
+    pub fn pop_theme(&mut self) -> Option<Theme> {
+        self.theme_stack.pop()
+    }
+
+    /// Call `hook` for every hover, click, value change and dropdown opening in the UI
+    ///
+    /// One hook covers every element, so sounds can be attached in a single place.
+    pub fn set_sound_hook(&mut self, hook: Box<dyn FnMut(UiSound)>) {
+        self.on_sound = Some(hook);
+    }
+
+    /// Stop calling the sound hook
+    pub fn clear_sound_hook(&mut self) {
+        self.on_sound = None;
+    }
+
+    /// Index path of the interactive element under the mouse, through nested panels
+    pub fn hovered_path(&self) -> Option<Vec<usize>> {
+        let (x, y) = mouse_position();
+        let point = vec2(x, y) / self.ui_scale;
+        let index = self
+            .z_order
+            .iter()
+            .rev()
+            .copied()
+            .find(|&index| element_under_point(self.elements[index].as_ref(), point))?;
+        let mut path = vec![index];
+        let mut element = self.elements[index].as_ref();
+        while let Some(panel) = element.as_any().downcast_ref::<UiPanel>().filter(|panel| !panel.minimized) {
+            let Some(child) = panel
+                .elements
+                .iter()
+                .rposition(|child| child.is_visible() && child.contains_point(point))
+            else {
+                break;
+            };
+            path.push(child);
+            element = panel.elements[child].as_ref();
+        }
+        Some(path)
+    }
+
+    /// Call the enter/leave and focus/blur hooks of elements whose state changed
+    ///
+    /// Entering a panel's child also enters the panel; leaving runs innermost first.
+    fn dispatch_hover_and_focus(&mut self) {
+        let pointer = self.hovered_path();
+        if pointer != self.pointer_path {
+            let old = self.pointer_path.take().unwrap_or_default();
+            let new = pointer.clone().unwrap_or_default();
+            let shared = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+            for depth in (shared..old.len()).rev() {
+                self.notify(&old[..=depth], |element| element.on_mouse_leave(), UiEventKind::MouseLeave);
+            }
+            for depth in shared..new.len() {
+                self.notify(&new[..=depth], |element| element.on_mouse_enter(), UiEventKind::MouseEnter);
+            }
+            self.pointer_path = pointer;
+        }
+
+        let focus = self
+            .nav
+            .focus
+            .clone()
+            .or_else(|| find_keyboard_focus(&self.elements, &mut Vec::new()));
+        if focus != self.focus_path {
+            if let Some(old) = self.focus_path.take() {
+                self.notify(&old, |element| element.on_blur(), UiEventKind::Blur);
+            }
+            if let Some(new) = &focus {
+                self.notify(new, |element| element.on_focus(), UiEventKind::Focus);
+            }
+            self.focus_path = focus;
+        }
+    }
+
+    /// Run a hook on the element at `path` and queue the matching event
+    fn notify(&mut self, path: &[usize], hook: impl FnOnce(&mut dyn UiElement), kind: UiEventKind) {
+        if let Some(element) = element_at_path_mut(&mut self.elements, path) {
+            EVENT_PATH.with(|current| current.replace(path.to_vec()));
+            hook(element);
+            emit_event(kind);
+            EVENT_PATH.with(|current| current.borrow_mut().clear());
+        }
+    }
+
+    /// Play hover sounds and pass queued element sounds to the hook
+    fn dispatch_sounds(&mut self) {
+        let (x, y) = mouse_position();
+        let point = vec2(x, y) / self.ui_scale;
+        // Only elements that show an interactive cursor count as hover targets
+        let hovered = self.hovered_path().filter(|path| {
+            element_at_path(&self.elements, path)
+                .is_some_and(|element| element.is_enabled() && element.cursor_icon(point).is_some())
+        });
+        let entered = hovered.is_some() && hovered != self.hovered;
+        self.hovered = hovered;
+
+        let pending = PENDING_SOUNDS.with(|sounds| std::mem::take(&mut *sounds.borrow_mut()));
+        if let Some(hook) = &mut self.on_sound {
+            if entered {
+                hook(UiSound::HoverEnter);
+            }
+            for sound in pending {
+                hook(sound);
+            }
+        }
+    }
+
+    /// Events raised since they were last drained
+    pub fn events(&self) -> &[UiEvent] {
+        &self.events
+    }
+
+    /// Take every queued event
+    ///
+    /// Call this once a frame; events left undrained are dropped after one more update.
+    pub fn drain_events(&mut self) -> Vec<UiEvent> {
+        self.stale_events = 0;
+        std::mem::take(&mut self.events)
+    }
+
+    /// Check if the named element was clicked since events were last drained
+    pub fn was_clicked(&self, name: &str) -> bool {
+        self.events
+            .iter()
+            .any(|event| event.kind == UiEventKind::Clicked && event.name.as_deref() == Some(name))
+    }
+
+    /// Move events raised by elements into the manager's queue
+    fn collect_events(&mut self) {
+        let pending = PENDING_EVENTS.with(|events| std::mem::take(&mut *events.borrow_mut()));
+        for (path, kind) in pending {
+            let name = self.name_at_path(&path);
+            self.events.push(UiEvent { path, name, kind });
+        }
+    }
+
+    /// Name an element was added with, looked up in its manager or parent panel
+    fn name_at_path(&self, path: &[usize]) -> Option<String> {
+        let (&last, parents) = path.split_last()?;
+        let names = if parents.is_empty() {
+            &self.names
+        } else {
+            &element_at_path(&self.elements, parents)?.as_any().downcast_ref::<UiPanel>()?.names
+        };
+        names.iter().find(|&(_, &index)| index == last).map(|(name, _)| name.clone())
+    }
+
+    /// Choose whether the manager sets the system cursor over interactive elements
+    pub fn set_cursor_management(&mut self, enabled: bool) {
+        if !enabled && self.manage_cursor {
+            set_mouse_cursor(CursorIcon::Default);
+            self.cursor = CursorIcon::Default;
+        }
+        self.manage_cursor = enabled;
+    }
+
+    /// Cursor for whatever element is topmost under the mouse
+    pub fn hovered_cursor(&self) -> CursorIcon {
+        let (x, y) = mouse_position();
+        let point = vec2(x, y) / self.ui_scale;
+        self.z_order
+            .iter()
+            .rev()
+            .filter_map(|&index| self.elements.get(index))
+            .find(|element| element.is_visible() && element.contains_point(point))
+            .and_then(|element| if element.is_enabled() { element.cursor_icon(point) } else { None })
+            .unwrap_or(CursorIcon::Default)
+    }
+
+    /// Update the system cursor, only calling into the window when it changes
+    fn update_cursor(&mut self) {
+        let cursor = self.hovered_cursor();
+        if cursor != self.cursor {
+            set_mouse_cursor(cursor);
+            self.cursor = cursor;
+        }
+    }
+
+    /// Use string tables for elements that refer to localization keys
+    pub fn set_localization(&mut self, localization: Localization) {
+        self.localization = Some(localization);
+        self.relocalize();
+    }
+
+    /// Get the string tables
+    pub fn localization(&self) -> Option<&Localization> {
+        self.localization.as_ref()
+    }
+
+    /// Switch language and update every localized element
+    pub fn set_language(&mut self, language: &str) {
+        if let Some(localization) = &mut self.localization {
+            localization.set_language(language);
+        }
+        self.relocalize();
+    }
+
+    /// Re-read every localization key, e.g. after loading more strings
+    pub fn relocalize(&mut self) {
+        if let Some(localization) = &self.localization {
+            for element in &mut self.elements {
+                element.localize(localization);
+            }
         }
     }
 
+    /// Set the accessibility options
+    pub fn set_accessibility(&mut self, accessibility: Accessibility) {
+        self.accessibility = accessibility;
+    }
+
+    /// Get the accessibility options
+    pub fn accessibility(&self) -> &Accessibility {
+        &self.accessibility
+    }
+
+    /// Get the accessibility options for editing
+    pub fn accessibility_mut(&mut self) -> &mut Accessibility {
+        &mut self.accessibility
+    }
+
+    /// Get the theme elements are drawn with, after accessibility overrides
+    pub fn active_theme(&self) -> &Theme {
+        resolve_theme(&self.theme, &self.theme_stack, &self.accessibility)
+    }
+
+    /// Publish scale and accessibility settings to the elements about to run
+    fn enter_context(&self) {
+        UI_SCALE.with(|scale| scale.set(self.ui_scale));
+        MIN_FONT_SIZE.with(|min| min.set(self.accessibility.min_font_size));
+        REDUCED_MOTION.with(|reduced| reduced.set(self.accessibility.reduced_motion));
+    }
+
+    /// Reset the settings published by `enter_context`
+    fn leave_context() {
+        UI_SCALE.with(|scale| scale.set(1.0));
+        MIN_FONT_SIZE.with(|min| min.set(0));
+        REDUCED_MOTION.with(|reduced| reduced.set(false));
+    }
+
+    /// Set the factor every element is scaled by when drawn
+    ///
+    /// Elements keep their unscaled coordinates; mouse input is mapped back to them.
+    pub fn set_ui_scale(&mut self, scale: f32) {
+        self.ui_scale = scale.max(0.1);
+    }
+
+    /// Get the UI scale factor
+    pub fn ui_scale(&self) -> f32 {
+        self.ui_scale
+    }
+
+    /// Scale the UI by the operating system's display scale factor
+    pub fn use_system_scale(&mut self) {
+        self.set_ui_scale(system_ui_scale());
+    }
+
     pub fn add_element(&mut self, element: Box<dyn UiElement>) -> usize {
+        self.add_element_with_z_index(element, UiLayer::Default.z_index())
+    }
+
+    /// Add an element on a layer
+    pub fn add_element_to_layer(&mut self, element: Box<dyn UiElement>, layer: UiLayer) -> usize {
+        self.add_element_with_z_index(element, layer.z_index())
+    }
+
+    /// Add an element with an explicit z-index
+    pub fn add_element_with_z_index(&mut self, mut element: Box<dyn UiElement>, z_index: i32) -> usize {
+        if let Some(localization) = &self.localization {
+            element.localize(localization);
+        }
         let index = self.elements.len();
         self.elements.push(element);
-        self.z_order.push(index);
+        self.z_indices.push(z_index);
+        self.restack(index, true);
+        index
+    }
+
+    /// Add an element that can later be looked up by name
+    pub fn add_named_element(&mut self, name: &str, element: Box<dyn UiElement>) -> usize {
+        let index = self.add_element(element);
+        self.names.insert(name.to_string(), index);
+        index
+    }
+
+    /// Add an element that plays `transition` over `duration` seconds as it appears
+    pub fn add_element_with_transition(
+        &mut self,
+        element: Box<dyn UiElement>,
+        transition: Transition,
+        duration: f32,
+    ) -> usize {
+        let index = self.add_element(element);
+        self.start_transition(index, transition, duration, false);
         index
     }
 
+    /// Add a named element that plays `transition` over `duration` seconds as it appears
+    pub fn add_named_element_with_transition(
+        &mut self,
+        name: &str,
+        element: Box<dyn UiElement>,
+        transition: Transition,
+        duration: f32,
+    ) -> usize {
+        let index = self.add_named_element(name, element);
+        self.start_transition(index, transition, duration, false);
+        index
+    }
+
+    /// Get a named element as its concrete type, searching nested panels too
+    pub fn get_named<T: 'static>(&mut self, name: &str) -> Option<&mut T> {
+        find_named(&mut self.elements, &self.names, name)
+    }
+
+    /// Get a named element as a trait object, searching nested panels too
+    pub fn get_named_element(&mut self, name: &str) -> Option<&mut dyn UiElement> {
+        find_named_element(&mut self.elements, &self.names, name)
+    }
+
+    /// Names of every named element, nested panels included, sorted
+    pub fn element_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.names.keys().cloned().collect();
+        collect_panel_names(&self.elements, &mut names);
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Remove the element at `index`
+    ///
+    /// Elements added after it move down one index.
+    pub fn remove_element(&mut self, index: usize) -> Option<Box<dyn UiElement>> {
+        if index >= self.elements.len() {
+            return None;
+        }
+        self.z_order.retain(|&i| i != index);
+        for i in self.z_order.iter_mut() {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+        self.z_indices.remove(index);
+        shift_names(&mut self.names, index);
+        for path in [&mut self.nav.focus, &mut self.hovered, &mut self.pointer_path, &mut self.focus_path] {
+            shift_path(path, index);
+        }
+        self.transitions.retain(|t| t.index != index);
+        for transition in self.transitions.iter_mut() {
+            if transition.index > index {
+                transition.index -= 1;
+            }
+        }
+        Some(self.elements.remove(index))
+    }
+
+    /// Remove a named element
+    pub fn remove_named(&mut self, name: &str) -> Option<Box<dyn UiElement>> {
+        let index = *self.names.get(name)?;
+        self.remove_element(index)
+    }
+
+    /// Play `transition` over `duration` seconds, then remove the element at `index`
+    ///
+    /// The element stops reacting to input while it leaves.
+    pub fn remove_element_with_transition(&mut self, index: usize, transition: Transition, duration: f32) {
+        if index < self.elements.len() {
+            self.start_transition(index, transition, duration, true);
+        }
+    }
+
+    /// Play `transition` over `duration` seconds, then remove a named element
+    pub fn remove_named_with_transition(&mut self, name: &str, transition: Transition, duration: f32) {
+        if let Some(&index) = self.names.get(name) {
+            self.remove_element_with_transition(index, transition, duration);
+        }
+    }
+
+    /// Check if the element at `index` is waiting on its exit transition
+    pub fn is_exiting(&self, index: usize) -> bool {
+        self.transitions.iter().any(|t| t.index == index && t.exiting)
+    }
+
+    /// Start a transition, replacing any already playing on the element
+    fn start_transition(&mut self, index: usize, kind: Transition, duration: f32, exiting: bool) {
+        let element = self.elements[index].as_mut();
+        if let Some(pos) = self.transitions.iter().position(|t| t.index == index) {
+            self.transitions.remove(pos).restore(element);
+        }
+        let transition = ActiveTransition::new(index, element, kind, duration, exiting);
+        transition.apply(element);
+        self.transitions.push(transition);
+    }
+
+    /// Advance transitions, removing elements whose exit has finished
+    fn update_transitions(&mut self) {
+        let dt = if reduced_motion() { f32::INFINITY } else { get_frame_time() };
+        let mut finished_exits = Vec::new();
+        for transition in &mut self.transitions {
+            transition.elapsed += dt;
+            let element = self.elements[transition.index].as_mut();
+            if !transition.is_finished() {
+                transition.apply(element);
+            } else if transition.exiting {
+                finished_exits.push(transition.index);
+            } else {
+                transition.restore(element);
+            }
+        }
+        self.transitions.retain(|t| !t.is_finished() || t.exiting);
+        finished_exits.sort_unstable();
+        for index in finished_exits.into_iter().rev() {
+            self.remove_element(index);
+        }
+    }
+
+    /// Remove every element matching `predicate`, returning how many were removed
+    pub fn remove_where(&mut self, mut predicate: impl FnMut(&dyn UiElement) -> bool) -> usize {
+        let mut removed = 0;
+        for index in (0..self.elements.len()).rev() {
+            if predicate(self.elements[index].as_ref()) {
+                self.remove_element(index);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Remove all elements
+    pub fn clear(&mut self) {
+        self.elements.clear();
+        self.z_order.clear();
+        self.z_indices.clear();
+        self.names.clear();
+        self.transitions.clear();
+        self.nav.focus = None;
+        self.hovered = None;
+        self.pointer_path = None;
+        self.focus_path = None;
+    }
+
+    /// Check if the mouse is over any visible UI element
+    ///
+    /// Gameplay code can skip world input while this is true.
+    pub fn is_pointer_over_ui(&self) -> bool {
+        let (x, y) = mouse_position();
+        let point = vec2(x, y) / self.ui_scale;
+        self.elements
+            .iter()
+            .any(|element| element_under_point(element.as_ref(), point))
+    }
+
+    /// Check if a mouse button pressed this frame landed on the UI
+    ///
+    /// Only valid after `update` has run for the frame.
+    pub fn consumed_click(&self) -> bool {
+        self.click_consumed
+    }
+
+    /// Move gamepad focus and activate elements from this frame's input
+    ///
+    /// Call once per frame. Directions move focus to the nearest focusable element,
+    /// repeating while held; sliders and open dropdowns use them instead while focused.
+    pub fn navigate(&mut self, input: &NavInput) {
+        let mut focusable = Vec::new();
+        collect_focusable(&self.elements, &mut Vec::new(), &mut focusable);
+        let focus = self.nav.focus.take().filter(|path| focusable.iter().any(|(p, _)| p == path));
+
+        let mut pressed = None;
+        if input.direction != self.nav.previous.direction {
+            pressed = input.direction;
+            self.nav.repeat_timer = NAV_REPEAT_DELAY;
+        } else if input.direction.is_some() {
+            self.nav.repeat_timer -= get_frame_time();
+            if self.nav.repeat_timer <= 0.0 {
+                pressed = input.direction;
+                self.nav.repeat_timer = NAV_REPEAT_INTERVAL;
+            }
+        }
+        let confirmed = input.confirm && !self.nav.previous.confirm;
+        self.nav.previous = *input;
+
+        self.nav.focus = match focus {
+            // The first input only shows the highlight, on the top-left element
+            None if pressed.is_some() || confirmed => focusable
+                .iter()
+                .min_by(|a, b| a.1.1.total_cmp(&b.1.1).then(a.1.0.total_cmp(&b.1.0)))
+                .map(|(path, _)| path.clone()),
+            None => None,
+            Some(mut path) => {
+                if let Some(direction) = pressed {
+                    EVENT_PATH.with(|current| current.replace(path.clone()));
+                    let consumed = element_at_path_mut(&mut self.elements, &path)
+                        .is_some_and(|element| element.navigate(direction));
+                    EVENT_PATH.with(|current| current.borrow_mut().clear());
+                    if !consumed {
+                        let bounds = focusable.iter().find(|(p, _)| *p == path).map(|(_, b)| *b);
+                        if let Some(next) = bounds.and_then(|b| nearest_in_direction(&focusable, b, direction)) {
+                            path = next;
+                        }
+                    }
+                }
+                if confirmed && let Some(element) = element_at_path_mut(&mut self.elements, &path) {
+                    EVENT_PATH.with(|current| current.replace(path.clone()));
+                    element.activate();
+                    EVENT_PATH.with(|current| current.borrow_mut().clear());
+                }
+                Some(path)
+            }
+        };
+        self.collect_events();
+    }
+
+    /// Get the index path of the element with gamepad focus
+    ///
+    /// The first index is into the manager; the rest step into nested panels.
+    pub fn nav_focus(&self) -> Option<&[usize]> {
+        self.nav.focus.as_deref()
+    }
+
+    /// Drop gamepad focus, hiding the focus highlight
+    pub fn clear_nav_focus(&mut self) {
+        self.nav.focus = None;
+    }
+
     pub fn add_style(&mut self, name: &str, style: UiStyle) {
         self.styles.insert(name.to_string(), style);
     }
@@ -1350,24 +5111,36 @@ impl UiManager {
     }
 
     pub fn update(&mut self) {
-        let theme = Theme::default();
-        
+        self.enter_context();
+        self.update_transitions();
+        let stale = self.stale_events.min(self.events.len());
+        self.events.drain(..stale);
+
+        let pressed = [MouseButton::Left, MouseButton::Right, MouseButton::Middle]
+            .into_iter()
+            .any(is_mouse_button_pressed);
+        self.click_consumed = pressed && self.is_pointer_over_ui();
+
         // First pass: collect indices of open dropdowns
         let mut open_dropdowns = Vec::new();
         for &index in self.z_order.iter().rev() {
-            if let Some(element) = self.elements.get(index) {
-                if let Some(dropdown) = element.as_any().downcast_ref::<UiDropdown>() {
-                    if dropdown.is_open {
-                        open_dropdowns.push(index);
-                    }
-                }
+            if let Some(element) = self.elements.get(index)
+                && let Some(dropdown) = element.as_any().downcast_ref::<UiDropdown>()
+                && dropdown.is_open
+            {
+                open_dropdowns.push(index);
             }
         }
         
-        // Second pass: update elements
+        // Second pass: update elements, skipping those on their way out
         for &index in self.z_order.iter().rev() {
+            if self.is_exiting(index) {
+                continue;
+            }
             if let Some(element) = self.elements.get_mut(index) {
-                element.update(&theme, None);
+                // Borrow the theme by field so elements can still be borrowed mutably
+                let theme = resolve_theme(&self.theme, &self.theme_stack, &self.accessibility);
+                with_event_path(index, || update_element(element.as_mut(), theme, None));
             }
         }
         
@@ -1375,42 +5148,118 @@ impl UiManager {
         for index in open_dropdowns {
             self.bring_to_front(index);
         }
+        if self.manage_cursor {
+            self.update_cursor();
+        }
+        self.dispatch_hover_and_focus();
+        self.dispatch_sounds();
+        self.collect_events();
+        self.stale_events = self.events.len();
+        Self::leave_context();
     }
 
     pub fn draw(&self) {
-        let theme = Theme::default();
-        
-        // First pass: Draw all non-dropdown elements
+        self.enter_context();
+        let theme = self.active_theme();
+        let scaled = self.ui_scale != 1.0;
+        if scaled {
+            push_camera_state();
+            set_camera(&ui_camera(screen_width() / self.ui_scale, screen_height() / self.ui_scale));
+        }
+
+        // Draw elements back to front, then what pops up over them, like open dropdown lists
         for &index in &self.z_order {
             if let Some(element) = self.elements.get(index) {
-                if element.as_any().downcast_ref::<UiDropdown>().is_none() {
-                    element.draw(&theme);
-                }
+                draw_element(element.as_ref(), theme);
             }
         }
-        
-        // Second pass: Draw all dropdowns
         for &index in &self.z_order {
             if let Some(element) = self.elements.get(index) {
-                if element.as_any().downcast_ref::<UiDropdown>().is_some() {
-                    element.draw(&theme);
-                }
+                draw_element_overlay(element.as_ref(), theme);
+            }
+        }
+
+        // Focus outlines on top of everything
+        let thickness = if self.accessibility.always_show_focus { 3.0 } else { 2.0 };
+        let focused = self.nav.focus.as_deref().and_then(|path| element_at_path(&self.elements, path));
+        if let Some(element) = focused {
+            draw_focus_outline(element, thickness, theme.accent);
+        }
+        if self.accessibility.always_show_focus {
+            for element in &self.elements {
+                draw_keyboard_focus(element.as_ref(), thickness, theme.accent);
             }
         }
+
+        if scaled {
+            pop_camera_state();
+        }
+        Self::leave_context();
     }
 
+    /// Draw the element above everything else with the same z-index
     pub fn bring_to_front(&mut self, index: usize) {
-        if let Some(pos) = self.z_order.iter().position(|&i| i == index) {
-            self.z_order.remove(pos);
-            self.z_order.push(index);
+        self.restack(index, true);
+    }
+
+    /// Draw the element below everything else with the same z-index
+    pub fn send_to_back(&mut self, index: usize) {
+        self.restack(index, false);
+    }
+
+    /// Set an element's z-index, moving it in front of others at that z-index
+    pub fn set_z_index(&mut self, index: usize, z_index: i32) {
+        if let Some(z) = self.z_indices.get_mut(index) {
+            *z = z_index;
+            self.restack(index, true);
         }
     }
 
-    pub fn get_element(&self, index: usize) -> Option<&Box<dyn UiElement>> {
-        self.elements.get(index)
+    /// Get an element's z-index
+    pub fn z_index(&self, index: usize) -> Option<i32> {
+        self.z_indices.get(index).copied()
+    }
+
+    /// Move an element onto a layer
+    pub fn set_layer(&mut self, index: usize, layer: UiLayer) {
+        self.set_z_index(index, layer.z_index());
+    }
+
+    /// Reinsert an element into the draw order at the front or back of its z-index
+    fn restack(&mut self, index: usize, front: bool) {
+        let Some(&z) = self.z_indices.get(index) else {
+            return;
+        };
+        self.z_order.retain(|&i| i != index);
+        let z_indices = &self.z_indices;
+        let pos = if front {
+            self.z_order.iter().position(|&i| z_indices[i] > z)
+        } else {
+            self.z_order.iter().position(|&i| z_indices[i] >= z)
+        };
+        self.z_order.insert(pos.unwrap_or(self.z_order.len()), index);
+    }
+
+    pub fn get_element(&self, index: usize) -> Option<&dyn UiElement> {
+        self.elements.get(index).map(|e| e.as_ref())
     }
 
     pub fn get_element_mut(&mut self, index: usize) -> Option<&mut Box<dyn UiElement>> {
         self.elements.get_mut(index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_ui_origin_is_top_left() {
+        // A 1280x720 screen at UI scale 2 shows 640x360 UI units
+        let matrix = ui_camera(640.0, 360.0).matrix();
+        let top_left = matrix.project_point3(vec3(0.0, 0.0, 0.0));
+        let bottom_right = matrix.project_point3(vec3(640.0, 360.0, 0.0));
+        assert!((top_left.x + 1.0).abs() < 1e-5 && (top_left.y - 1.0).abs() < 1e-5);
+        assert!((bottom_right.x - 1.0).abs() < 1e-5 && (bottom_right.y + 1.0).abs() < 1e-5);
+    }
+}
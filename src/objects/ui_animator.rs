@@ -0,0 +1,301 @@
+//! Keyframe Animation for UI Elements
+//!
+//! This module lets you script UI animations as keyframe timelines instead of
+//! updating element properties by hand every frame. Timelines target named
+//! elements in a `UiManager` and can be delayed, eased, looped and chained.
+//!
+//! # Examples
+//! ```rust
+//! use ruty::objects::ui_animator::{AnimProperty, Easing, Timeline, UiAnimator};
+//!
+//! let mut animator = UiAnimator::new();
+//! animator.play(
+//!     Timeline::new("menu", AnimProperty::Position)
+//!         .delay(0.2)
+//!         .keyframe(0.0, vec2(-300.0, 50.0))
+//!         .keyframe(0.5, vec2(50.0, 50.0))
+//!         .easing(Easing::EaseOut)
+//!         .then(
+//!             Timeline::new("menu", AnimProperty::Opacity)
+//!                 .keyframe(0.0, 0.5)
+//!                 .keyframe(0.3, 1.0),
+//!         ),
+//! );
+//!
+//! // In the game loop
+//! animator.update(get_frame_time(), &mut ui_manager);
+//! ```
+
+use macroquad::prelude::*;
+
+use crate::objects::ui::{UiElement, UiManager};
+
+/// Element property driven by a timeline
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimProperty {
+    /// Top-left corner of the element's bounds (`Vec2`)
+    Position,
+    /// Width and height of the element's bounds (`Vec2`)
+    Size,
+    /// Opacity from 0.0 to 1.0 (`f32`)
+    Opacity,
+    /// Main color of the element (`Color`)
+    Color,
+}
+
+/// Value stored in a keyframe
+#[derive(Clone, Copy, Debug)]
+pub enum AnimValue {
+    Float(f32),
+    Vec2(Vec2),
+    Color(Color),
+}
+
+impl From<f32> for AnimValue {
+    fn from(value: f32) -> Self {
+        AnimValue::Float(value)
+    }
+}
+
+impl From<Vec2> for AnimValue {
+    fn from(value: Vec2) -> Self {
+        AnimValue::Vec2(value)
+    }
+}
+
+impl From<Color> for AnimValue {
+    fn from(value: Color) -> Self {
+        AnimValue::Color(value)
+    }
+}
+
+impl AnimValue {
+    /// Interpolate between two values of the same kind
+    ///
+    /// Mismatched kinds snap to `other` once `t` reaches 1.0.
+    pub fn lerp(&self, other: &AnimValue, t: f32) -> AnimValue {
+        match (self, other) {
+            (AnimValue::Float(a), AnimValue::Float(b)) => AnimValue::Float(a + (b - a) * t),
+            (AnimValue::Vec2(a), AnimValue::Vec2(b)) => AnimValue::Vec2(*a + (*b - *a) * t),
+            (AnimValue::Color(a), AnimValue::Color(b)) => AnimValue::Color(Color::new(
+                a.r + (b.r - a.r) * t,
+                a.g + (b.g - a.g) * t,
+                a.b + (b.b - a.b) * t,
+                a.a + (b.a - a.a) * t,
+            )),
+            _ if t >= 1.0 => *other,
+            _ => *self,
+        }
+    }
+}
+
+/// Easing curve applied between keyframes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map linear progress `t` (0.0 to 1.0) onto the curve
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+        }
+    }
+}
+
+/// A value at a point in time
+#[derive(Clone, Copy, Debug)]
+pub struct Keyframe {
+    /// Time in seconds from the start of the timeline (after the delay)
+    pub time: f32,
+    pub value: AnimValue,
+}
+
+/// Keyframe track animating one property of one named element
+pub struct Timeline {
+    /// Name the element was registered with
+    pub target: String,
+    pub property: AnimProperty,
+    pub keyframes: Vec<Keyframe>,
+    /// Seconds to wait before the first keyframe
+    pub delay: f32,
+    pub easing: Easing,
+    /// Restart from the first keyframe when finished
+    pub looping: bool,
+    /// Timelines started once this one finishes
+    pub next: Vec<Timeline>,
+    elapsed: f32,
+}
+
+impl Timeline {
+    /// Create an empty timeline for a named element
+    pub fn new(target: &str, property: AnimProperty) -> Self {
+        Self {
+            target: target.to_string(),
+            property,
+            keyframes: Vec::new(),
+            delay: 0.0,
+            easing: Easing::Linear,
+            looping: false,
+            next: Vec::new(),
+            elapsed: 0.0,
+        }
+    }
+
+    /// Add a keyframe, keeping keyframes sorted by time
+    pub fn keyframe(mut self, time: f32, value: impl Into<AnimValue>) -> Self {
+        let keyframe = Keyframe { time, value: value.into() };
+        let index = self.keyframes.partition_point(|k| k.time <= time);
+        self.keyframes.insert(index, keyframe);
+        self
+    }
+
+    /// Set the delay before the timeline starts
+    pub fn delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set the easing curve used between keyframes
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Loop the timeline forever
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Start another timeline once this one finishes
+    pub fn then(mut self, next: Timeline) -> Self {
+        self.next.push(next);
+        self
+    }
+
+    /// Length of the timeline in seconds, excluding the delay
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    /// Check if the timeline has played to the end
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.elapsed >= self.delay + self.duration()
+    }
+
+    /// Value at the current time, or `None` while still delayed
+    pub fn current_value(&self) -> Option<AnimValue> {
+        let time = self.elapsed - self.delay;
+        if time < 0.0 {
+            return None;
+        }
+        let first = self.keyframes.first()?;
+        if time <= first.time {
+            return Some(first.value);
+        }
+        for pair in self.keyframes.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if time <= to.time {
+                let span = (to.time - from.time).max(f32::EPSILON);
+                let t = self.easing.apply((time - from.time) / span);
+                return Some(from.value.lerp(&to.value, t));
+            }
+        }
+        self.keyframes.last().map(|k| k.value)
+    }
+
+    /// Advance the timeline by `dt` seconds
+    fn advance(&mut self, dt: f32) {
+        self.elapsed += dt;
+        let end = self.delay + self.duration();
+        if self.looping && self.elapsed > end && self.duration() > 0.0 {
+            // Loops skip the delay after the first pass
+            self.elapsed = self.delay + (self.elapsed - end) % self.duration();
+        }
+    }
+
+    /// Write the current value into the element
+    fn apply(&self, element: &mut dyn UiElement) {
+        let Some(value) = self.current_value() else {
+            return;
+        };
+        match (self.property, value) {
+            (AnimProperty::Position, AnimValue::Vec2(v)) => element.set_position(v.x, v.y),
+            (AnimProperty::Size, AnimValue::Vec2(v)) => element.set_size(v.x, v.y),
+            (AnimProperty::Opacity, AnimValue::Float(v)) => element.set_opacity(v),
+            (AnimProperty::Color, AnimValue::Color(c)) => element.set_color(c),
+            _ => {}
+        }
+    }
+}
+
+/// Plays timelines against the elements of a `UiManager`
+pub struct UiAnimator {
+    timelines: Vec<Timeline>,
+}
+
+impl Default for UiAnimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UiAnimator {
+    /// Create an animator with nothing playing
+    pub fn new() -> Self {
+        Self { timelines: Vec::new() }
+    }
+
+    /// Start playing a timeline
+    pub fn play(&mut self, timeline: Timeline) {
+        self.timelines.push(timeline);
+    }
+
+    /// Stop every timeline targeting `target`
+    pub fn stop(&mut self, target: &str) {
+        self.timelines.retain(|t| t.target != target);
+    }
+
+    /// Stop all timelines
+    pub fn clear(&mut self) {
+        self.timelines.clear();
+    }
+
+    /// Check if any timeline is still playing
+    pub fn is_playing(&self) -> bool {
+        !self.timelines.is_empty()
+    }
+
+    /// Advance all timelines and apply their values
+    ///
+    /// Finished timelines are removed and their chained timelines started.
+    pub fn update(&mut self, dt: f32, manager: &mut UiManager) {
+        let mut started = Vec::new();
+        for timeline in &mut self.timelines {
+            timeline.advance(dt);
+            if let Some(element) = manager.get_named_element(&timeline.target) {
+                timeline.apply(element);
+            }
+            if timeline.is_finished() {
+                started.append(&mut timeline.next);
+            }
+        }
+        self.timelines.retain(|t| !t.is_finished());
+        self.timelines.extend(started);
+    }
+}
@@ -0,0 +1,180 @@
+//! Text in the world
+//!
+//! This module provides text placed in world coordinates, for damage
+//! numbers, signs and nameplates that move with the camera and stack with
+//! other objects by draw layer. Menus and HUDs fixed to the screen belong in
+//! the UI system instead.
+//!
+//! # Features
+//! - Text that zooms with the camera like any other object, rasterized at the zoomed size so it stays sharp
+//! - Billboard text that keeps the same size on screen at any zoom, for nameplates
+//! - Anchoring, so text can be centered over the point it labels
+//! - Rotation around the anchor
+//!
+//! # Examples
+//! ```rust
+//! use ruty::objects::world_text::WorldText;
+//!
+//! let sign = WorldText::new("Danger: falling rocks", 640.0, 400.0, 18.0, YELLOW).with_font(font.clone());
+//! let mut nameplate = WorldText::new("Guard", 0.0, 0.0, 16.0, WHITE).with_billboard(true).with_anchor(0.5, 1.0);
+//!
+//! set_camera(&camera);
+//! nameplate.position = (guard.position.0, guard.position.1 - 24.0);
+//! sign.draw();
+//! nameplate.draw();
+//! ```
+
+use macroquad::color::Color;
+use macroquad::math::{Vec2, vec2};
+use macroquad::text::{Font, TextDimensions, TextParams, camera_font_scale, draw_text_ex, measure_text};
+
+use crate::basics::Tagged;
+use crate::basics::render::Renderable;
+
+/// Text drawn at a point in the world
+///
+/// # Fields
+///
+/// - `text`: The text shown.
+/// - `position`: The (x, y) coordinates of the anchor point, in world coordinates.
+/// - `font`: Font the text is drawn in, or macroquad's default font if `None`.
+/// - `font_size`: Height of the text in world units, or in screen pixels for billboard text.
+/// - `color`: Color of the text.
+/// - `anchor`: Point of the text box placed at `position`, from (0, 0) at its top-left to (1, 1) at its bottom-right; centered by default.
+/// - `rotation`: Angle in radians, clockwise on screen, around the anchor.
+/// - `billboard`: Keep the text the same size on screen whatever the camera's zoom.
+/// - `draw_layer`: Layer a `Renderer` draws the text on, higher on top; 0 by default.
+/// - `tags`: Names gameplay code finds the text by, see `Tagged`.
+#[derive(Clone)]
+pub struct WorldText {
+    pub text: String,
+    pub position: (f32, f32),
+    pub font: Option<Font>,
+    pub font_size: f32,
+    pub color: Color,
+    pub anchor: Vec2,
+    pub rotation: f32,
+    pub billboard: bool,
+    pub draw_layer: i32,
+    pub tags: Vec<String>,
+}
+
+impl WorldText {
+    /// Creates text centered on `x`, `y` in the default font.
+    pub fn new(text: impl Into<String>, x: f32, y: f32, font_size: f32, color: Color) -> Self {
+        Self {
+            text: text.into(),
+            position: (x, y),
+            font: None,
+            font_size,
+            color,
+            anchor: vec2(0.5, 0.5),
+            rotation: 0.0,
+            billboard: false,
+            draw_layer: 0,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Draw the text in `font` instead of the default font
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Set the point of the text box placed at the position, from (0, 0) at its top-left to (1, 1) at its bottom-right
+    pub fn with_anchor(mut self, x: f32, y: f32) -> Self {
+        self.anchor = vec2(x, y);
+        self
+    }
+
+    /// Set the angle in radians, clockwise on screen, around the anchor
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set whether the text keeps the same size on screen whatever the camera's zoom
+    pub fn with_billboard(mut self, billboard: bool) -> Self {
+        self.billboard = billboard;
+        self
+    }
+
+    /// Change the text shown
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+    }
+
+    /// Returns the size of the text in world units under the current camera.
+    pub fn size(&self) -> Vec2 {
+        let (font_size, font_scale, aspect) = self.text_scale();
+        let dimensions = self.measure(font_size, font_scale);
+        vec2(dimensions.width * aspect, dimensions.height)
+    }
+
+    /// Draws the text with the current camera.
+    pub fn draw(&self) {
+        let (font_size, font_scale, aspect) = self.text_scale();
+        let dimensions = self.measure(font_size, font_scale);
+        // Text is drawn from the start of its baseline, so move there from the anchor
+        let offset = vec2(
+            -dimensions.width * aspect * self.anchor.x,
+            dimensions.offset_y - dimensions.height * self.anchor.y,
+        );
+        let baseline = vec2(self.position.0, self.position.1) + Vec2::from_angle(self.rotation).rotate(offset);
+        draw_text_ex(
+            &self.text,
+            baseline.x,
+            baseline.y,
+            TextParams {
+                font: self.font.as_ref(),
+                font_size,
+                font_scale,
+                font_scale_aspect: aspect,
+                rotation: self.rotation,
+                color: self.color,
+            },
+        );
+    }
+
+    /// Size to rasterize the font at, the scale from it to world units, and the camera's aspect correction
+    ///
+    /// Rasterizing at the size the text ends up on screen keeps it sharp when zoomed in.
+    fn text_scale(&self) -> (u16, f32, f32) {
+        let (screen_size, units_per_pixel, aspect) = camera_font_scale(self.font_size);
+        if self.billboard {
+            ((self.font_size.round() as u16).max(1), units_per_pixel, aspect)
+        } else {
+            (screen_size.max(1), units_per_pixel, aspect)
+        }
+    }
+
+    /// Size of the text rasterized at `font_size` and scaled by `font_scale`
+    fn measure(&self, font_size: u16, font_scale: f32) -> TextDimensions {
+        measure_text(&self.text, self.font.as_ref(), font_size, font_scale)
+    }
+}
+
+impl Renderable for WorldText {
+    fn draw_layer(&self) -> i32 {
+        self.draw_layer
+    }
+
+    fn sort_y(&self) -> f32 {
+        self.position.1 + self.size().y * (1.0 - self.anchor.y)
+    }
+
+    fn render(&self) {
+        self.draw();
+    }
+}
+
+impl Tagged for WorldText {
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
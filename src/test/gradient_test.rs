@@ -1,28 +1,50 @@
 use macroquad::prelude::*;
+use crate::basics::scene_manager::{Scene, SceneTransition};
 use crate::utils::gradient::Gradient;
 
-pub async fn run_gradient_test() {
-    let mut current_gradient = 0;
-    let gradients = vec![
-        ("Sunset", Gradient::sunset()),
-        ("Ocean", Gradient::ocean()),
-        ("Forest", Gradient::forest()),
-        ("Fire", Gradient::fire()),
-    ];
+/// Scene cycling through the built-in gradients
+pub struct GradientTest {
+    current_gradient: usize,
+    gradients: [(&'static str, Gradient); 4],
+}
 
-    loop {
+impl GradientTest {
+    pub fn new() -> Self {
+        Self {
+            current_gradient: 0,
+            gradients: [
+                ("Sunset", Gradient::sunset()),
+                ("Ocean", Gradient::ocean()),
+                ("Forest", Gradient::forest()),
+                ("Fire", Gradient::fire()),
+            ],
+        }
+    }
+}
+
+impl Default for GradientTest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene for GradientTest {
+    fn update(&mut self, _dt: f32) -> SceneTransition {
         if is_key_pressed(KeyCode::Escape) {
-            break;
+            return SceneTransition::Pop;
         }
 
         if is_key_pressed(KeyCode::Space) {
-            current_gradient = (current_gradient + 1) % gradients.len();
+            self.current_gradient = (self.current_gradient + 1) % self.gradients.len();
         }
+        SceneTransition::None
+    }
 
+    fn draw(&self) {
         clear_background(BLACK);
 
         // Draw the current gradient
-        let (name, gradient) = &gradients[current_gradient];
+        let (name, gradient) = &self.gradients[self.current_gradient];
         gradient.draw_vertical(0.0, 0.0, screen_width(), screen_height());
 
         // Draw instructions
@@ -33,7 +55,5 @@ pub async fn run_gradient_test() {
             20.0,
             WHITE,
         );
-
-        next_frame().await;
     }
-} 
\ No newline at end of file
+}
@@ -1,10 +1,13 @@
 use macroquad::prelude::*;
+use crate::basics::ComponentOwner;
 use crate::basics::collision::Collision;
 use crate::objects::point::Point;
 use crate::objects::constraint::Constraint;
+use crate::objects::terrain::TerrainCollider;
 use crate::basics::gravity::Gravity;
 use crate::basics::force::Force;
 use crate::basics::friction::Friction;
+use crate::basics::physics_config::PhysicsConfig;
 
 // Helper function to create a regular polygon
 fn create_polygon(center_x: f32, center_y: f32, radius: f32, sides: usize, color: Color, gravity: f32, is_pentagon: bool) -> (Vec<Point>, Vec<Constraint>) {
@@ -58,38 +61,6 @@ fn create_polygon(center_x: f32, center_y: f32, radius: f32, sides: usize, color
     (points, constraints)
 }
 
-// Helper function to create a slope
-fn create_slope(start_x: f32, start_y: f32, end_x: f32, end_y: f32, spacing: f32, color: Color) -> (Vec<Point>, Vec<Constraint>) {
-    let mut points = Vec::new();
-    let mut constraints = Vec::new();
-    
-    let dx = end_x - start_x;
-    let dy = end_y - start_y;
-    let length = (dx * dx + dy * dy).sqrt();
-    let steps = (length / spacing).ceil() as i32;
-    
-    for i in 0..=steps {
-        let t = i as f32 / steps as f32;
-        let x = start_x + dx * t;
-        let y = start_y + dy * t;
-        points.push(Point::new(x, y, 0.0, 12.0, color));
-    }
-    
-    // Connect points with constraints
-    for i in 0..points.len() - 1 {
-        let distance = spacing;
-        constraints.push(Constraint::new(i, i + 1, distance, 0.99, color));
-    }
-    
-    // Make all points fixed
-    for point in points.iter_mut() {
-        point.fixed = true;
-        point.add_component(Box::new(Friction::new(0.85))); // Lower friction for sliding
-    }
-    
-    (points, constraints)
-}
-
 // Helper function to create a rounded object (circle)
 fn create_circle(center_x: f32, center_y: f32, radius: f32, num_points: usize, color: Color, gravity: f32) -> (Vec<Point>, Vec<Constraint>) {
     let mut points = Vec::new();
@@ -129,6 +100,7 @@ fn create_circle(center_x: f32, center_y: f32, radius: f32, num_points: usize, c
 }
 
 pub async fn run_point_example() {
+    let physics_config = PhysicsConfig::new();
     let mut all_points = Vec::new();
     let mut all_constraints = Vec::new();
     
@@ -141,11 +113,11 @@ pub async fn run_point_example() {
     let num_shapes = 8;
     
     // Define different shapes to create
-    let shape_types = vec![
-        (30.0, 4, RED, 50.0),     // Square - reduced gravity
-        (25.0, 3, BLUE, 30.0),    // Triangle - reduced gravity
-        (35.0, 5, GREEN, 10.0),   // Pentagon - reduced gravity
-        (20.0, 6, YELLOW, 40.0),  // Hexagon - reduced gravity
+    let shape_types = [
+        (30.0, 4, RED, 3000.0),     // Square - reduced gravity
+        (25.0, 3, BLUE, 1800.0),    // Triangle - reduced gravity
+        (35.0, 5, GREEN, 600.0),   // Pentagon - reduced gravity
+        (20.0, 6, YELLOW, 2400.0),  // Hexagon - reduced gravity
     ];
     
     for i in 0..num_shapes {
@@ -170,8 +142,8 @@ pub async fn run_point_example() {
     
     // Add some rounded objects
     let circles = vec![
-        (200.0, 300.0, 40.0, 16, PURPLE, 20.0),  // Large circle
-        (600.0, 300.0, 30.0, 12, ORANGE, 15.0),  // Medium circle
+        (200.0, 300.0, 40.0, 16, PURPLE, 1200.0),  // Large circle
+        (600.0, 300.0, 30.0, 12, ORANGE, 900.0),  // Medium circle
     ];
     
     for (x, y, radius, points, color, gravity) in circles {
@@ -187,33 +159,28 @@ pub async fn run_point_example() {
     
     // Create a single point that will slide
     let mut sliding_point = Point::new(100.0, 100.0, 1.0, 20.0, RED); // Much larger radius
-    sliding_point.add_component(Box::new(Gravity::new(5.0))); // Much lower gravity
+    sliding_point.add_component(Box::new(Gravity::new(300.0))); // Much lower gravity
     sliding_point.add_component(Box::new(Friction::new(0.99))); // Very high friction
     sliding_point.add_component(Box::new(Collision::new(0.0, 0.0))); // Reduced slope friction
     all_points.push(sliding_point);
     
-    // Create a series of connected slopes
+    // Create the ground as one terrain of connected slopes
     let ground_y = 500.0;
-    let slopes = vec![
-        // Start with a gentle slope
-        (100.0, ground_y, 300.0, ground_y - 50.0, 8.0, BLUE), // Much tighter spacing
-        // Steeper slope
-        (300.0, ground_y - 50.0, 500.0, ground_y - 150.0, 8.0, GREEN),
-        // Gentle curve
-        (500.0, ground_y - 150.0, 700.0, ground_y - 100.0, 8.0, YELLOW),
-        // Final gentle slope
-        (700.0, ground_y - 100.0, 900.0, ground_y, 8.0, PURPLE),
-    ];
-    
-    for (start_x, start_y, end_x, end_y, spacing, color) in slopes {
-        let (mut slope_points, mut slope_constraints) = create_slope(start_x, start_y, end_x, end_y, spacing, color);
-        for p in slope_points.iter_mut() {
-            p.fixed = true;
-            p.add_component(Box::new(Collision::new(0.0, 0.85))); // Reduced slope friction
-        }
-        all_points.extend(slope_points);
-        all_constraints.extend(slope_constraints);
-    }
+    let terrain = TerrainCollider::from_polyline(
+        vec![
+            vec2(100.0, ground_y),
+            // Start with a gentle slope
+            vec2(300.0, ground_y - 50.0),
+            // Steeper slope
+            vec2(500.0, ground_y - 150.0),
+            // Gentle curve
+            vec2(700.0, ground_y - 100.0),
+            // Final gentle slope
+            vec2(900.0, ground_y),
+        ],
+        ground_y + 100.0,
+        DARKGREEN,
+    );
 
     println!("\n=== Starting Simulation ===");
     let mut frame_count = 0;
@@ -225,17 +192,22 @@ pub async fn run_point_example() {
 
         // Update all points
         for point in all_points.iter_mut() {
-            point.update_components();
+            point.update_components(1.0/60.0);
             point.update(1.0/60.0);
         }
 
         // Solve constraints multiple times for stability
-        for _ in 0..8 {
+        for _ in 0..physics_config.solver_iterations {
             for constraint in all_constraints.iter() {
                 constraint.solve(&mut all_points);
             }
         }
 
+        // Keep points on top of the terrain
+        for point in all_points.iter_mut() {
+            terrain.collide_point(point);
+        }
+
         // Check collisions between all points with debug prints
         let mut collision_count = 0;
         for i in 0..all_points.len() {
@@ -258,11 +230,11 @@ pub async fn run_point_example() {
                         println!("Point 1: pos=({:.1}, {:.1}), vel=({:.1}, {:.1}), radius={:.1}, fixed={}", 
                             point_i.position.0, point_i.position.1,
                             point_i.velocity.0, point_i.velocity.1,
-                            point_i.radius, point_i.fixed);
+                            point_i.radius, point_i.is_fixed());
                         println!("Point 2: pos=({:.1}, {:.1}), vel=({:.1}, {:.1}), radius={:.1}, fixed={}", 
                             point_j.position.0, point_j.position.1,
                             point_j.velocity.0, point_j.velocity.1,
-                            point_j.radius, point_j.fixed);
+                            point_j.radius, point_j.is_fixed());
                         println!("Distance: {:.1}, Min distance: {:.1}", distance, min_distance);
                         println!("=== End Collision Info ===\n");
                         last_collision_print = frame_count;
@@ -274,7 +246,7 @@ pub async fn run_point_example() {
                         let ny = dy / distance;
                         let push = (min_distance - distance) * 0.2;
                         
-                        if !point_i.fixed {
+                        if !point_i.is_fixed() {
                             point_i.position.0 += nx * push;
                             point_i.position.1 += ny * push;
                             // Much more aggressive damping
@@ -282,7 +254,7 @@ pub async fn run_point_example() {
                             point_i.velocity.0 *= damping;
                             point_i.velocity.1 *= damping;
                         }
-                        if !point_j.fixed {
+                        if !point_j.is_fixed() {
                             point_j.position.0 -= nx * push;
                             point_j.position.1 -= ny * push;
                             // Much more aggressive damping
@@ -297,6 +269,8 @@ pub async fn run_point_example() {
             }
         }
 
+        terrain.draw();
+
         // Draw all constraints
         for constraint in all_constraints.iter() {
             constraint.draw(&all_points);
@@ -316,7 +290,7 @@ pub async fn run_point_example() {
                 let dy = mouse_y - point.position.1;
                 let distance = (dx * dx + dy * dy).sqrt();
                 if distance < 100.0 {
-                    let force = Force::new(Vec2::new(dx * 0.1, dy * 0.1));
+                    let force = Force::new(Vec2::new(dx * 6.0, dy * 6.0));
                     point.add_component(Box::new(force));
                 }
             }
@@ -1,14 +1,24 @@
 use macroquad::prelude::*;
+use crate::basics::scene_manager::{Scene, SceneTransition};
 use crate::objects::ui::*;
-use std::path::Path;
 
-pub async fn run_ui_example() {
+/// Scene showing off the UI widgets with a custom theme
+pub struct UiExample {
+    ui_manager: UiManager,
+    progress: f32,
+}
+
+impl UiExample {
+    /// Build the example, loading its font
+    pub async fn load() -> Self {
+        Self { ui_manager: build_ui().await, progress: 0.0 }
+    }
+}
+
+async fn build_ui() -> UiManager {
     // Load custom font
     let font = load_ttf_font("rsrcs/font.ttf").await.unwrap();
     
-    // Create UI manager
-    let mut ui_manager = UiManager::new();
-    
     // Create modern theme
     let modern_theme = Theme {
         primary: Color::from_rgba(41, 128, 185, 255),    // Blue
@@ -23,13 +33,15 @@ pub async fn run_ui_example() {
         animation_speed: 0.2,
     };
 
+    // Create UI manager
+    let mut ui_manager = UiManager::with_theme(modern_theme.clone());
+
     // Create main panel
     let mut main_panel = UiPanel::new(
         50.0,
         50.0,
         800.0,
         600.0,
-        modern_theme.clone(),
         Some("Modern UI Example".to_string()),
     );
 
@@ -56,7 +68,6 @@ pub async fn run_ui_example() {
             "Option 3".to_string(),
             "Option 4".to_string(),
         ],
-        modern_theme.clone(),
         font.clone(),
         18,
         Some(Box::new(|index| {
@@ -74,7 +85,6 @@ pub async fn run_ui_example() {
         40.0,
         18,
         font.clone(),
-        modern_theme.clone(),
         Some(Box::new(|| {
             println!("Primary button clicked!");
         })),
@@ -89,7 +99,6 @@ pub async fn run_ui_example() {
         40.0,
         18,
         font.clone(),
-        modern_theme.clone(),
         Some(Box::new(|| {
             println!("Secondary button clicked!");
         })),
@@ -104,7 +113,6 @@ pub async fn run_ui_example() {
         40.0,
         18,
         font.clone(),
-        modern_theme.clone(),
         "Enter text here...",
         Some(Box::new(|text| {
             println!("Input changed: {}", text);
@@ -121,7 +129,6 @@ pub async fn run_ui_example() {
         0.0,
         100.0,
         50.0,
-        modern_theme.clone(),
         Some(Box::new(|value| {
             println!("Slider value: {}", value);
         })),
@@ -134,7 +141,6 @@ pub async fn run_ui_example() {
         450.0,
         24.0,
         false,
-        modern_theme.clone(),
         Some(Box::new(|checked| {
             println!("Checkbox state: {}", checked);
         })),
@@ -142,39 +148,44 @@ pub async fn run_ui_example() {
     main_panel.add_element(Box::new(checkbox));
 
     // Create progress bar
-    let mut progress_bar = UiProgressBar::new(
+    let progress_bar = UiProgressBar::new(
         100.0,
         500.0,
         200.0,
         20.0,
         0.0,
-        modern_theme.clone(),
     );
     main_panel.add_element(Box::new(progress_bar));
 
     // Add main panel to UI manager
     ui_manager.add_element(Box::new(main_panel));
 
-    // Main loop
-    let mut progress = 0.0;
-    loop {
-        clear_background(Color::from_rgba(52, 73, 94, 255));
+    ui_manager
+}
+
+impl Scene for UiExample {
+    fn update(&mut self, _dt: f32) -> SceneTransition {
+        if is_key_pressed(KeyCode::Escape) {
+            return SceneTransition::Pop;
+        }
 
         // Update progress bar
-        progress = (progress + 0.01) % 1.0;
-        if let Some(element) = ui_manager.get_element_mut(0) {
-            if let Some(panel) = element.as_any_mut().downcast_mut::<UiPanel>() {
-                if let Some(progress_bar) = panel.elements.last_mut() {
-                    if let Some(progress_bar) = progress_bar.as_any_mut().downcast_mut::<UiProgressBar>() {
-                        progress_bar.set_progress(progress);
-                    }
-                }
-            }
+        self.progress = (self.progress + 0.01) % 1.0;
+        if let Some(element) = self.ui_manager.get_element_mut(0)
+            && let Some(panel) = element.as_any_mut().downcast_mut::<UiPanel>()
+            && let Some(progress_bar) = panel.elements.last_mut()
+            && let Some(progress_bar) = progress_bar.as_any_mut().downcast_mut::<UiProgressBar>()
+        {
+            progress_bar.set_progress(self.progress);
         }
 
-        // Update and draw UI
-        ui_manager.update();
-        ui_manager.draw();
+        self.ui_manager.update();
+        SceneTransition::None
+    }
+
+    fn draw(&self) {
+        clear_background(Color::from_rgba(52, 73, 94, 255));
+        self.ui_manager.draw();
 
         // Draw FPS
         draw_text(
@@ -184,7 +195,5 @@ pub async fn run_ui_example() {
             20.0,
             WHITE,
         );
-
-        next_frame().await;
     }
-} 
\ No newline at end of file
+}
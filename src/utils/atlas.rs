@@ -0,0 +1,190 @@
+//! Texture atlases
+//!
+//! Named regions of one texture, so sprites and UI icons can share a sheet
+//! and be looked up by name instead of by pixel coordinates. Regions come
+//! from a regular grid or from the JSON data TexturePacker exports, in
+//! either its hash or array layout.
+//!
+//! # Examples
+//! ```rust
+//! use ruty::utils::atlas::TextureAtlas;
+//!
+//! let texture = load_texture("assets/sprites.png").await?;
+//! let atlas = TextureAtlas::load_json(texture, "assets/sprites.json")?;
+//!
+//! let mut coin = Quad::builder().pos(200.0, 300.0).size(16.0, 16.0).build();
+//! atlas.apply_to(&mut coin, "coin.png");
+//!
+//! let mut tiles = TextureAtlas::new(load_texture("assets/tiles.png").await?);
+//! tiles.add_grid("grass", 16.0, 16.0, 8, 2);
+//! tiles.draw_region("grass_3", 0.0, 0.0, vec2(32.0, 32.0));
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+use macroquad::color::WHITE;
+use macroquad::math::{Rect, Vec2};
+use macroquad::texture::{DrawTextureParams, Texture2D, draw_texture_ex};
+
+use crate::objects::quad::Quad;
+
+/// A texture split into named regions
+#[derive(Clone)]
+pub struct TextureAtlas {
+    pub texture: Texture2D,
+    /// Part of the texture each name stands for, in pixels
+    pub regions: HashMap<String, Rect>,
+}
+
+impl TextureAtlas {
+    /// Create an atlas of `texture` without regions
+    pub fn new(texture: Texture2D) -> Self {
+        Self {
+            texture,
+            regions: HashMap::new(),
+        }
+    }
+
+    /// Create an atlas of `texture` cut into a grid of `cell_width` by `cell_height` cells
+    ///
+    /// Cells are named by their index, counted left to right and then top to
+    /// bottom from `"0"`. Leftover pixels at the right and bottom are ignored.
+    pub fn from_grid(texture: Texture2D, cell_width: f32, cell_height: f32) -> Self {
+        let columns = (texture.width() / cell_width).floor() as usize;
+        let rows = (texture.height() / cell_height).floor() as usize;
+        let mut atlas = Self::new(texture);
+        for (index, rect) in grid(0.0, 0.0, cell_width, cell_height, columns, rows).enumerate() {
+            atlas.regions.insert(index.to_string(), rect);
+        }
+        atlas
+    }
+
+    /// Create an atlas of `texture` with the regions in TexturePacker JSON data
+    pub fn from_json(texture: Texture2D, json: &str) -> Result<Self, Box<dyn Error>> {
+        let mut atlas = Self::new(texture);
+        atlas.regions = parse_texture_packer(json)?;
+        Ok(atlas)
+    }
+
+    /// Create an atlas of `texture` with the regions in a TexturePacker `.json` file
+    pub fn load_json<P: AsRef<Path>>(texture: Texture2D, path: P) -> Result<Self, Box<dyn Error>> {
+        Self::from_json(texture, &std::fs::read_to_string(path)?)
+    }
+
+    /// Add or replace a region
+    pub fn add_region(&mut self, name: &str, rect: Rect) {
+        self.regions.insert(name.to_string(), rect);
+    }
+
+    /// Add a block of `columns` by `rows` cells from the top-left of the texture, named `"{prefix}_{index}"`
+    ///
+    /// Cells are counted left to right and then top to bottom from 0.
+    pub fn add_grid(&mut self, prefix: &str, cell_width: f32, cell_height: f32, columns: usize, rows: usize) {
+        self.add_grid_at(prefix, Vec2::ZERO, cell_width, cell_height, columns, rows);
+    }
+
+    /// Add a block of cells like `add_grid`, starting at `origin` on the texture instead of its top-left
+    pub fn add_grid_at(
+        &mut self,
+        prefix: &str,
+        origin: Vec2,
+        cell_width: f32,
+        cell_height: f32,
+        columns: usize,
+        rows: usize,
+    ) {
+        for (index, rect) in grid(origin.x, origin.y, cell_width, cell_height, columns, rows).enumerate() {
+            self.regions.insert(format!("{prefix}_{index}"), rect);
+        }
+    }
+
+    /// Get the part of the texture a name stands for
+    pub fn region(&self, name: &str) -> Option<Rect> {
+        self.regions.get(name).copied()
+    }
+
+    /// Names of every region, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.regions.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Show a region on a Quad, returning false if the atlas has no region by that name
+    pub fn apply_to(&self, quad: &mut Quad, name: &str) -> bool {
+        let Some(rect) = self.region(name) else {
+            return false;
+        };
+        quad.texture = Some(self.texture.clone());
+        quad.source_rect = Some(rect);
+        true
+    }
+
+    /// Draw a region with its top-left corner at `x`, `y`, stretched to `size`
+    ///
+    /// Does nothing if the atlas has no region by that name.
+    pub fn draw_region(&self, name: &str, x: f32, y: f32, size: Vec2) {
+        if let Some(rect) = self.region(name) {
+            draw_texture_ex(
+                &self.texture,
+                x,
+                y,
+                WHITE,
+                DrawTextureParams {
+                    dest_size: Some(size),
+                    source: Some(rect),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+}
+
+/// Cells of a grid starting at `x`, `y`, left to right and then top to bottom
+fn grid(x: f32, y: f32, cell_width: f32, cell_height: f32, columns: usize, rows: usize) -> impl Iterator<Item = Rect> {
+    (0..rows).flat_map(move |row| {
+        (0..columns).map(move |column| {
+            Rect::new(
+                x + column as f32 * cell_width,
+                y + row as f32 * cell_height,
+                cell_width,
+                cell_height,
+            )
+        })
+    })
+}
+
+/// Read the frames of TexturePacker JSON data, in its hash or array layout
+///
+/// Frames packed rotated are rejected, since regions can't be drawn turned.
+fn parse_texture_packer(json: &str) -> Result<HashMap<String, Rect>, Box<dyn Error>> {
+    let data: serde_json::Value = serde_json::from_str(json)?;
+    let frames: Vec<(String, &serde_json::Value)> = match data.get("frames") {
+        Some(serde_json::Value::Object(frames)) => frames.iter().map(|(name, frame)| (name.clone(), frame)).collect(),
+        Some(serde_json::Value::Array(frames)) => frames
+            .iter()
+            .map(|frame| {
+                let name = frame.get("filename").and_then(|name| name.as_str()).ok_or("frame without a filename")?;
+                Ok((name.to_string(), frame))
+            })
+            .collect::<Result<_, Box<dyn Error>>>()?,
+        _ => return Err("atlas data has no frames".into()),
+    };
+    let mut regions = HashMap::new();
+    for (name, frame) in frames {
+        if frame.get("rotated").and_then(|rotated| rotated.as_bool()) == Some(true) {
+            return Err(format!("frame {name} is rotated, which isn't supported; pack without rotation").into());
+        }
+        let rect = frame.get("frame").ok_or_else(|| format!("frame {name} has no rectangle"))?;
+        let field = |key: &str| {
+            rect.get(key)
+                .and_then(|value| value.as_f64())
+                .map(|value| value as f32)
+                .ok_or_else(|| format!("frame {name} is missing {key}"))
+        };
+        regions.insert(name.clone(), Rect::new(field("x")?, field("y")?, field("w")?, field("h")?));
+    }
+    Ok(regions)
+}
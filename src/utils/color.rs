@@ -0,0 +1,49 @@
+//! Color helpers
+//!
+//! Conversions between macroquad colors and `#RRGGBB` / `#RRGGBBAA` hex
+//! strings, plus a serde adapter so colors can be stored in config files.
+//!
+//! # Examples
+//! ```rust
+//! use ruty::utils::color::{parse_hex, to_hex};
+//!
+//! let color = parse_hex("#0078d7").unwrap();
+//! assert_eq!(to_hex(color), "#0078d7ff");
+//! ```
+
+use macroquad::color::Color;
+
+/// Parse a `#RRGGBB` or `#RRGGBBAA` string (the `#` is optional)
+pub fn parse_hex(hex: &str) -> Option<Color> {
+    let hex = hex.trim().trim_start_matches('#');
+    if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+    Some(Color::from_rgba(channel(0)?, channel(2)?, channel(4)?, alpha))
+}
+
+/// Format a color as a `#RRGGBBAA` string
+pub fn to_hex(color: Color) -> String {
+    let [r, g, b, a]: [u8; 4] = color.into();
+    format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+}
+
+/// Serde adapter storing a `Color` as a hex string
+///
+/// Use with `#[serde(with = "crate::utils::color::hex")]`.
+pub mod hex {
+    use super::{parse_hex, to_hex};
+    use macroquad::color::Color;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(*color))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        parse_hex(&hex).ok_or_else(|| D::Error::custom(format!("invalid hex color `{}`", hex)))
+    }
+}
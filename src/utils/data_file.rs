@@ -0,0 +1,51 @@
+//! Data files
+//!
+//! Reading and writing serde types as TOML or JSON, picked by the file's
+//! extension: `.json` files are JSON and anything else is TOML.
+//!
+//! # Examples
+//! ```rust
+//! use ruty::utils::data_file;
+//!
+//! let config: PhysicsConfig = data_file::load("assets/physics.toml")?;
+//! data_file::save(&config, "saves/physics.json")?;
+//! ```
+
+use std::error::Error;
+use std::path::Path;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Check if a path has a `.json` extension
+pub fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// Check if a path has a `.toml` or `.json` extension
+pub fn is_data_file(path: &Path) -> bool {
+    is_json(path) || path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+}
+
+/// Read a value from a `.toml` or `.json` file
+pub fn load<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, Box<dyn Error>> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)?;
+    if is_json(path) {
+        Ok(serde_json::from_str(&contents)?)
+    } else {
+        Ok(toml::from_str(&contents)?)
+    }
+}
+
+/// Write a value to a `.toml` or `.json` file, replacing the file if it exists
+pub fn save<T: Serialize, P: AsRef<Path>>(value: &T, path: P) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    let contents = if is_json(path) {
+        serde_json::to_string_pretty(value)?
+    } else {
+        toml::to_string_pretty(value)?
+    };
+    std::fs::write(path, contents)?;
+    Ok(())
+}
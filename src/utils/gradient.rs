@@ -1,5 +1,15 @@
 use macroquad::prelude::*;
 
+/// Axis a gradient runs along, from its first stop to its last
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientDirection {
+    /// From the left edge to the right
+    Horizontal,
+    /// From the top edge to the bottom
+    #[default]
+    Vertical,
+}
+
 /// Represents a gradient with multiple color stops
 #[derive(Clone)]
 pub struct Gradient {
@@ -0,0 +1,143 @@
+//! Localization
+//!
+//! Keyed string tables, one per language, loaded from TOML or JSON files.
+//! Nested tables are flattened into dotted keys, so `[menu] start = "Start"`
+//! is looked up as `menu.start`.
+//!
+//! # Examples
+//! ```rust
+//! use ruty::utils::localization::Localization;
+//!
+//! let mut localization = Localization::load_dir("assets/lang")?;
+//! localization.set_language("de");
+//! let label = localization.get("menu.start");
+//! let greeting = localization.format("greeting", &[("name", "Ada")]);
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+use crate::utils::data_file;
+
+/// String tables for every loaded language
+#[derive(Clone, Default)]
+pub struct Localization {
+    tables: HashMap<String, HashMap<String, String>>,
+    language: String,
+    /// Language used for keys missing from the current one
+    fallback: Option<String>,
+}
+
+impl Localization {
+    /// Create an empty localization using `language`
+    pub fn new(language: &str) -> Self {
+        Self {
+            tables: HashMap::new(),
+            language: language.to_string(),
+            fallback: None,
+        }
+    }
+
+    /// Load every `.toml` and `.json` table in a directory
+    ///
+    /// Each file is a language named after the file without its extension.
+    /// The first language loaded becomes the current one.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Localization, Box<dyn Error>> {
+        let mut localization = Localization::default();
+        let mut paths: Vec<_> = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()?;
+        paths.sort();
+        for path in paths {
+            if let (true, Some(name)) = (data_file::is_data_file(&path), path.file_stem().and_then(|stem| stem.to_str())) {
+                localization.load_file(name, &path)?;
+            }
+        }
+        Ok(localization)
+    }
+
+    /// Load the string table for `language` from a `.toml` or `.json` file
+    ///
+    /// Keys already loaded for the language are overwritten.
+    pub fn load_file<P: AsRef<Path>>(&mut self, language: &str, path: P) -> Result<(), Box<dyn Error>> {
+        let table: serde_json::Value = data_file::load(path)?;
+        let mut strings = HashMap::new();
+        flatten("", &table, &mut strings);
+        self.add_strings(language, strings);
+        Ok(())
+    }
+
+    /// Add strings to a language's table
+    pub fn add_strings(&mut self, language: &str, strings: HashMap<String, String>) {
+        if self.language.is_empty() {
+            self.language = language.to_string();
+        }
+        self.tables.entry(language.to_string()).or_default().extend(strings);
+    }
+
+    /// Switch the current language
+    pub fn set_language(&mut self, language: &str) {
+        self.language = language.to_string();
+    }
+
+    /// Get the current language
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Get the names of all loaded languages
+    pub fn languages(&self) -> Vec<&str> {
+        let mut languages: Vec<&str> = self.tables.keys().map(String::as_str).collect();
+        languages.sort_unstable();
+        languages
+    }
+
+    /// Use `language` for keys the current language is missing
+    pub fn set_fallback(&mut self, language: Option<&str>) {
+        self.fallback = language.map(str::to_string);
+    }
+
+    /// Look up a key in the current language, then the fallback
+    pub fn try_get(&self, key: &str) -> Option<&str> {
+        let lookup = |language: &str| self.tables.get(language)?.get(key).map(String::as_str);
+        lookup(&self.language).or_else(|| self.fallback.as_deref().and_then(lookup))
+    }
+
+    /// Look up a key, returning the key itself if no language has it
+    pub fn get(&self, key: &str) -> String {
+        self.try_get(key).unwrap_or(key).to_string()
+    }
+
+    /// Look up a key and replace `{name}` placeholders with `args`
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter().fold(self.get(key), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+    }
+}
+
+/// Join a parent key and a child key with a dot
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn flatten(prefix: &str, value: &serde_json::Value, out: &mut HashMap<String, String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                flatten(&join_key(prefix, key), value, out);
+            }
+        }
+        serde_json::Value::String(text) => {
+            out.insert(prefix.to_string(), text.clone());
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
@@ -1,4 +1,8 @@
 pub mod screen;
 pub mod font_text;
 pub mod window;
-pub mod gradient;
\ No newline at end of file
+pub mod gradient;
+pub mod atlas;
+pub mod color;
+pub mod localization;
+pub mod data_file;
@@ -27,6 +27,12 @@ pub struct WindowBuilder {
     config: WindowConfig,
 }
 
+impl Default for WindowBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WindowBuilder {
     /// Creates a new window builder with default settings
     /// 